@@ -67,7 +67,6 @@ fn main() -> Result<(), BuildError> {
             source: e,
         })?;
     let manifest: Manifest = serde_json::from_str(&manifest_string)?;
-    let pgns_to_generate: Vec<u32> = manifest.pgns.iter().map(|p| p.id).collect();
 
     // 2. Load the PGN database (download if missing).
     let canboat_doc_path =
@@ -87,7 +86,7 @@ fn main() -> Result<(), BuildError> {
     let canboat_value: serde_json::Value = serde_json::from_str(&canboat_doc_string)?;
 
     // 3. Iterate over the manifest and generate code for every lookup table and requested PGN.
-    let buffer_pgn_code: String = run_pgns_gen(&canboat_value, pgns_to_generate)?;
+    let buffer_pgn_code: String = run_pgns_gen(&canboat_value, &manifest.pgns)?;
     let buffer_lookup_code = run_lookup_gen(&canboat_value)?;
 
     // 4. Write the generated code into `OUT_DIR`.
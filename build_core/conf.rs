@@ -8,6 +8,8 @@ pub(crate) const CANBOAT_DOC_PATH: &str = "build_core/var/canboat.json";
 pub(crate) const OUT_DIR_PGN_FILE_NAME: &str = "generated_pgns.rs";
 /// Generated lookup enumeration file name (written to `OUT_DIR`).
 pub(crate) const OUT_DIR_ENUM_FILE_NAME: &str = "generated_lookups.rs";
-pub(crate) const _FORBIDEN_PGN: &[u32] = &[126208];
+pub(crate) const _FORBIDEN_PGN: &[u32] = &[];
+/// Group Function PGN, given typed builders for its variants.
+pub(crate) const GROUP_FUNCTION_PGN: u32 = 126208;
 //==========================================TESTS
 // pub(crate) const CANBOAT_DOC_PATH: &str = "_doc/technique/canboat_corrupted.json";
@@ -14,6 +14,9 @@ pub(crate) struct Manifest {
 /// Entry in the PGN list to generate.
 pub(crate) struct Pgn {
     pub(crate) id: u32,
+    /// Maximum entries of each repeating field set, instead of the default 32.
+    #[serde(default)]
+    pub(crate) max_repetitions: Option<usize>,
 }
 
 //==================================================================================CANBOAT_DOC
@@ -53,6 +56,8 @@ pub(crate) trait LookupGenerator {
     // OTHER -> 0
     /// Internal code used to qualify the enumeration kind.
     fn metadata_code(&self) -> u8;
+    /// Raw CANboat display name of every value, as printed by `analyzer`.
+    fn labels(&self) -> Vec<(u32, String)>;
 }
 //==========================================LOOKUP_FIELDTYPE_ENUM
 #[derive(Debug, Deserialize, Clone)]
@@ -111,6 +116,12 @@ impl LookupGenerator for LookupFieldTypeEnum {
     fn metadata_code(&self) -> u8 {
         2
     }
+    fn labels(&self) -> Vec<(u32, String)> {
+        self.fieldtype_enum_values
+            .iter()
+            .map(|v| (v.value, v.name.clone()))
+            .collect()
+    }
 }
 //==========================================LOOKUP_BIT_ENUM
 #[derive(Debug, Deserialize, Clone)]
@@ -150,6 +161,12 @@ impl LookupGenerator for LookupBitEnum {
     fn metadata_code(&self) -> u8 {
         0
     }
+    fn labels(&self) -> Vec<(u32, String)> {
+        self.bit_enum_values
+            .iter()
+            .map(|v| (v.bit as u32, v.name.clone()))
+            .collect()
+    }
 }
 // ==========================================LOOKUP_INDIRECT_ENUM
 #[derive(Debug, Deserialize, Clone)]
@@ -194,6 +211,12 @@ impl LookupGenerator for LookupIndirEnum {
     fn metadata_code(&self) -> u8 {
         1
     }
+    fn labels(&self) -> Vec<(u32, String)> {
+        self.indir_enum_values
+            .iter()
+            .map(|v| (((v.value1 as u32) << 8) | v.value2 as u32, v.name.clone()))
+            .collect()
+    }
 }
 
 //==========================================LOOKUP_ENUM
@@ -235,6 +258,12 @@ impl LookupGenerator for LookupEnum {
     fn metadata_code(&self) -> u8 {
         0
     }
+    fn labels(&self) -> Vec<(u32, String)> {
+        self.enum_values
+            .iter()
+            .map(|v| (v.value, v.name.clone()))
+            .collect()
+    }
 }
 //==============================================================PGN_DOMAIN
 //==========================================PGN
@@ -293,6 +322,24 @@ pub(crate) struct PgnInstructions {
     /// 17. Field descriptors.
     #[serde(rename = "Fields")]
     pub fields: Vec<Fields>,
+    /// 18. NAME sub-fields merged into the leading `ISO_NAME` field, if any.
+    #[serde(skip)]
+    pub iso_name_fields: Vec<Fields>,
+    /// 19. Repetition limit set by the manifest, if any.
+    #[serde(skip)]
+    pub max_repetitions: Option<usize>,
+}
+
+impl PgnInstructions {
+    /// Lookup value selecting this variant of a polymorphic PGN (e.g. "Request"
+    /// for 126208), when its first field carries one.
+    pub fn variant_selector(&self) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|f| f.order == 1 && f.enum_direct_name.is_some())?
+            .description
+            .as_deref()
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -346,6 +393,9 @@ pub(crate) struct Fields {
     /// 15. Bitfield enumeration name for BITLOOKUP fields.
     #[serde(rename = "LookupBitEnumeration")]
     pub enum_bit_name: Option<String>,
+    /// 16. Presence condition (e.g. "PGNIsProprietary").
+    #[serde(rename = "Condition")]
+    pub condition: Option<String>,
 }
 
 #[derive(Debug, Default, Hash)]
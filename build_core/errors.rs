@@ -12,6 +12,10 @@ pub(crate) enum BuildError {
     #[error("[MESSAGE]:Unvalid bitlength for [PGN]:{path}, [COMMENT]:{comment}")]
     BitLengthErr { path: String, comment: &'static str },
 
+    /// Group Function definition the builder generator cannot follow.
+    #[error("[MESSAGE]:Unsupported group function layout [PATH]:{path}, [COMMENT]:{comment}")]
+    GroupFunctionLayout { path: String, comment: &'static str },
+
     /// Failed to read the `OUT_DIR` environment variable.
     #[error("[MESSAGE]:OUT_DIR error. [ERROR]:{source}")]
     OutDirErr {
@@ -0,0 +1,357 @@
+//! Generate typed builders for the Group Function PGN (126208) variants.
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::core::FieldKind;
+
+use super::domain::*;
+use super::errors::*;
+use super::name_helpers::*;
+use super::repetitive_fields::*;
+use super::type_helpers::*;
+
+/// Variants given a builder, by function code name.
+const BUILT_VARIANTS: &[&str] = &["Request", "Command", "Acknowledge"];
+
+//==================================================================================GROUP_FUNCTION_BUILDER
+/// Generate the builder of a Group Function variant and its constructor on the
+/// polymorphic enum, e.g. `Pgn126208::request(pgn) -> Pgn126208RequestBuilder`.
+///
+/// Setters cover the fixed fields after the referenced PGN. Fields left unset
+/// hold their "no change" value: all bits set for numbers (transmit now, keep
+/// the timing), "Leave unchanged" for lookups that have it. Each parameter is
+/// appended with a method named after the repeating group's first field; a
+/// `VARIABLE` value is encoded from a `PgnValue` with the descriptor of the
+/// referenced field.
+pub(crate) fn generate_group_function_builder(
+    pgn: &PgnInstructions,
+    repeating_info: Option<&RepeatingFieldSetInfo>,
+    lookup_enum_map: &HashMap<String, LookupEnum>,
+    lookup_indir_map: &HashMap<String, LookupIndirEnum>,
+) -> Result<String, BuildError> {
+    let mut buffer = String::new();
+    let Some(selector) = pgn
+        .variant_selector()
+        .filter(|selector| BUILT_VARIANTS.contains(selector))
+    else {
+        return Ok(buffer);
+    };
+
+    let variant_name = to_pascal_case(&pgn.pgn_name, PascalCaseMode::Soft);
+    let struct_name = format!("Pgn{}{}", pgn.pgn_id, variant_name);
+    let builder_name = format!(
+        "Pgn{}{}Builder",
+        pgn.pgn_id,
+        to_pascal_case(selector, PascalCaseMode::Soft)
+    );
+    let repeated = repeating_info
+        .map(|info| info.start_field_index..info.start_field_index + info.size)
+        .unwrap_or_default();
+    let pgn_field = pgn
+        .fields
+        .iter()
+        .find(|field| map_to_fieldkind(field) == FieldKind::Pgn)
+        .ok_or(BuildError::GroupFunctionLayout {
+            path: struct_name.clone(),
+            comment: "Group Function variant without PGN field",
+        })?;
+    let pgn_field_name = to_snake_case(&pgn_field.id, "field");
+    // Parameter 1 of a polymorphic PGN selects the variant later parameters resolve against.
+    let has_variable = pgn
+        .fields
+        .get(repeated.clone())
+        .unwrap_or_default()
+        .iter()
+        .any(|field| map_to_fieldkind(field) == FieldKind::Variable);
+
+    //==========================================constructor
+    writeln!(buffer, "impl Pgn{} {{", pgn.pgn_id)?;
+    writeln!(
+        buffer,
+        "\t/// Build a {} group function for `pgn`.",
+        selector
+    )?;
+    if let Some(explanation) = &pgn.explanation {
+        writeln!(buffer, "\t///")?;
+        writeln!(buffer, "\t/// {}", explanation)?;
+    }
+    writeln!(
+        buffer,
+        "\tpub fn {}(pgn: u32) -> {} {{",
+        to_snake_case(selector, "fn"),
+        builder_name
+    )?;
+    writeln!(buffer, "\t\t{}::new(pgn)", builder_name)?;
+    writeln!(buffer, "\t}}")?;
+    writeln!(buffer, "}}\n")?;
+
+    //==========================================builder
+    writeln!(
+        buffer,
+        "/// Builder of PGN {} {} messages.",
+        pgn.pgn_id, selector
+    )?;
+    writeln!(buffer, "#[derive(Debug, Clone, Copy, PartialEq)]")?;
+    writeln!(buffer, "pub struct {} {{", builder_name)?;
+    writeln!(buffer, "\tinner: {},", struct_name)?;
+    if has_variable {
+        writeln!(buffer, "\tselector: Option<u64>,")?;
+    }
+    writeln!(buffer, "}}\n")?;
+
+    writeln!(buffer, "impl {} {{", builder_name)?;
+    writeln!(
+        buffer,
+        "\t/// Start a message for `pgn`, other fields left unchanged."
+    )?;
+    writeln!(buffer, "\tpub fn new(pgn: u32) -> Self {{")?;
+    writeln!(buffer, "\t\tlet mut inner = {}::new();", struct_name)?;
+    writeln!(buffer, "\t\tinner.{} = pgn;", pgn_field_name)?;
+
+    // Fixed fields the caller may set, with their "no change" defaults.
+    let mut setters = Vec::new();
+    for (idx, field) in pgn.fields.iter().enumerate() {
+        let kind = map_to_fieldkind(field);
+        if field.order == 1
+            || repeated.contains(&idx)
+            || repeating_info.is_some_and(|info| info.count_field_index == Some(idx))
+            || matches!(
+                kind,
+                FieldKind::Pgn | FieldKind::Reserved | FieldKind::Spare
+            )
+        {
+            continue;
+        }
+        let field_name = to_snake_case(&field.id, "field");
+        let field_type = map_type(field, lookup_enum_map, lookup_indir_map)?;
+        if let Some(default) = unchanged_value(field, &kind, &field_type, lookup_enum_map) {
+            writeln!(buffer, "\t\tinner.{} = {};", field_name, default)?;
+        }
+        setters.push((field, field_name, field_type));
+    }
+    if has_variable {
+        writeln!(buffer, "\t\tSelf {{ inner, selector: None }}")?;
+    } else {
+        writeln!(buffer, "\t\tSelf {{ inner }}")?;
+    }
+    writeln!(buffer, "\t}}")?;
+
+    for (field, field_name, field_type) in &setters {
+        writeln!(buffer)?;
+        writeln!(buffer, "\t/// Set the {} field.", field.name)?;
+        if let Some(description) = &field.description {
+            writeln!(buffer, "\t///")?;
+            writeln!(buffer, "\t/// {}", description)?;
+        }
+        writeln!(
+            buffer,
+            "\tpub fn {0}(mut self, value: {1}) -> Self {{",
+            field_name, field_type
+        )?;
+        writeln!(buffer, "\t\tself.inner.{} = value;", field_name)?;
+        writeln!(buffer, "\t\tself")?;
+        writeln!(buffer, "\t}}")?;
+    }
+
+    if let Some(info) = repeating_info {
+        buffer.push_str(&generate_parameter_fn(
+            pgn,
+            info,
+            &pgn_field_name,
+            lookup_enum_map,
+            lookup_indir_map,
+        )?);
+    }
+
+    writeln!(buffer)?;
+    writeln!(
+        buffer,
+        "\t/// Wrap the message into its PGN {} variant.",
+        pgn.pgn_id
+    )?;
+    writeln!(buffer, "\tpub fn build(self) -> Pgn{} {{", pgn.pgn_id)?;
+    writeln!(
+        buffer,
+        "\t\tPgn{}::{}(self.inner)",
+        pgn.pgn_id, variant_name
+    )?;
+    writeln!(buffer, "\t}}")?;
+    writeln!(buffer, "}}\n")?;
+
+    Ok(buffer)
+}
+
+/// Generate the method appending one element of the repeating group.
+fn generate_parameter_fn(
+    pgn: &PgnInstructions,
+    info: &RepeatingFieldSetInfo,
+    pgn_field_name: &str,
+    lookup_enum_map: &HashMap<String, LookupEnum>,
+    lookup_indir_map: &HashMap<String, LookupIndirEnum>,
+) -> Result<String, BuildError> {
+    let mut buffer = String::new();
+    let fields = &pgn.fields[info.start_field_index..info.start_field_index + info.size];
+    let Some(first_field) = fields.first() else {
+        return Ok(buffer);
+    };
+
+    let mut args = Vec::new();
+    let mut inits = Vec::new();
+    let mut index_name = None;
+    for field in fields {
+        let field_name = to_snake_case(&field.id, "field");
+        match map_to_fieldkind(field) {
+            FieldKind::Variable => {
+                let index = index_name.clone().ok_or(BuildError::GroupFunctionLayout {
+                    path: field.id.clone(),
+                    comment: "VARIABLE field without preceding FIELD_INDEX",
+                })?;
+                args.push(format!("{}: PgnValue", field_name));
+                writeln!(
+                    buffer,
+                    "\t\tlet field = crate::protocol::messages::variable_field(self.inner.{}, {}, self.selector)",
+                    pgn_field_name, index
+                )?;
+                writeln!(buffer, "\t\t\t.ok()")?;
+                writeln!(buffer, "\t\t\t.flatten()")?;
+                writeln!(
+                    buffer,
+                    "\t\t\t.ok_or(crate::error::SerializationError::InvalidData)?;"
+                )?;
+                writeln!(
+                    buffer,
+                    "\t\tlet {0} = crate::infra::codec::engine::encode_variable(field, &{0})?;",
+                    field_name
+                )?;
+                writeln!(buffer, "\t\tif {} == 1 {{", index)?;
+                writeln!(
+                    buffer,
+                    "\t\t\tself.selector = crate::infra::codec::engine::variable_selector(field, &{});",
+                    field_name
+                )?;
+                writeln!(buffer, "\t\t}}")?;
+            }
+            kind => {
+                if kind == FieldKind::FieldIndex {
+                    index_name = Some(field_name.clone());
+                }
+                let field_type = map_type(field, lookup_enum_map, lookup_indir_map)?;
+                args.push(format!("{}: {}", field_name, field_type));
+            }
+        }
+        inits.push(field_name);
+    }
+    let body = buffer;
+
+    let mut buffer = String::new();
+    writeln!(buffer)?;
+    writeln!(
+        buffer,
+        "\t/// Append a {} to the message.",
+        first_field.name
+    )?;
+    if index_name.is_some() {
+        writeln!(buffer, "\t///")?;
+        writeln!(
+            buffer,
+            "\t/// The index is the 1-based order of a field of the referenced PGN; the"
+        )?;
+        writeln!(
+            buffer,
+            "\t/// value uses the `PgnValue` variant that field decodes to. For a polymorphic"
+        )?;
+        writeln!(
+            buffer,
+            "\t/// PGN, index 1 comes first: its value selects the variant of the other fields."
+        )?;
+    }
+    writeln!(buffer, "\t///")?;
+    if index_name.is_some() {
+        writeln!(
+            buffer,
+            "\t/// Fails with `InvalidData` when the field cannot be resolved (PGN not"
+        )?;
+        writeln!(
+            buffer,
+            "\t/// generated, no such index, no matching variant) or encoded,"
+        )?;
+        writeln!(
+            buffer,
+            "\t/// and with `BufferTooSmall` past {} entries.",
+            info.max_repetitions
+        )?;
+    } else {
+        writeln!(
+            buffer,
+            "\t/// Fails with `BufferTooSmall` past {} entries.",
+            info.max_repetitions
+        )?;
+    }
+    writeln!(buffer, "\t#[allow(clippy::result_large_err)]")?;
+    writeln!(
+        buffer,
+        "\tpub fn {}(mut self, {}) -> Result<Self, crate::error::SerializationError> {{",
+        to_snake_case(&first_field.id, "field"),
+        args.join(", ")
+    )?;
+    buffer.push_str(&body);
+    writeln!(
+        buffer,
+        "\t\tlet count = self.inner.{};",
+        info.count_field_name
+    )?;
+    writeln!(
+        buffer,
+        "\t\t*self.inner.{}.get_mut(count).ok_or(crate::error::SerializationError::BufferTooSmall)? = {} {{ {} }};",
+        info.array_field_name,
+        info.struct_name,
+        inits.join(", ")
+    )?;
+    writeln!(
+        buffer,
+        "\t\tself.inner.{} = count + 1;",
+        info.count_field_name
+    )?;
+    if let Some(counter) = info.count_field_index.and_then(|idx| pgn.fields.get(idx)) {
+        writeln!(
+            buffer,
+            "\t\tself.inner.{} = (count + 1) as {};",
+            to_snake_case(&counter.id, "field"),
+            map_type(counter, lookup_enum_map, lookup_indir_map)?
+        )?;
+    }
+    writeln!(buffer, "\t\tOk(self)")?;
+    writeln!(buffer, "\t}}")?;
+    Ok(buffer)
+}
+
+/// "No change" value of a fixed field, when it differs from `new()`'s default.
+fn unchanged_value(
+    field: &Fields,
+    kind: &FieldKind,
+    field_type: &str,
+    lookup_enum_map: &HashMap<String, LookupEnum>,
+) -> Option<String> {
+    match kind {
+        FieldKind::Lookup => {
+            let lookup = lookup_enum_map.get(field.enum_direct_name.as_ref()?)?;
+            lookup
+                .enum_values
+                .iter()
+                .find(|value| value.name == "Leave unchanged")
+                .map(|value| {
+                    format!(
+                        "{}::{}",
+                        field_type,
+                        to_pascal_case(&value.name.to_lowercase(), PascalCaseMode::Hard)
+                    )
+                })
+        }
+        // Saturates to all bits set when encoded.
+        _ => match field_type {
+            "f32" | "f64" => Some(format!("{}::MAX", field_type)),
+            "u8" | "u16" | "u32" | "u64" => Some(format!("{}::MAX", field_type)),
+            _ => None,
+        },
+    }
+}
@@ -6,34 +6,40 @@ use super::type_helpers::*;
 
 use serde::de::DeserializeOwned;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Write};
 
 /// Iterate over lookup categories and emit the corresponding Rust code.
 pub(crate) fn run_lookup_gen(canboat_value: &Value) -> Result<String, BuildError> {
     let mut buffer_lookup_code = String::new();
+    let mut labels = LookupLabels::default();
 
     process_lookup_category::<LookupEnum>(
         canboat_value,
         "LookupEnumerations",
         &mut buffer_lookup_code,
+        &mut labels,
     )?;
     process_lookup_category::<LookupIndirEnum>(
         canboat_value,
         "LookupIndirectEnumerations",
         &mut buffer_lookup_code,
+        &mut labels,
     )?;
     process_lookup_category::<LookupBitEnum>(
         canboat_value,
         "LookupBitEnumerations",
         &mut buffer_lookup_code,
+        &mut labels,
     )?;
     process_lookup_category::<LookupFieldTypeEnum>(
         canboat_value,
         "LookupFieldTypeEnumerations",
         &mut buffer_lookup_code,
+        &mut labels,
     )?;
 
+    buffer_lookup_code.push_str(&labels.generate()?);
     Ok(buffer_lookup_code)
 }
 
@@ -42,6 +48,7 @@ fn process_lookup_category<T>(
     canboat_value: &serde_json::Value,
     category_key: &str,
     output_buffer: &mut String,
+    labels: &mut LookupLabels,
 ) -> Result<(), BuildError>
 where
     T: DeserializeOwned + LookupGenerator + Debug,
@@ -52,6 +59,7 @@ where
                 Ok(lookup_def) => match generate_lookup_code(&lookup_def) {
                     Ok(code) => {
                         output_buffer.push_str(&code);
+                        labels.add(&lookup_def)?;
                     }
                     Err(e) => {
                         println!(
@@ -79,6 +87,62 @@ where
     }
     Ok(())
 }
+//==================================================================================LOOKUP_LABELS
+/// Display names of every generated lookup, emitted as a single `lookup_name`
+/// dispatcher keyed by the PascalCase enumeration name used in field descriptors.
+#[derive(Default)]
+struct LookupLabels {
+    arms: String,
+    seen: HashSet<String>,
+}
+
+impl LookupLabels {
+    /// Append the match arm of `lookup`; later duplicates of an enumeration name are ignored.
+    fn add(&mut self, lookup: &dyn LookupGenerator) -> Result<(), BuildError> {
+        let enum_name = to_pascal_case(&lookup.name().to_lowercase(), PascalCaseMode::Hard);
+        if !self.seen.insert(enum_name.clone()) {
+            return Ok(());
+        }
+        let mut values = HashSet::new();
+        writeln!(self.arms, "\t\t\"{}\" => match value {{", enum_name)?;
+        for (value, label) in lookup.labels() {
+            if values.insert(value) {
+                writeln!(self.arms, "\t\t\t{} => Some({:?}),", value, label)?;
+            }
+        }
+        writeln!(self.arms, "\t\t\t_ => None,")?;
+        writeln!(self.arms, "\t\t}},")?;
+        Ok(())
+    }
+
+    fn generate(&self) -> Result<String, BuildError> {
+        let mut buffer = String::new();
+        writeln!(
+            buffer,
+            "/// CANboat display name of `value` in the lookup `enumeration`."
+        )?;
+        writeln!(buffer, "///")?;
+        writeln!(
+            buffer,
+            "/// `enumeration` is the PascalCase name found in field descriptors; indirect"
+        )?;
+        writeln!(
+            buffer,
+            "/// lookups take `(value1 << 8) | value2`, bit lookups the bit position."
+        )?;
+        writeln!(
+            buffer,
+            "pub fn lookup_name(enumeration: &str, value: u32) -> Option<&'static str> {{"
+        )?;
+        writeln!(buffer, "\tmatch enumeration {{")?;
+        buffer.push_str(&self.arms);
+        writeln!(buffer, "\t\t_ => None,")?;
+        writeln!(buffer, "\t}}")?;
+        writeln!(buffer, "}}")?;
+        Ok(buffer)
+    }
+}
+
 //==================================================================================LOOKUP_ENUM_GENERATION
 //==================================================================================LOOKUP_ENUM_GENERATION
 //==================================================================================LOOKUP_ENUM_GENERATION
@@ -135,7 +199,12 @@ fn generate_lookup_code(lookup: &dyn LookupGenerator) -> Result<String, BuildErr
 
     //======================Enum generation
     writeln!(buffer, "#[repr({})]", enum_repr)?;
-    writeln!(buffer, "#[derive(Debug, PartialEq, Copy, Clone)]")?;
+    // The first variant is the default value.
+    if variants.is_empty() {
+        writeln!(buffer, "#[derive(Debug, PartialEq, Copy, Clone)]")?;
+    } else {
+        writeln!(buffer, "#[derive(Debug, PartialEq, Copy, Clone, Default)]")?;
+    }
     writeln!(buffer, "pub enum {} {{", enum_name)?;
 
     let mut first_variant_name: Option<String> = None;
@@ -150,6 +219,7 @@ fn generate_lookup_code(lookup: &dyn LookupGenerator) -> Result<String, BuildErr
                         name.clone()
                     };
                 if first_variant_name.is_none() {
+                    writeln!(buffer, "\t#[default]")?;
                     first_variant_name = Some(field_name.clone());
                 }
                 writeln!(buffer, "\t{} = {},", field_name, value)?;
@@ -169,6 +239,7 @@ fn generate_lookup_code(lookup: &dyn LookupGenerator) -> Result<String, BuildErr
                     name.clone()
                 };
                 if first_variant_name.is_none() {
+                    writeln!(buffer, "\t#[default]")?;
                     first_variant_name = Some(field_name.clone());
                 }
                 writeln!(buffer, "\t{} = {},", field_name, value)?;
@@ -239,14 +310,6 @@ fn generate_lookup_code(lookup: &dyn LookupGenerator) -> Result<String, BuildErr
     writeln!(buffer, "}}")?;
     writeln!(buffer)?;
     if let Some(default_variant) = first_variant_name {
-        // Default implementation
-        writeln!(buffer, "impl Default for {} {{", enum_name)?;
-        writeln!(buffer, "\tfn default() -> Self {{")?;
-        writeln!(buffer, "\t\tSelf::{}", default_variant)?;
-        writeln!(buffer, "\t}}")?;
-        writeln!(buffer, "}}")?;
-        writeln!(buffer)?;
-
         // DEFAULT constant for backward compatibility
         writeln!(buffer, "impl {} {{", enum_name)?;
         writeln!(
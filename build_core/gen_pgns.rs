@@ -1,4 +1,5 @@
 //! Generate Rust code for the PGNs selected in the manifest.
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Write;
@@ -6,6 +7,8 @@ use std::fmt::Write;
 use crate::core::FieldKind;
 use serde_json::Value;
 
+use crate::build_core::conf::GROUP_FUNCTION_PGN;
+use crate::build_core::gen_group_function::generate_group_function_builder;
 use crate::build_core::gen_lookups::generate_indirect_lookup_helpers;
 use crate::build_core::gen_lookups::{
     set_lookup_bit_map, set_lookup_enum_map, set_lookup_indir_map, set_poly_lookup_map,
@@ -20,7 +23,7 @@ use super::type_helpers::*;
 /// Walk through the CANboat database and emit code for the requested PGNs.
 pub(crate) fn run_pgns_gen(
     canboat_value: &Value,
-    pgns_to_generate: Vec<u32>,
+    manifest_pgns: &[Pgn],
 ) -> Result<String, BuildError> {
     // Prepare tracking structures (polymorphic PGNs, caches, etc.).
     let lookup_enum_map = set_lookup_enum_map(canboat_value)?;
@@ -32,20 +35,22 @@ pub(crate) fn run_pgns_gen(
 
     let mut buffer_pgn_code = String::new();
 
-    writeln!(&mut buffer_pgn_code, "use super::lookups::*;")?;
-    writeln!(
-        buffer_pgn_code,
-        "use crate::core::{{PgnDescriptor, PgnValue, PgnBytes, RepeatingFieldSet}};\n\n"
-    )?;
+    // Types only some PGNs need, imported once the generated PGNs are known.
+    let mut optional_imports: BTreeSet<&'static str> = BTreeSet::new();
+
+    // PGN types actually emitted, in generation order, for the `N2kMessage` dispatcher.
+    let mut message_variants: Vec<MessageVariant> = Vec::new();
 
     if let Some(pgn_array) = canboat_value["PGNs"].as_array() {
         let mut poly_pgns_id_vec = Vec::new();
         for pgn_value in pgn_array {
             match serde_json::from_value::<PgnInstructions>(pgn_value.clone()) {
-                Ok(pgn_def) => {
-                    if !pgns_to_generate.contains(&pgn_def.pgn_id) {
+                Ok(mut pgn_def) => {
+                    let Some(entry) = manifest_pgns.iter().find(|p| p.id == pgn_def.pgn_id) else {
                         continue;
-                    }
+                    };
+                    pgn_def.max_repetitions = entry.max_repetitions;
+                    merge_iso_name_fields(&mut pgn_def);
 
                     match generate_pgn_code(
                         &pgn_def,
@@ -56,7 +61,34 @@ pub(crate) fn run_pgns_gen(
                         &mut poly_pgns_map,
                         &mut poly_pgns_id_vec,
                     ) {
-                        Ok(pgn_code) => buffer_pgn_code.push_str(&pgn_code),
+                        Ok(pgn_code) => {
+                            optional_imports
+                                .extend(pgn_def.fields.iter().filter_map(optional_import));
+                            // Polymorphic PGNs appear once per variant; the enum covers them all.
+                            if !pgn_code.is_empty() {
+                                let is_poly = poly_pgns_id_vec.contains(&pgn_def.pgn_id);
+                                if !message_variants.iter().any(|v| v.pgn_id == pgn_def.pgn_id) {
+                                    message_variants.push(MessageVariant {
+                                        pgn_id: pgn_def.pgn_id,
+                                        is_poly,
+                                        description: pgn_def.pgn_description.clone(),
+                                        descriptor: descriptor_path(&pgn_def, is_poly),
+                                        variants: Vec::new(),
+                                    });
+                                }
+                                let selector = variant_selector_value(&pgn_def, &lookup_enum_map)
+                                    .filter(|_| is_poly);
+                                let message = message_variants
+                                    .iter_mut()
+                                    .find(|v| v.pgn_id == pgn_def.pgn_id);
+                                if let (Some(selector), Some(message)) = (selector, message) {
+                                    message
+                                        .variants
+                                        .push((selector, descriptor_path(&pgn_def, true)));
+                                }
+                            }
+                            buffer_pgn_code.push_str(&pgn_code)
+                        }
                         Err(e) => {
                             println!(
                                 "cargo:warning=[PGN {}] Failed to generate code: {}",
@@ -77,7 +109,331 @@ pub(crate) fn run_pgns_gen(
             }
         }
     }
-    Ok(buffer_pgn_code)
+
+    buffer_pgn_code.push_str(&generate_message_enum(&message_variants)?);
+
+    let mut header = String::new();
+    writeln!(&mut header, "use super::lookups::*;")?;
+    writeln!(
+        header,
+        "use crate::core::{{PgnDescriptor, PgnValue, PgnBytes, RepeatingFieldSet}};"
+    )?;
+    for path in optional_imports {
+        writeln!(header, "use {};", path)?;
+    }
+    writeln!(header, "\n")?;
+    Ok(header + &buffer_pgn_code)
+}
+
+/// Path of the type generated for `field` when the common imports lack it.
+fn optional_import(field: &Fields) -> Option<&'static str> {
+    match map_to_fieldkind(field) {
+        FieldKind::Decimal => Some("crate::core::PgnDecimal"),
+        FieldKind::Variable => Some("crate::core::PgnVariable"),
+        FieldKind::IsoName => Some("crate::protocol::managment::iso_name::IsoName"),
+        _ => None,
+    }
+}
+
+//==================================================================================GENERATE_MESSAGE_ENUM
+/// PGN type emitted by the generator, as listed in the `N2kMessage` enum.
+struct MessageVariant {
+    pgn_id: u32,
+    is_poly: bool,
+    description: String,
+    /// Path of the descriptor constant; the first variant's one for polymorphic PGNs.
+    descriptor: String,
+    /// Selector value and descriptor path of each variant of a polymorphic PGN.
+    variants: Vec<(u32, String)>,
+}
+
+/// Value of the first field selecting this variant of a polymorphic PGN
+/// (e.g. 5 for the 126208 Write Fields variant).
+fn variant_selector_value(
+    pgn: &PgnInstructions,
+    lookup_enum_map: &HashMap<String, LookupEnum>,
+) -> Option<u32> {
+    let selector = pgn.variant_selector()?;
+    pgn.fields
+        .iter()
+        .find(|f| f.order == 1)?
+        .enum_direct_name
+        .as_ref()
+        .and_then(|name| lookup_enum_map.get(name))?
+        .enum_values
+        .iter()
+        .find(|v| v.name == selector)
+        .map(|v| v.value)
+}
+
+/// Path of the descriptor constant generated for `pgn`.
+fn descriptor_path(pgn: &PgnInstructions, is_poly: bool) -> String {
+    if is_poly {
+        format!(
+            "Pgn{}{}::PGN_{}_{}_DESCRIPTOR",
+            pgn.pgn_id,
+            to_pascal_case(&pgn.pgn_name, PascalCaseMode::Soft),
+            pgn.pgn_id,
+            to_snake_case(&pgn.pgn_name, "POLY").to_uppercase()
+        )
+    } else {
+        format!("Pgn{0}::PGN_{0}_DESCRIPTOR", pgn.pgn_id)
+    }
+}
+
+//==================================================================================MERGE_ISO_NAME
+/// Replace the NAME sub-fields CANboat spells out at the start of a payload
+/// (60928 Address Claim, 65240 Commanded Address) with one `ISO_NAME` field,
+/// so the generated struct exposes an `IsoName`.
+///
+/// Field orders are left untouched: Group Functions refer to fields by their
+/// CANboat order, so the sub-fields are kept aside to resolve those indices.
+fn merge_iso_name_fields(pgn: &mut PgnInstructions) {
+    let starts_with_name = pgn
+        .fields
+        .first()
+        .is_some_and(|field| field.id == "uniqueNumber" && field.bits_offset == Some(0));
+    // Index of the sub-field ending on bit 63 (Arbitrary Address Capable).
+    let last = pgn.fields.iter().position(|field| {
+        field
+            .bits_offset
+            .zip(field.bits_length)
+            .is_some_and(|(offset, length)| offset + length as u32 == 64)
+    });
+    let (true, Some(last)) = (starts_with_name, last) else {
+        return;
+    };
+
+    let name = Fields {
+        order: 1,
+        id: "name".to_string(),
+        name: "NAME".to_string(),
+        kind: "ISO_NAME".to_string(),
+        bits_length: Some(64),
+        bits_length_var: None,
+        bits_offset: Some(0),
+        signed: Some(false),
+        resolution: Some(1.0),
+        enum_direct_name: None,
+        enum_indirect_name: None,
+        enum_indirect_field_order: None,
+        physical_unit: None,
+        physical_qty: None,
+        description: Some("ISO 11783 NAME".to_string()),
+        enum_bit_name: None,
+        condition: None,
+    };
+    pgn.iso_name_fields = pgn.fields.splice(..=last, [name]).collect();
+    pgn.field_count = pgn.field_count.map(|count| count - last as u8);
+}
+
+/// Generate `N2kMessage`: one variant per generated PGN, with a `decode` dispatcher
+/// and a `pgn()` accessor.
+fn generate_message_enum(variants: &[MessageVariant]) -> Result<String, BuildError> {
+    let mut buffer = String::new();
+
+    writeln!(
+        buffer,
+        "/// Any PGN of the manifest, decoded into its generated type."
+    )?;
+    writeln!(buffer, "///")?;
+    writeln!(
+        buffer,
+        "/// Built by [`N2kMessage::decode`] from a PGN number and a reassembled payload."
+    )?;
+    writeln!(buffer, "#[derive(Debug, PartialEq, Copy, Clone)]")?;
+    // Variants are held by value: the crate does not allocate.
+    writeln!(buffer, "#[allow(clippy::large_enum_variant)]")?;
+    writeln!(buffer, "pub enum N2kMessage {{")?;
+    for variant in variants {
+        if variant.is_poly {
+            writeln!(
+                buffer,
+                "\t/// PGN {}, dispatched on its function code.",
+                variant.pgn_id
+            )?;
+        } else {
+            writeln!(
+                buffer,
+                "\t/// PGN {}: {}",
+                variant.pgn_id, variant.description
+            )?;
+        }
+        writeln!(buffer, "\tPgn{0}(Pgn{0}),", variant.pgn_id)?;
+    }
+    writeln!(buffer, "}}")?;
+
+    writeln!(buffer, "impl N2kMessage {{")?;
+    writeln!(
+        buffer,
+        "\t/// PGNs that [`N2kMessage::decode`] understands."
+    )?;
+    write!(buffer, "\tpub const SUPPORTED_PGNS: &'static [u32] = &[")?;
+    for variant in variants {
+        write!(buffer, "{}, ", variant.pgn_id)?;
+    }
+    writeln!(buffer, "];\n")?;
+
+    writeln!(
+        buffer,
+        "\t/// Decode `payload` with the generated type matching `pgn`."
+    )?;
+    writeln!(buffer, "\t///")?;
+    writeln!(
+        buffer,
+        "\t/// Returns [`DeserializationError::UnsupportedPgn`] when `pgn` is not part of the manifest."
+    )?;
+    writeln!(buffer, "\t#[allow(clippy::result_large_err)]")?;
+    writeln!(
+        buffer,
+        "\tpub fn decode(pgn: u32, payload: &[u8]) -> Result<Self, DeserializationError> {{"
+    )?;
+    writeln!(buffer, "\t\tmatch pgn {{")?;
+    for variant in variants {
+        writeln!(
+            buffer,
+            "\t\t\t{0} => Pgn{0}::from_payload(payload).map(Self::Pgn{0}),",
+            variant.pgn_id
+        )?;
+    }
+    writeln!(
+        buffer,
+        "\t\t\t_ => Err(DeserializationError::UnsupportedPgn {{ pgn }}),"
+    )?;
+    writeln!(buffer, "\t\t}}")?;
+    writeln!(buffer, "\t}}\n")?;
+
+    writeln!(
+        buffer,
+        "\t/// Static descriptor of `pgn` (transport type, priority, fields), if generated."
+    )?;
+    writeln!(buffer, "\t///")?;
+    writeln!(
+        buffer,
+        "\t/// Polymorphic PGNs return the descriptor of their first variant."
+    )?;
+    writeln!(
+        buffer,
+        "\tpub fn descriptor(pgn: u32) -> Option<&'static PgnDescriptor> {{"
+    )?;
+    writeln!(buffer, "\t\tmatch pgn {{")?;
+    for variant in variants {
+        writeln!(
+            buffer,
+            "\t\t\t{} => Some(&{}),",
+            variant.pgn_id, variant.descriptor
+        )?;
+    }
+    writeln!(buffer, "\t\t\t_ => None,")?;
+    writeln!(buffer, "\t\t}}")?;
+    writeln!(buffer, "\t}}\n")?;
+
+    writeln!(
+        buffer,
+        "\t/// Variants of a polymorphic `pgn` with the first-field value selecting"
+    )?;
+    writeln!(buffer, "\t/// each of them; empty for other PGNs.")?;
+    writeln!(
+        buffer,
+        "\tpub fn variant_descriptors(pgn: u32) -> &'static [(u64, &'static PgnDescriptor)] {{"
+    )?;
+    writeln!(buffer, "\t\tmatch pgn {{")?;
+    for variant in variants.iter().filter(|variant| variant.is_poly) {
+        write!(buffer, "\t\t\t{} => &[", variant.pgn_id)?;
+        for (selector, descriptor) in &variant.variants {
+            write!(buffer, "({}, &{}), ", selector, descriptor)?;
+        }
+        writeln!(buffer, "],")?;
+    }
+    writeln!(buffer, "\t\t\t_ => &[],")?;
+    writeln!(buffer, "\t\t}}")?;
+    writeln!(buffer, "\t}}\n")?;
+
+    writeln!(buffer, "\t/// PGN number of the decoded message.")?;
+    writeln!(buffer, "\tpub const fn pgn(&self) -> u32 {{")?;
+    writeln!(buffer, "\t\tmatch self {{")?;
+    for variant in variants {
+        writeln!(buffer, "\t\t\tSelf::Pgn{0}(_) => {0},", variant.pgn_id)?;
+    }
+    writeln!(buffer, "\t\t}}")?;
+    writeln!(buffer, "\t}}\n")?;
+
+    writeln!(
+        buffer,
+        "\t/// Descriptor of the decoded layout; polymorphic PGNs resolve to the variant held."
+    )?;
+    writeln!(
+        buffer,
+        "\tpub fn message_descriptor(&self) -> &'static PgnDescriptor {{"
+    )?;
+    writeln!(buffer, "\t\tmatch self {{")?;
+    for variant in variants {
+        if variant.is_poly {
+            writeln!(
+                buffer,
+                "\t\t\tSelf::Pgn{}(inner) => inner.variant_descriptor(),",
+                variant.pgn_id
+            )?;
+        } else {
+            writeln!(
+                buffer,
+                "\t\t\tSelf::Pgn{}(_) => &{},",
+                variant.pgn_id, variant.descriptor
+            )?;
+        }
+    }
+    writeln!(buffer, "\t\t}}")?;
+    writeln!(buffer, "\t}}")?;
+    writeln!(buffer, "}}")?;
+
+    // Field access delegated to the decoded PGN.
+    writeln!(buffer, "impl FieldAccess for N2kMessage {{")?;
+    let delegates = [
+        (
+            "fn field(&self, id: &'static str) -> Option<PgnValue>",
+            "field(id)",
+        ),
+        (
+            "fn field_mut(&mut self, id: &'static str, value: PgnValue) -> Option<()>",
+            "field_mut(id, value)",
+        ),
+        (
+            "fn repetitive_field(&self, array_id: &'static str, index: usize, field_id: &'static str) -> Option<PgnValue>",
+            "repetitive_field(array_id, index, field_id)",
+        ),
+        (
+            "fn repetitive_field_mut(&mut self, array_id: &'static str, index: usize, field_id: &'static str, value: PgnValue) -> Option<()>",
+            "repetitive_field_mut(array_id, index, field_id, value)",
+        ),
+        (
+            "fn repetitive_count(&self, array_id: &'static str) -> Option<usize>",
+            "repetitive_count(array_id)",
+        ),
+        (
+            "fn set_repetitive_count(&mut self, array_id: &'static str, count: usize) -> Option<()>",
+            "set_repetitive_count(array_id, count)",
+        ),
+        (
+            "fn variable_field(&self, pgn: u32, index: u8, selector: Option<u64>) -> Result<Option<&'static FieldDescriptor>, DeserializationError>",
+            "variable_field(pgn, index, selector)",
+        ),
+    ];
+    for (signature, call) in delegates {
+        writeln!(buffer, "\t{} {{", signature)?;
+        writeln!(buffer, "\t\tmatch self {{")?;
+        for variant in variants {
+            writeln!(
+                buffer,
+                "\t\t\tSelf::Pgn{}(inner) => inner.{},",
+                variant.pgn_id, call
+            )?;
+        }
+        writeln!(buffer, "\t\t}}")?;
+        writeln!(buffer, "\t}}")?;
+    }
+    writeln!(buffer, "}}")?;
+
+    Ok(buffer)
 }
 
 /// Assemble code (struct/impl/enum) for a specific PGN.
@@ -90,19 +446,21 @@ fn generate_pgn_code(
     poly_pgns_map: &mut HashMap<u32, Vec<PolyPgn>>,
     poly_pgns_id_vec: &mut Vec<u32>,
 ) -> Result<String, BuildError> {
-    // Guard: skip PGNs with multiple repeating groups (not supported yet).
-    // TODO: support multiple repeating groups (RepeatingFieldSet2, RepeatingFieldSet3)
-    if pgn.repeating_field_set_2_size.is_some() {
+    // Guard: entries sharing a polymorphic PGN without selecting a variant
+    // (e.g. the 0x1ED00-0x1EE00 range placeholder listed under 126208).
+    let is_poly_pgn =
+        poly_pgns_map.contains_key(&pgn.pgn_id) || poly_pgns_id_vec.contains(&pgn.pgn_id);
+    if is_poly_pgn && pgn.variant_selector().is_none() {
         return Ok(String::new());
     }
 
     let mut buffer = String::new();
 
     // Extract metadata for repeating fields (if any)
-    let repeating_info = RepeatingFieldSetInfo::extract_from_pgn(pgn, 1);
+    let repeating_sets = RepeatingFieldSetInfo::extract_all(pgn);
 
-    // Generate the repeating-field structure when applicable
-    if let Some(ref info) = repeating_info {
+    // Generate the repeating-field structures when applicable
+    for info in &repeating_sets {
         buffer.push_str(&generate_repetitive_struct(
             pgn,
             info,
@@ -127,7 +485,7 @@ fn generate_pgn_code(
     buffer.push_str(&generate_struct_definition(
         pgn,
         is_poly,
-        repeating_info.as_ref(),
+        &repeating_sets,
         lookup_enum_map,
         lookup_indir_map,
         lookup_bit_map,
@@ -142,12 +500,22 @@ fn generate_pgn_code(
     buffer.push_str(&generate_trait_impl(
         pgn,
         is_poly,
-        repeating_info.as_ref(),
+        &repeating_sets,
         lookup_enum_map,
         lookup_indir_map,
         lookup_bit_map,
     )?);
 
+    // Typed builders for the Group Function variants
+    if pgn.pgn_id == GROUP_FUNCTION_PGN {
+        buffer.push_str(&generate_group_function_builder(
+            pgn,
+            repeating_sets.first(),
+            lookup_enum_map,
+            lookup_indir_map,
+        )?);
+    }
+
     Ok(buffer)
 }
 
@@ -161,6 +529,7 @@ fn generate_enum_definition(
     let enum_name = format! {"Pgn{}", pgn.pgn_id};
 
     writeln!(buffer, "#[derive(Debug, PartialEq, Copy, Clone)]")?;
+    writeln!(buffer, "#[allow(clippy::large_enum_variant)]")?;
     writeln!(buffer, "pub enum {} {{", enum_name)?;
 
     if let Some(poly_pgn_vec) = poly_pgns_map.get(&pgn.pgn_id) {
@@ -182,7 +551,7 @@ fn generate_enum_definition(
 fn generate_struct_definition(
     pgn: &PgnInstructions,
     is_poly: bool,
-    repeating_info: Option<&RepeatingFieldSetInfo>,
+    repeating_sets: &[RepeatingFieldSetInfo],
     lookup_enum_map: &HashMap<String, LookupEnum>,
     lookup_indir_map: &HashMap<String, LookupIndirEnum>,
     _lookup_bit_map: &HashMap<String, LookupBitEnum>,
@@ -207,20 +576,11 @@ fn generate_struct_definition(
     }
     writeln!(buffer, "pub struct {} {{", struct_name)?;
 
-    // Determine which fields must be excluded (those in the repeating group)
-    let excluded_range = if let Some(info) = repeating_info {
-        Some(info.start_field_index..(info.start_field_index + info.size))
-    } else {
-        None
-    };
-
-    // Generate regular fields, excluding the repeating group
+    // Generate regular fields, excluding the repeating groups
     for (idx, field) in pgn.fields.iter().enumerate() {
-        // Skip fields that belong to the repeating group
-        if let Some(ref range) = excluded_range {
-            if range.contains(&idx) {
-                continue;
-            }
+        // Skip fields that belong to a repeating group
+        if repeating_sets.iter().any(|info| info.contains(idx)) {
+            continue;
         }
 
         let field_name = to_snake_case(&field.id, "field");
@@ -258,8 +618,8 @@ fn generate_struct_definition(
         }
     }
 
-    // Add repeating fields (array + counter)
-    if let Some(info) = repeating_info {
+    // Add repeating fields (array + counter) for each group
+    for info in repeating_sets {
         buffer.push_str(&generate_repetitive_fields(info)?);
     }
 
@@ -275,6 +635,15 @@ fn generate_enum_trait_impl(
 ) -> Result<String, BuildError> {
     let mut buffer = String::new();
 
+    //==========================================impl PgnMetadata
+    writeln!(buffer, "impl PgnMetadata for Pgn{} {{", pgn.pgn_id)?;
+    writeln!(
+        buffer,
+        "\tconst DESCRIPTOR: PgnDescriptor = {};",
+        descriptor_path(pgn, true)
+    )?;
+    writeln!(buffer, "}}")?;
+
     //==========================================impl PgnData
     writeln!(buffer, "impl PgnData for Pgn{} {{", pgn.pgn_id)?;
     //======================fn from_payload
@@ -340,7 +709,7 @@ fn generate_enum_trait_impl(
     )?;
     writeln!(
         buffer,
-        "\t\t\t_ => return Err(DeserializationError::MalformedData),"
+        "\t\t\t_ => Err(DeserializationError::MalformedData),"
     )?;
 
     writeln!(buffer, "\t\t}}")?; // End of match function_code
@@ -426,8 +795,86 @@ fn generate_enum_trait_impl(
     )?;
     writeln!(buffer, "\t\t}}")?; // end match_self
     writeln!(buffer, "\t}}")?; // end field_mut
+
+    //======================repeating field helpers
+    let delegates = [
+        (
+            "fn repetitive_field(&self, array_id: &'static str, index: usize, field_id: &'static str) -> Option<PgnValue>",
+            "repetitive_field(array_id, index, field_id)",
+        ),
+        (
+            "fn repetitive_field_mut(&mut self, array_id: &'static str, index: usize, field_id: &'static str, value: PgnValue) -> Option<()>",
+            "repetitive_field_mut(array_id, index, field_id, value)",
+        ),
+        (
+            "fn repetitive_count(&self, array_id: &'static str) -> Option<usize>",
+            "repetitive_count(array_id)",
+        ),
+        (
+            "fn set_repetitive_count(&mut self, array_id: &'static str, count: usize) -> Option<()>",
+            "set_repetitive_count(array_id, count)",
+        ),
+        (
+            "fn variable_field(&self, pgn: u32, index: u8, selector: Option<u64>) -> Result<Option<&'static FieldDescriptor>, DeserializationError>",
+            "variable_field(pgn, index, selector)",
+        ),
+    ];
+    for (signature, call) in delegates {
+        writeln!(buffer)?;
+        writeln!(buffer, "\t{} {{", signature)?;
+        writeln!(buffer, "\t\tmatch self {{")?;
+        generate_enum_impl_helper(
+            &mut buffer,
+            pgn,
+            poly_pgns_map,
+            poly_lookup_map,
+            |writer, _lookup, poly_pgn| {
+                writeln!(
+                    writer,
+                    "\t\t\tPgn{}::{}(inner) => inner.{},",
+                    pgn.pgn_id, poly_pgn.name, call
+                )
+            },
+        )?;
+        writeln!(buffer, "\t\t}}")?; // end match_self
+        writeln!(buffer, "\t}}")?;
+    }
     writeln!(buffer, "}}")?; // end impl FieldAccess
     writeln!(buffer)?;
+
+    //==========================================fn variant_descriptor
+    writeln!(buffer, "impl Pgn{} {{", pgn.pgn_id)?;
+    writeln!(
+        buffer,
+        "\t/// Descriptor of the variant held, selected by its function code."
+    )?;
+    writeln!(
+        buffer,
+        "\tpub fn variant_descriptor(&self) -> &'static PgnDescriptor {{"
+    )?;
+    writeln!(buffer, "\t\tmatch self {{")?;
+    generate_enum_impl_helper(
+        &mut buffer,
+        pgn,
+        poly_pgns_map,
+        poly_lookup_map,
+        |writer, _lookup, poly_pgn| {
+            writeln!(
+                writer,
+                "\t\t\tPgn{}::{}(_) => &Pgn{}{}::PGN_{}_{}_DESCRIPTOR,",
+                pgn.pgn_id,
+                poly_pgn.name,
+                pgn.pgn_id,
+                poly_pgn.name,
+                pgn.pgn_id,
+                to_snake_case(&poly_pgn.name, "POLY").to_uppercase()
+            )
+        },
+    )?;
+    writeln!(buffer, "\t\t}}")?; // end match_self
+    writeln!(buffer, "\t}}")?; // end variant_descriptor
+    writeln!(buffer, "}}")?;
+    writeln!(buffer)?;
     Ok(buffer)
 }
 //==================================================================================HELPER_IMPL_POLY_PGN
@@ -469,7 +916,7 @@ fn generate_impl_bloc_with_descriptor(
     // Extract repeating-field information.
     // Note: unlike struct/trait generation we MUST keep repeating fields inside the
     // descriptor because the codec engine relies on them for the binary layout.
-    let repeating_info = RepeatingFieldSetInfo::extract_from_pgn(pgn, 1);
+    let repeating_sets = RepeatingFieldSetInfo::extract_all(pgn);
     let mut buffer = String::new();
     let pgn_id = if is_poly {
         format!(
@@ -514,86 +961,48 @@ fn generate_impl_bloc_with_descriptor(
 
     // Emit every field, including those belonging to repeating groups, so the codec
     // engine has an accurate binary descriptor.
-    for (_idx, field) in pgn.fields.iter().enumerate() {
-        let is_resolution = field.resolution.filter(|&r| r != 1.0);
-        let is_signed = field.signed.filter(|&s| s);
-
-        let pascal_cased = |name: &Option<String>| {
-            name.as_ref()
-                .map(|str| to_pascal_case(&str.to_lowercase(), PascalCaseMode::Hard))
-        };
-
-        writeln!(buffer, "\t\t\tFieldDescriptor {{")?;
-        writeln!(
-            buffer,
-            "\t\t\t\tid: \"{}\",",
-            to_pascal_case(&field.id, PascalCaseMode::Soft)
-        )?;
-        writeln!(buffer, "\t\t\t\tname: \"{}\",", field.name)?;
-        writeln!(
-            buffer,
-            "\t\t\t\tkind: FieldKind::{:?},",
-            map_to_fieldkind(field)
-        )?;
-        writeln!(buffer, "\t\t\t\tbits_length: {:?},", field.bits_length)?;
-        let bits_length_var = if field.bits_length_var.unwrap_or(false) {
-            Some(0u32)
-        } else {
-            None
-        };
-        writeln!(buffer, "\t\t\t\tbits_length_var: {:?},", bits_length_var)?;
-        writeln!(buffer, "\t\t\t\tbits_offset: {:?},", field.bits_offset)?;
-        writeln!(buffer, "\t\t\t\tis_signed: {:?},", is_signed)?;
-        writeln!(buffer, "\t\t\t\tresolution: {:?},", is_resolution)?;
-        writeln!(
-            buffer,
-            "\t\t\t\tenum_direct_name: {:?},",
-            pascal_cased(&field.enum_direct_name)
-        )?;
-        writeln!(
-            buffer,
-            "\t\t\t\tenum_indirect_name: {:?},",
-            pascal_cased(&field.enum_indirect_name)
-        )?;
-        writeln!(
-            buffer,
-            "\t\t\t\tenum_indirect_field_order: {:?},",
-            field.enum_indirect_field_order
-        )?;
-        writeln!(buffer, "\t\t\t\tphysical_unit: {:?},", field.physical_unit)?;
-        writeln!(buffer, "\t\t\t\tphysical_qtity: {:?},", field.physical_qty)?;
-        writeln!(buffer, "\t\t\t}},")?;
-    }
+    write_field_descriptors(&mut buffer, &pgn.fields)?;
     writeln!(buffer, "\t\t],")?;
 
     // Add repeating-group metadata to the descriptor
-    if let Some(ref info) = repeating_info {
+    if repeating_sets.is_empty() {
+        writeln!(buffer, "\t\trepeating_field_sets: &[],")?;
+    } else {
         writeln!(buffer, "\t\trepeating_field_sets: &[")?;
-        writeln!(buffer, "\t\t\tRepeatingFieldSet {{")?;
-        writeln!(buffer, "\t\t\t\tarray_id: \"{}\",", info.array_field_name)?;
-        writeln!(
-            buffer,
-            "\t\t\t\tcount_field_index: {:?},",
-            info.count_field_index
-        )?;
-        writeln!(
-            buffer,
-            "\t\t\t\tstart_field_index: {},",
-            info.start_field_index
-        )?;
-        writeln!(buffer, "\t\t\t\tsize: {},", info.size)?;
-        writeln!(buffer, "\t\t\t\tmax_repetitions: {},", info.max_repetitions)?;
-        writeln!(buffer, "\t\t\t}},")?;
+        for info in &repeating_sets {
+            writeln!(buffer, "\t\t\tRepeatingFieldSet {{")?;
+            writeln!(buffer, "\t\t\t\tarray_id: \"{}\",", info.array_field_name)?;
+            writeln!(
+                buffer,
+                "\t\t\t\tcount_field_index: {:?},",
+                info.count_field_index
+            )?;
+            writeln!(
+                buffer,
+                "\t\t\t\tstart_field_index: {},",
+                info.start_field_index
+            )?;
+            writeln!(buffer, "\t\t\t\tsize: {},", info.size)?;
+            writeln!(buffer, "\t\t\t\tmax_repetitions: {},", info.max_repetitions)?;
+            writeln!(buffer, "\t\t\t}},")?;
+        }
         writeln!(buffer, "\t\t],")?;
+    }
+
+    if pgn.iso_name_fields.is_empty() {
+        writeln!(buffer, "\t\tiso_name_fields: &[],")?;
     } else {
-        writeln!(buffer, "\t\trepeating_field_sets: &[],")?;
+        writeln!(buffer, "\t\tiso_name_fields: &[")?;
+        write_field_descriptors(&mut buffer, &pgn.iso_name_fields)?;
+        writeln!(buffer, "\t\t],")?;
     }
 
     writeln!(buffer, "\t}};")?;
     writeln!(buffer)?;
     buffer.push_str(&generate_new_fn(
         pgn,
-        RepeatingFieldSetInfo::extract_from_pgn(pgn, 1).as_ref(),
+        is_poly,
+        &repeating_sets,
         lookup_enum_map,
         lookup_indir_map,
     )?);
@@ -634,11 +1043,92 @@ fn generate_impl_bloc_with_descriptor(
         }
     }
 
+    writeln!(buffer, "}}")?;
+    writeln!(buffer)?;
+
+    writeln!(buffer, "impl Default for {} {{", struct_name)?;
+    writeln!(buffer, "\tfn default() -> Self {{")?;
+    writeln!(buffer, "\t\tSelf::new()")?;
+    writeln!(buffer, "\t}}")?;
+    writeln!(buffer, "}}")?;
+    writeln!(buffer)?;
+
+    writeln!(buffer, "impl PgnMetadata for {} {{", struct_name)?;
+    writeln!(
+        buffer,
+        "\tconst DESCRIPTOR: PgnDescriptor = Self::{};",
+        decriptor_name
+    )?;
     writeln!(buffer, "}}")?;
     writeln!(buffer)?;
     Ok(buffer)
 }
 
+/// Emit one `FieldDescriptor` literal per field, inside a descriptor slice.
+fn write_field_descriptors(buffer: &mut String, fields: &[Fields]) -> Result<(), BuildError> {
+    for field in fields {
+        let is_resolution = field.resolution.filter(|&r| r != 1.0);
+        let is_signed = field.signed.filter(|&s| s);
+
+        let pascal_cased = |name: &Option<String>| {
+            name.as_ref()
+                .map(|str| to_pascal_case(&str.to_lowercase(), PascalCaseMode::Hard))
+        };
+
+        writeln!(buffer, "\t\t\tFieldDescriptor {{")?;
+        writeln!(
+            buffer,
+            "\t\t\t\tid: \"{}\",",
+            to_pascal_case(&field.id, PascalCaseMode::Soft)
+        )?;
+        writeln!(buffer, "\t\t\t\tname: \"{}\",", field.name)?;
+        writeln!(
+            buffer,
+            "\t\t\t\tkind: FieldKind::{:?},",
+            map_to_fieldkind(field)
+        )?;
+        writeln!(buffer, "\t\t\t\tbits_length: {:?},", field.bits_length)?;
+        let bits_length_var = if field.bits_length_var.unwrap_or(false) {
+            Some(0u32)
+        } else {
+            None
+        };
+        writeln!(buffer, "\t\t\t\tbits_length_var: {:?},", bits_length_var)?;
+        writeln!(buffer, "\t\t\t\tbits_offset: {:?},", field.bits_offset)?;
+        writeln!(buffer, "\t\t\t\tis_signed: {:?},", is_signed)?;
+        writeln!(buffer, "\t\t\t\tresolution: {:?},", is_resolution)?;
+        writeln!(
+            buffer,
+            "\t\t\t\tenum_direct_name: {:?},",
+            pascal_cased(&field.enum_direct_name)
+        )?;
+        writeln!(
+            buffer,
+            "\t\t\t\tenum_bit_name: {:?},",
+            pascal_cased(&field.enum_bit_name)
+        )?;
+        writeln!(
+            buffer,
+            "\t\t\t\tenum_indirect_name: {:?},",
+            pascal_cased(&field.enum_indirect_name)
+        )?;
+        writeln!(
+            buffer,
+            "\t\t\t\tenum_indirect_field_order: {:?},",
+            field.enum_indirect_field_order
+        )?;
+        writeln!(buffer, "\t\t\t\tphysical_unit: {:?},", field.physical_unit)?;
+        writeln!(buffer, "\t\t\t\tphysical_qtity: {:?},", field.physical_qty)?;
+        writeln!(
+            buffer,
+            "\t\t\t\tproprietary_only: {},",
+            field.condition.as_deref() == Some("PGNIsProprietary")
+        )?;
+        writeln!(buffer, "\t\t\t}},")?;
+    }
+    Ok(())
+}
+
 /// Generate helpers to test/modify bits within a BITLOOKUP field.
 fn generate_bitlookup_helpers(
     field_id: &str,
@@ -703,7 +1193,7 @@ fn generate_bitlookup_helpers(
 fn generate_trait_impl(
     pgn: &PgnInstructions,
     is_poly: bool,
-    repeating_info: Option<&RepeatingFieldSetInfo>,
+    repeating_sets: &[RepeatingFieldSetInfo],
     lookup_enum_map: &HashMap<String, LookupEnum>,
     lookup_indir_map: &HashMap<String, LookupIndirEnum>,
     _lookup_bit_map: &HashMap<String, LookupBitEnum>,
@@ -732,13 +1222,6 @@ fn generate_trait_impl(
     // Implement FieldAccess
     writeln!(buffer, "impl FieldAccess for {} {{", struct_name)?;
 
-    // Determine which fields must be excluded (those in the repeating group)
-    let excluded_range = if let Some(info) = repeating_info {
-        Some(info.start_field_index..(info.start_field_index + info.size))
-    } else {
-        None
-    };
-
     // `field` method (read access)
     writeln!(
         buffer,
@@ -746,11 +1229,9 @@ fn generate_trait_impl(
     )?;
     writeln!(buffer, "\t\tmatch id {{")?;
     for (idx, field) in pgn.fields.iter().enumerate() {
-        // Skip fields that belong to the repeating group
-        if let Some(ref range) = excluded_range {
-            if range.contains(&idx) {
-                continue;
-            }
+        // Skip fields that belong to a repeating group
+        if repeating_sets.iter().any(|info| info.contains(idx)) {
+            continue;
         }
         // if PASSIVE_FIELDS.contains(&field.kind.as_str()) {
         //     continue;
@@ -761,11 +1242,9 @@ fn generate_trait_impl(
         let lookup_repr = lookup_repr_from_field(field, lookup_enum_map, lookup_indir_map);
 
         // Detect whether this field serves as the counter for a repeating group
-        let is_counter_field = repeating_info
-            .as_ref()
-            .and_then(|info| info.count_field_index)
-            .map(|counter_idx| counter_idx == idx)
-            .unwrap_or(false);
+        let counted_set = repeating_sets
+            .iter()
+            .find(|info| info.count_field_index == Some(idx));
 
         // For lookups, rely on the enum representation instead of the Rust enum type
         let type_for_variant = if matches!(
@@ -778,9 +1257,9 @@ fn generate_trait_impl(
         };
         let pgn_value_variant = get_pgn_value_variant_from_type(type_for_variant, field)?;
 
-        if is_counter_field {
+        if let Some(info) = counted_set {
             // This field is a counter: expose the `_count` backing field instead of the raw field
-            let count_field_name = &repeating_info.as_ref().unwrap().count_field_name;
+            let count_field_name = &info.count_field_name;
             writeln!(
                 buffer,
                 "\t\t\t\"{}\" => Some({}(self.{} as {})),",
@@ -818,10 +1297,9 @@ fn generate_trait_impl(
                 }
                 FieldKind::StringFix => {
                     writeln!(buffer, "\t\t\t\"{}\" => {{ ", field_name_pascal)?;
-                    writeln!(buffer, "\t\t\t\tlet mut bytes = PgnBytes::default();",)?;
                     writeln!(
                         buffer,
-                        "\t\t\t\tbytes.len = self.{}.len();",
+                        "\t\t\t\tlet mut bytes = PgnBytes {{ len: self.{}.len(), ..PgnBytes::default() }};",
                         field_name_snake
                     )?;
                     writeln!(
@@ -838,10 +1316,9 @@ fn generate_trait_impl(
                     if field_type_str.starts_with("[") {
                         // Array path: copy bytes into the temporary buffer
                         writeln!(buffer, "\t\t\t\"{}\" => {{ ", field_name_pascal)?;
-                        writeln!(buffer, "\t\t\t\tlet mut bytes = PgnBytes::default();",)?;
                         writeln!(
                             buffer,
-                            "\t\t\t\tbytes.len = self.{}.len();",
+                            "\t\t\t\tlet mut bytes = PgnBytes {{ len: self.{}.len(), ..PgnBytes::default() }};",
                             field_name_snake
                         )?;
                         writeln!(
@@ -863,7 +1340,14 @@ fn generate_trait_impl(
                 FieldKind::StringLz | FieldKind::StringLau => {
                     writeln!(
                         buffer,
-                        "\t\t\t\"{}\" => Some(PgnValue::Bytes(self.{}.clone())),",
+                        "\t\t\t\"{}\" => Some(PgnValue::Bytes(self.{})),",
+                        field_name_pascal, field_name_snake
+                    )?;
+                }
+                FieldKind::IsoName => {
+                    writeln!(
+                        buffer,
+                        "\t\t\t\"{}\" => Some(PgnValue::U64(self.{}.raw())),",
                         field_name_pascal, field_name_snake
                     )?;
                 }
@@ -888,11 +1372,9 @@ fn generate_trait_impl(
     )?;
     writeln!(buffer, "\t\tmatch id {{")?;
     for (idx, field) in pgn.fields.iter().enumerate() {
-        // Skip fields that belong to the repeating group
-        if let Some(ref range) = excluded_range {
-            if range.contains(&idx) {
-                continue;
-            }
+        // Skip fields that belong to a repeating group
+        if repeating_sets.iter().any(|info| info.contains(idx)) {
+            continue;
         }
         // if PASSIVE_FIELDS.contains(&field.kind.as_str()) {
         //     continue;
@@ -902,11 +1384,9 @@ fn generate_trait_impl(
         let field_type_str = map_type(field, lookup_enum_map, lookup_indir_map)?;
         let lookup_repr = lookup_repr_from_field(field, lookup_enum_map, lookup_indir_map);
 
-        let is_counter_field = repeating_info
-            .as_ref()
-            .and_then(|info| info.count_field_index)
-            .map(|counter_idx| counter_idx == idx)
-            .unwrap_or(false);
+        let counted_set = repeating_sets
+            .iter()
+            .find(|info| info.count_field_index == Some(idx));
 
         // For lookups, rely on the enum representation instead of the Rust enum type
         let type_for_variant = if matches!(
@@ -919,8 +1399,8 @@ fn generate_trait_impl(
         };
         let pgn_value_variant = get_pgn_value_variant_from_type(type_for_variant, field)?;
 
-        if is_counter_field {
-            let count_field_name = &repeating_info.as_ref().unwrap().count_field_name;
+        if let Some(info) = counted_set {
+            let count_field_name = &info.count_field_name;
             writeln!(buffer, "\t\t\t\"{}\" => {{ ", field_name_pascal)?;
             writeln!(
                 buffer,
@@ -969,6 +1449,14 @@ fn generate_trait_impl(
                     writeln!(buffer, "\t\t\t\t}} else {{")?;
                     writeln!(buffer, "\t\t\t\t\tNone")?;
                     writeln!(buffer, "\t\t\t\t}}")?;
+                } else if matches!(field_type_str.as_str(), "u8" | "u16" | "u32" | "u64") {
+                    // Lookup without a generated enum: the raw value widens losslessly.
+                    writeln!(buffer, "\t\t\t\tif let PgnValue::U8(val) = value {{")?;
+                    writeln!(buffer, "\t\t\t\t\tself.{} = val.into();", field_name_snake)?;
+                    writeln!(buffer, "\t\t\t\t\tSome(())")?;
+                    writeln!(buffer, "\t\t\t\t}} else {{")?;
+                    writeln!(buffer, "\t\t\t\t\tNone")?;
+                    writeln!(buffer, "\t\t\t\t}}")?;
                 } else {
                     writeln!(buffer, "\t\t\t\tif let PgnValue::U8(val) = value {{")?;
                     writeln!(
@@ -1053,6 +1541,16 @@ fn generate_trait_impl(
                 writeln!(buffer, "\t\t\t\t\tNone")?;
                 writeln!(buffer, "\t\t\t\t}}")?;
             }
+            FieldKind::IsoName => {
+                writeln!(buffer, "\t\t\t\tif let PgnValue::U64(val) = value {{")?;
+                writeln!(
+                    buffer,
+                    "\t\t\t\t\tself.{} = IsoName::from_raw(val);",
+                    field_name_snake
+                )?;
+                writeln!(buffer, "\t\t\t\t\tSome(())")?;
+                writeln!(buffer, "\t\t\t\t}} else {{\n\t\t\t\t\tNone\n\t\t\t\t}}")?;
+            }
             _ => {
                 writeln!(
                     buffer,
@@ -1071,15 +1569,30 @@ fn generate_trait_impl(
     writeln!(buffer, "\t}}")?;
 
     // Generate trait methods for repeating fields when present
-    if let Some(info) = repeating_info {
+    if !repeating_sets.is_empty() {
         buffer.push_str(&generate_repetitive_field_access(
             pgn,
-            info,
+            repeating_sets,
             lookup_enum_map,
             lookup_indir_map,
         )?);
     }
 
+    // Group Function parameters resolve their `Variable` values against the manifest
+    if pgn
+        .fields
+        .iter()
+        .any(|field| map_to_fieldkind(field) == FieldKind::Variable)
+    {
+        writeln!(buffer)?;
+        writeln!(
+            buffer,
+            "\tfn variable_field(&self, pgn: u32, index: u8, selector: Option<u64>) -> Result<Option<&'static FieldDescriptor>, DeserializationError> {{"
+        )?;
+        writeln!(buffer, "\t\tvariable_field(pgn, index, selector)")?;
+        writeln!(buffer, "\t}}")?;
+    }
+
     writeln!(buffer, "}}")?;
     writeln!(buffer)?;
 
@@ -1130,7 +1643,8 @@ fn default_implementation(pgn: &PgnInstructions, is_poly: bool) -> Result<String
 /// Emit the `new()` function associated with the generated struct.
 fn generate_new_fn(
     pgn: &PgnInstructions,
-    repeating_info: Option<&RepeatingFieldSetInfo>,
+    is_poly: bool,
+    repeating_sets: &[RepeatingFieldSetInfo],
     lookup_enum_map: &HashMap<String, LookupEnum>,
     lookup_indir_map: &HashMap<String, LookupIndirEnum>,
 ) -> Result<String, BuildError> {
@@ -1143,7 +1657,7 @@ fn generate_new_fn(
 
     // When repeating fields exist we cannot expose a `const fn`
     // car Default::default() n'est pas const
-    if repeating_info.is_some() {
+    if !repeating_sets.is_empty() {
         writeln!(buffer, "\tpub fn new() -> Self {{")?;
     } else {
         writeln!(buffer, "\tpub const fn new() -> Self {{")?;
@@ -1151,19 +1665,28 @@ fn generate_new_fn(
 
     writeln!(buffer, "\t\tSelf {{")?;
 
-    // Determine which fields must be excluded (those in the repeating group)
-    let excluded_range = if let Some(info) = repeating_info {
-        Some(info.start_field_index..(info.start_field_index + info.size))
-    } else {
-        None
-    };
+    // Polymorphic variants start with their own selector value (e.g. Request for
+    // 126208) so that a fresh instance encodes as the variant it was built from.
+    let selector_value = pgn
+        .variant_selector()
+        .filter(|_| is_poly)
+        .and_then(|selector| {
+            pgn.fields
+                .iter()
+                .find(|f| f.order == 1)?
+                .enum_direct_name
+                .as_ref()
+                .and_then(|name| lookup_enum_map.get(name))?
+                .enum_values
+                .iter()
+                .find(|v| v.name == selector)
+                .map(|v| to_pascal_case(&v.name.to_lowercase(), PascalCaseMode::Hard))
+        });
 
     for (idx, field) in pgn.fields.iter().enumerate() {
-        // Skip fields that belong to the repeating group
-        if let Some(ref range) = excluded_range {
-            if range.contains(&idx) {
-                continue;
-            }
+        // Skip fields that belong to a repeating group
+        if repeating_sets.iter().any(|info| info.contains(idx)) {
+            continue;
         }
         let field_name = to_snake_case(&field.id, "field");
         let field_kind = map_to_fieldkind(field);
@@ -1178,16 +1701,21 @@ fn generate_new_fn(
                 let bits = field.bits_length.unwrap_or(0);
                 if bits > 0 && bits <= 64 {
                     let val = u64::MAX >> (64 - bits);
-                    format!("{}u64 as {}", val, field_type)
+                    if field_type == "u64" {
+                        format!("{}u64", val)
+                    } else {
+                        format!("{}u64 as {}", val, field_type)
+                    }
                 } else {
                     "0".to_string() // Safe fallback
                 }
             }
 
             // All other public fields default to zero.
-            FieldKind::Lookup => {
-                format!("{}::DEFAULT", field_type)
-            }
+            FieldKind::Lookup => match (&selector_value, field.order) {
+                (Some(variant), 1) => format!("{}::{}", field_type, variant),
+                _ => format!("{}::DEFAULT", field_type),
+            },
 
             // INDIRECT_LOOKUP fields are stored as u8 values, initialized to zero.
             FieldKind::IndirectLookup => "0".to_string(),
@@ -1195,6 +1723,9 @@ fn generate_new_fn(
             _ => match field_type.as_str() {
                 "f32" | "f64" => "0.0".to_string(),
                 "PgnBytes" => "PgnBytes::new()".to_string(),
+                "PgnDecimal" => "PgnDecimal::new()".to_string(),
+                "PgnVariable" => "PgnVariable::new()".to_string(),
+                "IsoName" => "IsoName::from_raw(0)".to_string(),
                 slice if slice.starts_with("[") => {
                     // Array fields (e.g. [u8; N])
                     let size = slice.split(&['[', ';', ']'][..]).nth(2).unwrap_or("0");
@@ -1208,7 +1739,7 @@ fn generate_new_fn(
     }
 
     // Initialize repeating-field storage when available
-    if let Some(info) = repeating_info {
+    for info in repeating_sets {
         // Initialize repeating-structure array
         writeln!(
             buffer,
@@ -2,6 +2,7 @@
 pub mod conf;
 pub mod domain;
 pub mod errors;
+pub mod gen_group_function;
 pub mod gen_lookups;
 pub mod gen_pgns;
 pub mod name_helpers;
@@ -29,7 +29,7 @@ use std::fmt::Write;
 /// Metadata extracted for a repeating-field group.
 #[derive(Debug, Clone)]
 pub(crate) struct RepeatingFieldSetInfo {
-    /// Index of the field that stores the repetition counter (None = dynamic length)
+    /// Index of the field that stores the repetition counter (None = payload length)
     pub count_field_index: Option<usize>,
 
     /// Index of the first field in the group (0-based)
@@ -52,11 +52,23 @@ pub(crate) struct RepeatingFieldSetInfo {
 }
 
 impl RepeatingFieldSetInfo {
+    /// Extract the metadata of every repeating group of the PGN, in set order.
+    pub fn extract_all(pgn: &PgnInstructions) -> Vec<Self> {
+        (1..=2)
+            .filter_map(|set_number| Self::extract_from_pgn(pgn, set_number))
+            .collect()
+    }
+
+    /// Whether the field at `idx` belongs to the group.
+    pub fn contains(&self, idx: usize) -> bool {
+        (self.start_field_index..self.start_field_index + self.size).contains(&idx)
+    }
+
     /// Extract repeating-field metadata from the PGN definition.
     ///
     /// # Arguments
     /// * `pgn` - Full PGN definition from canboat.json
-    /// * `set_number` - Group number (1, 2, or 3)
+    /// * `set_number` - Group number (1 or 2)
     ///
     /// # Returns
     /// Returns `Some(RepeatingFieldSetInfo)` if the group exists, `None` otherwise.
@@ -89,14 +101,25 @@ impl RepeatingFieldSetInfo {
         // Derive the nested struct name from the group's first field.
         // Example: "satellitePrn" → "SatellitePrnInfo".
         // More reliable than using the counter, which may have ambiguous names.
-        let struct_name = {
+        let base_struct_name = {
             let first_field = pgn.fields.get(start_field_index)?;
             let base_name = to_pascal_case(&first_field.id, PascalCaseMode::Soft);
             format!("{}Info", base_name)
         };
 
         // Array name: plural snake_case form of the struct name ("SatelliteInfo" → "satellites").
-        let array_field_name = pluralize_field_name(&struct_name);
+        let array_field_name = pluralize_field_name(&base_struct_name);
+
+        // Variants of a polymorphic PGN share field names: prefix the struct with
+        // the variant selector ("ParameterInfo" → "RequestParameterInfo").
+        let struct_name = match pgn.variant_selector() {
+            Some(selector) => format!(
+                "{}{}",
+                to_pascal_case(selector, PascalCaseMode::Soft),
+                base_struct_name
+            ),
+            None => base_struct_name,
+        };
 
         // Counter name: array name + "_count" ("satellites" → "satellites_count").
         let count_field_name = format!("{}_count", array_field_name);
@@ -165,7 +188,8 @@ fn pluralize_field_name(struct_name: &str) -> String {
 /// 1. Compute the bit-size of a single group instance
 /// 2. Determine remaining payload space (223 bytes max)
 /// 3. Divide to obtain the maximum instance count
-/// 4. Clamp to a reasonable value (32 by default)
+/// 4. Clamp to the manifest's `max_repetitions`, or a reasonable value (32),
+///    whether a counter field drives the group or the payload length does
 ///
 /// # Arguments
 /// * `pgn` – PGN definition
@@ -184,8 +208,9 @@ fn calculate_max_repetitions(pgn: &PgnInstructions, start_index: usize, size: us
         }
     }
 
+    let limit = pgn.max_repetitions.unwrap_or(DEFAULT_MAX);
     if group_size_bits == 0 {
-        return DEFAULT_MAX;
+        return limit;
     }
 
     // Compute bit-size of the fixed portion (before the repeating group)
@@ -202,8 +227,8 @@ fn calculate_max_repetitions(pgn: &PgnInstructions, start_index: usize, size: us
     // Nombre max d'instances
     let calculated_max = available_bits / group_size_bits;
 
-    // Clamp to a reasonable value
-    calculated_max.min(DEFAULT_MAX)
+    // Clamp to the configured or default limit
+    calculated_max.min(limit)
 }
 
 /// Generate the Rust struct definition for a repeating-field group.
@@ -244,19 +269,23 @@ pub(crate) fn generate_repetitive_struct(
         buffer,
         "/// Represents a single element of the parent PGN's repeating data array."
     )?;
-    writeln!(
-        buffer,
-        "/// The number of instances is driven by the counter field at index {}.",
-        info.count_field_index
-            .map(|i| i.to_string())
-            .unwrap_or_else(|| "dynamic".to_string())
-    )?;
+    match info.count_field_index {
+        Some(counter_idx) => writeln!(
+            buffer,
+            "/// The number of instances is driven by the counter field at index {}.",
+            counter_idx
+        )?,
+        None => writeln!(
+            buffer,
+            "/// The number of instances is inferred from the payload length."
+        )?,
+    }
     writeln!(
         buffer,
         "/// Maximum number of instances: {}",
         info.max_repetitions
     )?;
-    writeln!(buffer, "#[derive(Debug, Clone, Copy, PartialEq)]")?;
+    writeln!(buffer, "#[derive(Debug, Clone, Copy, PartialEq, Default)]")?;
     writeln!(buffer, "pub struct {} {{", info.struct_name)?;
 
     // Generate fields for the repeating group
@@ -278,22 +307,6 @@ pub(crate) fn generate_repetitive_struct(
 
     writeln!(buffer, "}}\n")?;
 
-    // Generate the Default implementation
-    writeln!(buffer, "impl Default for {} {{", info.struct_name)?;
-    writeln!(buffer, "\tfn default() -> Self {{")?;
-    writeln!(buffer, "\t\tSelf {{")?;
-
-    for i in info.start_field_index..end_index {
-        if let Some(field) = pgn.fields.get(i) {
-            let field_name = to_snake_case(&field.id, "");
-            writeln!(buffer, "\t\t\t{}: Default::default(),", field_name)?;
-        }
-    }
-
-    writeln!(buffer, "\t\t}}")?;
-    writeln!(buffer, "\t}}")?;
-    writeln!(buffer, "}}\n")?;
-
     Ok(buffer)
 }
 
@@ -352,12 +365,16 @@ pub(crate) fn generate_repetitive_fields(
     )?;
     writeln!(buffer, "\t///")?;
 
-    if let Some(counter_idx) = info.count_field_index {
-        writeln!(
+    match info.count_field_index {
+        Some(counter_idx) => writeln!(
             buffer,
             "\t/// This value corresponds to the counter field at index {}",
             counter_idx
-        )?;
+        )?,
+        None => writeln!(
+            buffer,
+            "\t/// This value is inferred from the payload length when decoding"
+        )?,
     }
 
     writeln!(
@@ -398,25 +415,11 @@ pub(crate) fn generate_repetitive_fields(
 /// ```
 pub(crate) fn generate_repetitive_field_access(
     pgn: &PgnInstructions,
-    info: &RepeatingFieldSetInfo,
+    infos: &[RepeatingFieldSetInfo],
     lookup_enum_map: &HashMap<String, LookupEnum>,
     lookup_indir_map: &HashMap<String, LookupIndirEnum>,
 ) -> Result<String, BuildError> {
     let mut buffer = String::new();
-    let counter_field_props = if let Some(counter_idx) = info.count_field_index {
-        if let Some(field) = pgn.fields.get(counter_idx) {
-            Some((
-                to_snake_case(&field.id, "field"),
-                map_type(field, lookup_enum_map, lookup_indir_map)?,
-            ))
-        } else {
-            None
-        }
-    } else {
-        None
-    };
-
-    let end_index = (info.start_field_index + info.size).min(pgn.fields.len());
 
     // ==================== repetitive_field (read access) ====================
     writeln!(buffer)?;
@@ -425,79 +428,85 @@ pub(crate) fn generate_repetitive_field_access(
         "\tfn repetitive_field(&self, array_id: &'static str, index: usize, field_id: &'static str) -> Option<PgnValue> {{"
     )?;
     writeln!(buffer, "\t\tmatch array_id {{")?;
-    writeln!(buffer, "\t\t\t\"{}\" => {{", info.array_field_name)?;
+    for info in infos {
+        let end_index = (info.start_field_index + info.size).min(pgn.fields.len());
+        writeln!(buffer, "\t\t\t\"{}\" => {{", info.array_field_name)?;
 
-    // Bounds check
-    writeln!(
-        buffer,
-        "\t\t\t\tif index >= self.{} {{",
-        info.count_field_name
-    )?;
-    writeln!(buffer, "\t\t\t\t\treturn None;")?;
-    writeln!(buffer, "\t\t\t\t}}")?;
-
-    // Match on the element's fields
-    writeln!(buffer, "\t\t\t\tmatch field_id {{")?;
-    for i in info.start_field_index..end_index {
-        if let Some(field) = pgn.fields.get(i) {
-            let field_name_pascal = to_pascal_case(&field.id, PascalCaseMode::Soft);
-            let field_name_snake = to_snake_case(&field.id, "");
-            let field_type_str = map_type(field, lookup_enum_map, lookup_indir_map)?;
-            let lookup_repr = lookup_repr_from_field(field, lookup_enum_map, lookup_indir_map);
-
-            // For lookups, operate on the enum representation rather than the Rust enum type
-            let type_for_variant = if matches!(
-                map_to_fieldkind(field),
-                FieldKind::Lookup | FieldKind::IndirectLookup
-            ) {
-                lookup_repr.unwrap_or("u8")
-            } else {
-                &field_type_str
-            };
-            let pgn_value_variant = get_pgn_value_variant_from_type(type_for_variant, field)?;
-
-            if matches!(
-                map_to_fieldkind(field),
-                FieldKind::Lookup | FieldKind::IndirectLookup
-            ) {
-                // For lookups, convert the enum into its primitive representation
-                if let Some(repr) = lookup_repr {
-                    let cast_type = match repr {
-                        "u16" => "u16",
-                        "u32" => "u32",
-                        _ => "u8",
-                    };
-                    writeln!(
-                        buffer,
-                        "\t\t\t\t\t\"{}\" => Some({}({}::from(self.{}[index].{}))),",
-                        field_name_pascal,
-                        pgn_value_variant,
-                        cast_type,
-                        info.array_field_name,
-                        field_name_snake
-                    )?;
+        // Bounds check
+        writeln!(
+            buffer,
+            "\t\t\t\tif index >= self.{} {{",
+            info.count_field_name
+        )?;
+        writeln!(buffer, "\t\t\t\t\treturn None;")?;
+        writeln!(buffer, "\t\t\t\t}}")?;
+
+        // Match on the element's fields
+        writeln!(buffer, "\t\t\t\tmatch field_id {{")?;
+        for i in info.start_field_index..end_index {
+            if let Some(field) = pgn.fields.get(i) {
+                let field_name_pascal = to_pascal_case(&field.id, PascalCaseMode::Soft);
+                let field_name_snake = to_snake_case(&field.id, "");
+                let field_type_str = map_type(field, lookup_enum_map, lookup_indir_map)?;
+                let lookup_repr = lookup_repr_from_field(field, lookup_enum_map, lookup_indir_map);
+
+                // For lookups, operate on the enum representation rather than the Rust enum type
+                let type_for_variant = if matches!(
+                    map_to_fieldkind(field),
+                    FieldKind::Lookup | FieldKind::IndirectLookup
+                ) {
+                    lookup_repr.unwrap_or("u8")
+                } else {
+                    &field_type_str
+                };
+                let pgn_value_variant = get_pgn_value_variant_from_type(type_for_variant, field)?;
+
+                if matches!(
+                    map_to_fieldkind(field),
+                    FieldKind::Lookup | FieldKind::IndirectLookup
+                ) {
+                    // For lookups, convert the enum into its primitive representation
+                    if let Some(repr) = lookup_repr {
+                        let cast_type = match repr {
+                            "u16" => "u16",
+                            "u32" => "u32",
+                            _ => "u8",
+                        };
+                        writeln!(
+                            buffer,
+                            "\t\t\t\t\t\"{}\" => Some({}({}::from(self.{}[index].{}))),",
+                            field_name_pascal,
+                            pgn_value_variant,
+                            cast_type,
+                            info.array_field_name,
+                            field_name_snake
+                        )?;
+                    } else {
+                        writeln!(
+                            buffer,
+                            "\t\t\t\t\t\"{}\" => Some({}(u8::from(self.{}[index].{}))),",
+                            field_name_pascal,
+                            pgn_value_variant,
+                            info.array_field_name,
+                            field_name_snake
+                        )?;
+                    }
                 } else {
                     writeln!(
                         buffer,
-                        "\t\t\t\t\t\"{}\" => Some({}(u8::from(self.{}[index].{}))),",
+                        "\t\t\t\t\t\"{}\" => Some({}(self.{}[index].{})),",
                         field_name_pascal,
                         pgn_value_variant,
                         info.array_field_name,
                         field_name_snake
                     )?;
                 }
-            } else {
-                writeln!(
-                    buffer,
-                    "\t\t\t\t\t\"{}\" => Some({}(self.{}[index].{})),",
-                    field_name_pascal, pgn_value_variant, info.array_field_name, field_name_snake
-                )?;
             }
         }
+        writeln!(buffer, "\t\t\t\t\t_ => None,")?;
+        writeln!(buffer, "\t\t\t\t}}")?;
+        writeln!(buffer, "\t\t\t}}")?;
     }
-    writeln!(buffer, "\t\t\t\t\t_ => None,")?;
-    writeln!(buffer, "\t\t\t\t}}")?;
-    writeln!(buffer, "\t\t\t}}")?;
     writeln!(buffer, "\t\t\t_ => None,")?;
     writeln!(buffer, "\t\t}}")?;
     writeln!(buffer, "\t}}")?;
@@ -509,90 +518,93 @@ pub(crate) fn generate_repetitive_field_access(
         "\tfn repetitive_field_mut(&mut self, array_id: &'static str, index: usize, field_id: &'static str, value: PgnValue) -> Option<()> {{"
     )?;
     writeln!(buffer, "\t\tmatch array_id {{")?;
-    writeln!(buffer, "\t\t\t\"{}\" => {{", info.array_field_name)?;
+    for info in infos {
+        let end_index = (info.start_field_index + info.size).min(pgn.fields.len());
+        writeln!(buffer, "\t\t\t\"{}\" => {{", info.array_field_name)?;
 
-    // Bounds check
-    writeln!(
-        buffer,
-        "\t\t\t\tif index >= self.{} {{",
-        info.count_field_name
-    )?;
-    writeln!(buffer, "\t\t\t\t\treturn None;")?;
-    writeln!(buffer, "\t\t\t\t}}")?;
+        // Bounds check
+        writeln!(
+            buffer,
+            "\t\t\t\tif index >= self.{} {{",
+            info.count_field_name
+        )?;
+        writeln!(buffer, "\t\t\t\t\treturn None;")?;
+        writeln!(buffer, "\t\t\t\t}}")?;
+
+        // Match on the element's fields
+        writeln!(buffer, "\t\t\t\tmatch field_id {{")?;
+        for i in info.start_field_index..end_index {
+            if let Some(field) = pgn.fields.get(i) {
+                let field_name_pascal = to_pascal_case(&field.id, PascalCaseMode::Soft);
+                let field_name_snake = to_snake_case(&field.id, "");
+                let field_type_str = map_type(field, lookup_enum_map, lookup_indir_map)?;
+                let lookup_repr = lookup_repr_from_field(field, lookup_enum_map, lookup_indir_map);
+
+                // For lookups, operate on the enum representation rather than the Rust enum type
+                let type_for_variant = if matches!(
+                    map_to_fieldkind(field),
+                    FieldKind::Lookup | FieldKind::IndirectLookup
+                ) {
+                    lookup_repr.unwrap_or("u8")
+                } else {
+                    &field_type_str
+                };
+                let pgn_value_variant = get_pgn_value_variant_from_type(type_for_variant, field)?;
 
-    // Match on the element's fields
-    writeln!(buffer, "\t\t\t\tmatch field_id {{")?;
-    for i in info.start_field_index..end_index {
-        if let Some(field) = pgn.fields.get(i) {
-            let field_name_pascal = to_pascal_case(&field.id, PascalCaseMode::Soft);
-            let field_name_snake = to_snake_case(&field.id, "");
-            let field_type_str = map_type(field, lookup_enum_map, lookup_indir_map)?;
-            let lookup_repr = lookup_repr_from_field(field, lookup_enum_map, lookup_indir_map);
-
-            // For lookups, operate on the enum representation rather than the Rust enum type
-            let type_for_variant = if matches!(
-                map_to_fieldkind(field),
-                FieldKind::Lookup | FieldKind::IndirectLookup
-            ) {
-                lookup_repr.unwrap_or("u8")
-            } else {
-                &field_type_str
-            };
-            let pgn_value_variant = get_pgn_value_variant_from_type(type_for_variant, field)?;
-
-            writeln!(buffer, "\t\t\t\t\t\"{}\" => {{", field_name_pascal)?;
-
-            if matches!(
-                map_to_fieldkind(field),
-                FieldKind::Lookup | FieldKind::IndirectLookup
-            ) {
-                // For lookups, convert the primitive value back into the enum
-                writeln!(
-                    buffer,
-                    "\t\t\t\t\t\tif let {}(val) = value {{",
-                    pgn_value_variant
-                )?;
-                writeln!(
-                    buffer,
-                    "\t\t\t\t\t\t\tmatch {}::try_from(val) {{",
-                    field_type_str
-                )?;
-                writeln!(buffer, "\t\t\t\t\t\t\t\tOk(enum_val) => {{")?;
-                writeln!(
-                    buffer,
-                    "\t\t\t\t\t\t\t\t\tself.{}[index].{} = enum_val;",
-                    info.array_field_name, field_name_snake
-                )?;
-                writeln!(buffer, "\t\t\t\t\t\t\t\t\tSome(())")?;
-                writeln!(buffer, "\t\t\t\t\t\t\t\t}}")?;
-                writeln!(buffer, "\t\t\t\t\t\t\t\tErr(_) => None")?;
-                writeln!(buffer, "\t\t\t\t\t\t\t}}")?;
-                writeln!(buffer, "\t\t\t\t\t\t}} else {{")?;
-                writeln!(buffer, "\t\t\t\t\t\t\tNone")?;
-                writeln!(buffer, "\t\t\t\t\t\t}}")?;
-            } else {
-                writeln!(
-                    buffer,
-                    "\t\t\t\t\t\tif let {}(val) = value {{",
-                    pgn_value_variant
-                )?;
-                writeln!(
-                    buffer,
-                    "\t\t\t\t\t\t\tself.{}[index].{} = val;",
-                    info.array_field_name, field_name_snake
-                )?;
-                writeln!(buffer, "\t\t\t\t\t\t\tSome(())")?;
-                writeln!(buffer, "\t\t\t\t\t\t}} else {{")?;
-                writeln!(buffer, "\t\t\t\t\t\t\tNone")?;
-                writeln!(buffer, "\t\t\t\t\t\t}}")?;
-            }
+                writeln!(buffer, "\t\t\t\t\t\"{}\" => {{", field_name_pascal)?;
+
+                if matches!(
+                    map_to_fieldkind(field),
+                    FieldKind::Lookup | FieldKind::IndirectLookup
+                ) {
+                    // For lookups, convert the primitive value back into the enum
+                    writeln!(
+                        buffer,
+                        "\t\t\t\t\t\tif let {}(val) = value {{",
+                        pgn_value_variant
+                    )?;
+                    writeln!(
+                        buffer,
+                        "\t\t\t\t\t\t\tmatch {}::try_from(val) {{",
+                        field_type_str
+                    )?;
+                    writeln!(buffer, "\t\t\t\t\t\t\t\tOk(enum_val) => {{")?;
+                    writeln!(
+                        buffer,
+                        "\t\t\t\t\t\t\t\t\tself.{}[index].{} = enum_val;",
+                        info.array_field_name, field_name_snake
+                    )?;
+                    writeln!(buffer, "\t\t\t\t\t\t\t\t\tSome(())")?;
+                    writeln!(buffer, "\t\t\t\t\t\t\t\t}}")?;
+                    writeln!(buffer, "\t\t\t\t\t\t\t\tErr(_) => None")?;
+                    writeln!(buffer, "\t\t\t\t\t\t\t}}")?;
+                    writeln!(buffer, "\t\t\t\t\t\t}} else {{")?;
+                    writeln!(buffer, "\t\t\t\t\t\t\tNone")?;
+                    writeln!(buffer, "\t\t\t\t\t\t}}")?;
+                } else {
+                    writeln!(
+                        buffer,
+                        "\t\t\t\t\t\tif let {}(val) = value {{",
+                        pgn_value_variant
+                    )?;
+                    writeln!(
+                        buffer,
+                        "\t\t\t\t\t\t\tself.{}[index].{} = val;",
+                        info.array_field_name, field_name_snake
+                    )?;
+                    writeln!(buffer, "\t\t\t\t\t\t\tSome(())")?;
+                    writeln!(buffer, "\t\t\t\t\t\t}} else {{")?;
+                    writeln!(buffer, "\t\t\t\t\t\t\tNone")?;
+                    writeln!(buffer, "\t\t\t\t\t\t}}")?;
+                }
 
-            writeln!(buffer, "\t\t\t\t\t}}")?;
+                writeln!(buffer, "\t\t\t\t\t}}")?;
+            }
         }
+        writeln!(buffer, "\t\t\t\t\t_ => None,")?;
+        writeln!(buffer, "\t\t\t\t}}")?;
+        writeln!(buffer, "\t\t\t}}")?;
     }
-    writeln!(buffer, "\t\t\t\t\t_ => None,")?;
-    writeln!(buffer, "\t\t\t\t}}")?;
-    writeln!(buffer, "\t\t\t}}")?;
     writeln!(buffer, "\t\t\t_ => None,")?;
     writeln!(buffer, "\t\t}}")?;
     writeln!(buffer, "\t}}")?;
@@ -604,11 +616,13 @@ pub(crate) fn generate_repetitive_field_access(
         "\tfn repetitive_count(&self, array_id: &'static str) -> Option<usize> {{"
     )?;
     writeln!(buffer, "\t\tmatch array_id {{")?;
-    writeln!(
-        buffer,
-        "\t\t\t\"{}\" => Some(self.{}),",
-        info.array_field_name, info.count_field_name
-    )?;
+    for info in infos {
+        writeln!(
+            buffer,
+            "\t\t\t\"{}\" => Some(self.{}),",
+            info.array_field_name, info.count_field_name
+        )?;
+    }
     writeln!(buffer, "\t\t\t_ => None,")?;
     writeln!(buffer, "\t\t}}")?;
     writeln!(buffer, "\t}}")?;
@@ -620,22 +634,25 @@ pub(crate) fn generate_repetitive_field_access(
         "\tfn set_repetitive_count(&mut self, array_id: &'static str, count: usize) -> Option<()> {{"
     )?;
     writeln!(buffer, "\t\tmatch array_id {{")?;
-    writeln!(buffer, "\t\t\t\"{}\" => {{", info.array_field_name)?;
-
-    // Validate that the requested count does not exceed max_repetitions
-    writeln!(buffer, "\t\t\t\tif count > {} {{", info.max_repetitions)?;
-    writeln!(buffer, "\t\t\t\t\treturn None;")?;
-    writeln!(buffer, "\t\t\t\t}}")?;
-    writeln!(buffer, "\t\t\t\tself.{} = count;", info.count_field_name)?;
-    if let Some((ref counter_field_name, ref counter_field_type)) = counter_field_props {
-        writeln!(
-            buffer,
-            "\t\t\t\tself.{} = count as {};",
-            counter_field_name, counter_field_type
-        )?;
+    for info in infos {
+        writeln!(buffer, "\t\t\t\"{}\" => {{", info.array_field_name)?;
+
+        // Validate that the requested count does not exceed max_repetitions
+        writeln!(buffer, "\t\t\t\tif count > {} {{", info.max_repetitions)?;
+        writeln!(buffer, "\t\t\t\t\treturn None;")?;
+        writeln!(buffer, "\t\t\t\t}}")?;
+        writeln!(buffer, "\t\t\t\tself.{} = count;", info.count_field_name)?;
+        if let Some(counter) = info.count_field_index.and_then(|idx| pgn.fields.get(idx)) {
+            writeln!(
+                buffer,
+                "\t\t\t\tself.{} = count as {};",
+                to_snake_case(&counter.id, "field"),
+                map_type(counter, lookup_enum_map, lookup_indir_map)?
+            )?;
+        }
+        writeln!(buffer, "\t\t\t\tSome(())")?;
+        writeln!(buffer, "\t\t\t}}")?;
     }
-    writeln!(buffer, "\t\t\t\tSome(())")?;
-    writeln!(buffer, "\t\t\t}}")?;
     writeln!(buffer, "\t\t\t_ => None,")?;
     writeln!(buffer, "\t\t}}")?;
     writeln!(buffer, "\t}}")?;
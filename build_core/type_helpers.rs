@@ -29,6 +29,10 @@ pub(crate) fn get_pgn_value_variant_from_type(
         FieldKind::StringFix | FieldKind::StringLz | FieldKind::StringLau => {
             Ok("PgnValue::Bytes".to_string())
         }
+        FieldKind::Decimal => Ok("PgnValue::Decimal".to_string()),
+        // NAME fields travel as their raw 64-bit value.
+        FieldKind::IsoName => Ok("PgnValue::U64".to_string()),
+        FieldKind::Variable => Ok("PgnValue::Variable".to_string()),
         FieldKind::Binary => {
             // BINARY fields may be fixed-size byte arrays or integers
             if type_str.starts_with("[") {
@@ -184,9 +188,14 @@ pub(crate) fn map_type(
             Ok("u8".to_string())
         }
         FieldKind::StringLz | FieldKind::StringLau => Ok("PgnBytes".to_string()),
+        // BCD digits, leading zeros included.
+        FieldKind::Decimal => Ok("PgnDecimal".to_string()),
+        FieldKind::IsoName => Ok("IsoName".to_string()),
+        // Sized at run time from the referenced PGN's descriptor.
+        FieldKind::Variable => Ok("PgnVariable".to_string()),
         _ => {
             // Fields with a resolution become floating-point values.
-            if field.resolution.is_some_and(|r| r != 1.0) || field.kind.contains("DECIMAL") {
+            if field.resolution.is_some_and(|r| r != 1.0) {
                 match field.bits_length.ok_or(BuildError::BitLengthErr {
                     path: field.id.clone(),
                     comment: "build.rs / map_type",
@@ -345,6 +354,8 @@ pub(crate) fn map_to_fieldkind(field: &Fields) -> FieldKind {
         "RESERVED" => FieldKind::Reserved,
         "SPARE" => FieldKind::Spare,
         "ISO_NAME" => FieldKind::IsoName,
+        "FIELD_INDEX" => FieldKind::FieldIndex,
+        "VARIABLE" => FieldKind::Variable,
         _ => FieldKind::Unimplemented,
     }
 }
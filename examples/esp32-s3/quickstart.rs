@@ -102,18 +102,10 @@ impl<'d> CanBus for EspCanBus<'d> {
 }
 
 // ============================================================================
-// Timer implementation for ESP32-S3
+// Timer: the crate's embassy-time implementation
 // ============================================================================
 
-use korri_n2k::protocol::transport::traits::korri_timer::KorriTimer;
-
-pub struct EspTimer;
-
-impl KorriTimer for EspTimer {
-    async fn delay_ms(&mut self, millis: u32) {
-        embassy_time::Timer::after(Duration::from_millis(millis as u64)).await;
-    }
-}
+use korri_n2k::protocol::transport::timers::EmbassyTimer;
 
 // ============================================================================
 // Main application
@@ -121,7 +113,7 @@ impl KorriTimer for EspTimer {
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
-type AddressManagerType = AddressManager<EspCanBus<'static>, EspTimer>;
+type AddressManagerType = AddressManager<EspCanBus<'static>, EmbassyTimer>;
 
 static MANAGER_CELL: StaticCell<Mutex<CriticalSectionRawMutex, AddressManagerType>> =
     StaticCell::new();
@@ -156,7 +148,7 @@ async fn main(spawner: Spawner) {
 
     let can_peripheral = can_config.start();
     let can_bus = EspCanBus::new(can_peripheral);
-    let timer = EspTimer;
+    let timer = EmbassyTimer::new();
 
     // 5. Build the ISO Name identity for this device
     let iso_name = IsoName::builder()
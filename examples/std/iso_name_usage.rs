@@ -2,7 +2,7 @@
 //! used during the NMEA 2000 address claim procedure.
 
 use korri_n2k::protocol::{
-    lookups::{DeviceClass, IndustryCode, ManufacturerCode, YesNo},
+    lookups::{DeviceClass, IndustryCode, ManufacturerCode},
     managment::{address_claiming::build_address_claim_frame, iso_name::IsoName},
     messages::Pgn60928,
     transport::can_frame::CanFrame,
@@ -56,16 +56,18 @@ fn main() {
     // Example 4: conversions between IsoName and PGN 60928.
     println!("4. Converting between IsoName and Pgn60928:");
     let mut pgn = Pgn60928::new();
-    pgn.unique_number = 999888;
-    pgn.manufacturer_code = ManufacturerCode::Actisense;
-    pgn.device_function = 255;
-    pgn.device_class = DeviceClass::InternetworkDevice;
-    pgn.industry_group = IndustryCode::MarineIndustry;
-    pgn.arbitrary_address_capable = YesNo::Yes;
+    pgn.name = IsoName::builder()
+        .unique_number(999888)
+        .manufacturer_code(u16::from(ManufacturerCode::Actisense))
+        .device_function(255)
+        .device_class(u8::from(DeviceClass::InternetworkDevice))
+        .industry_group(u8::from(IndustryCode::MarineIndustry))
+        .arbitrary_address_capable(true)
+        .build();
 
     println!(
         "  Created Pgn60928 with unique_number: {}",
-        pgn.unique_number
+        pgn.name.unique_number()
     );
 
     let iso_from_pgn: IsoName = pgn.into();
@@ -79,7 +81,7 @@ fn main() {
     println!("  Converted back to Pgn60928");
     println!(
         "  Round-trip successful: {}\n",
-        pgn_from_iso.unique_number == 999888
+        pgn_from_iso.name.unique_number() == 999888
     );
 
     // Example 5: prepare a broadcast-ready Address Claim frame.
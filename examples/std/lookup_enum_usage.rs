@@ -1,4 +1,5 @@
 use korri_n2k::protocol::lookups::*;
+use korri_n2k::protocol::managment::iso_name::IsoName;
 use korri_n2k::protocol::messages::*;
 
 fn main() {
@@ -24,21 +25,21 @@ fn main() {
     println!("\n=== Test PGN 60928 (ISO Address Claim) ===\n");
     let mut address_claim = Pgn60928::new();
 
-    // Direct LOOKUP – assign using the enum
-    address_claim.manufacturer_code = ManufacturerCode::ArksEnterprisesInc;
-    address_claim.device_class = DeviceClass::SteeringAndControlSurfaces;
-    address_claim.industry_group = IndustryCode::MarineIndustry;
-    address_claim.arbitrary_address_capable = YesNo::Yes;
-
-    println!("manufacturer_code: {:?}", address_claim.manufacturer_code);
-    println!("device_class: {:?}", address_claim.device_class);
-
-    // INDIRECT_LOOKUP – use helper accessors
-    address_claim.set_device_function(DeviceFunction::Diagnostic);
-
-    if let Some(device_fn) = address_claim.get_device_function() {
-        println!("device_function (via helper): {:?}", device_fn);
-    }
+    // The NAME is an `IsoName`; lookup enums convert to and from its raw sub-fields.
+    address_claim.name = IsoName::builder()
+        .manufacturer_code(u16::from(ManufacturerCode::ArksEnterprisesInc))
+        .device_class(u8::from(DeviceClass::SteeringAndControlSurfaces))
+        .industry_group(u8::from(IndustryCode::MarineIndustry))
+        .arbitrary_address_capable(true)
+        .build();
 
+    println!(
+        "manufacturer_code: {:?}",
+        ManufacturerCode::try_from(address_claim.name.manufacturer_code())
+    );
+    println!(
+        "device_class: {:?}",
+        DeviceClass::try_from(address_claim.name.device_class())
+    );
     println!("\n✅ All enum assignments work correctly!");
 }
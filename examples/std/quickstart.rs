@@ -100,7 +100,7 @@ fn main() {
 
     let pgn60928: Pgn60928 = iso_name.into();
     println!("   PGN60928 created from ISO Name");
-    println!("   Unique number: {}", pgn60928.unique_number);
+    println!("   Unique number: {}", pgn60928.name.unique_number());
 
     let iso_name_restored: IsoName = pgn60928.into();
     println!("   ISO Name restored from PGN60928");
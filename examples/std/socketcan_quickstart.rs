@@ -0,0 +1,85 @@
+//! # SocketCAN Quickstart
+//!
+//! Runs a complete node on a Linux CAN interface:
+//! - Claim an address with `AddressManager`
+//! - Broadcast a GPS position (PGN 129025) every second
+//! - Decode and print every message heard on the bus
+//!
+//! ```bash
+//! sudo modprobe vcan
+//! sudo ip link add dev vcan0 type vcan
+//! sudo ip link set up vcan0
+//!
+//! cargo run --example socketcan_quickstart --features socketcan -- vcan0
+//! # In another terminal: candump vcan0
+//! ```
+
+use korri_n2k::protocol::{
+    decoder::N2kDecoder,
+    managment::{address_manager::AddressManager, iso_name::IsoName},
+    messages::Pgn129025,
+    transport::{socketcan::SocketCanBus, timers::TokioTimer, traits::korri_timer::KorriTimer},
+};
+use tokio::time::{timeout, Duration};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let interface = std::env::args().nth(1).unwrap_or_else(|| "vcan0".into());
+    println!("=== korri-n2k SocketCAN Quickstart on {interface} ===\n");
+
+    let bus = SocketCanBus::open(&interface)?;
+    let timer = TokioTimer::new();
+
+    let iso_name = IsoName::builder()
+        .unique_number(12345)
+        .manufacturer_code(229)
+        .device_function(145) // GPS
+        .device_class(75) // Navigation
+        .industry_group(4) // Marine
+        .arbitrary_address_capable(true)
+        .build();
+
+    let mut manager = AddressManager::new(bus, timer, iso_name.raw(), 42)
+        .await
+        .map_err(|e| format!("address claim failed: {e:?}"))?;
+    println!("Claimed address {}", manager.current_address());
+
+    let mut decoder = N2kDecoder::new();
+    let clock = TokioTimer::new();
+    let mut position = Pgn129025::new();
+    position.latitude = 47.7223;
+    position.longitude = -4.0022;
+
+    loop {
+        manager
+            .send_message(&position, None)
+            .await
+            .map_err(|e| format!("send failed: {e:?}"))?;
+
+        // Listen until the next position is due.
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(1);
+        while let Ok(received) =
+            timeout(deadline - tokio::time::Instant::now(), manager.recv()).await
+        {
+            let Some(frame) = received.map_err(|e| format!("receive failed: {e:?}"))? else {
+                continue;
+            };
+            if let Some(message) = decoder.process_frame(&frame, clock.now_ms()) {
+                match message.decode() {
+                    Ok(decoded) => println!(
+                        "[{:3}] PGN {}: {:?}",
+                        message.id.source_address(),
+                        decoded.pgn(),
+                        decoded
+                    ),
+                    Err(_) => println!(
+                        "[{:3}] PGN {} ({} bytes, no decoder)",
+                        message.id.source_address(),
+                        message.pgn(),
+                        message.len
+                    ),
+                }
+            }
+        }
+    }
+}
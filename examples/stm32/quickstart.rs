@@ -128,25 +128,17 @@ use korri_n2k::protocol::transport::traits::can_bus::CanBus;
 // }
 
 // ============================================================================
-// Timer implementation for STM32
+// Timer: the crate's embassy-time implementation
 // ============================================================================
 
-use korri_n2k::protocol::transport::traits::korri_timer::KorriTimer;
-
-pub struct Stm32Timer;
-
-impl KorriTimer for Stm32Timer {
-    async fn delay_ms(&mut self, millis: u32) {
-        embassy_time::Timer::after(embassy_time::Duration::from_millis(millis as u64)).await;
-    }
-}
+use korri_n2k::protocol::transport::timers::EmbassyTimer;
 
 // ============================================================================
 // Main application
 // ============================================================================
 
 // NOTE: Type alias to uncomment once the implementation is complete
-// type AddressManagerType = AddressManager<Stm32CanBus<'static>, Stm32Timer>;
+// type AddressManagerType = AddressManager<Stm32CanBus<'static>, EmbassyTimer>;
 // static MANAGER_CELL: StaticCell<Mutex<CriticalSectionRawMutex, AddressManagerType>> =
 //     StaticCell::new();
 
@@ -186,7 +178,7 @@ impl KorriTimer for Stm32Timer {
 //         .enable();
 //
 //     let can_bus = Stm32CanBus::new(can);
-//     let timer = Stm32Timer;
+//     let timer = EmbassyTimer::new();
 //
 //     // 4. Build the ISO Name identity
 //     let iso_name = IsoName::builder()
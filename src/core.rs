@@ -10,6 +10,13 @@
 // Maximum payload size for PgnBytes. 223 bytes + safety margin.
 pub const MAX_PGN_BYTES: usize = 230;
 
+// Maximum number of digits held by PgnDecimal (16 BCD bytes).
+pub const MAX_DECIMAL_DIGITS: usize = 32;
+
+// Maximum width of a PgnVariable value: a 70-character length-prefixed string
+// (e.g. the PGN 126998 installation descriptions) with its length and encoding bytes.
+pub const MAX_VARIABLE_BYTES: usize = 72;
+
 /// Semantic type of a field within a PGN.
 /// Mirrors the `FieldType` entries found in `canboat.json`.
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -51,13 +58,17 @@ pub enum FieldKind {
     /// 64-bit field describing the device identity.
     /// (PGN 60928 – "ISO Address Claim") transports this unique `ISO_NAME`.
     IsoName,
+    /// 1-based order of a field in the PGN referenced by the message
+    /// (Group Function parameters, PGN 126208).
+    FieldIndex,
+    /// Value of the field selected by the preceding `FieldIndex`; its width is
+    /// the referenced field's, rounded up to whole bytes.
+    Variable,
     /// Placeholder for field types not supported yet.
     Unimplemented,
     // DYNAMIC_FIELD_KEY
     // DYNAMIC_FIELD_LENGTH
     // DYNAMIC_FIELD_VALUE
-    // VARIABLE
-    // FIELD_INDEX
 }
 
 /// Descriptor for a single PGN field.
@@ -81,14 +92,19 @@ pub struct FieldDescriptor {
     pub resolution: Option<f32>,
     /// 9. Direct lookup enumeration identifier.
     pub enum_direct_name: Option<&'static str>,
-    /// 10. Indirect lookup identifier.
+    /// 10. Bit lookup enumeration identifier, for `BitLookup` fields.
+    pub enum_bit_name: Option<&'static str>,
+    /// 11. Indirect lookup identifier.
     pub enum_indirect_name: Option<&'static str>,
-    /// 11. Order index among indirect fields.
+    /// 12. Order index among indirect fields.
     pub enum_indirect_field_order: Option<u16>,
-    /// 12. Physical unit (e.g. "m/s", "deg", "meters").
+    /// 13. Physical unit (e.g. "m/s", "deg", "meters").
     pub physical_unit: Option<&'static str>,
-    /// 13. Physical quantity (e.g. "GEOGRAPHICAL_LATITUDE", "SPEED").
+    /// 14. Physical quantity (e.g. "GEOGRAPHICAL_LATITUDE", "SPEED").
     pub physical_qtity: Option<&'static str>,
+    /// 15. Present only for a proprietary referenced PGN (Group Function
+    ///     Read/Write Fields manufacturer fields).
+    pub proprietary_only: bool,
 }
 
 /// Describes a repeating field set within a PGN.
@@ -121,7 +137,8 @@ pub struct RepeatingFieldSet {
     /// This field must appear BEFORE the first repeating field.
     /// Its value determines how many times the group repeats.
     ///
-    /// **Note:** `None` means the repetitions depend on the payload length (rare case, e.g. PGN 126464).
+    /// **Note:** `None` means the repetitions depend on the payload length (e.g. PGN 126464):
+    /// groups are read until the payload is exhausted, a trailing partial group being ignored.
     pub count_field_index: Option<usize>,
 
     /// Index of the first field in the repeating group (0-based).
@@ -172,6 +189,25 @@ pub struct PgnDescriptor {
     ///
     /// A PGN may define up to three different repeating groups (RepeatingFieldSet1..3).
     pub repeating_field_sets: &'static [RepeatingFieldSet],
+    /// 12. CANboat fields merged into the leading `IsoName` field (can be empty).
+    ///
+    /// Group Functions address fields by their CANboat order, NAME sub-fields included.
+    pub iso_name_fields: &'static [FieldDescriptor],
+}
+
+impl PgnDescriptor {
+    /// Field at the 1-based CANboat `index`, as Group Function parameters address it.
+    ///
+    /// Indices covered by a merged NAME resolve to its sub-fields; the following
+    /// ones are shifted past the single `IsoName` field standing for them.
+    pub fn indexed_field(&self, index: u8) -> Option<&FieldDescriptor> {
+        let position = (index as usize).checked_sub(1)?;
+        match self.iso_name_fields.len() {
+            0 => self.fields.get(position),
+            merged if position < merged => self.iso_name_fields.get(position),
+            merged => self.fields.get(position - merged + 1),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -237,6 +273,153 @@ impl PgnBytes {
     }
 }
 
+/// Digits of a `Decimal` (BCD) field, most significant first.
+///
+/// Leading zeros are kept: a 40-bit field always yields ten digits, so an
+/// address such as `0022712345` keeps its meaning. An empty value stands for
+/// a field that is not available (all bytes `0xFF`) or not valid BCD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PgnDecimal {
+    len: usize,
+    digits: [u8; MAX_DECIMAL_DIGITS],
+}
+
+impl Default for PgnDecimal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PgnDecimal {
+    /// Create an empty (not available) value.
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            digits: [0; MAX_DECIMAL_DIGITS],
+        }
+    }
+
+    /// Build a value from digit values (`0..=9`).
+    ///
+    /// Returns `None` for a value above 9 or more than [`MAX_DECIMAL_DIGITS`] digits.
+    pub fn from_digits(digits: &[u8]) -> Option<Self> {
+        if digits.len() > MAX_DECIMAL_DIGITS || digits.iter().any(|&digit| digit > 9) {
+            return None;
+        }
+        let mut value = Self::new();
+        value.digits[..digits.len()].copy_from_slice(digits);
+        value.len = digits.len();
+        Some(value)
+    }
+
+    /// Build a value from ASCII digits (e.g. `"0022712345"`).
+    pub fn from_ascii(text: &str) -> Option<Self> {
+        if text.len() > MAX_DECIMAL_DIGITS || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        let mut value = Self::new();
+        for (slot, byte) in value.digits.iter_mut().zip(text.bytes()) {
+            *slot = byte - b'0';
+        }
+        value.len = text.len();
+        Some(value)
+    }
+
+    /// Digit values, most significant first.
+    #[inline]
+    pub fn digits(&self) -> &[u8] {
+        &self.digits[..self.len]
+    }
+
+    /// Number of digits stored.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks whether the value is empty (field not available).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Numeric value, `None` when empty or above `u64::MAX`.
+    pub fn to_u64(self) -> Option<u64> {
+        if self.is_empty() {
+            return None;
+        }
+        self.digits().iter().try_fold(0u64, |value, &digit| {
+            value.checked_mul(10)?.checked_add(digit as u64)
+        })
+    }
+}
+
+impl ::core::fmt::Display for PgnDecimal {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        self.digits()
+            .iter()
+            .try_for_each(|&digit| ::core::fmt::Write::write_char(f, (b'0' + digit) as char))
+    }
+}
+
+/// Raw bytes of a `Variable` field, as laid out by the field of another PGN
+/// it stands for.
+///
+/// The codec engine sizes them from the referenced `PgnDescriptor`, or from
+/// the leading length byte of a string, when decoding, and converts them from and to typed values
+/// (`engine::encode_variable`, `engine::decode_variable`). An empty value
+/// marks a parameter whose referenced PGN could not be resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PgnVariable {
+    len: u8,
+    data: [u8; MAX_VARIABLE_BYTES],
+}
+
+impl Default for PgnVariable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PgnVariable {
+    /// Create an empty value.
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            data: [0; MAX_VARIABLE_BYTES],
+        }
+    }
+
+    /// Copy `bytes`; `None` beyond [`MAX_VARIABLE_BYTES`].
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() > MAX_VARIABLE_BYTES {
+            return None;
+        }
+        let mut value = Self::new();
+        value.data[..bytes.len()].copy_from_slice(bytes);
+        value.len = bytes.len() as u8;
+        Some(value)
+    }
+
+    /// Populated bytes, in wire order.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.len as usize]
+    }
+
+    /// Number of bytes stored.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Checks whether the value is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum PgnValue {
     U64(u64),
@@ -250,5 +433,7 @@ pub enum PgnValue {
     F64(f64),
     F32(f32),
     Bytes(PgnBytes),
+    Decimal(PgnDecimal),
+    Variable(PgnVariable),
     Ignored,
 }
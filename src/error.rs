@@ -86,6 +86,8 @@ pub enum ExtractionError {
 
 #[derive(Debug, Error)]
 /// Issues encountered while serializing a PGN into a buffer.
+// `CodecError` carries the offending `PgnValue` by value: the crate does not allocate.
+#[allow(clippy::large_enum_variant)]
 pub enum SerializationError {
     /// Provided buffer is too small for the payload.
     #[error("Buffer too small")]
@@ -115,6 +117,8 @@ pub enum SerializationError {
 
 #[derive(Error, Debug)]
 /// Errors raised while deserializing a CAN buffer into a PGN structure.
+// `CodecError` carries the offending `PgnValue` by value: the crate does not allocate.
+#[allow(clippy::large_enum_variant)]
 pub enum DeserializationError {
     /// Payload size does not match the expected schema.
     #[error("Invalid data length")]
@@ -146,6 +150,13 @@ pub enum DeserializationError {
     /// Bit-level access on the buffer failed (out of bounds, misalignment…).
     #[error("BitReader error: {err}")]
     BitReaderError { err: BitReaderError },
+    /// PGN not generated from the manifest, so no decoder is available.
+    #[error("Unsupported PGN {pgn}")]
+    UnsupportedPgn { pgn: u32 },
+    /// Group Function parameters address a polymorphic PGN without a parameter 1
+    /// selecting one of its variants.
+    #[error("No variant of PGN {pgn} matches the parameters")]
+    UnmatchedVariant { pgn: u32 },
 }
 
 #[derive(Error, Debug)]
@@ -171,6 +182,197 @@ pub enum SendPgnError<E: core::fmt::Debug> {
     Send(E),
 }
 
+//==================================================================================TRANSPORT_ERROR
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+/// Connection Abort reasons carried by TP.CM_Abort (ISO 11783-3 / J1939-21).
+pub enum TpAbortReason {
+    /// Already in one or more connection-managed sessions and cannot support another.
+    #[error("Already in a connection-managed session")]
+    AlreadyInSession,
+    /// System resources were needed for another task.
+    #[error("System resources needed for another task")]
+    ResourcesUnavailable,
+    /// A timeout (T1–T4) occurred.
+    #[error("Timeout")]
+    Timeout,
+    /// CTS received while a data transfer was in progress.
+    #[error("CTS received during data transfer")]
+    CtsWhileTransferring,
+    /// Maximum retransmit request limit reached.
+    #[error("Maximum retransmit limit reached")]
+    MaxRetransmitReached,
+    /// Unexpected data transfer packet.
+    #[error("Unexpected data transfer packet")]
+    UnexpectedDataTransfer,
+    /// Bad sequence number.
+    #[error("Bad sequence number")]
+    BadSequence,
+    /// Duplicate sequence number.
+    #[error("Duplicate sequence number")]
+    DuplicateSequence,
+    /// Total message size exceeds 1785 bytes.
+    #[error("Message size greater than 1785 bytes")]
+    MessageTooLarge,
+    /// Reason code not covered by the variants above.
+    #[error("Abort reason {0}")]
+    Other(u8),
+}
+
+impl TpAbortReason {
+    /// Code transmitted in byte 1 of the TP.CM_Abort frame.
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::AlreadyInSession => 1,
+            Self::ResourcesUnavailable => 2,
+            Self::Timeout => 3,
+            Self::CtsWhileTransferring => 4,
+            Self::MaxRetransmitReached => 5,
+            Self::UnexpectedDataTransfer => 6,
+            Self::BadSequence => 7,
+            Self::DuplicateSequence => 8,
+            Self::MessageTooLarge => 9,
+            Self::Other(code) => code,
+        }
+    }
+}
+
+impl From<u8> for TpAbortReason {
+    fn from(code: u8) -> Self {
+        match code {
+            1 => Self::AlreadyInSession,
+            2 => Self::ResourcesUnavailable,
+            3 => Self::Timeout,
+            4 => Self::CtsWhileTransferring,
+            5 => Self::MaxRetransmitReached,
+            6 => Self::UnexpectedDataTransfer,
+            7 => Self::BadSequence,
+            8 => Self::DuplicateSequence,
+            9 => Self::MessageTooLarge,
+            other => Self::Other(other),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+/// Failures of a connection-mode (RTS/CTS) transport session.
+pub enum TpSessionError {
+    /// The peer closed the session with a TP.CM_Abort.
+    #[error("Transfer aborted by peer: {0}")]
+    AbortedByPeer(TpAbortReason),
+    /// The local node closed the session and sent a TP.CM_Abort.
+    #[error("Transfer aborted: {0}")]
+    Aborted(TpAbortReason),
+    /// Every slot of the session table is busy.
+    #[error("No transport session available")]
+    NoSessionAvailable,
+    /// A session with the same peer is already running in this direction.
+    #[error("Transport session already in progress with {peer}")]
+    SessionInProgress { peer: u8 },
+    /// Payload size is outside the 9..=1785 byte range.
+    #[error("Invalid transport payload size: {len}")]
+    InvalidSize { len: usize },
+    /// Unable to build the CAN identifier.
+    #[error(transparent)]
+    Build(#[from] CanIdBuildError),
+}
+
+#[derive(Error, Debug)]
+/// Errors encountered while driving a transport session over the CAN bus.
+pub enum TransportError<E: core::fmt::Debug> {
+    /// Session-level failure (abort, table full, invalid size…).
+    #[error(transparent)]
+    Session(#[from] TpSessionError),
+    /// CAN layer refused or failed to send the frame.
+    #[error("CAN bus send error: {0:?}")]
+    Send(E),
+    /// Unable to receive frames from the bus.
+    #[error("CAN bus receive error: {0:?}")]
+    Receive(E),
+}
+
+//==================================================================================EMBEDDED_CAN_ERRORS
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+/// Reasons an `embedded_can::Frame` cannot be exchanged with a [`CanFrame`](crate::protocol::transport::can_frame::CanFrame).
+pub enum FrameConversionError {
+    /// NMEA 2000 only uses extended (29-bit) identifiers.
+    #[error("Standard (11-bit) identifier")]
+    StandardId,
+    /// NMEA 2000 never uses remote frames.
+    #[error("Remote frame")]
+    RemoteFrame,
+    /// Payload longer than eight bytes, or refused by the target frame type.
+    #[error("Invalid frame data")]
+    InvalidData,
+}
+
+#[derive(Error, Debug)]
+/// Errors reported by the `embedded-can` driver adapters.
+pub enum EmbeddedCanError<E: core::fmt::Debug> {
+    /// The underlying driver failed.
+    #[error("CAN driver error: {0:?}")]
+    Driver(E),
+    /// The frame could not be converted for the driver.
+    #[error(transparent)]
+    Frame(#[from] FrameConversionError),
+}
+
+//==================================================================================FORMAT_ERRORS
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+/// Errors raised while parsing or writing a log / gateway record.
+pub enum FormatError {
+    /// The record does not follow the expected layout.
+    #[error("Malformed record")]
+    Syntax,
+    /// A field expected to be hexadecimal is not.
+    #[error("Invalid hexadecimal field")]
+    InvalidHex,
+    /// The timestamp cannot be parsed.
+    #[error("Invalid timestamp")]
+    InvalidTimestamp,
+    /// Standard identifier, remote frame, or CAN FD frame: not NMEA 2000 traffic.
+    #[error("Unsupported frame kind")]
+    UnsupportedFrame,
+    /// More payload bytes than the record kind can carry.
+    #[error("Payload too long: {len} bytes")]
+    DataTooLong { len: usize },
+    /// The output buffer or writer refused the record.
+    #[error("Write error")]
+    Write,
+    /// The record checksum does not match its content.
+    #[error("Checksum mismatch")]
+    Checksum,
+    /// The message cannot be split back into frames (e.g. addressed ISO transport).
+    #[error("Message cannot be split into frames")]
+    Segmentation,
+}
+
+#[cfg(feature = "std")]
+#[derive(Error, Debug)]
+/// Errors raised by the `std` log readers.
+pub enum LogReadError {
+    /// The underlying reader failed.
+    #[error("I/O error: {0}")]
+    Io(std::io::Error),
+    /// A record could not be parsed.
+    #[error("Record {record}: {error}")]
+    Format { record: usize, error: FormatError },
+    /// The capture does not carry SocketCAN frames.
+    #[error("Unsupported link type {0}")]
+    UnsupportedLinkType(u32),
+}
+
+//==================================================================================VIRTUAL_BUS_ERRORS
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+/// Errors reported by a simulated [`VirtualBus`](crate::protocol::transport::virtual_bus::VirtualBus) endpoint.
+pub enum VirtualBusError {
+    /// The endpoint is bus-off: it can neither transmit nor receive.
+    #[error("Endpoint is bus-off")]
+    BusOff,
+    /// An injected error destroyed the frame on the wire.
+    #[error("Transmission error")]
+    Transmit,
+}
+
 //==================================================================================BITREADER_ERRORS
 #[derive(Debug, Error)]
 /// Errors raised during bitwise buffer reads.
@@ -0,0 +1,214 @@
+//! Actisense N2K ASCII format (W2K-1, NGX-1, and EMU-1 text output).
+//!
+//! One complete message per line: the gateway reassembles Fast Packets, so
+//! the payload may exceed eight bytes.
+//!
+//! ```text
+//! A173321.107 23FF7 1F513 012F3070002F30709F
+//! ```
+//!
+//! | Field | Content |
+//! |-------|---------|
+//! | `Ahhmmss.ddd` | time of day |
+//! | `SSDDP` | source, destination, priority (hexadecimal) |
+//! | `PPPPP` | PGN (hexadecimal) |
+//! | data | payload as contiguous hexadecimal digit pairs |
+//!
+//! [`AsciiRecord::frames`] splits a message back into the frames that carried
+//! it, so these logs replay through the same path as frame-level captures.
+//!
+//! ```rust,ignore
+//! let mut decoder = N2kDecoder::new();
+//! let mut sequences = SequenceCounter::new();
+//! for line in log.lines() {
+//!     for record in actisense_ascii::parse_line(line)?.frames(&mut sequences) {
+//!         let record = record?;
+//!         if let Some(Ok(message)) = decoder.decode_frame(&record.frame, record.timestamp_us / 1000) {
+//!             println!("{message:?}");
+//!         }
+//!     }
+//! }
+//! ```
+use core::fmt;
+
+use crate::{
+    error::FormatError,
+    formats::{
+        can_id_from_parts,
+        text::{
+            parse_hex_bytes, parse_hex_u32, parse_hex_u8, parse_time_of_day, time_of_day_parts,
+            write_hex_bytes,
+        },
+        TimestampedFrame,
+    },
+    protocol::{
+        decoder::RawMessage,
+        messages::N2kMessage,
+        transport::{
+            can_frame::CanFrame,
+            fast_packet::{
+                builder::{FastPacketBuilder, FrameIterator},
+                sequence::SequenceCounter,
+                MAX_FAST_PACKET_PAYLOAD,
+            },
+            iso_tp::{
+                builder::{BamBuilder, BamFrameIterator},
+                TP_GLOBAL_ADDRESS,
+            },
+        },
+    },
+};
+
+//==================================================================================RECORD
+/// Complete message of an N2K ASCII line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsciiRecord {
+    /// Time of day in microseconds since midnight.
+    pub timestamp_us: u64,
+    /// Complete PGN payload with its rebuilt identifier.
+    pub message: RawMessage,
+}
+
+impl AsciiRecord {
+    /// Frames carrying the message on the bus, all stamped with the record time.
+    ///
+    /// Payloads up to eight bytes fit a single frame. Longer ones use the
+    /// transport of their PGN definition: Fast Packet fragments with the next
+    /// identifier of `sequences`, or an ISO transport BAM session for
+    /// broadcast ISO PGNs. PGNs without definition are sent as Fast Packets.
+    ///
+    /// # Errors
+    ///
+    /// Yields [`FormatError::Segmentation`] and stops when a frame cannot be
+    /// built, or for an addressed ISO transport message, which needs a
+    /// connection session with its destination.
+    pub fn frames<const N: usize>(
+        &self,
+        sequences: &mut SequenceCounter<N>,
+    ) -> impl Iterator<Item = Result<TimestampedFrame, FormatError>> + '_ {
+        let id = self.message.id;
+        let payload = self.message.payload();
+        let fast_packet = N2kMessage::descriptor(id.pgn()).is_none_or(|pgn| pgn.fastpacket);
+
+        let segments = if payload.len() <= 8 || fast_packet {
+            Segments::FastPacket(
+                FastPacketBuilder::new(
+                    id.pgn(),
+                    id.source_address(),
+                    id.destination(),
+                    payload,
+                    sequences,
+                )
+                .with_priority(id.priority())
+                .build(),
+            )
+        } else if matches!(id.destination(), None | Some(TP_GLOBAL_ADDRESS)) {
+            Segments::Bam(
+                BamBuilder::new(id.pgn(), id.source_address(), payload)
+                    .with_priority(id.priority())
+                    .build(),
+            )
+        } else {
+            Segments::Refused
+        };
+
+        let timestamp_us = self.timestamp_us;
+        segments.map(move |frame| frame.map(|frame| TimestampedFrame::new(timestamp_us, frame)))
+    }
+}
+
+/// Frames of one message, in the transport chosen by [`AsciiRecord::frames`].
+enum Segments<'a> {
+    FastPacket(FrameIterator<'a>),
+    Bam(BamFrameIterator<'a>),
+    Refused,
+    Failed,
+}
+
+impl Iterator for Segments<'_> {
+    type Item = Result<CanFrame, FormatError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let frame = match self {
+            Self::FastPacket(frames) => frames.next()?.ok(),
+            Self::Bam(frames) => frames.next()?.ok(),
+            Self::Refused => None,
+            Self::Failed => return None,
+        };
+        // Nothing follows a frame that could not be built.
+        if frame.is_none() {
+            *self = Self::Failed;
+        }
+        Some(frame.ok_or(FormatError::Segmentation))
+    }
+}
+
+//==================================================================================LINE
+/// Parse one N2K ASCII line.
+///
+/// # Errors
+///
+/// - [`FormatError::Syntax`] when a part of the line is missing or misplaced
+/// - [`FormatError::InvalidTimestamp`] / [`FormatError::InvalidHex`] for malformed fields
+/// - [`FormatError::DataTooLong`] for payloads beyond the Fast Packet limit
+pub fn parse_line(line: &str) -> Result<AsciiRecord, FormatError> {
+    let mut parts = line.split_whitespace();
+    let time = parts.next().ok_or(FormatError::Syntax)?;
+    let header = parts.next().ok_or(FormatError::Syntax)?;
+    let pgn = parts.next().ok_or(FormatError::Syntax)?;
+    let data = parts.next().unwrap_or("");
+    if parts.next().is_some() {
+        return Err(FormatError::Syntax);
+    }
+
+    let time = time.strip_prefix('A').ok_or(FormatError::Syntax)?;
+    let timestamp_us = match (time.get(..2), time.get(2..4), time.get(4..)) {
+        (Some(hours), Some(minutes), Some(seconds)) => parse_time_of_day(hours, minutes, seconds)?,
+        _ => return Err(FormatError::InvalidTimestamp),
+    };
+
+    if header.len() != 5 || pgn.len() != 5 {
+        return Err(FormatError::Syntax);
+    }
+    let source = parse_hex_u8(header.get(..2).ok_or(FormatError::InvalidHex)?)?;
+    let destination = parse_hex_u8(header.get(2..4).ok_or(FormatError::InvalidHex)?)?;
+    let priority = parse_hex_u8(header.get(4..).ok_or(FormatError::InvalidHex)?)?;
+    let pgn = parse_hex_u32(pgn)?;
+    if priority > 7 || pgn > 0x3_FFFF {
+        return Err(FormatError::InvalidHex);
+    }
+
+    let mut message = RawMessage {
+        id: can_id_from_parts(priority, pgn, source, destination),
+        payload: [0; MAX_FAST_PACKET_PAYLOAD],
+        len: 0,
+    };
+    message.len = parse_hex_bytes(data, &mut message.payload)?;
+
+    Ok(AsciiRecord {
+        timestamp_us,
+        message,
+    })
+}
+
+/// Write `record` as one N2K ASCII line (without the trailing newline).
+///
+/// The timestamp is written as a time of day with millisecond resolution;
+/// broadcast PGNs get destination 255.
+pub fn write_line<W: fmt::Write>(out: &mut W, record: &AsciiRecord) -> fmt::Result {
+    let (hours, minutes, seconds, millis) = time_of_day_parts(record.timestamp_us);
+    let id = record.message.id;
+    write!(
+        out,
+        "A{hours:02}{minutes:02}{seconds:02}.{millis:03} {:02X}{:02X}{:X} {:05X} ",
+        id.source_address(),
+        id.destination().unwrap_or(255),
+        id.priority(),
+        id.pgn()
+    )?;
+    write_hex_bytes(out, record.message.payload())
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,152 @@
+//! N2K ASCII tests: line parsing, writing, and frame splitting.
+// ACTISENSE_ASCII
+use super::*;
+use crate::{
+    formats::text::LineBuffer,
+    protocol::{
+        decoder::N2kDecoder,
+        transport::iso_tp::assembler::{BamAssembler, ProcessResult as BamResult},
+    },
+};
+
+const DISTANCE_LOG: &str = "A173321.107 23FF7 1F513 012F3070002F30709F";
+
+#[test]
+/// A reassembled message is parsed field by field.
+fn test_parse_line() {
+    let record = parse_line(DISTANCE_LOG).unwrap();
+    assert_eq!(
+        record.timestamp_us,
+        ((17 * 60 + 33) * 60 + 21) * 1_000_000 + 107_000
+    );
+
+    let id = record.message.id;
+    assert_eq!(id.pgn(), 128275);
+    assert_eq!(id.source_address(), 0x23);
+    assert_eq!(id.destination(), None);
+    assert_eq!(id.priority(), 7);
+    assert_eq!(
+        record.message.payload(),
+        &[0x01, 0x2F, 0x30, 0x70, 0x00, 0x2F, 0x30, 0x70, 0x9F]
+    );
+
+    // PDU1 PGNs keep their destination.
+    let record = parse_line("A000001.000 0A235 0EA00 14F001").unwrap();
+    assert_eq!(record.message.id.pgn(), 59904);
+    assert_eq!(record.message.id.destination(), Some(0x23));
+    assert_eq!(record.message.id.priority(), 5);
+}
+
+#[test]
+/// Parsing then writing reproduces the original line.
+fn test_write_line_roundtrip() {
+    for line in [DISTANCE_LOG, "A000001.000 0A235 0EA00 14F001"] {
+        let mut written = LineBuffer::new();
+        write_line(&mut written, &parse_line(line).unwrap()).unwrap();
+        assert_eq!(written.as_str(), line);
+    }
+}
+
+#[test]
+/// Malformed lines are rejected with the matching error.
+fn test_parse_errors() {
+    assert_eq!(parse_line("").unwrap_err(), FormatError::Syntax);
+    assert_eq!(
+        parse_line("173321.107 23FF7 1F513 01").unwrap_err(),
+        FormatError::Syntax
+    );
+    assert_eq!(
+        parse_line("A176321.107 23FF7 1F513 01").unwrap_err(),
+        FormatError::InvalidTimestamp
+    );
+    assert_eq!(
+        parse_line("A173321.107 23FF 1F513 01").unwrap_err(),
+        FormatError::Syntax
+    );
+    assert_eq!(
+        parse_line("A173321.107 23FF9 1F513 01").unwrap_err(),
+        FormatError::InvalidHex
+    );
+    assert_eq!(
+        parse_line("A173321.107 23FF7 1F513 012").unwrap_err(),
+        FormatError::InvalidHex
+    );
+}
+
+#[test]
+/// Long payloads are split into Fast Packet fragments the decoder reassembles.
+fn test_frames_replay() {
+    let line = "A120000.000 03FF3 1F805 000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F202122232425262728292A";
+    let record = parse_line(line).unwrap();
+    assert_eq!(record.message.len, 43);
+
+    let mut decoder = N2kDecoder::new();
+    let mut frames = 0;
+    let mut completed = None;
+    for frame in record.frames(&mut SequenceCounter::new()) {
+        let frame = frame.unwrap();
+        assert_eq!(frame.timestamp_us, record.timestamp_us);
+        assert_eq!(frame.frame.id, record.message.id);
+        frames += 1;
+        if let Some(message) = decoder.process_frame(&frame.frame, frame.timestamp_us / 1000) {
+            completed = Some(message);
+        }
+    }
+    assert_eq!(frames, 7);
+    assert_eq!(completed.unwrap(), record.message);
+
+    // Short payloads fit a single frame.
+    let record = parse_line("A000001.000 0A235 0EA00 14F001").unwrap();
+    let mut sequences = SequenceCounter::new();
+    let mut frames = record.frames(&mut sequences);
+    let frame = frames.next().unwrap().unwrap().frame;
+    assert_eq!(frame.id, record.message.id);
+    assert_eq!(&frame.data[..frame.len], &[0x14, 0xF0, 0x01]);
+    assert!(frames.next().is_none());
+}
+
+#[test]
+/// Consecutive long messages take consecutive Fast Packet sequence identifiers.
+fn test_frames_sequence_counter() {
+    let line = "A120000.000 03FF3 1F805 000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F202122232425262728292A";
+    let record = parse_line(line).unwrap();
+    let mut sequences = SequenceCounter::new();
+
+    let first = record.frames(&mut sequences).next().unwrap().unwrap();
+    let second = record.frames(&mut sequences).next().unwrap().unwrap();
+    assert_eq!(
+        second.frame.data[0] >> 5,
+        ((first.frame.data[0] >> 5) + 1) & 0x07
+    );
+}
+
+#[test]
+/// Long ISO PGNs go through a BAM session; addressed ones cannot be split.
+fn test_frames_iso_transport() {
+    // PGN 65240 (ISO Commanded Address): NAME and new address, 9 bytes.
+    let record = parse_line("A120000.000 03FF6 0FED8 0102030405060708A0").unwrap();
+    let mut assembler = BamAssembler::new();
+    let mut frames = 0;
+    let mut completed = None;
+    for frame in record.frames(&mut SequenceCounter::new()) {
+        frames += 1;
+        if let BamResult::MessageComplete(message) =
+            assembler.process_frame(&frame.unwrap().frame, 0)
+        {
+            completed = Some(message);
+        }
+    }
+    assert_eq!(frames, 3, "announcement and two data packets");
+    let message = completed.unwrap();
+    assert_eq!(message.pgn, 65240);
+    assert_eq!(&message.payload[..message.len], record.message.payload());
+
+    // An addressed ISO PGN needs an RTS/CTS session with its destination.
+    let record = parse_line("A120000.000 03236 0EA00 0102030405060708A0").unwrap();
+    let mut frames = record.frames(&mut SequenceCounter::new());
+    assert_eq!(
+        frames.next().unwrap().unwrap_err(),
+        FormatError::Segmentation
+    );
+    assert!(frames.next().is_none());
+}
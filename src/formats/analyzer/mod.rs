@@ -0,0 +1,815 @@
+//! CANboat `analyzer` output for decoded PGNs.
+//!
+//! Renders any [`FieldAccess`] type against its [`PgnDescriptor`] in the two
+//! formats of CANboat's `analyzer`, so decoding can be diffed against the same
+//! `canboat.json` the build consumes:
+//!
+//! ```text
+//! 2011-11-24-22:42:04.388 2   3 255 127250 Vessel Heading: SID = 1; Heading = 182.802 deg; Reference = Magnetic
+//! {"timestamp":"2011-11-24-22:42:04.388","prio":2,"src":3,"dst":255,"pgn":127250,"description":"Vessel Heading","fields":{"SID":1,"Heading":182.802,"Reference":"Magnetic"}}
+//! ```
+//!
+//! Field names come from [`FieldDescriptor::name`], units from
+//! [`FieldDescriptor::physical_unit`] (text only), and lookup values are
+//! printed by their CANboat name. As in `analyzer`'s default mode, angles are
+//! shown in degrees and temperatures in degrees Celsius. Reserved fields and
+//! fields holding the "not available" value (or no bit set) are left out; repeating sets are
+//! flattened in text and listed under `"list"` in JSON.
+//!
+//! ```rust,ignore
+//! let header = AnalyzerHeader::new("2011-11-24-22:42:04.388", decoded.id);
+//! let message = &decoded.message;
+//! write_text(&mut line, &header, message.message_descriptor(), message)?;
+//! ```
+use core::{f64::consts::PI, fmt};
+
+use crate::{
+    core::{FieldDescriptor, FieldKind, PgnDecimal, PgnDescriptor, PgnValue, PgnVariable},
+    infra::codec::{
+        engine::{decode_variable, variable_selector},
+        traits::FieldAccess,
+    },
+    protocol::{lookups::lookup_name, managment::iso_name::IsoName, transport::can_id::CanId},
+};
+
+//==================================================================================HEADER
+/// Leading columns of an `analyzer` record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnalyzerHeader<'a> {
+    /// Timestamp text, echoed as found in the source log.
+    pub timestamp: &'a str,
+    /// Message priority (0–7).
+    pub priority: u8,
+    /// Source address.
+    pub source: u8,
+    /// Destination address, 255 for broadcast PGNs.
+    pub destination: u8,
+    /// Parameter Group Number.
+    pub pgn: u32,
+}
+
+impl<'a> AnalyzerHeader<'a> {
+    /// Header of a message received with identifier `id`.
+    pub fn new(timestamp: &'a str, id: CanId) -> Self {
+        Self {
+            timestamp,
+            priority: id.priority(),
+            source: id.source_address(),
+            destination: id.destination().unwrap_or(255),
+            pgn: id.pgn(),
+        }
+    }
+}
+
+//==================================================================================TEXT
+/// Write `message` as one `analyzer` text line (without the trailing newline).
+pub fn write_text<W, M>(
+    out: &mut W,
+    header: &AnalyzerHeader<'_>,
+    descriptor: &PgnDescriptor,
+    message: &M,
+) -> fmt::Result
+where
+    W: fmt::Write,
+    M: FieldAccess + ?Sized,
+{
+    write!(
+        out,
+        "{} {} {:3} {:3} {:6} {}:",
+        header.timestamp,
+        header.priority,
+        header.source,
+        header.destination,
+        header.pgn,
+        descriptor.description
+    )?;
+
+    let mut separator = "";
+    for_each_value(descriptor, message, |entry| {
+        match entry {
+            Entry::Field(name, value) => {
+                write!(out, "{separator} {name} = ")?;
+                value.write_text(out)?;
+                separator = ";";
+            }
+            Entry::ElementStart(_) | Entry::ElementEnd => {}
+        }
+        Ok(())
+    })
+}
+
+//==================================================================================JSON
+/// Write `message` as one `analyzer -json` object (without the trailing newline).
+pub fn write_json<W, M>(
+    out: &mut W,
+    header: &AnalyzerHeader<'_>,
+    descriptor: &PgnDescriptor,
+    message: &M,
+) -> fmt::Result
+where
+    W: fmt::Write,
+    M: FieldAccess + ?Sized,
+{
+    out.write_str("{\"timestamp\":")?;
+    write_json_string(out, header.timestamp.chars())?;
+    write!(
+        out,
+        ",\"prio\":{},\"src\":{},\"dst\":{},\"pgn\":{},\"description\":",
+        header.priority, header.source, header.destination, header.pgn
+    )?;
+    write_json_string(out, descriptor.description.chars())?;
+    out.write_str(",\"fields\":{")?;
+
+    let mut first = true;
+    let mut in_list = false;
+    for_each_value(descriptor, message, |entry| {
+        match entry {
+            Entry::Field(name, value) => {
+                if !first {
+                    out.write_char(',')?;
+                }
+                write_json_string(out, name.chars())?;
+                out.write_char(':')?;
+                value.write_json(out)?;
+                first = false;
+            }
+            Entry::ElementStart(index) => {
+                if !in_list {
+                    if !first {
+                        out.write_char(',')?;
+                    }
+                    out.write_str("\"list\":[")?;
+                    in_list = true;
+                }
+                if index > 0 {
+                    out.write_char(',')?;
+                }
+                out.write_char('{')?;
+                first = true;
+            }
+            Entry::ElementEnd => {
+                out.write_char('}')?;
+                first = false;
+            }
+        }
+        Ok(())
+    })?;
+    if in_list {
+        out.write_char(']')?;
+    }
+    out.write_str("}}")
+}
+
+/// Write `text` as a JSON string literal.
+fn write_json_string<W: fmt::Write>(out: &mut W, text: impl Iterator<Item = char>) -> fmt::Result {
+    out.write_char('"')?;
+    for c in text {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+//==================================================================================FIELD_WALK
+/// Step of the walk over the printable values of a message.
+// Values are handed over by value: the crate does not allocate.
+#[allow(clippy::large_enum_variant)]
+enum Entry {
+    /// Field name and value.
+    Field(&'static str, Value),
+    /// Start of the repeating-set element at the given index.
+    ElementStart(usize),
+    ElementEnd,
+}
+
+/// Visit the printable fields of `message` in descriptor order, expanding the
+/// first repeating set once per element.
+fn for_each_value<M, F>(descriptor: &PgnDescriptor, message: &M, mut visit: F) -> fmt::Result
+where
+    M: FieldAccess + ?Sized,
+    F: FnMut(Entry) -> fmt::Result,
+{
+    let fields = descriptor.fields;
+    let Some(set) = descriptor.repeating_field_sets.first() else {
+        return fields.iter().try_for_each(|field| {
+            let master = master_value(fields, field, |master| message.field(master.id));
+            visit_field(field, message.field(field.id), master, &mut visit)
+        });
+    };
+
+    let repeated = set.start_field_index..set.start_field_index + set.size;
+    // PGN whose fields the Group Function parameters of each element address.
+    let referenced_pgn = fields
+        .iter()
+        .find(|field| field.kind == FieldKind::Pgn)
+        .and_then(|field| message.field(field.id))
+        .as_ref()
+        .and_then(as_u64);
+    for field in fields.get(..repeated.start).unwrap_or_default() {
+        let master = master_value(fields, field, |master| message.field(master.id));
+        visit_field(field, message.field(field.id), master, &mut visit)?;
+    }
+    // Value of parameter 1, selecting the variant of a polymorphic referenced PGN.
+    let mut selector = None;
+    for element in 0..message.repetitive_count(set.array_id).unwrap_or(0) {
+        visit(Entry::ElementStart(element))?;
+        let mut parameter = None;
+        for field in fields.get(repeated.clone()).unwrap_or_default() {
+            let master = master_value(fields, field, |master| {
+                // The selecting field may belong to the element or to the message.
+                message
+                    .repetitive_field(set.array_id, element, master.id)
+                    .or_else(|| message.field(master.id))
+            });
+            let value = message.repetitive_field(set.array_id, element, field.id);
+            match (&field.kind, &value) {
+                (FieldKind::FieldIndex, _) => parameter = value.as_ref().and_then(as_u64),
+                (FieldKind::Variable, Some(PgnValue::Variable(variable))) => {
+                    let target = referenced_pgn.zip(parameter).and_then(|(pgn, index)| {
+                        let pgn = u32::try_from(pgn).ok()?;
+                        let index = u8::try_from(index).ok()?;
+                        message.variable_field(pgn, index, selector).ok()?
+                    });
+                    if let (Some(target), Some(1)) = (target, parameter) {
+                        selector = variable_selector(target, variable);
+                    }
+                    visit_variable(field, variable, target, &mut visit)?;
+                    continue;
+                }
+                _ => {}
+            }
+            visit_field(field, value, master, &mut visit)?;
+        }
+        visit(Entry::ElementEnd)?;
+    }
+    for field in fields.get(repeated.end..).unwrap_or_default() {
+        let master = master_value(fields, field, |master| message.field(master.id));
+        visit_field(field, message.field(field.id), master, &mut visit)?;
+    }
+    Ok(())
+}
+
+/// Visit `value` of `field` unless it is absent or not printable.
+fn visit_field<F>(
+    field: &'static FieldDescriptor,
+    value: Option<PgnValue>,
+    master: Option<u64>,
+    visit: &mut F,
+) -> fmt::Result
+where
+    F: FnMut(Entry) -> fmt::Result,
+{
+    if let (FieldKind::IsoName, Some(0), Some(PgnValue::U64(raw))) =
+        (&field.kind, field.bits_offset, &value)
+    {
+        return visit_iso_name(*raw, visit);
+    }
+    match value.and_then(|value| Value::from_field(field, &value, master)) {
+        Some(value) => visit(Entry::Field(field.name, value)),
+        None => Ok(()),
+    }
+}
+
+/// Visit a Group Function parameter value as the `target` field of the
+/// referenced PGN it stands for, or as raw bytes when that field is unresolved.
+fn visit_variable<F>(
+    field: &'static FieldDescriptor,
+    variable: &PgnVariable,
+    target: Option<&'static FieldDescriptor>,
+    visit: &mut F,
+) -> fmt::Result
+where
+    F: FnMut(Entry) -> fmt::Result,
+{
+    let resolved = target.and_then(|target| {
+        let value = decode_variable(target, variable).ok()?;
+        Some(value.and_then(|value| Value::from_field(target, &value, None)))
+    });
+    match resolved.unwrap_or_else(|| Value::from_field(field, &PgnValue::Variable(*variable), None))
+    {
+        Some(value) => visit(Entry::Field(field.name, value)),
+        None => Ok(()),
+    }
+}
+
+/// Visit the sub-fields of a NAME opening the payload (Address Claim,
+/// Commanded Address), named as CANboat describes PGN 60928. NAMEs embedded
+/// further in a payload stay one number, as in `analyzer`.
+fn visit_iso_name<F>(raw: u64, visit: &mut F) -> fmt::Result
+where
+    F: FnMut(Entry) -> fmt::Result,
+{
+    let name = IsoName::from_raw(raw);
+    let number = |value: u64| Value::Integer {
+        value: value as i64,
+        unit: None,
+    };
+    let lookup = |enumeration: &str, value: u32| match lookup_name(enumeration, value) {
+        Some(name) => Value::Name(name),
+        None => number(value as u64),
+    };
+    let class = name.device_class() as u32;
+
+    visit(Entry::Field(
+        "Unique Number",
+        number(name.unique_number() as u64),
+    ))?;
+    visit(Entry::Field(
+        "Manufacturer Code",
+        lookup("ManufacturerCode", name.manufacturer_code() as u32),
+    ))?;
+    visit(Entry::Field(
+        "Device Instance Lower",
+        number(name.device_instance_lower() as u64),
+    ))?;
+    visit(Entry::Field(
+        "Device Instance Upper",
+        number(name.device_instance_upper() as u64),
+    ))?;
+    visit(Entry::Field(
+        "Device Function",
+        lookup(
+            "DeviceFunction",
+            (class << 8) | name.device_function() as u32,
+        ),
+    ))?;
+    visit(Entry::Field("Device Class", lookup("DeviceClass", class)))?;
+    visit(Entry::Field(
+        "System Instance",
+        number(name.system_instance() as u64),
+    ))?;
+    visit(Entry::Field(
+        "Industry Group",
+        lookup("IndustryCode", name.industry_group() as u32),
+    ))?;
+    visit(Entry::Field(
+        "Arbitrary address capable",
+        lookup("YesNo", name.is_arbitrary_address_capable() as u32),
+    ))
+}
+
+/// Raw value of the field selecting the indirect lookup of `field`, if any.
+fn master_value<F>(
+    fields: &'static [FieldDescriptor],
+    field: &FieldDescriptor,
+    read: F,
+) -> Option<u64>
+where
+    F: FnOnce(&'static FieldDescriptor) -> Option<PgnValue>,
+{
+    let order = field.enum_indirect_field_order? as usize;
+    let master = fields.get(order.checked_sub(1)?)?;
+    read(master).as_ref().and_then(as_u64)
+}
+
+//==================================================================================VALUES
+/// Printable form of one field value.
+enum Value {
+    /// Scaled number and the number of decimals its resolution allows.
+    Number {
+        value: f64,
+        decimals: usize,
+        unit: Option<&'static str>,
+    },
+    Integer {
+        value: i64,
+        unit: Option<&'static str>,
+    },
+    /// Latitude or longitude in decimal degrees.
+    Coordinate(f64),
+    Name(&'static str),
+    /// Set bits of a bit lookup.
+    Bits {
+        enumeration: Option<&'static str>,
+        bits: u64,
+    },
+    Date {
+        year: i64,
+        month: u32,
+        day: u32,
+    },
+    Time {
+        seconds: u64,
+        fraction: u64,
+    },
+    Mmsi(u64),
+    /// Digits of a BCD field.
+    Decimal(PgnDecimal),
+    /// Raw value of a NAME embedded in the payload.
+    IsoName(u64),
+    /// Text of a string field, already trimmed of its padding.
+    Text {
+        bytes: [u8; crate::core::MAX_PGN_BYTES],
+        len: usize,
+        utf16: bool,
+    },
+    Binary {
+        bytes: [u8; crate::core::MAX_PGN_BYTES],
+        len: usize,
+    },
+}
+
+impl Value {
+    /// Interpret `value` according to `field`; `None` for reserved fields and
+    /// "not available" values.
+    fn from_field(field: &FieldDescriptor, value: &PgnValue, master: Option<u64>) -> Option<Self> {
+        match field.kind {
+            FieldKind::Reserved | FieldKind::Spare => None,
+            FieldKind::Lookup | FieldKind::IndirectLookup => {
+                let raw = as_u64(value)?;
+                let name = match (field.enum_direct_name, field.enum_indirect_name) {
+                    (Some(enumeration), _) => lookup_name(enumeration, raw as u32),
+                    (None, Some(enumeration)) => {
+                        lookup_name(enumeration, ((master? as u32) << 8) | raw as u32)
+                    }
+                    (None, None) => None,
+                };
+                match name {
+                    Some(name) => Some(Self::Name(name)),
+                    None if is_unavailable(field, raw) => None,
+                    None => Some(Self::Integer {
+                        value: raw as i64,
+                        unit: None,
+                    }),
+                }
+            }
+            FieldKind::BitLookup => match as_u64(value)? {
+                0 => None,
+                bits => Some(Self::Bits {
+                    enumeration: field.enum_bit_name,
+                    bits,
+                }),
+            },
+            FieldKind::Date => {
+                let days = as_u64(value)?;
+                if is_unavailable(field, days) {
+                    return None;
+                }
+                let (year, month, day) = civil_from_days(days as i64);
+                Some(Self::Date { year, month, day })
+            }
+            FieldKind::Time => {
+                let seconds = as_f64(value)?;
+                let resolution = field.resolution.map_or(1.0, decimal_resolution);
+                if is_unavailable(field, round(seconds / resolution) as u64) {
+                    return None;
+                }
+                let ticks = round(seconds * 10_000.0).max(0) as u64;
+                Some(Self::Time {
+                    seconds: ticks / 10_000,
+                    fraction: ticks % 10_000,
+                })
+            }
+            FieldKind::Mmsi => {
+                let mmsi = as_u64(value)?;
+                (!is_unavailable(field, mmsi)).then_some(Self::Mmsi(mmsi))
+            }
+            FieldKind::StringFix | FieldKind::StringLz | FieldKind::StringLau => {
+                let PgnValue::Bytes(bytes) = value else {
+                    return None;
+                };
+                let mut text = bytes.as_slice();
+                let mut utf16 = false;
+                if field.kind == FieldKind::StringLau {
+                    let (&encoding, rest) = text.split_first()?;
+                    utf16 = encoding == 0;
+                    text = rest;
+                }
+                let end = text
+                    .iter()
+                    .position(|&byte| byte == 0x00 || byte == 0xFF)
+                    .unwrap_or(text.len());
+                let mut text = &text[..end];
+                while let [rest @ .., b'@' | b' '] = text {
+                    text = rest;
+                }
+                let mut out = [0; crate::core::MAX_PGN_BYTES];
+                out[..text.len()].copy_from_slice(text);
+                Some(Self::Text {
+                    bytes: out,
+                    len: text.len(),
+                    utf16,
+                })
+            }
+            FieldKind::IsoName => match as_u64(value)? {
+                u64::MAX => None,
+                raw => Some(Self::IsoName(raw)),
+            },
+            FieldKind::Decimal => match value {
+                PgnValue::Decimal(digits) if digits.is_empty() => None,
+                PgnValue::Decimal(digits) => Some(Self::Decimal(*digits)),
+                _ => Self::number(field, value),
+            },
+            FieldKind::Binary | FieldKind::Unimplemented | FieldKind::Variable => match value {
+                PgnValue::Bytes(bytes) => Some(Self::Binary {
+                    bytes: bytes.data,
+                    len: bytes.len,
+                }),
+                PgnValue::Variable(variable) => {
+                    let mut bytes = [0; crate::core::MAX_PGN_BYTES];
+                    bytes[..variable.len()].copy_from_slice(variable.as_bytes());
+                    Some(Self::Binary {
+                        bytes,
+                        len: variable.len(),
+                    })
+                }
+                _ => Self::number(field, value),
+            },
+            _ => Self::number(field, value),
+        }
+    }
+
+    /// Plain numeric field (numbers, PGNs, durations, names, …).
+    fn number(field: &FieldDescriptor, value: &PgnValue) -> Option<Self> {
+        let unit = field.physical_unit;
+        match value {
+            PgnValue::F32(_) | PgnValue::F64(_) => {
+                let resolution = field.resolution.map_or(1.0, decimal_resolution);
+                let raw = round(as_f64(value)? / resolution);
+                if field.is_signed == Some(true) {
+                    if is_unavailable_signed(field, raw) {
+                        return None;
+                    }
+                } else if raw >= 0 && is_unavailable(field, raw as u64) {
+                    return None;
+                }
+                // Rescale from the raw value, as `analyzer` does, to avoid `f32` noise.
+                if is_coordinate(field) {
+                    return Some(Self::Coordinate(raw as f64 * resolution));
+                }
+                let (value, resolution, unit) =
+                    display_unit(raw as f64 * resolution, resolution, unit);
+                Some(Self::Number {
+                    value,
+                    decimals: decimals(resolution),
+                    unit,
+                })
+            }
+            PgnValue::I8(_) | PgnValue::I16(_) | PgnValue::I32(_) | PgnValue::I64(_) => {
+                let raw = as_i64(value)?;
+                (!is_unavailable_signed(field, raw)).then_some(Self::Integer { value: raw, unit })
+            }
+            _ => {
+                let raw = as_u64(value)?;
+                (!is_unavailable(field, raw)).then_some(Self::Integer {
+                    value: raw as i64,
+                    unit,
+                })
+            }
+        }
+    }
+
+    fn write_text<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        match self {
+            Self::Number {
+                value,
+                decimals,
+                unit,
+            } => {
+                write!(out, "{value:.decimals$}")?;
+                write_unit(out, *unit)
+            }
+            Self::Integer { value, unit } => {
+                write!(out, "{value}")?;
+                write_unit(out, *unit)
+            }
+            Self::Coordinate(degrees) => write_coordinate(out, *degrees),
+            Self::Name(name) => out.write_str(name),
+            Self::Bits { enumeration, bits } => {
+                let mut separator = "";
+                for_each_bit(*enumeration, *bits, |name| {
+                    out.write_str(separator)?;
+                    separator = ", ";
+                    match name {
+                        BitName::Named(name) => out.write_str(name),
+                        BitName::Unnamed(bit) => write!(out, "Bit {bit}"),
+                    }
+                })
+            }
+            Self::Date { year, month, day } => write!(out, "{year:04}.{month:02}.{day:02}"),
+            Self::Time { seconds, fraction } => write!(
+                out,
+                "{:02}:{:02}:{:02}.{fraction:04}",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            ),
+            Self::Mmsi(mmsi) => write!(out, "{mmsi:09}"),
+            Self::Decimal(digits) => write!(out, "{digits}"),
+            Self::IsoName(raw) => write!(out, "{raw}"),
+            Self::Text { .. } => self.text_chars().try_for_each(|c| out.write_char(c)),
+            Self::Binary { bytes, len } => {
+                let mut separator = "";
+                for byte in &bytes[..*len] {
+                    write!(out, "{separator}{byte:02X}")?;
+                    separator = " ";
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn write_json<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        match self {
+            Self::Number {
+                value, decimals, ..
+            } => write!(out, "{value:.decimals$}"),
+            Self::Integer { value, .. } => write!(out, "{value}"),
+            Self::Coordinate(degrees) => write_coordinate(out, *degrees),
+            Self::IsoName(raw) => write!(out, "{raw}"),
+            Self::Name(name) => write_json_string(out, name.chars()),
+            Self::Bits { enumeration, bits } => {
+                out.write_char('[')?;
+                let mut separator = "";
+                for_each_bit(*enumeration, *bits, |name| {
+                    out.write_str(separator)?;
+                    separator = ",";
+                    match name {
+                        BitName::Named(name) => write_json_string(out, name.chars()),
+                        BitName::Unnamed(bit) => write!(out, "{bit}"),
+                    }
+                })?;
+                out.write_char(']')
+            }
+            Self::Text { .. } => write_json_string(out, self.text_chars()),
+            Self::Date { .. }
+            | Self::Time { .. }
+            | Self::Mmsi(_)
+            | Self::Decimal(_)
+            | Self::Binary { .. } => {
+                out.write_char('"')?;
+                self.write_text(out)?;
+                out.write_char('"')
+            }
+        }
+    }
+
+    /// Characters of a string field: UTF-16LE or single-byte (Latin-1) text.
+    fn text_chars(&self) -> impl Iterator<Item = char> + '_ {
+        let (bytes, utf16): (&[u8], bool) = match self {
+            Self::Text { bytes, len, utf16 } => (&bytes[..*len], *utf16),
+            _ => (&[], false),
+        };
+        let step = if utf16 { 2 } else { 1 };
+        bytes.chunks(step).map(move |chunk| match *chunk {
+            [low, high] => char::from_u32(u16::from_le_bytes([low, high]) as u32)
+                .unwrap_or(char::REPLACEMENT_CHARACTER),
+            [byte, ..] => byte as char,
+            [] => char::REPLACEMENT_CHARACTER,
+        })
+    }
+}
+
+/// Decimal degrees as `analyzer` prints them (`%10.7f`), whatever the field resolution.
+fn write_coordinate<W: fmt::Write>(out: &mut W, degrees: f64) -> fmt::Result {
+    write!(out, "{degrees:10.7}")
+}
+
+fn write_unit<W: fmt::Write>(out: &mut W, unit: Option<&str>) -> fmt::Result {
+    match unit {
+        Some(unit) => write!(out, " {unit}"),
+        None => Ok(()),
+    }
+}
+
+enum BitName {
+    Named(&'static str),
+    Unnamed(u32),
+}
+
+/// Call `visit` with the name of every bit set in `bits`.
+fn for_each_bit<F>(enumeration: Option<&'static str>, bits: u64, mut visit: F) -> fmt::Result
+where
+    F: FnMut(BitName) -> fmt::Result,
+{
+    (0..64)
+        .filter(|bit| bits & (1 << bit) != 0)
+        .try_for_each(|bit| {
+            match enumeration.and_then(|enumeration| lookup_name(enumeration, bit)) {
+                Some(name) => visit(BitName::Named(name)),
+                None => visit(BitName::Unnamed(bit)),
+            }
+        })
+}
+
+//==================================================================================HELPERS
+/// Convert SI angles and temperatures to the units `analyzer` prints by default.
+fn display_unit(
+    value: f64,
+    resolution: f64,
+    unit: Option<&'static str>,
+) -> (f64, f64, Option<&'static str>) {
+    match unit {
+        Some("rad") => (value * 180.0 / PI, resolution * 180.0 / PI, Some("deg")),
+        Some("rad/s") => (value * 180.0 / PI, resolution * 180.0 / PI, Some("deg/s")),
+        Some("K") => (value - 273.15, resolution, Some("C")),
+        _ => (value, resolution, unit),
+    }
+}
+
+/// `resolution` as the decimal value written in `canboat.json` (descriptors store `f32`).
+fn decimal_resolution(resolution: f32) -> f64 {
+    let scale = pow10(decimals(resolution as f64) + 6);
+    round(resolution as f64 * scale) as f64 / scale
+}
+
+fn pow10(exponent: usize) -> f64 {
+    (0..exponent).fold(1.0, |power, _| power * 10.0)
+}
+
+/// Decimals needed to show steps of `resolution`.
+fn decimals(resolution: f64) -> usize {
+    let mut step = resolution.abs();
+    let mut decimals = 0;
+    while step > 0.0 && step < 0.999_999 && decimals < 16 {
+        step *= 10.0;
+        decimals += 1;
+    }
+    decimals
+}
+
+/// Whether `field` holds a latitude or a longitude, which `analyzer` prints
+/// with a fixed precision.
+fn is_coordinate(field: &FieldDescriptor) -> bool {
+    matches!(
+        field.physical_qtity,
+        Some("GEOGRAPHICAL_LATITUDE" | "GEOGRAPHICAL_LONGITUDE")
+    )
+}
+
+/// Whether unsigned `raw` is the "not available" value (all bits set) of `field`.
+fn is_unavailable(field: &FieldDescriptor, raw: u64) -> bool {
+    match field.bits_length {
+        Some(bits @ 2..=63) => raw == (1 << bits) - 1,
+        Some(64) => raw == u64::MAX,
+        _ => false,
+    }
+}
+
+/// Whether signed `raw` is the "not available" value (largest positive) of `field`.
+fn is_unavailable_signed(field: &FieldDescriptor, raw: i64) -> bool {
+    match field.bits_length {
+        Some(bits @ 2..=64) => raw == ((1u64 << (bits - 1)) - 1) as i64,
+        _ => false,
+    }
+}
+
+/// Round half away from zero (`f64::round` needs `std`).
+fn round(value: f64) -> i64 {
+    if value >= 0.0 {
+        (value + 0.5) as i64
+    } else {
+        (value - 0.5) as i64
+    }
+}
+
+fn as_u64(value: &PgnValue) -> Option<u64> {
+    match *value {
+        PgnValue::U8(v) => Some(v as u64),
+        PgnValue::U16(v) => Some(v as u64),
+        PgnValue::U32(v) => Some(v as u64),
+        PgnValue::U64(v) => Some(v),
+        _ => as_i64(value).map(|v| v as u64),
+    }
+}
+
+fn as_i64(value: &PgnValue) -> Option<i64> {
+    match *value {
+        PgnValue::I8(v) => Some(v as i64),
+        PgnValue::I16(v) => Some(v as i64),
+        PgnValue::I32(v) => Some(v as i64),
+        PgnValue::I64(v) => Some(v),
+        _ => None,
+    }
+}
+
+fn as_f64(value: &PgnValue) -> Option<f64> {
+    match *value {
+        PgnValue::F32(v) => Some(v as f64),
+        PgnValue::F64(v) => Some(v),
+        _ => as_u64(value).map(|v| v as f64),
+    }
+}
+
+/// Proleptic Gregorian date of a day count since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,218 @@
+//! `analyzer` output tests against generated PGN types.
+// ANALYZER
+use super::*;
+use crate::{
+    formats::text::LineBuffer,
+    infra::codec::traits::PgnMetadata,
+    protocol::managment::iso_name::IsoName,
+    protocol::messages::{N2kMessage, Pgn126985, Pgn127489, Pgn129029, Pgn60928},
+};
+
+const TIMESTAMP: &str = "2011-11-24-22:42:04.388";
+
+fn header(pgn: u32) -> AnalyzerHeader<'static> {
+    AnalyzerHeader {
+        timestamp: TIMESTAMP,
+        priority: 2,
+        source: 3,
+        destination: 255,
+        pgn,
+    }
+}
+
+fn text<M: FieldAccess + PgnMetadata>(message: &M) -> LineBuffer {
+    let mut line = LineBuffer::new();
+    write_text(&mut line, &header(M::PGN), &M::DESCRIPTOR, message).unwrap();
+    line
+}
+
+fn json<M: FieldAccess + PgnMetadata>(message: &M) -> LineBuffer {
+    let mut line = LineBuffer::new();
+    write_json(&mut line, &header(M::PGN), &M::DESCRIPTOR, message).unwrap();
+    line
+}
+
+#[test]
+/// Units, lookups, and "not available" fields of a decoded heading.
+fn test_vessel_heading() {
+    // Heading 3.1905 rad, deviation and variation not available, magnetic.
+    let payload = [0x01, 0xA1, 0x7C, 0xFF, 0x7F, 0xFF, 0x7F, 0xFD];
+    let message = N2kMessage::decode(127250, &payload).unwrap();
+    let N2kMessage::Pgn127250(heading) = message else {
+        panic!("unexpected variant");
+    };
+
+    assert_eq!(
+        text(&heading).as_str(),
+        "2011-11-24-22:42:04.388 2   3 255 127250 Vessel Heading: SID = 1; Heading = 182.802 deg; Reference = Magnetic"
+    );
+    assert_eq!(
+        json(&heading).as_str(),
+        r#"{"timestamp":"2011-11-24-22:42:04.388","prio":2,"src":3,"dst":255,"pgn":127250,"description":"Vessel Heading","fields":{"SID":1,"Heading":182.802,"Reference":"Magnetic"}}"#
+    );
+
+    // The decoded enum renders through its own descriptor.
+    let mut line = LineBuffer::new();
+    write_text(
+        &mut line,
+        &header(127250),
+        message.message_descriptor(),
+        &message,
+    )
+    .unwrap();
+    assert_eq!(line.as_str(), text(&heading).as_str());
+    assert!(core::ptr::eq(
+        message.message_descriptor(),
+        N2kMessage::descriptor(127250).unwrap()
+    ));
+}
+
+#[test]
+/// Latitude and longitude print with seven decimals and no unit, whatever
+/// their resolution, like `analyzer` in its default decimal-degree mode.
+fn test_position_precision() {
+    let payload = [0xC0, 0xEC, 0x4F, 0x1C, 0x40, 0xC4, 0xB8, 0xFC];
+    let N2kMessage::Pgn129025(position) = N2kMessage::decode(129025, &payload).unwrap() else {
+        panic!("unexpected variant");
+    };
+
+    assert_eq!(
+        text(&position).as_str(),
+        "2011-11-24-22:42:04.388 2   3 255 129025 Position, Rapid Update: Latitude = 47.5000000; Longitude = -5.5000000"
+    );
+    assert_eq!(
+        json(&position).as_str(),
+        r#"{"timestamp":"2011-11-24-22:42:04.388","prio":2,"src":3,"dst":255,"pgn":129025,"description":"Position, Rapid Update","fields":{"Latitude":47.5000000,"Longitude":-5.5000000}}"#
+    );
+}
+
+#[test]
+/// The Address Claim NAME is printed field by field, with its indirect lookup.
+fn test_address_claim_indirect_lookup() {
+    let mut claim = Pgn60928::new();
+    claim.name = IsoName::builder()
+        .manufacturer_code(69)
+        .device_function(130)
+        .device_class(25)
+        .industry_group(4)
+        .build();
+
+    let line = text(&claim);
+    assert!(line
+        .as_str()
+        .contains("Manufacturer Code = ARKS Enterprises, Inc.;"));
+    assert!(line.as_str().contains("Device Function = PC Gateway;"));
+    assert!(line
+        .as_str()
+        .contains("Device Class = Internetwork device;"));
+    assert!(line.as_str().contains("Industry Group = Marine Industry"));
+    assert!(!line.as_str().contains("Spare"));
+}
+
+#[test]
+/// A NAME embedded further in the payload stays one unsigned number.
+fn test_embedded_iso_name() {
+    let mut alert = Pgn126985::new();
+    alert.data_source_network_id_name = IsoName::from_raw(0xC000_0000_0000_0001);
+
+    let line = text(&alert);
+    assert!(line
+        .as_str()
+        .contains("Data Source Network ID NAME = 13835058055282163713;"));
+    assert!(!line.as_str().contains("Unique Number"));
+}
+
+#[test]
+/// Dates, times, and repeating sets.
+fn test_gnss_position_repeating_set() {
+    let mut position = Pgn129029::new();
+    position.field_mut("Date", PgnValue::U16(15302));
+    position.field_mut("Time", PgnValue::F64(37_324.5));
+    position.field_mut("Latitude", PgnValue::F64(47.5));
+    position.set_repetitive_count("reference_station_types", 2);
+    position.repetitive_field_mut(
+        "reference_station_types",
+        0,
+        "ReferenceStationId",
+        PgnValue::U16(101),
+    );
+    position.repetitive_field_mut(
+        "reference_station_types",
+        1,
+        "ReferenceStationId",
+        PgnValue::U16(202),
+    );
+
+    let line = text(&position);
+    assert!(line
+        .as_str()
+        .contains("Date = 2011.11.24; Time = 10:22:04.5000; Latitude = 47.5000000;"));
+    assert!(line.as_str().contains("Reference Station ID = 101;"));
+    assert!(line.as_str().contains("Reference Station ID = 202;"));
+
+    let line = json(&position);
+    assert!(line
+        .as_str()
+        .contains(r#""Date":"2011.11.24","Time":"10:22:04.5000""#));
+    assert!(line.as_str().contains(r#","list":[{"#));
+    assert!(line.as_str().contains(r#""Reference Station ID":101"#));
+    assert!(line.as_str().ends_with("}]}}"));
+}
+
+#[test]
+/// Bit lookups list the name of every set bit.
+fn test_bit_lookup() {
+    let mut engine = Pgn127489::new();
+    engine.discrete_status1 = 0b110;
+
+    let line = text(&engine);
+    assert!(line
+        .as_str()
+        .contains("Discrete Status 1 = Over Temperature, Low Oil Pressure;"));
+    let line = json(&engine);
+    assert!(line
+        .as_str()
+        .contains(r#""Discrete Status 1":["Over Temperature","Low Oil Pressure"]"#));
+}
+
+#[test]
+/// Gregorian dates from day counts.
+fn test_civil_from_days() {
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(civil_from_days(15302), (2011, 11, 24));
+    assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+}
+
+#[test]
+/// BCD digits print with their leading zeros, quoted in JSON.
+fn test_decimal_value() {
+    let value = Value::Decimal(PgnDecimal::from_ascii("0022712345").unwrap());
+
+    let mut line = LineBuffer::new();
+    value.write_text(&mut line).unwrap();
+    assert_eq!(line.as_str(), "0022712345");
+    let mut line = LineBuffer::new();
+    value.write_json(&mut line).unwrap();
+    assert_eq!(line.as_str(), r#""0022712345""#);
+}
+
+#[test]
+/// Group Function values print as the field of the commanded PGN they set.
+fn test_group_function_parameters() {
+    let payload = [
+        0x01, 0x12, 0xF1, 0x01, 0xF8, 0x02, 0x02, 0x98, 0x3A, 0x05, 0xFD,
+    ];
+    let message = N2kMessage::decode(126208, &payload).unwrap();
+
+    let mut line = LineBuffer::new();
+    write_text(
+        &mut line,
+        &header(126208),
+        message.message_descriptor(),
+        &message,
+    )
+    .unwrap();
+    assert!(line.as_str().ends_with(
+        "Function Code = Command; PGN = 127250; Priority = Leave unchanged; Number of Parameters = 2; Parameter = 2; Value = 85.944 deg; Parameter = 5; Value = Magnetic"
+    ), "{}", line.as_str());
+}
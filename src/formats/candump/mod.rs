@@ -0,0 +1,191 @@
+//! SocketCAN `candump -L` log format (`std` feature).
+//!
+//! One frame per line: `(seconds.micros) interface IDENTIFIER#DATA`, with an
+//! eight-digit identifier for extended frames and upper-case data bytes.
+//!
+//! ```text
+//! (1436509052.249713) can0 09F80103#A0B1C2D3E4F50617
+//! ```
+//!
+//! Only extended data frames are NMEA 2000 traffic: standard identifiers,
+//! remote frames (`#R`), and CAN FD frames (`##`) are reported as
+//! [`FormatError::UnsupportedFrame`] by [`parse_line`] and skipped by
+//! [`CandumpReader`].
+//!
+//! ```rust,ignore
+//! let file = std::io::BufReader::new(std::fs::File::open("sea_trial.log")?);
+//! let mut decoder = N2kDecoder::new();
+//! for record in CandumpReader::new(file) {
+//!     let record = record?;
+//!     if let Some(Ok(message)) = decoder.decode_frame(&record.frame, record.timestamp_us / 1000) {
+//!         println!("{message:?}");
+//!     }
+//! }
+//! ```
+use core::fmt;
+use std::{
+    io::{self, BufRead},
+    string::String,
+};
+
+use crate::{
+    error::{FormatError, LogReadError},
+    formats::{
+        text::{parse_hex_bytes, parse_hex_u32, parse_seconds_us, write_hex_bytes},
+        TimestampedFrame,
+    },
+    protocol::transport::{can_frame::CanFrame, can_id::CanId},
+};
+
+/// Number of identifier digits marking an extended (29-bit) frame.
+const EXTENDED_ID_DIGITS: usize = 8;
+
+//==================================================================================LINE
+/// Parse one `candump -L` line; the interface name is not kept.
+///
+/// # Errors
+///
+/// - [`FormatError::Syntax`] when a part of the line is missing
+/// - [`FormatError::InvalidTimestamp`] / [`FormatError::InvalidHex`] for malformed fields
+/// - [`FormatError::UnsupportedFrame`] for standard, remote, and CAN FD frames
+/// - [`FormatError::DataTooLong`] for more than eight data bytes
+pub fn parse_line(line: &str) -> Result<TimestampedFrame, FormatError> {
+    let mut parts = line.split_whitespace();
+    let timestamp = parts.next().ok_or(FormatError::Syntax)?;
+    let _interface = parts.next().ok_or(FormatError::Syntax)?;
+    let frame = parts.next().ok_or(FormatError::Syntax)?;
+
+    let timestamp_us = parse_seconds_us(
+        timestamp
+            .strip_prefix('(')
+            .and_then(|t| t.strip_suffix(')'))
+            .ok_or(FormatError::Syntax)?,
+    )?;
+
+    let (id, data) = frame.split_once('#').ok_or(FormatError::Syntax)?;
+    if id.len() != EXTENDED_ID_DIGITS || data.starts_with(['#', 'R', 'r']) {
+        return Err(FormatError::UnsupportedFrame);
+    }
+    let id = parse_hex_u32(id)?;
+    if id > 0x1FFF_FFFF {
+        return Err(FormatError::InvalidHex);
+    }
+
+    let mut payload = [0; 8];
+    let len = parse_hex_bytes(data, &mut payload)?;
+    Ok(TimestampedFrame::new(
+        timestamp_us,
+        CanFrame {
+            id: CanId(id),
+            data: payload,
+            len,
+        },
+    ))
+}
+
+/// Write `record` as one `candump -L` line (without the trailing newline).
+pub fn write_line<W: fmt::Write>(
+    out: &mut W,
+    interface: &str,
+    record: &TimestampedFrame,
+) -> fmt::Result {
+    let frame = &record.frame;
+    write!(
+        out,
+        "({}.{:06}) {} {:08X}#",
+        record.timestamp_us / 1_000_000,
+        record.timestamp_us % 1_000_000,
+        interface,
+        frame.id.0
+    )?;
+    write_hex_bytes(out, &frame.data[..frame.len.min(8)])
+}
+
+//==================================================================================READER
+/// Iterator over the frames of a `candump -L` log.
+///
+/// Blank lines and non-NMEA 2000 frames are skipped; other malformed lines
+/// are reported with their 1-based line number.
+#[derive(Debug)]
+pub struct CandumpReader<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+}
+
+impl<R: BufRead> CandumpReader<R> {
+    /// Read records from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_number: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for CandumpReader<R> {
+    type Item = Result<TimestampedFrame, LogReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => self.line_number += 1,
+                Err(error) => return Some(Err(LogReadError::Io(error))),
+            }
+            if self.line.trim().is_empty() {
+                continue;
+            }
+            match parse_line(&self.line) {
+                Err(FormatError::UnsupportedFrame) => continue,
+                result => {
+                    return Some(result.map_err(|error| LogReadError::Format {
+                        record: self.line_number,
+                        error,
+                    }))
+                }
+            }
+        }
+    }
+}
+
+//==================================================================================WRITER
+/// Writes frames as `candump -L` lines.
+#[derive(Debug)]
+pub struct CandumpWriter<W> {
+    writer: W,
+    interface: String,
+    line: String,
+}
+
+impl<W: io::Write> CandumpWriter<W> {
+    /// Write lines attributed to `interface` (e.g. `can0`) into `writer`.
+    pub fn new(writer: W, interface: &str) -> Self {
+        Self {
+            writer,
+            interface: interface.into(),
+            line: String::new(),
+        }
+    }
+
+    /// Append `record` as one line.
+    pub fn write(&mut self, record: &TimestampedFrame) -> io::Result<()> {
+        self.line.clear();
+        write_line(&mut self.line, &self.interface, record)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "frame formatting"))?;
+        self.line.push('\n');
+        self.writer.write_all(self.line.as_bytes())
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
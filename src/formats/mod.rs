@@ -0,0 +1,65 @@
+//! Capture and gateway formats.
+//!
+//! Every reader produces, and every writer consumes, the same
+//! [`TimestampedFrame`], so a capture taken with one tool can be replayed
+//! through [`FastPacketAssembler`](crate::protocol::transport::fast_packet::assembler::FastPacketAssembler),
+//! [`N2kDecoder`](crate::protocol::decoder::N2kDecoder), written back in
+//! another format, or sent on a bus with [`Replayer`](crate::replay::Replayer).
+//!
+//! - [`analyzer`]: CANboat `analyzer` text and JSON output of decoded messages.
+//! - [`candump`]: SocketCAN `candump -L` log lines (`std` feature).
+//! - [`ngt1`]: Actisense NGT-1 binary serial protocol.
+//! - [`pcap`]: pcap / pcapng captures with the SocketCAN link type (`std` feature).
+//! - [`actisense_ascii`]: Actisense N2K ASCII lines (reassembled messages).
+//! - [`seasmart`]: `$PCDIN` / `$MXPGN` NMEA 0183 sentences (reassembled messages).
+//! - [`yd_raw`]: Yacht Devices RAW lines.
+use crate::protocol::transport::{can_frame::CanFrame, can_id::CanId};
+
+pub mod actisense_ascii;
+pub mod analyzer;
+#[cfg(feature = "std")]
+pub mod candump;
+pub mod ngt1;
+#[cfg(feature = "std")]
+pub mod pcap;
+pub mod seasmart;
+pub mod yd_raw;
+
+// Hexadecimal and time helpers of the text formats.
+mod text;
+
+//==================================================================================TIMESTAMPED_FRAME
+/// CAN frame paired with its capture time.
+#[derive(Debug, Clone)]
+pub struct TimestampedFrame {
+    /// Capture time in microseconds. The origin depends on the format (Unix
+    /// epoch for candump, midnight for time-of-day logs, …).
+    pub timestamp_us: u64,
+    /// Captured frame (29-bit identifier).
+    pub frame: CanFrame,
+}
+
+impl TimestampedFrame {
+    /// Pair `frame` with `timestamp_us`.
+    pub fn new(timestamp_us: u64, frame: CanFrame) -> Self {
+        Self {
+            timestamp_us,
+            frame,
+        }
+    }
+}
+
+//==================================================================================GATEWAY_HEADERS
+/// Rebuild the 29-bit identifier of a message described by its header fields,
+/// as gateways report them.
+///
+/// `destination` only matters for PDU1 PGNs (PF < 240); PDU2 PGNs are broadcast.
+pub(crate) fn can_id_from_parts(priority: u8, pgn: u32, source: u8, destination: u8) -> CanId {
+    let pgn = pgn & 0x3_FFFF;
+    let pdu_specific = if (pgn >> 8) & 0xFF < 240 {
+        (pgn & 0x3_FF00) | destination as u32
+    } else {
+        pgn
+    };
+    CanId((((priority & 0x07) as u32) << 26) | (pdu_specific << 8) | source as u32)
+}
@@ -0,0 +1,350 @@
+//! Actisense NGT-1 binary serial protocol (BST / N2K messages).
+//!
+//! Every message travels as `DLE STX <command> <length> <data…> <checksum> DLE ETX`.
+//! Data bytes equal to `DLE` are doubled, and the checksum makes the sum of
+//! command, length, data, and checksum bytes zero (mod 256).
+//!
+//! The gateway reassembles Fast Packet and transport messages itself, so each
+//! received N2K message (command `0x93`) already carries a full PGN payload:
+//!
+//! | Bytes | Content |
+//! |-------|---------|
+//! | 0     | priority |
+//! | 1–3   | PGN (little endian) |
+//! | 4     | destination |
+//! | 5     | source |
+//! | 6–9   | gateway timestamp (ms, little endian) |
+//! | 10    | payload length |
+//! | 11…   | payload |
+//!
+//! Messages sent to the bus (command `0x94`) omit source and timestamp; the
+//! gateway transmits with its own address.
+//!
+//! ```rust,ignore
+//! let mut decoder = Ngt1Decoder::new();
+//! for byte in serial_bytes {
+//!     if let Some(Ok(record)) = decoder.push(byte) {
+//!         let message = record.message.decode()?;
+//!     }
+//! }
+//!
+//! let mut out = [0u8; NGT1_MAX_ENCODED_LEN];
+//! let len = encode_n2k_send(id, &payload, &mut out)?;
+//! serial.write_all(&out[..len]);
+//! ```
+use crate::{
+    error::FormatError,
+    formats::can_id_from_parts,
+    protocol::{
+        decoder::RawMessage,
+        transport::{can_id::CanId, fast_packet::MAX_FAST_PACKET_PAYLOAD},
+    },
+};
+
+//==================================================================================FRAMING
+/// Data Link Escape.
+pub const DLE: u8 = 0x10;
+/// Start of Text, follows `DLE` to open a message.
+pub const STX: u8 = 0x02;
+/// End of Text, follows `DLE` to close a message.
+pub const ETX: u8 = 0x03;
+
+/// Command of N2K messages received from the bus.
+pub const N2K_MSG_RECEIVED: u8 = 0x93;
+/// Command of N2K messages to transmit on the bus.
+pub const N2K_MSG_SEND: u8 = 0x94;
+
+/// Largest unescaped message body: command, length, 255 data bytes, checksum.
+const MAX_BODY_LEN: usize = 3 + 255;
+/// Largest encoded message: every body byte escaped, plus the two delimiters.
+pub const NGT1_MAX_ENCODED_LEN: usize = 4 + 2 * MAX_BODY_LEN;
+
+/// Header bytes of a received N2K message before its payload.
+const RECEIVED_HEADER_LEN: usize = 11;
+/// Header bytes of an N2K message to send before its payload.
+const SEND_HEADER_LEN: usize = 6;
+
+//==================================================================================RECORD
+/// N2K message received through the gateway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ngt1Record {
+    /// Gateway timestamp (ms since the gateway started).
+    pub timestamp_ms: u32,
+    /// Complete PGN payload with its rebuilt identifier.
+    pub message: RawMessage,
+}
+
+//==================================================================================DECODER
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DecoderState {
+    /// Waiting for the `DLE` of a `DLE STX` opening.
+    Idle,
+    /// `DLE` seen outside a message, waiting for `STX`.
+    Opening,
+    /// Inside a message body.
+    Body,
+    /// `DLE` seen inside a message body.
+    BodyEscape,
+}
+
+/// Incremental decoder of an NGT-1 byte stream.
+///
+/// Bytes can be pushed one at a time as they arrive from the serial port.
+/// Only N2K messages produce records; other commands (BST / NGT control
+/// messages) are consumed silently.
+#[derive(Debug, Clone)]
+pub struct Ngt1Decoder {
+    state: DecoderState,
+    body: [u8; MAX_BODY_LEN],
+    len: usize,
+}
+
+impl Default for Ngt1Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ngt1Decoder {
+    /// Create a decoder waiting for the start of a message.
+    pub const fn new() -> Self {
+        Self {
+            state: DecoderState::Idle,
+            body: [0; MAX_BODY_LEN],
+            len: 0,
+        }
+    }
+
+    /// Push one byte from the stream.
+    ///
+    /// Returns `Some` when the byte closes a message: the N2K record, or the
+    /// reason the message was rejected. The decoder resynchronises on the next
+    /// `DLE STX` after an error.
+    ///
+    /// # Errors
+    ///
+    /// - [`FormatError::Checksum`] for a corrupted message
+    /// - [`FormatError::Syntax`] for an invalid escape or inconsistent lengths
+    /// - [`FormatError::DataTooLong`] for oversized bodies or payloads
+    pub fn push(&mut self, byte: u8) -> Option<Result<Ngt1Record, FormatError>> {
+        match (self.state, byte) {
+            (DecoderState::Idle, DLE) => self.state = DecoderState::Opening,
+            (DecoderState::Idle, _) => {}
+            (DecoderState::Opening, STX) => self.open(),
+            (DecoderState::Opening, DLE) => {}
+            (DecoderState::Opening, _) => self.state = DecoderState::Idle,
+            (DecoderState::Body, DLE) => self.state = DecoderState::BodyEscape,
+            (DecoderState::Body, _) => return self.store(byte),
+            (DecoderState::BodyEscape, DLE) => {
+                self.state = DecoderState::Body;
+                return self.store(DLE);
+            }
+            (DecoderState::BodyEscape, ETX) => {
+                self.state = DecoderState::Idle;
+                return self.close();
+            }
+            // A new message starts before the previous one ended.
+            (DecoderState::BodyEscape, STX) => self.open(),
+            (DecoderState::BodyEscape, _) => {
+                self.state = DecoderState::Idle;
+                return Some(Err(FormatError::Syntax));
+            }
+        }
+        None
+    }
+
+    /// Push every byte of `bytes`, calling `on_record` for each completed message.
+    pub fn feed<F>(&mut self, bytes: &[u8], mut on_record: F)
+    where
+        F: FnMut(Result<Ngt1Record, FormatError>),
+    {
+        for &byte in bytes {
+            if let Some(result) = self.push(byte) {
+                on_record(result);
+            }
+        }
+    }
+
+    fn open(&mut self) {
+        self.state = DecoderState::Body;
+        self.len = 0;
+    }
+
+    fn store(&mut self, byte: u8) -> Option<Result<Ngt1Record, FormatError>> {
+        if self.len == self.body.len() {
+            self.state = DecoderState::Idle;
+            return Some(Err(FormatError::DataTooLong { len: self.len + 1 }));
+        }
+        self.body[self.len] = byte;
+        self.len += 1;
+        None
+    }
+
+    /// Validate the body accumulated between `DLE STX` and `DLE ETX`.
+    fn close(&mut self) -> Option<Result<Ngt1Record, FormatError>> {
+        let body = &self.body[..self.len];
+        let [command, length, ..] = *body else {
+            return Some(Err(FormatError::Syntax));
+        };
+        if body.len() != length as usize + 3 {
+            return Some(Err(FormatError::Syntax));
+        }
+        if checksum(body) != 0 {
+            return Some(Err(FormatError::Checksum));
+        }
+        if command != N2K_MSG_RECEIVED {
+            return None;
+        }
+        Some(parse_received(&body[2..body.len() - 1]))
+    }
+}
+
+/// Parse the data of an `N2K_MSG_RECEIVED` message.
+fn parse_received(data: &[u8]) -> Result<Ngt1Record, FormatError> {
+    let header = data.get(..RECEIVED_HEADER_LEN).ok_or(FormatError::Syntax)?;
+    let payload = &data[RECEIVED_HEADER_LEN..];
+    if payload.len() != header[10] as usize {
+        return Err(FormatError::Syntax);
+    }
+    if payload.len() > MAX_FAST_PACKET_PAYLOAD {
+        return Err(FormatError::DataTooLong { len: payload.len() });
+    }
+
+    let pgn = u32::from_le_bytes([header[1], header[2], header[3], 0]);
+    let mut message = RawMessage {
+        id: can_id_from_parts(header[0], pgn, header[5], header[4]),
+        payload: [0; MAX_FAST_PACKET_PAYLOAD],
+        len: payload.len(),
+    };
+    message.payload[..payload.len()].copy_from_slice(payload);
+
+    Ok(Ngt1Record {
+        timestamp_ms: u32::from_le_bytes([header[6], header[7], header[8], header[9]]),
+        message,
+    })
+}
+
+//==================================================================================ENCODER
+/// Encode the command asking the gateway to transmit `payload` on the bus.
+///
+/// Priority, PGN, and destination (255 for broadcast PGNs) come from `id`;
+/// the gateway uses its own source address. Returns the number of bytes
+/// written to `out` ([`NGT1_MAX_ENCODED_LEN`] always suffices).
+///
+/// # Errors
+///
+/// - [`FormatError::DataTooLong`] when the payload exceeds the message size
+/// - [`FormatError::Write`] when `out` is too small
+pub fn encode_n2k_send(id: CanId, payload: &[u8], out: &mut [u8]) -> Result<usize, FormatError> {
+    let payload_len = payload_len(payload, SEND_HEADER_LEN)?;
+    let pgn = id.pgn().to_le_bytes();
+    let header = [
+        id.priority(),
+        pgn[0],
+        pgn[1],
+        pgn[2],
+        id.destination().unwrap_or(255),
+        payload_len,
+    ];
+    encode(N2K_MSG_SEND, &header, payload, out)
+}
+
+/// Encode `record` the way the gateway reports received messages.
+///
+/// Useful to write NGT-1 logs or to simulate a gateway in tests. Returns the
+/// number of bytes written to `out`.
+///
+/// # Errors
+///
+/// - [`FormatError::DataTooLong`] when the payload exceeds the message size
+/// - [`FormatError::Write`] when `out` is too small
+pub fn encode_n2k_received(record: &Ngt1Record, out: &mut [u8]) -> Result<usize, FormatError> {
+    let message = &record.message;
+    let payload = message.payload();
+    let payload_len = payload_len(payload, RECEIVED_HEADER_LEN)?;
+    let id = message.id;
+    let pgn = id.pgn().to_le_bytes();
+    let timestamp = record.timestamp_ms.to_le_bytes();
+    let header = [
+        id.priority(),
+        pgn[0],
+        pgn[1],
+        pgn[2],
+        id.destination().unwrap_or(255),
+        id.source_address(),
+        timestamp[0],
+        timestamp[1],
+        timestamp[2],
+        timestamp[3],
+        payload_len,
+    ];
+    encode(N2K_MSG_RECEIVED, &header, payload, out)
+}
+
+/// Payload length as a byte, once checked against the message data limit.
+fn payload_len(payload: &[u8], header_len: usize) -> Result<u8, FormatError> {
+    if header_len + payload.len() > 255 {
+        return Err(FormatError::DataTooLong { len: payload.len() });
+    }
+    Ok(payload.len() as u8)
+}
+
+/// Frame `command` and its data (`header` then `payload`) with escaping and checksum.
+fn encode(
+    command: u8,
+    header: &[u8],
+    payload: &[u8],
+    out: &mut [u8],
+) -> Result<usize, FormatError> {
+    let data_len = (header.len() + payload.len()) as u8;
+    let sum = checksum(&[command, data_len])
+        .wrapping_add(checksum(header))
+        .wrapping_add(checksum(payload));
+
+    let mut writer = EscapedWriter { out, len: 0 };
+    writer.raw(&[DLE, STX])?;
+    writer.escaped(&[command, data_len])?;
+    writer.escaped(header)?;
+    writer.escaped(payload)?;
+    writer.escaped(&[sum.wrapping_neg()])?;
+    writer.raw(&[DLE, ETX])?;
+    Ok(writer.len)
+}
+
+/// Sum of `bytes` modulo 256; zero for a valid message body.
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+}
+
+/// Output cursor doubling `DLE` bytes of the message body.
+struct EscapedWriter<'a> {
+    out: &'a mut [u8],
+    len: usize,
+}
+
+impl EscapedWriter<'_> {
+    fn raw(&mut self, bytes: &[u8]) -> Result<(), FormatError> {
+        let end = self.len + bytes.len();
+        self.out
+            .get_mut(self.len..end)
+            .ok_or(FormatError::Write)?
+            .copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+
+    fn escaped(&mut self, bytes: &[u8]) -> Result<(), FormatError> {
+        for &byte in bytes {
+            if byte == DLE {
+                self.raw(&[DLE, DLE])?;
+            } else {
+                self.raw(&[byte])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,122 @@
+//! NGT-1 codec tests: framing, escaping, checksums, and resynchronisation.
+// NGT1
+use super::*;
+use crate::formats::can_id_from_parts;
+
+/// Vessel Heading (PGN 127250) from source 35, as sent by a gateway.
+const HEADING_MESSAGE: [u8; 27] = [
+    DLE, STX, 0x93, 0x13, // command, data length (19)
+    0x02, 0x12, 0xF1, 0x01, 0xFF, 0x23, // priority, PGN, destination, source
+    0x10, 0x10, 0x27, 0x00, 0x00, // timestamp 10000 ms (0x10 escaped)
+    0x08, // payload length
+    0x00, 0xE8, 0x03, 0xFF, 0x7F, 0xFF, 0x7F, 0xFD, // payload
+    0x0F, // checksum
+    DLE, ETX,
+];
+
+fn decode_all(bytes: &[u8]) -> ([Option<Result<Ngt1Record, FormatError>>; 4], usize) {
+    let mut decoder = Ngt1Decoder::new();
+    let mut results = [None, None, None, None];
+    let mut count = 0;
+    decoder.feed(bytes, |result| {
+        results[count] = Some(result);
+        count += 1;
+    });
+    (results, count)
+}
+
+#[test]
+/// A received N2K message yields its header fields and payload.
+fn test_decode_received_message() {
+    let (results, count) = decode_all(&HEADING_MESSAGE);
+    assert_eq!(count, 1);
+
+    let record = results[0].clone().unwrap().unwrap();
+    assert_eq!(record.timestamp_ms, 10_000);
+    assert_eq!(record.message.pgn(), 127250);
+    assert_eq!(record.message.id.priority(), 2);
+    assert_eq!(record.message.id.source_address(), 35);
+    assert_eq!(record.message.id.destination(), None);
+    assert_eq!(
+        record.message.payload(),
+        &[0x00, 0xE8, 0x03, 0xFF, 0x7F, 0xFF, 0x7F, 0xFD]
+    );
+}
+
+#[test]
+/// Encoding a received record reproduces the gateway bytes.
+fn test_encode_received_roundtrip() {
+    let (results, _) = decode_all(&HEADING_MESSAGE);
+    let record = results[0].clone().unwrap().unwrap();
+
+    let mut out = [0u8; NGT1_MAX_ENCODED_LEN];
+    let len = encode_n2k_received(&record, &mut out).unwrap();
+    assert_eq!(&out[..len], &HEADING_MESSAGE);
+}
+
+#[test]
+/// The send command carries priority, PGN, destination, and payload.
+fn test_encode_send_command() {
+    let id = CanId::builder(59904, 42)
+        .with_priority(6)
+        .to_destination(0x10)
+        .build()
+        .unwrap();
+    let mut out = [0u8; NGT1_MAX_ENCODED_LEN];
+    let len = encode_n2k_send(id, &[0x14, 0xF0, 0x01], &mut out).unwrap();
+
+    assert_eq!(
+        &out[..len],
+        &[
+            DLE, STX, 0x94, 0x09, 0x06, 0x00, 0xEA, 0x00, DLE, DLE, 0x03, 0x14, 0xF0, 0x01, 0x5B,
+            DLE, ETX
+        ]
+    );
+    let body_sum = [
+        0x94u8, 0x09, 0x06, 0x00, 0xEA, 0x00, 0x10, 0x03, 0x14, 0xF0, 0x01, 0x5B,
+    ]
+    .iter()
+    .fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+    assert_eq!(body_sum, 0);
+
+    let mut small = [0u8; 8];
+    assert_eq!(
+        encode_n2k_send(id, &[0; 3], &mut small),
+        Err(FormatError::Write)
+    );
+}
+
+#[test]
+/// Noise before a message and foreign commands are skipped; corruption is reported.
+fn test_stream_resynchronisation() {
+    let mut stream = [0u8; 80];
+    let mut len = 0;
+    let mut append = |bytes: &[u8]| {
+        stream[len..len + bytes.len()].copy_from_slice(bytes);
+        len += bytes.len();
+    };
+
+    append(&[0x55, DLE, 0x42, 0x00]); // noise and a stray escape
+    append(&[DLE, STX, 0xA0, 0x01, 0x11, 0x4E, DLE, ETX]); // NGT message, valid checksum
+    let mut corrupted = HEADING_MESSAGE;
+    corrupted[20] ^= 0xFF;
+    append(&corrupted);
+    append(&HEADING_MESSAGE);
+
+    let (results, count) = decode_all(&stream[..len]);
+    assert_eq!(count, 2);
+    assert_eq!(results[0], Some(Err(FormatError::Checksum)));
+    assert_eq!(results[1].clone().unwrap().unwrap().message.pgn(), 127250);
+}
+
+#[test]
+/// Gateway header fields rebuild addressed and broadcast identifiers.
+fn test_can_id_from_parts() {
+    let addressed = can_id_from_parts(6, 59904, 42, 7);
+    assert_eq!(addressed.pgn(), 59904);
+    assert_eq!(addressed.destination(), Some(7));
+    assert_eq!(addressed.source_address(), 42);
+
+    let broadcast = can_id_from_parts(2, 129025, 3, 255);
+    assert_eq!(broadcast.0, 0x09F8_0103);
+}
@@ -0,0 +1,574 @@
+//! pcap and pcapng captures with the SocketCAN link type (`std` feature).
+//!
+//! Wireshark dissects NMEA 2000 traffic carried by `LINKTYPE_CAN_SOCKETCAN`
+//! (227) packets. Each packet is a 16-byte classic `struct can_frame`:
+//!
+//! | Bytes | Content |
+//! |-------|---------|
+//! | 0–3   | identifier and flags (big endian, `CAN_EFF_FLAG` set for 29-bit identifiers) |
+//! | 4     | payload length |
+//! | 5–7   | padding / reserved |
+//! | 8–15  | payload, zero padded |
+//!
+//! [`PcapWriter`] produces either container with microsecond timestamps.
+//! [`PcapReader`] detects the container, byte order, and timestamp
+//! resolution, and skips what is not NMEA 2000 traffic: standard, remote,
+//! error, and CAN FD frames, as well as pcapng packets of interfaces with
+//! another link type.
+//!
+//! ```rust,ignore
+//! let file = std::io::BufWriter::new(std::fs::File::create("sea_trial.pcapng")?);
+//! let mut writer = PcapWriter::new(file, PcapFormat::PcapNg)?;
+//! for record in CandumpReader::new(log) {
+//!     writer.write(&record?)?;
+//! }
+//! writer.into_inner()?;
+//! ```
+use std::{
+    io::{self, Read},
+    vec::Vec,
+};
+
+use crate::{
+    error::{FormatError, LogReadError},
+    formats::TimestampedFrame,
+    protocol::transport::{can_frame::CanFrame, can_id::CanId},
+};
+
+//==================================================================================SOCKETCAN
+/// Link type of SocketCAN `struct can_frame` packets.
+pub const LINKTYPE_CAN_SOCKETCAN: u32 = 227;
+
+/// Extended frame format flag of the SocketCAN identifier word.
+pub const CAN_EFF_FLAG: u32 = 0x8000_0000;
+/// Remote transmission request flag.
+pub const CAN_RTR_FLAG: u32 = 0x4000_0000;
+/// Error frame flag.
+pub const CAN_ERR_FLAG: u32 = 0x2000_0000;
+
+/// Size of a classic SocketCAN frame.
+pub const SOCKETCAN_FRAME_LEN: usize = 16;
+
+/// Encode `frame` as a SocketCAN packet with the extended-identifier flag.
+pub fn encode_socketcan(frame: &CanFrame) -> [u8; SOCKETCAN_FRAME_LEN] {
+    let len = frame.len.min(8);
+    let mut packet = [0; SOCKETCAN_FRAME_LEN];
+    packet[..4].copy_from_slice(&(CAN_EFF_FLAG | (frame.id.0 & 0x1FFF_FFFF)).to_be_bytes());
+    packet[4] = len as u8;
+    packet[8..8 + len].copy_from_slice(&frame.data[..len]);
+    packet
+}
+
+/// Decode a SocketCAN packet.
+///
+/// # Errors
+///
+/// - [`FormatError::Syntax`] for a packet shorter than its header or payload
+/// - [`FormatError::UnsupportedFrame`] for standard, remote, error, and CAN FD frames
+pub fn decode_socketcan(packet: &[u8]) -> Result<CanFrame, FormatError> {
+    let header = packet.get(..8).ok_or(FormatError::Syntax)?;
+    if packet.len() > SOCKETCAN_FRAME_LEN {
+        return Err(FormatError::UnsupportedFrame);
+    }
+    let id = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+    if id & (CAN_EFF_FLAG | CAN_RTR_FLAG | CAN_ERR_FLAG) != CAN_EFF_FLAG {
+        return Err(FormatError::UnsupportedFrame);
+    }
+    let len = header[4] as usize;
+    if len > 8 {
+        return Err(FormatError::UnsupportedFrame);
+    }
+    let payload = packet.get(8..8 + len).ok_or(FormatError::Syntax)?;
+
+    let mut data = [0; 8];
+    data[..len].copy_from_slice(payload);
+    Ok(CanFrame {
+        id: CanId(id & 0x1FFF_FFFF),
+        data,
+        len,
+    })
+}
+
+//==================================================================================CONTAINERS
+/// Capture container written by [`PcapWriter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcapFormat {
+    /// Classic libpcap file (microsecond timestamps).
+    Pcap,
+    /// pcapng file with one SocketCAN interface (microsecond timestamps).
+    PcapNg,
+}
+
+/// pcap magic number for microsecond timestamps.
+const PCAP_MAGIC_US: u32 = 0xA1B2_C3D4;
+/// pcap magic number for nanosecond timestamps.
+const PCAP_MAGIC_NS: u32 = 0xA1B2_3C4D;
+/// Largest snapshot length written in headers.
+const SNAPLEN: u32 = 0x4_0000;
+
+/// pcapng Section Header Block type.
+const SHB_TYPE: u32 = 0x0A0D_0D0A;
+/// pcapng Interface Description Block type.
+const IDB_TYPE: u32 = 0x0000_0001;
+/// pcapng Simple Packet Block type.
+const SPB_TYPE: u32 = 0x0000_0003;
+/// pcapng Enhanced Packet Block type.
+const EPB_TYPE: u32 = 0x0000_0006;
+/// pcapng byte-order magic of the Section Header Block.
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+/// Interface Description option giving the timestamp resolution.
+const IF_TSRESOL: u16 = 9;
+/// Blocks larger than this are considered corrupted rather than allocated.
+const MAX_BLOCK_LEN: usize = 1 << 24;
+
+//==================================================================================WRITER
+/// Writes frames as a pcap or pcapng capture.
+#[derive(Debug)]
+pub struct PcapWriter<W> {
+    writer: W,
+    format: PcapFormat,
+}
+
+impl<W: io::Write> PcapWriter<W> {
+    /// Write the file header of `format` into `writer`.
+    pub fn new(mut writer: W, format: PcapFormat) -> io::Result<Self> {
+        match format {
+            PcapFormat::Pcap => {
+                let mut header = Vec::with_capacity(24);
+                header.extend_from_slice(&PCAP_MAGIC_US.to_le_bytes());
+                header.extend_from_slice(&2u16.to_le_bytes());
+                header.extend_from_slice(&4u16.to_le_bytes());
+                // Time zone offset and timestamp accuracy, always zero.
+                header.extend_from_slice(&[0; 8]);
+                header.extend_from_slice(&SNAPLEN.to_le_bytes());
+                header.extend_from_slice(&LINKTYPE_CAN_SOCKETCAN.to_le_bytes());
+                writer.write_all(&header)?;
+            }
+            PcapFormat::PcapNg => {
+                let mut section = Vec::with_capacity(16);
+                section.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+                section.extend_from_slice(&1u16.to_le_bytes());
+                section.extend_from_slice(&0u16.to_le_bytes());
+                // Unknown section length.
+                section.extend_from_slice(&(-1i64).to_le_bytes());
+                write_block(&mut writer, SHB_TYPE, &section)?;
+
+                // No if_tsresol option: the default resolution is 1 µs.
+                let mut interface = Vec::with_capacity(8);
+                interface.extend_from_slice(&(LINKTYPE_CAN_SOCKETCAN as u16).to_le_bytes());
+                interface.extend_from_slice(&0u16.to_le_bytes());
+                interface.extend_from_slice(&SNAPLEN.to_le_bytes());
+                write_block(&mut writer, IDB_TYPE, &interface)?;
+            }
+        }
+        Ok(Self { writer, format })
+    }
+
+    /// Append `record` as one packet.
+    pub fn write(&mut self, record: &TimestampedFrame) -> io::Result<()> {
+        let packet = encode_socketcan(&record.frame);
+        let len = (SOCKETCAN_FRAME_LEN as u32).to_le_bytes();
+        let mut body = Vec::with_capacity(20 + SOCKETCAN_FRAME_LEN);
+        match self.format {
+            PcapFormat::Pcap => {
+                let seconds = (record.timestamp_us / 1_000_000) as u32;
+                let micros = (record.timestamp_us % 1_000_000) as u32;
+                body.extend_from_slice(&seconds.to_le_bytes());
+                body.extend_from_slice(&micros.to_le_bytes());
+                body.extend_from_slice(&len);
+                body.extend_from_slice(&len);
+                body.extend_from_slice(&packet);
+                self.writer.write_all(&body)
+            }
+            PcapFormat::PcapNg => {
+                let timestamp = record.timestamp_us;
+                body.extend_from_slice(&0u32.to_le_bytes());
+                body.extend_from_slice(&((timestamp >> 32) as u32).to_le_bytes());
+                body.extend_from_slice(&(timestamp as u32).to_le_bytes());
+                body.extend_from_slice(&len);
+                body.extend_from_slice(&len);
+                body.extend_from_slice(&packet);
+                write_block(&mut self.writer, EPB_TYPE, &body)
+            }
+        }
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Write a little-endian pcapng block around `body`, padded to 32 bits.
+fn write_block<W: io::Write>(writer: &mut W, block_type: u32, body: &[u8]) -> io::Result<()> {
+    let padding = (4 - body.len() % 4) % 4;
+    let total_len = ((12 + body.len() + padding) as u32).to_le_bytes();
+    writer.write_all(&block_type.to_le_bytes())?;
+    writer.write_all(&total_len)?;
+    writer.write_all(body)?;
+    writer.write_all(&[0; 3][..padding])?;
+    writer.write_all(&total_len)
+}
+
+//==================================================================================READER
+/// Byte order of the capture being read.
+#[derive(Debug, Clone, Copy)]
+struct Endian {
+    big: bool,
+}
+
+impl Endian {
+    fn u16(self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+        if self.big {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    fn u32(self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        if self.big {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+}
+
+/// Timestamp resolution of an interface.
+#[derive(Debug, Clone, Copy)]
+enum Resolution {
+    /// Units of 10^-n seconds.
+    Decimal(u8),
+    /// Units of 2^-n seconds.
+    Binary(u8),
+}
+
+impl Resolution {
+    fn to_micros(self, ticks: u64) -> u64 {
+        let ticks = ticks as u128;
+        let micros = match self {
+            Resolution::Decimal(exponent) if exponent <= 6 => {
+                ticks * 10u128.pow(6 - exponent as u32)
+            }
+            Resolution::Decimal(exponent) => ticks / 10u128.pow((exponent as u32 - 6).min(38)),
+            Resolution::Binary(exponent) => (ticks * 1_000_000) >> exponent.min(127),
+        };
+        micros.min(u64::MAX as u128) as u64
+    }
+}
+
+/// pcapng interface: whether it carries SocketCAN frames, and its resolution.
+#[derive(Debug, Clone, Copy)]
+struct Interface {
+    socketcan: bool,
+    resolution: Resolution,
+}
+
+#[derive(Debug)]
+enum Container {
+    Pcap {
+        endian: Endian,
+        nanos: bool,
+    },
+    PcapNg {
+        endian: Endian,
+        interfaces: Vec<Interface>,
+    },
+}
+
+/// Iterator over the frames of a pcap or pcapng capture.
+///
+/// Non-NMEA 2000 packets are skipped; malformed packets are reported with
+/// their 1-based packet number.
+#[derive(Debug)]
+pub struct PcapReader<R> {
+    reader: R,
+    container: Container,
+    buffer: Vec<u8>,
+    packet_number: usize,
+}
+
+impl<R: Read> PcapReader<R> {
+    /// Read the file header from `reader` and detect the container.
+    ///
+    /// # Errors
+    ///
+    /// - [`LogReadError::Io`] when the header cannot be read
+    /// - [`LogReadError::Format`] (record 0) for an unknown file magic
+    /// - [`LogReadError::UnsupportedLinkType`] for a pcap file without SocketCAN frames
+    pub fn new(mut reader: R) -> Result<Self, LogReadError> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic).map_err(LogReadError::Io)?;
+
+        let mut capture = Self {
+            reader,
+            container: Container::PcapNg {
+                endian: Endian { big: false },
+                interfaces: Vec::new(),
+            },
+            buffer: Vec::new(),
+            packet_number: 0,
+        };
+        if u32::from_le_bytes(magic) == SHB_TYPE {
+            capture.read_section_header()?;
+        } else {
+            capture.read_pcap_header(magic)?;
+        }
+        Ok(capture)
+    }
+
+    /// Read the rest of a pcap file header starting with `magic`.
+    fn read_pcap_header(&mut self, magic: [u8; 4]) -> Result<(), LogReadError> {
+        let mut header = [0; 20];
+        self.reader
+            .read_exact(&mut header)
+            .map_err(LogReadError::Io)?;
+        let (endian, nanos) = match (u32::from_le_bytes(magic), u32::from_be_bytes(magic)) {
+            (PCAP_MAGIC_US, _) => (Endian { big: false }, false),
+            (PCAP_MAGIC_NS, _) => (Endian { big: false }, true),
+            (_, PCAP_MAGIC_US) => (Endian { big: true }, false),
+            (_, PCAP_MAGIC_NS) => (Endian { big: true }, true),
+            _ => return Err(header_error()),
+        };
+        // The FCS and reserved bits share the link type word.
+        let link_type = endian.u32(&header[16..]) & 0x0FFF_FFFF;
+        if link_type != LINKTYPE_CAN_SOCKETCAN {
+            return Err(LogReadError::UnsupportedLinkType(link_type));
+        }
+        self.container = Container::Pcap { endian, nanos };
+        Ok(())
+    }
+
+    /// Read the next pcap record, `None` at end of file.
+    fn next_pcap_packet(
+        &mut self,
+        endian: Endian,
+        nanos: bool,
+    ) -> Result<Option<u64>, LogReadError> {
+        let mut header = [0; 16];
+        if !read_exact_or_eof(&mut self.reader, &mut header).map_err(LogReadError::Io)? {
+            return Ok(None);
+        }
+        let seconds = endian.u32(&header[0..]) as u64;
+        let fraction = endian.u32(&header[4..]) as u64;
+        let captured_len = endian.u32(&header[8..]) as usize;
+        if captured_len > MAX_BLOCK_LEN {
+            return Err(self.syntax_error());
+        }
+        self.buffer.resize(captured_len, 0);
+        self.reader
+            .read_exact(&mut self.buffer)
+            .map_err(LogReadError::Io)?;
+
+        let fraction_us = if nanos { fraction / 1000 } else { fraction };
+        Ok(Some(seconds * 1_000_000 + fraction_us))
+    }
+
+    /// Read pcapng blocks until the next packet of a SocketCAN interface,
+    /// leaving its data in `buffer`; `None` at end of file.
+    fn next_pcapng_packet(&mut self) -> Result<Option<u64>, LogReadError> {
+        loop {
+            let mut head = [0; 8];
+            if !read_exact_or_eof(&mut self.reader, &mut head).map_err(LogReadError::Io)? {
+                return Ok(None);
+            }
+            let Container::PcapNg { endian, .. } = self.container else {
+                unreachable!("pcapng blocks are only read from pcapng captures");
+            };
+            if u32::from_le_bytes([head[0], head[1], head[2], head[3]]) == SHB_TYPE {
+                self.read_section_header_body(head[4..].try_into().unwrap())?;
+                continue;
+            }
+            let block_type = endian.u32(&head[0..]);
+            let total_len = endian.u32(&head[4..]) as usize;
+            self.read_block_body(endian, total_len, 8)?;
+
+            let Container::PcapNg { interfaces, .. } = &mut self.container else {
+                unreachable!("pcapng blocks are only read from pcapng captures");
+            };
+            let body = &self.buffer;
+            match block_type {
+                IDB_TYPE if body.len() >= 8 => {
+                    interfaces.push(Interface {
+                        socketcan: endian.u16(body) as u32 == LINKTYPE_CAN_SOCKETCAN,
+                        resolution: interface_resolution(endian, &body[8..]),
+                    });
+                }
+                EPB_TYPE if body.len() >= 20 => {
+                    let interface = interfaces.get(endian.u32(body) as usize).copied();
+                    let Some(interface) = interface.filter(|i| i.socketcan) else {
+                        continue;
+                    };
+                    let ticks =
+                        ((endian.u32(&body[4..]) as u64) << 32) | endian.u32(&body[8..]) as u64;
+                    let captured_len = (endian.u32(&body[12..]) as usize).min(body.len() - 20);
+                    self.buffer.copy_within(20..20 + captured_len, 0);
+                    self.buffer.truncate(captured_len);
+                    return Ok(Some(interface.resolution.to_micros(ticks)));
+                }
+                // Simple packets belong to the first interface and have no timestamp.
+                SPB_TYPE if body.len() >= 4 => {
+                    if !interfaces.first().is_some_and(|i| i.socketcan) {
+                        continue;
+                    }
+                    let captured_len = (endian.u32(body) as usize).min(body.len() - 4);
+                    self.buffer.copy_within(4..4 + captured_len, 0);
+                    self.buffer.truncate(captured_len);
+                    return Ok(Some(0));
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    /// Read a Section Header Block after its type word.
+    fn read_section_header(&mut self) -> Result<(), LogReadError> {
+        let mut total_len = [0; 4];
+        self.reader
+            .read_exact(&mut total_len)
+            .map_err(LogReadError::Io)?;
+        self.read_section_header_body(total_len)
+    }
+
+    /// Read the rest of a Section Header Block, whose length word is
+    /// `total_len`, and start a new section.
+    fn read_section_header_body(&mut self, total_len: [u8; 4]) -> Result<(), LogReadError> {
+        let mut magic = [0; 4];
+        self.reader
+            .read_exact(&mut magic)
+            .map_err(LogReadError::Io)?;
+        let endian = match u32::from_le_bytes(magic) {
+            BYTE_ORDER_MAGIC => Endian { big: false },
+            _ if u32::from_be_bytes(magic) == BYTE_ORDER_MAGIC => Endian { big: true },
+            _ => return Err(header_error()),
+        };
+        // The byte-order magic is already consumed.
+        let total_len = endian.u32(&total_len) as usize;
+        self.read_block_body(endian, total_len, 12)?;
+        self.container = Container::PcapNg {
+            endian,
+            interfaces: Vec::new(),
+        };
+        Ok(())
+    }
+
+    /// Error reported for a corrupted record following the last packet read.
+    fn syntax_error(&self) -> LogReadError {
+        LogReadError::Format {
+            record: self.packet_number + 1,
+            error: FormatError::Syntax,
+        }
+    }
+
+    /// Read the body and trailing length of a block of `total_len` bytes
+    /// whose first `consumed` bytes are already read.
+    ///
+    /// The length must be a multiple of 4 and repeated after the body.
+    fn read_block_body(
+        &mut self,
+        endian: Endian,
+        total_len: usize,
+        consumed: usize,
+    ) -> Result<(), LogReadError> {
+        if !total_len.is_multiple_of(4) || !(consumed + 4..=MAX_BLOCK_LEN).contains(&total_len) {
+            return Err(self.syntax_error());
+        }
+        self.buffer.resize(total_len - consumed, 0);
+        self.reader
+            .read_exact(&mut self.buffer)
+            .map_err(LogReadError::Io)?;
+        let body_len = self.buffer.len() - 4;
+        if endian.u32(&self.buffer[body_len..]) as usize != total_len {
+            return Err(self.syntax_error());
+        }
+        self.buffer.truncate(body_len);
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for PcapReader<R> {
+    type Item = Result<TimestampedFrame, LogReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let timestamp_us = match self.container {
+                Container::Pcap { endian, nanos } => self.next_pcap_packet(endian, nanos),
+                Container::PcapNg { .. } => self.next_pcapng_packet(),
+            };
+            let timestamp_us = match timestamp_us {
+                Ok(Some(timestamp_us)) => timestamp_us,
+                Ok(None) => return None,
+                Err(error) => return Some(Err(error)),
+            };
+            self.packet_number += 1;
+
+            match decode_socketcan(&self.buffer) {
+                Err(FormatError::UnsupportedFrame) => continue,
+                result => {
+                    return Some(
+                        result
+                            .map(|frame| TimestampedFrame::new(timestamp_us, frame))
+                            .map_err(|error| LogReadError::Format {
+                                record: self.packet_number,
+                                error,
+                            }),
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// Timestamp resolution from the options of an Interface Description Block.
+fn interface_resolution(endian: Endian, mut options: &[u8]) -> Resolution {
+    while options.len() >= 4 {
+        let code = endian.u16(options);
+        let len = endian.u16(&options[2..]) as usize;
+        let Some(value) = options.get(4..4 + len) else {
+            break;
+        };
+        if code == IF_TSRESOL && len >= 1 {
+            let exponent = value[0] & 0x7F;
+            return if value[0] & 0x80 == 0 {
+                Resolution::Decimal(exponent)
+            } else {
+                Resolution::Binary(exponent)
+            };
+        }
+        options = options
+            .get(4 + len.next_multiple_of(4)..)
+            .unwrap_or_default();
+    }
+    Resolution::Decimal(6)
+}
+
+/// Error reported for an unrecognised file or section header.
+fn header_error() -> LogReadError {
+    LogReadError::Format {
+        record: 0,
+        error: FormatError::Syntax,
+    }
+}
+
+/// Fill `buf`, returning `false` on a clean end of file before its first byte.
+fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<bool> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(read) => filled += read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,229 @@
+//! pcap / pcapng tests: SocketCAN packets, both containers, and foreign captures.
+// PCAP
+use super::*;
+use std::vec;
+
+fn record(timestamp_us: u64, id: u32, data: &[u8]) -> TimestampedFrame {
+    let mut payload = [0; 8];
+    payload[..data.len()].copy_from_slice(data);
+    TimestampedFrame::new(
+        timestamp_us,
+        CanFrame {
+            id: CanId(id),
+            data: payload,
+            len: data.len(),
+        },
+    )
+}
+
+fn capture() -> [TimestampedFrame; 3] {
+    [
+        record(
+            1_436_509_052_249_713,
+            0x09F8_0103,
+            &[0xA0, 0xB1, 0xC2, 0xD3],
+        ),
+        record(
+            1_436_509_052_250_000,
+            0x09F1_1207,
+            &[1, 2, 3, 4, 5, 6, 7, 8],
+        ),
+        record(1_436_509_053_000_001, 0x18EA_FF00, &[0x00, 0xEE, 0x00]),
+    ]
+}
+
+fn write_capture(format: PcapFormat, records: &[TimestampedFrame]) -> Vec<u8> {
+    let mut writer = PcapWriter::new(Vec::new(), format).unwrap();
+    for record in records {
+        writer.write(record).unwrap();
+    }
+    writer.into_inner().unwrap()
+}
+
+fn read_capture(bytes: &[u8]) -> Vec<TimestampedFrame> {
+    PcapReader::new(bytes)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+fn assert_same(read: &[TimestampedFrame], expected: &[TimestampedFrame]) {
+    assert_eq!(read.len(), expected.len());
+    for (read, expected) in read.iter().zip(expected) {
+        assert_eq!(read.timestamp_us, expected.timestamp_us);
+        assert_eq!(read.frame.id, expected.frame.id);
+        assert_eq!(read.frame.data, expected.frame.data);
+        assert_eq!(read.frame.len, expected.frame.len);
+    }
+}
+
+#[test]
+/// Extended frames carry `CAN_EFF_FLAG` and a big-endian identifier.
+fn test_socketcan_packet() {
+    let frame = record(0, 0x09F8_0103, &[0xA0, 0xB1]).frame;
+    let packet = encode_socketcan(&frame);
+    assert_eq!(
+        packet,
+        [0x89, 0xF8, 0x01, 0x03, 2, 0, 0, 0, 0xA0, 0xB1, 0, 0, 0, 0, 0, 0]
+    );
+
+    let decoded = decode_socketcan(&packet).unwrap();
+    assert_eq!(decoded.id, frame.id);
+    assert_eq!(decoded.data[..decoded.len], [0xA0, 0xB1]);
+
+    // Standard, remote, and error frames are not NMEA 2000 traffic.
+    for id in [0x0000_0123, 0xC9F8_0103, 0xA9F8_0103] {
+        let mut packet = packet;
+        packet[..4].copy_from_slice(&u32::to_be_bytes(id));
+        assert!(matches!(
+            decode_socketcan(&packet),
+            Err(FormatError::UnsupportedFrame)
+        ));
+    }
+    assert!(matches!(
+        decode_socketcan(&packet[..9]),
+        Err(FormatError::Syntax)
+    ));
+}
+
+#[test]
+/// A pcap file starts with the libpcap header and reads back unchanged.
+fn test_pcap_roundtrip() {
+    let records = capture();
+    let bytes = write_capture(PcapFormat::Pcap, &records);
+
+    assert_eq!(
+        bytes[..24],
+        [0xD4, 0xC3, 0xB2, 0xA1, 2, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 227, 0, 0, 0]
+    );
+    assert_eq!(bytes.len(), 24 + 3 * (16 + SOCKETCAN_FRAME_LEN));
+    assert_same(&read_capture(&bytes), &records);
+}
+
+#[test]
+/// A pcapng file holds one SocketCAN interface and reads back unchanged.
+fn test_pcapng_roundtrip() {
+    let records = capture();
+    let bytes = write_capture(PcapFormat::PcapNg, &records);
+
+    assert_eq!(bytes[..4], SHB_TYPE.to_le_bytes());
+    // Section header (28 bytes), interface (20 bytes), then 48-byte packet blocks.
+    assert_eq!(bytes.len(), 28 + 20 + 3 * 48);
+    assert_same(&read_capture(&bytes), &records);
+}
+
+#[test]
+/// Big-endian files with nanosecond timestamps are converted to microseconds.
+fn test_big_endian_nanosecond_pcap() {
+    let mut bytes = vec![0xA1, 0xB2, 0x3C, 0x4D, 0, 2, 0, 4];
+    bytes.extend_from_slice(&[0; 8]);
+    bytes.extend_from_slice(&0x0004_0000u32.to_be_bytes());
+    bytes.extend_from_slice(&227u32.to_be_bytes());
+
+    bytes.extend_from_slice(&12u32.to_be_bytes());
+    bytes.extend_from_slice(&345_678_901u32.to_be_bytes());
+    bytes.extend_from_slice(&16u32.to_be_bytes());
+    bytes.extend_from_slice(&16u32.to_be_bytes());
+    bytes.extend_from_slice(&encode_socketcan(&record(0, 0x09F8_0103, &[7]).frame));
+
+    let frames = read_capture(&bytes);
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].timestamp_us, 12_345_678);
+    assert_eq!(frames[0].frame.data[..frames[0].frame.len], [7]);
+}
+
+#[test]
+/// Packets of other interfaces and non-extended frames are skipped, and the
+/// `if_tsresol` option of each interface is honoured.
+fn test_pcapng_interfaces_and_resolution() {
+    let mut bytes = Vec::new();
+    let mut section = BYTE_ORDER_MAGIC.to_le_bytes().to_vec();
+    section.extend_from_slice(&[1, 0, 0, 0]);
+    section.extend_from_slice(&(-1i64).to_le_bytes());
+    write_block(&mut bytes, SHB_TYPE, &section).unwrap();
+
+    // Interface 0: Ethernet. Interface 1: SocketCAN in milliseconds.
+    write_block(&mut bytes, IDB_TYPE, &[1, 0, 0, 0, 0, 0, 4, 0]).unwrap();
+    let mut interface = vec![227, 0, 0, 0, 0, 0, 4, 0];
+    interface.extend_from_slice(&[9, 0, 1, 0, 3, 0, 0, 0, 0, 0, 0, 0]);
+    write_block(&mut bytes, IDB_TYPE, &interface).unwrap();
+
+    let packet = |interface: u32, ticks: u64, id: u32| {
+        let mut body = interface.to_le_bytes().to_vec();
+        body.extend_from_slice(&((ticks >> 32) as u32).to_le_bytes());
+        body.extend_from_slice(&(ticks as u32).to_le_bytes());
+        body.extend_from_slice(&16u32.to_le_bytes());
+        body.extend_from_slice(&16u32.to_le_bytes());
+        body.extend_from_slice(&encode_socketcan(&record(0, 0x09F8_0103, &[1]).frame));
+        body[20..24].copy_from_slice(&id.to_be_bytes());
+        body
+    };
+    write_block(&mut bytes, EPB_TYPE, &packet(0, 5, 0x89F8_0103)).unwrap();
+    write_block(&mut bytes, EPB_TYPE, &packet(1, 1_500, 0x0000_0123)).unwrap();
+    write_block(&mut bytes, EPB_TYPE, &packet(1, 2_500, 0x89F8_0103)).unwrap();
+
+    let frames = read_capture(&bytes);
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].timestamp_us, 2_500_000);
+    assert_eq!(frames[0].frame.id, CanId(0x09F8_0103));
+}
+
+#[test]
+/// Captures of other link types and unknown files are rejected up front;
+/// truncated packets are reported.
+fn test_rejected_captures() {
+    let mut bytes = write_capture(PcapFormat::Pcap, &capture());
+    bytes[20] = 1;
+    assert!(matches!(
+        PcapReader::new(&bytes[..]),
+        Err(LogReadError::UnsupportedLinkType(1))
+    ));
+
+    assert!(matches!(
+        PcapReader::new(&b"(1436509052.249713) can0"[..]),
+        Err(LogReadError::Format { record: 0, .. })
+    ));
+
+    let bytes = write_capture(PcapFormat::PcapNg, &capture());
+    let mut reader = PcapReader::new(&bytes[..bytes.len() - 10]).unwrap();
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_ok());
+    assert!(matches!(reader.next(), Some(Err(LogReadError::Io(_)))));
+}
+
+#[test]
+/// pcapng blocks whose trailing length disagrees with the leading one, or
+/// whose length is not a multiple of 4, are reported as corrupted.
+fn test_corrupted_pcapng_blocks() {
+    let bytes = write_capture(PcapFormat::PcapNg, &capture());
+    // Section header (28 bytes) and interface description (20 bytes) precede
+    // the first Enhanced Packet Block.
+    let epb = 48;
+    let total_len = u32::from_le_bytes(bytes[epb + 4..epb + 8].try_into().unwrap()) as usize;
+
+    let mut trailer = bytes.clone();
+    trailer[epb + total_len - 4] ^= 0x04;
+    let mut reader = PcapReader::new(&trailer[..]).unwrap();
+    assert!(matches!(
+        reader.next(),
+        Some(Err(LogReadError::Format {
+            record: 1,
+            error: FormatError::Syntax
+        }))
+    ));
+
+    let mut misaligned = bytes.clone();
+    misaligned[epb + 4] += 2;
+    let mut reader = PcapReader::new(&misaligned[..]).unwrap();
+    assert!(matches!(
+        reader.next(),
+        Some(Err(LogReadError::Format {
+            record: 1,
+            error: FormatError::Syntax
+        }))
+    ));
+
+    let mut section = bytes;
+    section[24] ^= 0x04;
+    assert!(PcapReader::new(&section[..]).is_err());
+}
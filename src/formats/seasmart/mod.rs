@@ -0,0 +1,265 @@
+//! N2K messages encapsulated in NMEA 0183 sentences.
+//!
+//! Two proprietary sentences are supported, both closed by the usual
+//! `*CS` checksum (XOR of the characters between `$` and `*`):
+//!
+//! - `$PCDIN` (SeaSmart): one complete message, Fast Packets reassembled.
+//!
+//!   ```text
+//!   $PCDIN,01F119,00000000,0F,2AAF00D1067414FF*59
+//!   ```
+//!
+//!   Fields: PGN (6 hex digits), sender time stamp (8 hex digits), source
+//!   address, payload. Priority and destination are not carried.
+//!
+//! - `$MXPGN` (ShipModul MiniPlex): one single-frame message.
+//!
+//!   ```text
+//!   $MXPGN,01F801,2801,C1308AC40C5DE343*19
+//!   ```
+//!
+//!   Fields: PGN, attribute word (bit 15 set when the gateway transmits,
+//!   bits 14–12 priority, 11–8 data length, 7–0 source address — destination
+//!   when transmitting), and the data bytes **last byte first**.
+//!
+//! ```rust,ignore
+//! // Publish decoded bus traffic on a 0183 link.
+//! if let Some(raw) = decoder.process_frame(&frame, now_ms) {
+//!     let mut line = String::new();
+//!     write_pcdin(&mut line, &PcdinRecord { timestamp: 0, message: raw })?;
+//!     uart.write_all(line.as_bytes())?;
+//!     uart.write_all(b"\r\n")?;
+//! }
+//! ```
+use core::fmt::{self, Write};
+
+use crate::{
+    error::FormatError,
+    formats::{
+        can_id_from_parts,
+        text::{parse_hex_bytes, parse_hex_u32, parse_hex_u8, write_hex_bytes},
+    },
+    protocol::{decoder::RawMessage, transport::fast_packet::MAX_FAST_PACKET_PAYLOAD},
+};
+
+/// Priority given to `$PCDIN` messages, which do not carry one.
+pub const PCDIN_DEFAULT_PRIORITY: u8 = 6;
+
+/// Source address given to messages a MiniPlex gateway transmits (NULL address).
+const UNKNOWN_SOURCE: u8 = 254;
+
+/// Attribute bit set on `$MXPGN` sentences the gateway transmits.
+const MXPGN_TRANSMIT: u16 = 0x8000;
+
+//==================================================================================PCDIN
+/// Complete message of a `$PCDIN` sentence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PcdinRecord {
+    /// Sender time stamp, opaque to the receiver.
+    pub timestamp: u32,
+    /// Complete PGN payload; priority [`PCDIN_DEFAULT_PRIORITY`] and broadcast
+    /// destination once parsed.
+    pub message: RawMessage,
+}
+
+/// Parse a `$PCDIN` sentence (trailing line terminator allowed).
+///
+/// # Errors
+///
+/// - [`FormatError::Syntax`] for another sentence or a missing field
+/// - [`FormatError::Checksum`] when the checksum does not match
+/// - [`FormatError::InvalidHex`] for malformed fields
+/// - [`FormatError::DataTooLong`] for payloads beyond the Fast Packet limit
+pub fn parse_pcdin(sentence: &str) -> Result<PcdinRecord, FormatError> {
+    let mut fields = sentence_fields(sentence, "PCDIN")?;
+    let pgn = parse_pgn(fields.next())?;
+    let timestamp = fields.next().ok_or(FormatError::Syntax)?;
+    let source = parse_hex_u8(fields.next().ok_or(FormatError::Syntax)?)?;
+    let data = fields.next().ok_or(FormatError::Syntax)?;
+    if fields.next().is_some() || timestamp.len() != 8 {
+        return Err(FormatError::Syntax);
+    }
+
+    let mut message = RawMessage {
+        id: can_id_from_parts(PCDIN_DEFAULT_PRIORITY, pgn, source, 255),
+        payload: [0; MAX_FAST_PACKET_PAYLOAD],
+        len: 0,
+    };
+    message.len = parse_hex_bytes(data, &mut message.payload)?;
+
+    Ok(PcdinRecord {
+        timestamp: parse_hex_u32(timestamp)?,
+        message,
+    })
+}
+
+/// Write `record` as a `$PCDIN` sentence, checksum included, without the
+/// trailing `\r\n`.
+///
+/// # Errors
+///
+/// - [`FormatError::Write`] when `out` fails
+pub fn write_pcdin<W: fmt::Write>(out: &mut W, record: &PcdinRecord) -> Result<(), FormatError> {
+    let id = record.message.id;
+    write_sentence(out, |body| {
+        write!(
+            body,
+            "PCDIN,{:06X},{:08X},{:02X},",
+            id.pgn(),
+            record.timestamp,
+            id.source_address()
+        )?;
+        write_hex_bytes(body, record.message.payload())
+    })
+    .map_err(|_| FormatError::Write)
+}
+
+//==================================================================================MXPGN
+/// Parse a `$MXPGN` sentence (trailing line terminator allowed).
+///
+/// Sentences the gateway transmits (attribute bit 15) carry the destination
+/// instead of the source; the source is then set to the NULL address (254).
+///
+/// # Errors
+///
+/// - [`FormatError::Syntax`] for another sentence, a missing field, or a data
+///   length disagreeing with the attribute word
+/// - [`FormatError::Checksum`] when the checksum does not match
+/// - [`FormatError::InvalidHex`] for malformed fields
+/// - [`FormatError::DataTooLong`] for more than eight data bytes
+pub fn parse_mxpgn(sentence: &str) -> Result<RawMessage, FormatError> {
+    let mut fields = sentence_fields(sentence, "MXPGN")?;
+    let pgn = parse_pgn(fields.next())?;
+    let attribute = fields.next().ok_or(FormatError::Syntax)?;
+    let data = fields.next().ok_or(FormatError::Syntax)?;
+    if fields.next().is_some() || attribute.len() != 4 {
+        return Err(FormatError::Syntax);
+    }
+    let attribute = parse_hex_u32(attribute)? as u16;
+
+    let mut reversed = [0; 8];
+    let len = parse_hex_bytes(data, &mut reversed)?;
+    if len != ((attribute >> 8) & 0x0F) as usize {
+        return Err(FormatError::Syntax);
+    }
+
+    let priority = ((attribute >> 12) & 0x07) as u8;
+    let address = attribute as u8;
+    let (source, destination) = if attribute & MXPGN_TRANSMIT != 0 {
+        (UNKNOWN_SOURCE, address)
+    } else {
+        (address, 255)
+    };
+
+    let mut message = RawMessage {
+        id: can_id_from_parts(priority, pgn, source, destination),
+        payload: [0; MAX_FAST_PACKET_PAYLOAD],
+        len,
+    };
+    for (byte, reversed) in message.payload[..len]
+        .iter_mut()
+        .zip(reversed[..len].iter().rev())
+    {
+        *byte = *reversed;
+    }
+    Ok(message)
+}
+
+/// Write `message` as a received `$MXPGN` sentence, checksum included,
+/// without the trailing `\r\n`.
+///
+/// # Errors
+///
+/// - [`FormatError::DataTooLong`] for more than eight payload bytes (the
+///   sentence carries single frames only)
+/// - [`FormatError::Write`] when `out` fails
+pub fn write_mxpgn<W: fmt::Write>(out: &mut W, message: &RawMessage) -> Result<(), FormatError> {
+    let payload = message.payload();
+    if payload.len() > 8 {
+        return Err(FormatError::DataTooLong { len: payload.len() });
+    }
+    let id = message.id;
+    let attribute =
+        ((id.priority() as u16) << 12) | ((payload.len() as u16) << 8) | id.source_address() as u16;
+
+    write_sentence(out, |body| {
+        write!(body, "MXPGN,{:06X},{attribute:04X},", id.pgn())?;
+        payload
+            .iter()
+            .rev()
+            .try_for_each(|byte| write!(body, "{byte:02X}"))
+    })
+    .map_err(|_| FormatError::Write)
+}
+
+//==================================================================================SENTENCE
+/// Check the framing and checksum of `sentence` and return its fields after
+/// the `$<tag>` address field.
+fn sentence_fields<'a>(
+    sentence: &'a str,
+    tag: &str,
+) -> Result<impl Iterator<Item = &'a str>, FormatError> {
+    let sentence = sentence.trim_end_matches(['\r', '\n']);
+    let body = sentence.strip_prefix('$').ok_or(FormatError::Syntax)?;
+    let (body, checksum) = body.split_once('*').ok_or(FormatError::Syntax)?;
+    if checksum.len() != 2 {
+        return Err(FormatError::Syntax);
+    }
+    if parse_hex_u8(checksum)? != nmea_checksum(body) {
+        return Err(FormatError::Checksum);
+    }
+
+    let mut fields = body.split(',');
+    if fields.next() != Some(tag) {
+        return Err(FormatError::Syntax);
+    }
+    Ok(fields)
+}
+
+/// Parse a six-digit PGN field.
+fn parse_pgn(field: Option<&str>) -> Result<u32, FormatError> {
+    let field = field.ok_or(FormatError::Syntax)?;
+    if field.len() != 6 {
+        return Err(FormatError::Syntax);
+    }
+    let pgn = parse_hex_u32(field)?;
+    if pgn > 0x3_FFFF {
+        return Err(FormatError::InvalidHex);
+    }
+    Ok(pgn)
+}
+
+/// XOR of every character between `$` and `*`.
+pub fn nmea_checksum(body: &str) -> u8 {
+    body.bytes().fold(0, |checksum, byte| checksum ^ byte)
+}
+
+/// Write `$`, the body produced by `write_body`, then `*` and its checksum.
+fn write_sentence<W, F>(out: &mut W, write_body: F) -> fmt::Result
+where
+    W: fmt::Write,
+    F: FnOnce(&mut ChecksumWriter<'_, W>) -> fmt::Result,
+{
+    out.write_char('$')?;
+    let mut body = ChecksumWriter { out, checksum: 0 };
+    write_body(&mut body)?;
+    let checksum = body.checksum;
+    write!(out, "*{checksum:02X}")
+}
+
+/// Forwards text to `out` while accumulating its NMEA checksum.
+struct ChecksumWriter<'a, W> {
+    out: &'a mut W,
+    checksum: u8,
+}
+
+impl<W: fmt::Write> fmt::Write for ChecksumWriter<'_, W> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.checksum ^= nmea_checksum(text);
+        self.out.write_str(text)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,124 @@
+//! SeaSmart sentence tests: parsing, writing, and checksum validation.
+// SEASMART
+use super::*;
+use crate::{formats::text::LineBuffer, protocol::transport::can_id::CanId};
+
+const PCDIN: &str = "$PCDIN,01F119,00000000,0F,2AAF00D1067414FF*59";
+const MXPGN: &str = "$MXPGN,01F801,2801,C1308AC40C5DE343*19";
+
+#[test]
+/// A `$PCDIN` sentence yields the full payload with its source address.
+fn test_parse_pcdin() {
+    let record = parse_pcdin(PCDIN).unwrap();
+    assert_eq!(record.timestamp, 0);
+    assert_eq!(record.message.pgn(), 127257);
+    assert_eq!(record.message.id.source_address(), 0x0F);
+    assert_eq!(record.message.id.priority(), PCDIN_DEFAULT_PRIORITY);
+    assert_eq!(
+        record.message.payload(),
+        &[0x2A, 0xAF, 0x00, 0xD1, 0x06, 0x74, 0x14, 0xFF]
+    );
+
+    // Line terminators are tolerated.
+    let mut line = LineBuffer::new();
+    write!(line, "{PCDIN}\r\n").unwrap();
+    assert_eq!(parse_pcdin(line.as_str()).unwrap(), record);
+}
+
+#[test]
+/// Payloads longer than one frame survive a write/parse roundtrip.
+fn test_pcdin_roundtrip() {
+    let mut message = RawMessage {
+        id: can_id_from_parts(PCDIN_DEFAULT_PRIORITY, 129029, 3, 255),
+        payload: [0; MAX_FAST_PACKET_PAYLOAD],
+        len: 43,
+    };
+    for (index, byte) in message.payload[..43].iter_mut().enumerate() {
+        *byte = index as u8;
+    }
+    let record = PcdinRecord {
+        timestamp: 0x1234_ABCD,
+        message,
+    };
+
+    let mut line = LineBuffer::new();
+    write_pcdin(&mut line, &record).unwrap();
+    assert!(line
+        .as_str()
+        .starts_with("$PCDIN,01F805,1234ABCD,03,000102"));
+    assert_eq!(parse_pcdin(line.as_str()).unwrap(), record);
+
+    let mut line = LineBuffer::new();
+    write_pcdin(&mut line, &parse_pcdin(PCDIN).unwrap()).unwrap();
+    assert_eq!(line.as_str(), PCDIN);
+}
+
+#[test]
+/// `$MXPGN` data bytes come last byte first; the attribute word holds
+/// priority, length, and source.
+fn test_parse_mxpgn() {
+    let message = parse_mxpgn(MXPGN).unwrap();
+    assert_eq!(message.pgn(), 129025);
+    assert_eq!(message.id.priority(), 2);
+    assert_eq!(message.id.source_address(), 0x01);
+    assert_eq!(
+        message.payload(),
+        &[0x43, 0xE3, 0x5D, 0x0C, 0xC4, 0x8A, 0x30, 0xC1]
+    );
+
+    let mut line = LineBuffer::new();
+    write_mxpgn(&mut line, &message).unwrap();
+    assert_eq!(line.as_str(), MXPGN);
+}
+
+#[test]
+/// Transmitted `$MXPGN` sentences address a destination.
+fn test_parse_mxpgn_transmit() {
+    // PGN 59904 (ISO Request) to address 0x23, priority 6, three bytes.
+    let body = "MXPGN,00EA00,E323,01F014";
+    let mut line = LineBuffer::new();
+    write!(line, "${body}*{:02X}", nmea_checksum(body)).unwrap();
+
+    let message = parse_mxpgn(line.as_str()).unwrap();
+    assert_eq!(message.pgn(), 59904);
+    assert_eq!(message.id.destination(), Some(0x23));
+    assert_eq!(message.id.source_address(), 254);
+    assert_eq!(message.payload(), &[0x14, 0xF0, 0x01]);
+}
+
+#[test]
+/// Framing, checksum, and length errors are reported.
+fn test_sentence_errors() {
+    assert_eq!(
+        parse_pcdin("$PCDIN,01F119,00000000,0F,2AAF00D1067414FF*58").unwrap_err(),
+        FormatError::Checksum
+    );
+    assert_eq!(
+        parse_pcdin("PCDIN,01F119,00000000,0F,2AAF00D1067414FF*59").unwrap_err(),
+        FormatError::Syntax
+    );
+    assert_eq!(
+        parse_pcdin("$PCDIN,01F119,00000000,0F,2AAF00D1067414FF").unwrap_err(),
+        FormatError::Syntax
+    );
+    // A valid sentence of the other kind.
+    assert_eq!(parse_pcdin(MXPGN).unwrap_err(), FormatError::Syntax);
+    assert_eq!(parse_mxpgn(PCDIN).unwrap_err(), FormatError::Syntax);
+
+    // Attribute length disagreeing with the data.
+    let body = "MXPGN,01F801,2701,C1308AC40C5DE343";
+    let mut line = LineBuffer::new();
+    write!(line, "${body}*{:02X}", nmea_checksum(body)).unwrap();
+    assert_eq!(parse_mxpgn(line.as_str()).unwrap_err(), FormatError::Syntax);
+
+    // `$MXPGN` carries single frames only.
+    let message = RawMessage {
+        id: CanId(0x0DF8_0503),
+        payload: [0; MAX_FAST_PACKET_PAYLOAD],
+        len: 9,
+    };
+    assert_eq!(
+        write_mxpgn(&mut LineBuffer::new(), &message).unwrap_err(),
+        FormatError::DataTooLong { len: 9 }
+    );
+}
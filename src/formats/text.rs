@@ -0,0 +1,138 @@
+//! Hexadecimal and time helpers shared by the text formats.
+use core::fmt;
+
+use crate::error::FormatError;
+
+/// Parse a hexadecimal identifier of one to eight digits.
+pub(crate) fn parse_hex_u32(text: &str) -> Result<u32, FormatError> {
+    if text.is_empty() || text.len() > 8 {
+        return Err(FormatError::InvalidHex);
+    }
+    u32::from_str_radix(text, 16).map_err(|_| FormatError::InvalidHex)
+}
+
+/// Parse one hexadecimal byte (one or two digits).
+pub(crate) fn parse_hex_u8(text: &str) -> Result<u8, FormatError> {
+    if text.is_empty() || text.len() > 2 {
+        return Err(FormatError::InvalidHex);
+    }
+    u8::from_str_radix(text, 16).map_err(|_| FormatError::InvalidHex)
+}
+
+/// Decode contiguous hexadecimal digit pairs into `out`, returning the byte count.
+pub(crate) fn parse_hex_bytes(text: &str, out: &mut [u8]) -> Result<usize, FormatError> {
+    if !text.len().is_multiple_of(2) {
+        return Err(FormatError::InvalidHex);
+    }
+    let len = text.len() / 2;
+    if len > out.len() {
+        return Err(FormatError::DataTooLong { len });
+    }
+    for (index, byte) in out[..len].iter_mut().enumerate() {
+        *byte = parse_hex_u8(
+            text.get(index * 2..index * 2 + 2)
+                .ok_or(FormatError::InvalidHex)?,
+        )?;
+    }
+    Ok(len)
+}
+
+/// Write `bytes` as contiguous upper-case hexadecimal digit pairs.
+pub(crate) fn write_hex_bytes<W: fmt::Write>(out: &mut W, bytes: &[u8]) -> fmt::Result {
+    bytes.iter().try_for_each(|byte| write!(out, "{byte:02X}"))
+}
+
+/// Parse `seconds.fraction` into microseconds (extra fraction digits are truncated).
+pub(crate) fn parse_seconds_us(text: &str) -> Result<u64, FormatError> {
+    let (seconds, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let all_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if seconds.is_empty() || !all_digits(seconds) || !all_digits(fraction) {
+        return Err(FormatError::InvalidTimestamp);
+    }
+
+    let seconds: u64 = seconds.parse().map_err(|_| FormatError::InvalidTimestamp)?;
+    let mut micros = 0u64;
+    for position in 0..6 {
+        let digit = fraction
+            .as_bytes()
+            .get(position)
+            .map_or(0, |byte| byte - b'0');
+        micros = micros * 10 + digit as u64;
+    }
+    seconds
+        .checked_mul(1_000_000)
+        .and_then(|us| us.checked_add(micros))
+        .ok_or(FormatError::InvalidTimestamp)
+}
+
+/// Microseconds in a day, the wrap-around of time-of-day timestamps.
+pub(crate) const DAY_US: u64 = 86_400_000_000;
+
+/// Parse a time of day from its two-digit hours and minutes and its
+/// `ss.fraction` seconds into microseconds since midnight.
+pub(crate) fn parse_time_of_day(
+    hours: &str,
+    minutes: &str,
+    seconds: &str,
+) -> Result<u64, FormatError> {
+    let two_digits = |text: &str, max: u64| -> Result<u64, FormatError> {
+        if text.len() != 2 || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(FormatError::InvalidTimestamp);
+        }
+        let value: u64 = text.parse().map_err(|_| FormatError::InvalidTimestamp)?;
+        if value >= max {
+            return Err(FormatError::InvalidTimestamp);
+        }
+        Ok(value)
+    };
+    let hours = two_digits(hours, 24)?;
+    let minutes = two_digits(minutes, 60)?;
+    if seconds.split('.').next().map(str::len) != Some(2) {
+        return Err(FormatError::InvalidTimestamp);
+    }
+    let seconds = parse_seconds_us(seconds)?;
+    if seconds >= 60_000_000 {
+        return Err(FormatError::InvalidTimestamp);
+    }
+    Ok((hours * 60 + minutes) * 60_000_000 + seconds)
+}
+
+/// Split `timestamp_us` (wrapped to a day) into hours, minutes, seconds, and milliseconds.
+pub(crate) fn time_of_day_parts(timestamp_us: u64) -> (u64, u64, u64, u64) {
+    let ms = (timestamp_us % DAY_US) / 1000;
+    (ms / 3_600_000, ms / 60_000 % 60, ms / 1000 % 60, ms % 1000)
+}
+
+/// Fixed-capacity `fmt::Write` sink for line-writing tests without `std`.
+#[cfg(test)]
+pub(crate) struct LineBuffer {
+    bytes: [u8; 1024],
+    len: usize,
+}
+
+#[cfg(test)]
+impl LineBuffer {
+    pub(crate) fn new() -> Self {
+        Self {
+            bytes: [0; 1024],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+#[cfg(test)]
+impl fmt::Write for LineBuffer {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let end = self.len + text.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(text.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+//! Yacht Devices RAW format (YDWG-02, YDEN-02, YDNR-02 gateways).
+//!
+//! One frame per line: time of day, direction (`R` received, `T` transmitted),
+//! the eight-digit identifier, and up to eight space-separated data bytes.
+//!
+//! ```text
+//! 17:33:21.107 R 19F51323 01 2F 30 70 00 2F 30 70
+//! ```
+//!
+//! Timestamps are gateway time of day, so [`TimestampedFrame::timestamp_us`]
+//! counts microseconds since midnight. Frames are sent as seen on the bus:
+//! Fast Packet fragments must go through
+//! [`FastPacketAssembler`](crate::protocol::transport::fast_packet::assembler::FastPacketAssembler)
+//! (or [`N2kDecoder`](crate::protocol::decoder::N2kDecoder)) like live traffic.
+//!
+//! ```rust,ignore
+//! let mut decoder = N2kDecoder::new();
+//! for line in log.lines() {
+//!     let record = yd_raw::parse_line(line)?;
+//!     if let Some(Ok(message)) = decoder.decode_frame(&record.frame, record.timestamp_us / 1000) {
+//!         println!("{message:?}");
+//!     }
+//! }
+//! ```
+use core::fmt;
+
+use crate::{
+    error::FormatError,
+    formats::{
+        text::{parse_hex_u32, parse_hex_u8, parse_time_of_day, time_of_day_parts},
+        TimestampedFrame,
+    },
+    protocol::transport::{can_frame::CanFrame, can_id::CanId},
+};
+
+/// Direction of a frame relative to the gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Frame read from the bus (`R`).
+    Received,
+    /// Frame sent by the gateway (`T`).
+    Transmitted,
+}
+
+impl Direction {
+    fn from_marker(marker: &str) -> Result<Self, FormatError> {
+        match marker {
+            "R" => Ok(Self::Received),
+            "T" => Ok(Self::Transmitted),
+            _ => Err(FormatError::Syntax),
+        }
+    }
+
+    fn marker(self) -> char {
+        match self {
+            Self::Received => 'R',
+            Self::Transmitted => 'T',
+        }
+    }
+}
+
+//==================================================================================LINE
+/// Parse one YD RAW line, returning the frame and its direction.
+///
+/// # Errors
+///
+/// - [`FormatError::Syntax`] when a part of the line is missing or the direction is unknown
+/// - [`FormatError::InvalidTimestamp`] / [`FormatError::InvalidHex`] for malformed fields
+/// - [`FormatError::DataTooLong`] for more than eight data bytes
+pub fn parse_line(line: &str) -> Result<(Direction, TimestampedFrame), FormatError> {
+    let mut parts = line.split_whitespace();
+    let time = parts.next().ok_or(FormatError::Syntax)?;
+    let direction = Direction::from_marker(parts.next().ok_or(FormatError::Syntax)?)?;
+    let id = parts.next().ok_or(FormatError::Syntax)?;
+
+    let mut fields = time.splitn(3, ':');
+    let timestamp_us = match (fields.next(), fields.next(), fields.next()) {
+        (Some(hours), Some(minutes), Some(seconds)) => parse_time_of_day(hours, minutes, seconds)?,
+        _ => return Err(FormatError::InvalidTimestamp),
+    };
+
+    let id = parse_hex_u32(id)?;
+    if id > 0x1FFF_FFFF {
+        return Err(FormatError::InvalidHex);
+    }
+
+    let mut data = [0; 8];
+    let mut len = 0;
+    for byte in parts {
+        if len == data.len() {
+            return Err(FormatError::DataTooLong { len: len + 1 });
+        }
+        data[len] = parse_hex_u8(byte)?;
+        len += 1;
+    }
+
+    let frame = CanFrame {
+        id: CanId(id),
+        data,
+        len,
+    };
+    Ok((direction, TimestampedFrame::new(timestamp_us, frame)))
+}
+
+/// Write `record` as one YD RAW line (without the trailing newline).
+///
+/// The timestamp is written as a time of day with millisecond resolution.
+pub fn write_line<W: fmt::Write>(
+    out: &mut W,
+    direction: Direction,
+    record: &TimestampedFrame,
+) -> fmt::Result {
+    let (hours, minutes, seconds, millis) = time_of_day_parts(record.timestamp_us);
+    let frame = &record.frame;
+    write!(
+        out,
+        "{hours:02}:{minutes:02}:{seconds:02}.{millis:03} {} {:08X}",
+        direction.marker(),
+        frame.id.0
+    )?;
+    frame.data[..frame.len.min(8)]
+        .iter()
+        .try_for_each(|byte| write!(out, " {byte:02X}"))
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,109 @@
+//! YD RAW tests: line parsing, writing, and Fast Packet replay.
+// YD_RAW
+use super::*;
+use crate::{
+    formats::text::LineBuffer,
+    protocol::{decoder::N2kDecoder, messages::N2kMessage},
+};
+
+#[test]
+/// A received frame is parsed field by field.
+fn test_parse_line() {
+    let (direction, record) =
+        parse_line("17:33:21.107 R 19F51323 01 2F 30 70 00 2F 30 70").unwrap();
+    assert_eq!(direction, Direction::Received);
+    assert_eq!(
+        record.timestamp_us,
+        ((17 * 60 + 33) * 60 + 21) * 1_000_000 + 107_000
+    );
+    assert_eq!(record.frame.id.0, 0x19F5_1323);
+    assert_eq!(record.frame.id.pgn(), 128275);
+    assert_eq!(record.frame.id.source_address(), 0x23);
+    assert_eq!(record.frame.len, 8);
+    assert_eq!(
+        record.frame.data,
+        [0x01, 0x2F, 0x30, 0x70, 0x00, 0x2F, 0x30, 0x70]
+    );
+
+    // Transmitted frames and short payloads.
+    let (direction, record) = parse_line("00:00:01.5 T 09F80103 a0 B1\r\n").unwrap();
+    assert_eq!(direction, Direction::Transmitted);
+    assert_eq!(record.timestamp_us, 1_500_000);
+    assert_eq!(record.frame.len, 2);
+}
+
+#[test]
+/// Parsing then writing reproduces the original line.
+fn test_write_line_roundtrip() {
+    let line = "23:59:59.999 T 09F80103 A0 B1 C2";
+    let (direction, record) = parse_line(line).unwrap();
+    let mut written = LineBuffer::new();
+    write_line(&mut written, direction, &record).unwrap();
+    assert_eq!(written.as_str(), line);
+
+    // Timestamps beyond a day wrap around midnight.
+    let mut written = LineBuffer::new();
+    let record = TimestampedFrame::new(86_400_000_000 + 61_002_000, record.frame);
+    write_line(&mut written, Direction::Received, &record).unwrap();
+    assert!(written.as_str().starts_with("00:01:01.002 R "));
+}
+
+#[test]
+/// Malformed lines are rejected with the matching error.
+fn test_parse_errors() {
+    assert_eq!(parse_line("").unwrap_err(), FormatError::Syntax);
+    assert_eq!(
+        parse_line("17:33:21.107 X 19F51323 01").unwrap_err(),
+        FormatError::Syntax
+    );
+    assert_eq!(
+        parse_line("17:33 R 19F51323 01").unwrap_err(),
+        FormatError::InvalidTimestamp
+    );
+    assert_eq!(
+        parse_line("24:00:00.000 R 19F51323 01").unwrap_err(),
+        FormatError::InvalidTimestamp
+    );
+    assert_eq!(
+        parse_line("17:33:21.107 R 3FFFFFFF 01").unwrap_err(),
+        FormatError::InvalidHex
+    );
+    assert_eq!(
+        parse_line("17:33:21.107 R 19F51323 0G").unwrap_err(),
+        FormatError::InvalidHex
+    );
+    assert_eq!(
+        parse_line("17:33:21.107 R 19F51323 01 02 03 04 05 06 07 08 09").unwrap_err(),
+        FormatError::DataTooLong { len: 9 }
+    );
+}
+
+#[test]
+/// A Fast Packet captured by a gateway is reassembled by the decoder.
+fn test_replay_fast_packet() {
+    // PGN 129029 (GNSS Position Data), 43-byte payload in seven fragments.
+    let log = "\
+12:00:00.000 R 0DF80503 40 2B 01 02 03 04 05 06
+12:00:00.001 R 0DF80503 41 07 08 09 0A 0B 0C 0D
+12:00:00.002 R 0DF80503 42 0E 0F 10 11 12 13 14
+12:00:00.003 R 0DF80503 43 15 16 17 18 19 1A 1B
+12:00:00.004 R 0DF80503 44 1C 1D 1E 1F 20 21 22
+12:00:00.005 R 0DF80503 45 23 24 25 26 27 28 29
+12:00:00.006 R 0DF80503 46 2A 2B FF FF FF FF FF";
+
+    let mut decoder = N2kDecoder::new();
+    let mut completed = None;
+    for line in log.lines() {
+        let (_, record) = parse_line(line).unwrap();
+        if let Some(message) = decoder.process_frame(&record.frame, record.timestamp_us / 1000) {
+            completed = Some(message);
+        }
+    }
+
+    let message = completed.unwrap();
+    assert_eq!(message.pgn(), 129029);
+    assert_eq!(message.len, 43);
+    assert_eq!(message.payload()[0], 0x01);
+    assert_eq!(message.payload()[42], 0x2B);
+    assert!(N2kMessage::descriptor(129029).unwrap().fastpacket);
+}
@@ -21,6 +21,11 @@ impl<'a> BitReader<'a> {
         }
     }
 
+    /// Number of bits left between the cursor and the end of the buffer.
+    pub fn remaining_bits(&self) -> usize {
+        self.buffer.len() * 8 - self.bit_cursor
+    }
+
     /// Read `num_bits` bits starting at the current cursor and return a `u64`.
     /// `num_bits` must stay in the [1, 64] range.
     pub fn read_u64(&mut self, num_bits: u8) -> Result<u64, BitReaderError> {
@@ -135,7 +140,7 @@ impl<'a> BitReader<'a> {
     /// Cursor must be aligned on an octet boundary.
     pub fn read_slice(&mut self, len: usize) -> Result<&'a [u8], BitReaderError> {
         // Slices are only allowed when aligned.
-        if self.bit_cursor % 8 != 0 {
+        if !self.bit_cursor.is_multiple_of(8) {
             return Err(BitReaderError::NonAlignedBit {
                 cursor: self.bit_cursor,
             });
@@ -283,7 +288,7 @@ impl<'a> BitWriter<'a> {
 
     /// Copy an already-aligned byte slice into the buffer.
     pub fn write_slice(&mut self, slice: &[u8]) -> Result<(), BitWriterError> {
-        if self.bit_cursor % 8 != 0 {
+        if !self.bit_cursor.is_multiple_of(8) {
             return Err(BitWriterError::NonAlignedBit {
                 cursor: self.bit_cursor,
             });
@@ -1,9 +1,14 @@
 //! Generic serialization/deserialization engine driven by compile-time PGN descriptors.
 //! It controls the bit-level readers/writers and turns dynamic `PgnValue`s into
 //! strongly typed domain structures.
+// Codec errors carry the offending `PgnValue` by value: the crate does not allocate.
+#![allow(clippy::result_large_err)]
 use super::bits::{BitReader, BitWriter};
 use super::traits::FieldAccess;
-use crate::core::{FieldDescriptor, FieldKind, PgnBytes, PgnDescriptor, PgnValue, MAX_PGN_BYTES};
+use crate::core::{
+    FieldDescriptor, FieldKind, PgnBytes, PgnDecimal, PgnDescriptor, PgnValue, PgnVariable,
+    MAX_DECIMAL_DIGITS, MAX_PGN_BYTES, MAX_VARIABLE_BYTES,
+};
 use crate::error::{CodecError, DeserializationError, SerializationError};
 
 /// Deserializes a payload into a generic PGN struct `T`.
@@ -35,13 +40,23 @@ pub fn deserialize_into<T: FieldAccess>(
         false
     };
 
+    // PGN referenced by the message (Group Function): it sizes the `Variable`
+    // fields and tells whether the proprietary-only fields are present
+    let mut referenced_pgn = None;
+
     for (field_idx, field_desc) in descriptor.fields.iter().enumerate() {
         // Skip fields that belong to repeating groups; they are handled later
         if is_repetitive_field(field_idx) {
             continue;
         }
+        if field_desc.proprietary_only && !referenced_pgn.is_some_and(is_proprietary) {
+            continue;
+        }
 
         if let Some(value) = read_field_value(&mut reader, field_desc)? {
+            if field_desc.kind == FieldKind::Pgn {
+                referenced_pgn = pgn_value_to_u64(&value).ok();
+            }
             instance.field_mut(field_desc.id, value).ok_or(
                 DeserializationError::FieldAssignmentFailed {
                     desc: field_desc.id,
@@ -50,9 +65,12 @@ pub fn deserialize_into<T: FieldAccess>(
         }
     }
 
+    // Value of parameter 1, selecting the variant of a polymorphic referenced PGN
+    let mut selector = None;
+
     // ==================== Repeating field handling ====================
     // After processing all regular fields, handle repeating groups
-    for rfs in descriptor.repeating_field_sets {
+    for (set_idx, rfs) in descriptor.repeating_field_sets.iter().enumerate() {
         // 1. Read the counter field to know how many elements to expect
         let count = if let Some(counter_idx) = rfs.count_field_index {
             // The counter is a regular field already parsed; retrieve it via the descriptor
@@ -62,30 +80,60 @@ pub fn deserialize_into<T: FieldAccess>(
                 .ok_or(DeserializationError::InvalidDataLength)?;
 
             // Fetch the counter value from the instance
-            match instance.field(counter_field.id) {
+            let count = match instance.field(counter_field.id) {
                 Some(PgnValue::U8(v)) => v as usize,
                 Some(PgnValue::U16(v)) => v as usize,
                 Some(PgnValue::U32(v)) => v as usize,
                 _ => return Err(DeserializationError::InvalidDataLength),
-            }
+            };
+
+            // Clamp the counter against the maximum allowed repetitions
+            Some(count.min(rfs.max_repetitions))
         } else {
-            // No explicit counter: would require computing the length on the fly.
-            // This branch is not supported yet because the scenario is uncommon.
-            return Err(DeserializationError::UnsupportedFieldKind {
-                field_kind: crate::core::FieldKind::Unimplemented,
-            });
+            // No explicit counter: the group repeats until the payload is exhausted
+            None
         };
 
-        // Clamp the counter against the maximum allowed repetitions
-        let count = count.min(rfs.max_repetitions);
+        // Bits taken by one element, when every field of the group has a fixed width
+        let group_bits: Option<usize> = descriptor
+            .fields
+            .iter()
+            .skip(rfs.start_field_index)
+            .take(rfs.size)
+            .map(|field_desc| match field_desc.kind {
+                FieldKind::Variable => None,
+                _ => field_desc.bits_length.map(|bits| bits as usize),
+            })
+            .sum();
 
         // 2. Set the number of valid elements through the FieldAccess trait
         instance
-            .set_repetitive_count(rfs.array_id, count)
+            .set_repetitive_count(rfs.array_id, count.unwrap_or(0))
             .ok_or(DeserializationError::FieldAssignmentFailed { desc: rfs.array_id })?;
 
         // 3. Iterate through every element of the repeating group
-        for elem_idx in 0..count {
+        let mut elem_idx = 0;
+        while elem_idx < count.unwrap_or(rfs.max_repetitions) {
+            if count.is_none() {
+                // Stop on a trailing partial group (padding or truncated payload);
+                // variable-width groups stop on the first element that fails to read
+                let exhausted = match group_bits {
+                    Some(bits) => bits == 0 || reader.remaining_bits() < bits,
+                    None => reader.remaining_bits() == 0,
+                };
+                if exhausted {
+                    break;
+                }
+                instance
+                    .set_repetitive_count(rfs.array_id, elem_idx + 1)
+                    .ok_or(DeserializationError::FieldAssignmentFailed { desc: rfs.array_id })?;
+            }
+
+            // Parameter index selecting the field a `Variable` stands for
+            let mut parameter = None;
+            // Element of a variable-width group cut short by the end of the payload
+            let mut partial = false;
+
             // For each field in the group
             for field_offset in 0..rfs.size {
                 let field_idx = rfs.start_field_index + field_offset;
@@ -94,7 +142,46 @@ pub fn deserialize_into<T: FieldAccess>(
                     .get(field_idx)
                     .ok_or(DeserializationError::InvalidDataLength)?;
 
-                if let Some(value) = read_field_value(&mut reader, field_desc)? {
+                let read = match field_desc.kind {
+                    FieldKind::Variable => {
+                        let (Some(pgn), Some(index)) = (referenced_pgn, parameter) else {
+                            return Err(DeserializationError::MalformedData);
+                        };
+                        let Some(field) =
+                            instance.variable_field(pgn as u32, index as u8, selector)?
+                        else {
+                            // Unresolved: the width of what follows is unknown, so the
+                            // element keeps an empty value and parsing stops here
+                            return stop_unresolved(
+                                instance,
+                                descriptor,
+                                set_idx,
+                                elem_idx,
+                                field_desc.id,
+                            );
+                        };
+                        read_variable(&mut reader, field).map(|variable| {
+                            if index == 1 {
+                                selector = variable_selector(field, &variable);
+                            }
+                            Some(PgnValue::Variable(variable))
+                        })
+                    }
+                    _ => read_field_value(&mut reader, field_desc),
+                };
+                let value = match read {
+                    Ok(value) => value,
+                    Err(_) if count.is_none() && group_bits.is_none() => {
+                        partial = true;
+                        break;
+                    }
+                    Err(err) => return Err(err),
+                };
+                if field_desc.kind == FieldKind::FieldIndex {
+                    parameter = value.as_ref().and_then(|v| pgn_value_to_u64(v).ok());
+                }
+
+                if let Some(value) = value {
                     // Write the value into the array entry through FieldAccess
                     instance
                         .repetitive_field_mut(rfs.array_id, elem_idx, field_desc.id, value)
@@ -103,12 +190,48 @@ pub fn deserialize_into<T: FieldAccess>(
                         })?;
                 }
             }
+            if partial {
+                // Trailing bytes that do not form an element (padding)
+                instance
+                    .set_repetitive_count(rfs.array_id, elem_idx)
+                    .ok_or(DeserializationError::FieldAssignmentFailed { desc: rfs.array_id })?;
+                break;
+            }
+            elem_idx += 1;
         }
     }
 
     Ok(())
 }
 
+/// End the decoding of a Group Function on an unresolved parameter: element
+/// `elem_idx` of set `set_idx` is the last one kept, its `field_id` left empty,
+/// and the following sets hold no element.
+fn stop_unresolved<T: FieldAccess>(
+    instance: &mut T,
+    descriptor: &'static PgnDescriptor,
+    set_idx: usize,
+    elem_idx: usize,
+    field_id: &'static str,
+) -> Result<(), DeserializationError> {
+    for (idx, rfs) in descriptor
+        .repeating_field_sets
+        .iter()
+        .enumerate()
+        .skip(set_idx)
+    {
+        let count = if idx == set_idx { elem_idx + 1 } else { 0 };
+        instance
+            .set_repetitive_count(rfs.array_id, count)
+            .ok_or(DeserializationError::FieldAssignmentFailed { desc: rfs.array_id })?;
+    }
+    let unresolved = PgnValue::Variable(PgnVariable::new());
+    let rfs = &descriptor.repeating_field_sets[set_idx];
+    instance
+        .repetitive_field_mut(rfs.array_id, elem_idx, field_id, unresolved)
+        .ok_or(DeserializationError::FieldAssignmentFailed { desc: field_id })
+}
+
 /// Serializes a PGN struct `T` into a buffer.
 ///
 /// # Parameters
@@ -118,8 +241,8 @@ pub fn deserialize_into<T: FieldAccess>(
 ///
 /// # Return value
 /// Number of bytes written into the buffer.
-pub fn serialize<'a, T: FieldAccess>(
-    pgn_instance: &'a T,
+pub fn serialize<T: FieldAccess>(
+    pgn_instance: &T,
     buffer: &mut [u8],
     descriptor: &'static PgnDescriptor,
 ) -> Result<usize, SerializationError> {
@@ -139,24 +262,34 @@ pub fn serialize<'a, T: FieldAccess>(
         false
     };
 
+    // PGN referenced by the message, deciding on the proprietary-only fields
+    let mut referenced_pgn = None;
+
     for (field_idx, field_desc) in descriptor.fields.iter().enumerate() {
         // Skip repeating fields; they are processed afterwards
         if is_repetitive_field(field_idx) {
             continue;
         }
+        if field_desc.proprietary_only && !referenced_pgn.is_some_and(is_proprietary) {
+            continue;
+        }
 
         let value = pgn_instance
             .field(field_desc.id)
             .ok_or(SerializationError::FieldNotFound {
                 field_id: field_desc.id,
             })?;
+        if field_desc.kind == FieldKind::Pgn {
+            referenced_pgn = pgn_value_to_u64(&value).ok();
+        }
         write_field(&mut writer, field_desc, &value)?;
     }
 
     // ==================== Repeating field serialization ====================
     // After writing regular fields, serialize the repeating groups
     for rfs in descriptor.repeating_field_sets {
-        // 1. Retrieve the number of valid elements for the array
+        // 1. Retrieve the number of valid elements for the array. Groups without
+        // a counter field are delimited by the payload length alone.
         let count = pgn_instance.repetitive_count(rfs.array_id).ok_or(
             SerializationError::FieldNotFound {
                 field_id: rfs.array_id,
@@ -191,7 +324,7 @@ pub fn serialize<'a, T: FieldAccess>(
 
     let bits_written = writer.bit_cursor();
 
-    Ok((bits_written + 7) / 8)
+    Ok(bits_written.div_ceil(8))
 }
 
 /// Shared helper to read a single field, applying business logic (signedness,
@@ -226,7 +359,11 @@ fn read_field_value(
             Ok(Some(value))
         }
 
-        FieldKind::Number | FieldKind::Lookup | FieldKind::IndirectLookup | FieldKind::Pgn => {
+        FieldKind::Number
+        | FieldKind::Lookup
+        | FieldKind::IndirectLookup
+        | FieldKind::Pgn
+        | FieldKind::FieldIndex => {
             let raw_val = if let Some(bits) = field_desc.bits_length {
                 match reader.read_u64(bits as u8) {
                     Ok(val) => val,
@@ -291,8 +428,10 @@ fn read_field_value(
             let slice = reader
                 .read_slice(num_bytes)
                 .map_err(|e| DeserializationError::BitReaderError { err: e })?;
-            let mut pgn_bytes = PgnBytes::default();
-            pgn_bytes.len = num_bytes;
+            let mut pgn_bytes = PgnBytes {
+                len: num_bytes,
+                ..Default::default()
+            };
             pgn_bytes.data[..num_bytes].copy_from_slice(slice);
             Ok(Some(PgnValue::Bytes(pgn_bytes)))
         }
@@ -358,12 +497,50 @@ fn read_field_value(
             let slice = reader
                 .read_slice(num_bytes)
                 .map_err(|e| DeserializationError::BitReaderError { err: e })?;
-            let mut pgn_bytes = PgnBytes::default();
-            pgn_bytes.len = num_bytes;
+            let mut pgn_bytes = PgnBytes {
+                len: num_bytes,
+                ..Default::default()
+            };
             pgn_bytes.data[..num_bytes].copy_from_slice(slice);
             Ok(Some(PgnValue::Bytes(pgn_bytes)))
         }
 
+        // Two digits per byte, most significant nibble first. Every digit is
+        // kept, leading zeros included; all bytes at 0xFF mean "not available",
+        // and a nibble above 9 leaves the field empty as well.
+        FieldKind::Decimal => {
+            let num_bytes =
+                decimal_bytes(field_desc).ok_or(DeserializationError::InvalidFieldBits {
+                    field_name: field_desc.id,
+                })?;
+            let slice = reader
+                .read_slice(num_bytes)
+                .map_err(|e| DeserializationError::BitReaderError { err: e })?;
+            if slice.iter().all(|&byte| byte == 0xFF) {
+                return Ok(Some(PgnValue::Decimal(PgnDecimal::new())));
+            }
+            let mut digits = [0u8; MAX_DECIMAL_DIGITS];
+            for (pair, byte) in digits.chunks_exact_mut(2).zip(slice) {
+                pair[0] = byte >> 4;
+                pair[1] = byte & 0x0F;
+            }
+            let decimal = PgnDecimal::from_digits(&digits[..2 * num_bytes]).unwrap_or_default();
+            Ok(Some(PgnValue::Decimal(decimal)))
+        }
+
+        // ISO 11783 NAME: 64 bits, little-endian, handed over raw.
+        FieldKind::IsoName => {
+            if field_desc.bits_length != Some(64) {
+                return Err(DeserializationError::InvalidFieldBits {
+                    field_name: field_desc.id,
+                });
+            }
+            let raw = reader
+                .read_u64(64)
+                .map_err(|e| DeserializationError::BitReaderError { err: e })?;
+            Ok(Some(PgnValue::U64(raw)))
+        }
+
         FieldKind::Date | FieldKind::Mmsi => {
             let raw_val = if let Some(value) = field_desc.bits_length {
                 reader
@@ -451,13 +628,13 @@ fn read_field_value(
 /// Private helper that writes a single value according to its descriptor.
 /// Encapsulates all business rules tied to `FieldKind` (signed/unsigned,
 /// lookup, strings, binary blocks, etc.).
-fn write_field<'a>(
+fn write_field(
     writer: &mut BitWriter,
     field_desc: &'static FieldDescriptor,
-    value: &'a PgnValue,
+    value: &PgnValue,
 ) -> Result<(), SerializationError> {
     match field_desc.kind {
-        FieldKind::Number | FieldKind::Pgn => {
+        FieldKind::Number | FieldKind::Pgn | FieldKind::FieldIndex => {
             let bits_to_write = if field_desc.is_signed.is_some_and(|s| s) {
                 let prepared_val = if let Some(res) = field_desc.resolution {
                     // Common path: floating-point value that must be scaled back to an integer
@@ -682,11 +859,81 @@ fn write_field<'a>(
                 });
             }
         }
+        FieldKind::Decimal => {
+            let PgnValue::Decimal(decimal) = value else {
+                return Err(SerializationError::CodecError {
+                    source: CodecError::DataTypeMismatch {
+                        value: value.clone(),
+                        func: "write_field // Decimal",
+                    },
+                });
+            };
+            let num_bytes =
+                decimal_bytes(field_desc).ok_or(SerializationError::InvalidFieldBits {
+                    field_name: field_desc.id,
+                })?;
+            if decimal.len() > 2 * num_bytes {
+                return Err(SerializationError::InvalidData);
+            }
+            let mut bytes = [0xFFu8; MAX_DECIMAL_DIGITS / 2];
+            if !decimal.is_empty() {
+                // Left-pad with zeros up to the field width.
+                let mut digits = [0u8; MAX_DECIMAL_DIGITS];
+                digits[2 * num_bytes - decimal.len()..2 * num_bytes]
+                    .copy_from_slice(decimal.digits());
+                for (byte, pair) in bytes.iter_mut().zip(digits.chunks_exact(2)) {
+                    *byte = (pair[0] << 4) | pair[1];
+                }
+            }
+            writer
+                .write_slice(&bytes[..num_bytes])
+                .map_err(|e| SerializationError::BitWriteError { err: e })?;
+        }
+        FieldKind::IsoName => {
+            let PgnValue::U64(raw) = value else {
+                return Err(SerializationError::CodecError {
+                    source: CodecError::DataTypeMismatch {
+                        value: value.clone(),
+                        func: "write_field // IsoName",
+                    },
+                });
+            };
+            if field_desc.bits_length != Some(64) {
+                return Err(SerializationError::InvalidFieldBits {
+                    field_name: field_desc.id,
+                });
+            }
+            writer
+                .write_u64(*raw, 64)
+                .map_err(|e| SerializationError::BitWriteError { err: e })?;
+        }
+        FieldKind::Variable => {
+            // Already laid out by `encode_variable`.
+            let PgnValue::Variable(variable) = value else {
+                return Err(SerializationError::CodecError {
+                    source: CodecError::DataTypeMismatch {
+                        value: value.clone(),
+                        func: "write_field // Variable",
+                    },
+                });
+            };
+            writer
+                .write_slice(variable.as_bytes())
+                .map_err(|e| SerializationError::BitWriteError { err: e })?;
+        }
         _ => return Err(SerializationError::UnsupportedFieldKind),
     }
     Ok(())
 }
 
+/// Byte length of a `Decimal` field: whole bytes, two digits each, within
+/// [`MAX_DECIMAL_DIGITS`].
+fn decimal_bytes(field_desc: &FieldDescriptor) -> Option<usize> {
+    let bits = field_desc.bits_length?;
+    let num_bytes = (bits / 8) as usize;
+    (bits % 8 == 0 && 2 * num_bytes <= MAX_DECIMAL_DIGITS).then_some(num_bytes)
+}
+
 /// Converts a `PgnValue` into `f64`.
 /// Normalizes values to double precision when a resolution must be applied during serialization.
 fn pgn_value_to_f64(value: &PgnValue) -> Result<f64, CodecError> {
@@ -734,6 +981,103 @@ fn pgn_value_to_u64(value: &PgnValue) -> Result<u64, CodecError> {
     }
 }
 
+//==================================================================================VARIABLE
+/// Encode `value` as the bytes a `Variable` field standing for `field` carries.
+///
+/// `value` uses the variant the field decodes to (e.g. `PgnValue::F32` for a
+/// scaled number); unused trailing bits are set to `1`. A length-prefixed string
+/// takes as many bytes as its value needs, within [`MAX_VARIABLE_BYTES`].
+pub fn encode_variable(
+    field: &'static FieldDescriptor,
+    value: &PgnValue,
+) -> Result<PgnVariable, SerializationError> {
+    let mut bytes = [0xFFu8; MAX_VARIABLE_BYTES];
+    let num_bytes = if is_length_prefixed(field) {
+        let mut writer = BitWriter::new(&mut bytes);
+        write_field(&mut writer, field, value)?;
+        writer.bit_cursor() / 8
+    } else {
+        let num_bytes = variable_bytes(field).ok_or(SerializationError::InvalidFieldBits {
+            field_name: field.id,
+        })?;
+        let mut writer = BitWriter::new(&mut bytes[..num_bytes]);
+        write_field(&mut writer, field, value)?;
+        num_bytes
+    };
+    PgnVariable::from_bytes(&bytes[..num_bytes]).ok_or(SerializationError::InvalidData)
+}
+
+/// Decode the bytes of a `Variable` field standing for `field`.
+pub fn decode_variable(
+    field: &'static FieldDescriptor,
+    variable: &PgnVariable,
+) -> Result<Option<PgnValue>, DeserializationError> {
+    let mut reader = BitReader::new(variable.as_bytes());
+    read_field_value(&mut reader, field)
+}
+
+/// Value of a `Variable` standing for `field` as an unsigned number, e.g. the
+/// function code given to parameter 1 to select a polymorphic PGN variant.
+pub fn variable_selector(field: &'static FieldDescriptor, variable: &PgnVariable) -> Option<u64> {
+    decode_variable(field, variable)
+        .ok()
+        .flatten()
+        .and_then(|value| pgn_value_to_u64(&value).ok())
+}
+
+/// Read a `Variable` field standing for `field`, rounded up to whole bytes. A
+/// length-prefixed string is sized from its leading length byte.
+fn read_variable(
+    reader: &mut BitReader,
+    field: &'static FieldDescriptor,
+) -> Result<PgnVariable, DeserializationError> {
+    let mut bytes = [0u8; MAX_VARIABLE_BYTES];
+    let (start, num_bytes) = if is_length_prefixed(field) {
+        bytes[0] = reader
+            .read_u8(8)
+            .map_err(|e| DeserializationError::BitReaderError { err: e })?;
+        (1, 1 + bytes[0] as usize)
+    } else {
+        let num_bytes =
+            variable_bytes(field).ok_or(DeserializationError::UnsupportedFieldKind {
+                field_kind: field.kind.clone(),
+            })?;
+        (0, num_bytes)
+    };
+    if num_bytes > MAX_VARIABLE_BYTES {
+        return Err(DeserializationError::InvalidDataLength);
+    }
+
+    for byte in &mut bytes[start..num_bytes] {
+        *byte = reader
+            .read_u8(8)
+            .map_err(|e| DeserializationError::BitReaderError { err: e })?;
+    }
+    PgnVariable::from_bytes(&bytes[..num_bytes]).ok_or(DeserializationError::InvalidDataLength)
+}
+
+/// Whether `pgn` lies in a manufacturer proprietary range (PDU1 or PDU2,
+/// single frame or Fast Packet).
+fn is_proprietary(pgn: u64) -> bool {
+    matches!(
+        pgn,
+        0xEF00..=0xEFFF | 0xFF00..=0xFFFF | 0x1EF00..=0x1EFFF | 0x1FF00..=0x1FFFF
+    )
+}
+
+/// Byte length of a `Variable` standing for `field`: fixed width only, rounded
+/// up to whole bytes, within [`MAX_VARIABLE_BYTES`].
+fn variable_bytes(field: &FieldDescriptor) -> Option<usize> {
+    let num_bytes = field.bits_length?.div_ceil(8) as usize;
+    (num_bytes <= MAX_VARIABLE_BYTES).then_some(num_bytes)
+}
+
+/// Whether `field` carries its own length in a leading byte (`STRING_LZ`,
+/// `STRING_LAU`), which then sizes its `Variable`.
+fn is_length_prefixed(field: &FieldDescriptor) -> bool {
+    matches!(field.kind, FieldKind::StringLz | FieldKind::StringLau)
+}
+
 //==================================================================================
 
 /// Two's complement helper.
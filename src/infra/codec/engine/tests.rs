@@ -1,19 +1,25 @@
 //! End-to-end tests for the generic PGN serialization/deserialization engine.
-use crate::core::{FieldDescriptor, FieldKind, PgnDescriptor, PgnValue};
+use crate::core::{
+    FieldDescriptor, FieldKind, PgnBytes, PgnDescriptor, PgnValue, RepeatingFieldSet,
+};
 
 use crate::{
+    error::DeserializationError,
     infra::codec::{
-        engine::{deserialize_into, serialize},
-        traits::FieldAccess,
+        engine::{decode_variable, deserialize_into, encode_variable, serialize},
+        traits::{FieldAccess, PgnData},
     },
     protocol::{
         lookups::{
-            AcLine, Acceptability, DeviceClass, DeviceFunction, IndustryCode, ManufacturerCode,
-            RangeResidualMode, SatelliteStatus, YesNo,
+            AcLine, Acceptability, DeviceClass, GroupFunction, IndustryCode, ManufacturerCode,
+            ParameterField, PgnErrorCode, PgnListFunction, Priority, RangeResidualMode,
+            SatelliteStatus,
         },
+        managment::iso_name::IsoName,
         messages::{
-            LineInfo, Pgn127503, Pgn129025, Pgn129029, Pgn129040, Pgn129044, Pgn129540,
-            Pgn130821NavicoAsciiData, Pgn59904, Pgn60160, Pgn60928,
+            variable_field, LineInfo, Pgn126208, Pgn126464, Pgn127503, Pgn129025, Pgn129029,
+            Pgn129040, Pgn129044, Pgn129540, Pgn130821NavicoAsciiData, Pgn59904, Pgn60160,
+            Pgn60928, Pgn65240,
         },
     },
 };
@@ -100,10 +106,12 @@ fn test_round_trip_multiple_way_pgn() {
                     is_signed: Some(true),
                     resolution: Some(1e-2),
                     enum_direct_name: None,
+                    enum_bit_name: None,
                     enum_indirect_name: None,
                     enum_indirect_field_order: None,
                     physical_unit: None,
                     physical_qtity: None,
+                    proprietary_only: false,
                 },
                 FieldDescriptor {
                     id: "value_f64",
@@ -115,10 +123,12 @@ fn test_round_trip_multiple_way_pgn() {
                     is_signed: Some(true),
                     resolution: Some(1e-10),
                     enum_direct_name: None,
+                    enum_bit_name: None,
                     enum_indirect_name: None,
                     enum_indirect_field_order: None,
                     physical_unit: None,
                     physical_qtity: None,
+                    proprietary_only: false,
                 },
                 FieldDescriptor {
                     id: "value_i16",
@@ -130,10 +140,12 @@ fn test_round_trip_multiple_way_pgn() {
                     is_signed: Some(true),
                     resolution: None,
                     enum_direct_name: None,
+                    enum_bit_name: None,
                     enum_indirect_name: None,
                     enum_indirect_field_order: None,
                     physical_unit: None,
                     physical_qtity: None,
+                    proprietary_only: false,
                 },
                 FieldDescriptor {
                     id: "value_u32_scaled",
@@ -145,18 +157,21 @@ fn test_round_trip_multiple_way_pgn() {
                     is_signed: None,
                     resolution: Some(1e-1),
                     enum_direct_name: None,
+                    enum_bit_name: None,
                     enum_indirect_name: None,
                     enum_indirect_field_order: None,
                     physical_unit: None,
                     physical_qtity: None,
+                    proprietary_only: false,
                 },
             ],
             repeating_field_sets: &[],
+            iso_name_fields: &[],
         };
     }
     let mocked_pgn = PgnFloatTest {
-        value_f32: 9.12345678,
-        value_f64: 1.23456789123456789,
+        value_f32: 9.123_457,
+        value_f64: 1.234_567_891_234_568,
         value_i16: -2542,
         value_u32_scaled: 429_496.4,
     };
@@ -184,19 +199,11 @@ fn test_round_trip_multiple_way_pgn() {
 
 #[test]
 fn test_string_lz_roundtrip() {
-    #[derive(Debug, PartialEq, Copy, Clone)]
+    #[derive(Debug, PartialEq, Copy, Clone, Default)]
     struct PgnStringLz {
         text: crate::core::PgnBytes,
     }
 
-    impl Default for PgnStringLz {
-        fn default() -> Self {
-            Self {
-                text: crate::core::PgnBytes::default(),
-            }
-        }
-    }
-
     impl FieldAccess for PgnStringLz {
         fn field(&self, id: &'static str) -> Option<PgnValue> {
             match id {
@@ -237,12 +244,15 @@ fn test_string_lz_roundtrip() {
                 is_signed: None,
                 resolution: None,
                 enum_direct_name: None,
+                enum_bit_name: None,
                 enum_indirect_name: None,
                 enum_indirect_field_order: None,
                 physical_unit: None,
                 physical_qtity: None,
+                proprietary_only: false,
             }],
             repeating_field_sets: &[],
+            iso_name_fields: &[],
         };
     }
 
@@ -270,20 +280,153 @@ fn test_string_lz_roundtrip() {
 }
 
 #[test]
-fn test_string_lau_roundtrip() {
-    #[derive(Debug, PartialEq, Copy, Clone)]
-    struct PgnStringLau {
-        description: crate::core::PgnBytes,
+/// BCD digits survive a round trip with their leading zeros; all-0xFF bytes
+/// mean "not available" and non-decimal nibbles are rejected.
+fn test_decimal_roundtrip() {
+    use crate::core::PgnDecimal;
+
+    #[derive(Debug, PartialEq, Copy, Clone, Default)]
+    struct PgnDecimalTest {
+        address: PgnDecimal,
+        tail: u8,
     }
 
-    impl Default for PgnStringLau {
-        fn default() -> Self {
-            Self {
-                description: crate::core::PgnBytes::default(),
+    impl FieldAccess for PgnDecimalTest {
+        fn field(&self, id: &'static str) -> Option<PgnValue> {
+            match id {
+                "Address" => Some(PgnValue::Decimal(self.address)),
+                "Tail" => Some(PgnValue::U8(self.tail)),
+                _ => None,
+            }
+        }
+
+        fn field_mut(&mut self, id: &'static str, value: PgnValue) -> Option<()> {
+            match (id, value) {
+                ("Address", PgnValue::Decimal(decimal)) => {
+                    self.address = decimal;
+                    Some(())
+                }
+                ("Tail", PgnValue::U8(tail)) => {
+                    self.tail = tail;
+                    Some(())
+                }
+                _ => None,
             }
         }
     }
 
+    impl PgnDecimalTest {
+        pub const DESCRIPTOR: PgnDescriptor = PgnDescriptor {
+            id: 42421,
+            name: "MockDecimal",
+            description: "Mocked DECIMAL field",
+            priority: Some(6),
+            fastpacket: false,
+            length: Some(6),
+            field_count: Some(2),
+            trans_interval: None,
+            trans_irregular: Some(true),
+            fields: &[
+                FieldDescriptor {
+                    id: "Address",
+                    name: "Address",
+                    kind: FieldKind::Decimal,
+                    bits_length: Some(40),
+                    bits_length_var: None,
+                    bits_offset: Some(0),
+                    is_signed: Some(false),
+                    resolution: None,
+                    enum_direct_name: None,
+                    enum_bit_name: None,
+                    enum_indirect_name: None,
+                    enum_indirect_field_order: None,
+                    physical_unit: None,
+                    physical_qtity: None,
+                    proprietary_only: false,
+                },
+                FieldDescriptor {
+                    id: "Tail",
+                    name: "Tail",
+                    kind: FieldKind::Number,
+                    bits_length: Some(8),
+                    bits_length_var: None,
+                    bits_offset: Some(40),
+                    is_signed: Some(false),
+                    resolution: None,
+                    enum_direct_name: None,
+                    enum_bit_name: None,
+                    enum_indirect_name: None,
+                    enum_indirect_field_order: None,
+                    physical_unit: None,
+                    physical_qtity: None,
+                    proprietary_only: false,
+                },
+            ],
+            repeating_field_sets: &[],
+            iso_name_fields: &[],
+        };
+    }
+
+    // Shorter values are left-padded to the ten digits of the field.
+    let payload = PgnDecimalTest {
+        address: PgnDecimal::from_ascii("22712345").unwrap(),
+        tail: 0,
+    };
+    let mut buffer = [0u8; 8];
+    let bytes_written = serialize(&payload, &mut buffer, &PgnDecimalTest::DESCRIPTOR).unwrap();
+    assert_eq!(bytes_written, 6);
+    assert_eq!(buffer[..6], [0x00, 0x22, 0x71, 0x23, 0x45, 0x00]);
+
+    let mut decoded = PgnDecimalTest::default();
+    deserialize_into(&mut decoded, &buffer[..6], &PgnDecimalTest::DESCRIPTOR).unwrap();
+    assert_eq!(decoded.address.len(), 10);
+    assert_eq!(decoded.address.digits(), [0, 0, 2, 2, 7, 1, 2, 3, 4, 5]);
+    assert_eq!(decoded.address.to_u64(), Some(22_712_345));
+
+    // Not available.
+    let mut decoded = PgnDecimalTest::default();
+    deserialize_into(
+        &mut decoded,
+        &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00],
+        &PgnDecimalTest::DESCRIPTOR,
+    )
+    .unwrap();
+    assert!(decoded.address.is_empty());
+    serialize(&decoded, &mut buffer, &PgnDecimalTest::DESCRIPTOR).unwrap();
+    assert_eq!(buffer[..5], [0xFF; 5]);
+
+    // Nibbles above 9 are not BCD: only that field is left empty.
+    let mut decoded = PgnDecimalTest {
+        address: PgnDecimal::from_ascii("1").unwrap(),
+        tail: 0,
+    };
+    deserialize_into(
+        &mut decoded,
+        &[0x12, 0x3A, 0x00, 0x00, 0x00, 0x2A],
+        &PgnDecimalTest::DESCRIPTOR,
+    )
+    .unwrap();
+    assert!(decoded.address.is_empty());
+    assert_eq!(decoded.tail, 0x2A);
+
+    // Eleven digits do not fit in five bytes.
+    let too_long = PgnDecimalTest {
+        address: PgnDecimal::from_ascii("12345678901").unwrap(),
+        tail: 0,
+    };
+    assert!(matches!(
+        serialize(&too_long, &mut buffer, &PgnDecimalTest::DESCRIPTOR),
+        Err(crate::error::SerializationError::InvalidData)
+    ));
+}
+
+#[test]
+fn test_string_lau_roundtrip() {
+    #[derive(Debug, PartialEq, Copy, Clone, Default)]
+    struct PgnStringLau {
+        description: crate::core::PgnBytes,
+    }
+
     impl FieldAccess for PgnStringLau {
         fn field(&self, id: &'static str) -> Option<PgnValue> {
             match id {
@@ -324,12 +467,15 @@ fn test_string_lau_roundtrip() {
                 is_signed: None,
                 resolution: None,
                 enum_direct_name: None,
+                enum_bit_name: None,
                 enum_indirect_name: None,
                 enum_indirect_field_order: None,
                 physical_unit: None,
                 physical_qtity: None,
+                proprietary_only: false,
             }],
             repeating_field_sets: &[],
+            iso_name_fields: &[],
         };
     }
 
@@ -391,43 +537,46 @@ fn test_round_trip_pgn_129025() {
 }
 
 #[test]
-/// PGN 60928: full NAME field and conversion round-trips.
+/// PGN 60928: the NAME is one little-endian 64-bit `IsoName` field.
 fn test_round_trip_pgn_60928() {
     let mut pgn = Pgn60928::new();
-    pgn.unique_number = 2122;
-    pgn.manufacturer_code = ManufacturerCode::Airmar;
-    pgn.device_instance_lower = 4;
-    pgn.device_instance_upper = 11;
-    pgn.device_class = DeviceClass::SystemTools;
-    // device_function is an INDIRECT_LOOKUP stored as a u8
-    // Use helper to assign the full DeviceFunction value
-    pgn.set_device_function(DeviceFunction::Diagnostic);
-    pgn.system_instance = 4;
-    pgn.arbitrary_address_capable = YesNo::Yes;
+    pgn.name = IsoName::builder()
+        .unique_number(2122)
+        .manufacturer_code(u16::from(ManufacturerCode::Airmar))
+        .device_instance(92)
+        .device_function(130)
+        .device_class(u8::from(DeviceClass::SystemTools))
+        .system_instance(4)
+        .industry_group(4)
+        .arbitrary_address_capable(true)
+        .build();
 
-    let mut pgn_rounded = Pgn60928::new();
     let mut buffer = [0xFF; Pgn60928::PGN_60928_DESCRIPTOR.length.unwrap() as usize];
     let bytes_written = serialize(&pgn, &mut buffer, &Pgn60928::PGN_60928_DESCRIPTOR).unwrap();
-    let payload_slice = &buffer[..bytes_written];
-    assert!(deserialize_into::<Pgn60928>(
-        &mut pgn_rounded,
-        payload_slice,
-        &Pgn60928::PGN_60928_DESCRIPTOR
-    )
-    .is_ok());
+    assert_eq!(bytes_written, 8);
+    assert_eq!(buffer, pgn.name.raw().to_le_bytes());
 
-    assert_eq!(pgn.unique_number, pgn_rounded.unique_number);
-    assert_eq!(pgn.manufacturer_code, pgn_rounded.manufacturer_code);
-    assert_eq!(pgn.device_instance_lower, pgn_rounded.device_instance_lower);
-    assert_eq!(pgn.device_instance_upper, pgn_rounded.device_instance_upper);
-    assert_eq!(pgn.device_function, pgn_rounded.device_function);
-    assert_eq!(pgn.device_class, pgn_rounded.device_class);
-    assert_eq!(pgn.system_instance, pgn_rounded.system_instance);
-    assert_eq!(pgn.industry_group, pgn_rounded.industry_group);
-    assert_eq!(
-        pgn.arbitrary_address_capable,
-        pgn_rounded.arbitrary_address_capable
-    );
+    let mut pgn_rounded = Pgn60928::new();
+    deserialize_into(&mut pgn_rounded, &buffer, &Pgn60928::PGN_60928_DESCRIPTOR).unwrap();
+    assert_eq!(pgn_rounded.name, pgn.name);
+}
+
+#[test]
+/// PGN 65240: NAME followed by the commanded address.
+fn test_round_trip_pgn_65240() {
+    let name = IsoName::from_raw(0xC0AB_CDEF_0123_4567);
+    let mut payload = [0u8; 9];
+    payload[..8].copy_from_slice(&name.raw().to_le_bytes());
+    payload[8] = 42;
+
+    let mut pgn = Pgn65240::new();
+    deserialize_into(&mut pgn, &payload, &Pgn65240::PGN_65240_DESCRIPTOR).unwrap();
+    assert_eq!(pgn.name, name);
+    assert_eq!(pgn.new_source_address, 42);
+
+    let mut buffer = [0u8; 9];
+    let bytes_written = serialize(&pgn, &mut buffer, &Pgn65240::PGN_65240_DESCRIPTOR).unwrap();
+    assert_eq!(buffer[..bytes_written], payload);
 }
 
 #[test]
@@ -652,8 +801,194 @@ fn test_round_trip_pgn_129029_repetitive_fields() {
     }
 }
 
+#[test]
+/// PGN 126464: without a counter field, groups are read until the payload ends.
+fn test_round_trip_pgn_126464_payload_driven_repetitions() {
+    let pgns: [u32; 3] = [59904, 126996, 129025];
+    let mut payload = [0u8; 12];
+    payload[0] = 0; // Transmit PGN list
+    for (i, pgn) in pgns.iter().enumerate() {
+        payload[1 + i * 3..4 + i * 3].copy_from_slice(&pgn.to_le_bytes()[..3]);
+    }
+    // Trailing partial group: ignored.
+    payload[10..].copy_from_slice(&[0xFF, 0xFF]);
+
+    let mut pgn = Pgn126464::new();
+    deserialize_into(&mut pgn, &payload, &Pgn126464::PGN_126464_DESCRIPTOR).unwrap();
+    assert_eq!(pgn.function_code, PgnListFunction::TransmitPgnList);
+    assert_eq!(pgn.pgns_count, 3);
+    for (entry, expected) in pgn.pgns.iter().zip(pgns) {
+        assert_eq!(entry.pgn, expected);
+    }
+
+    let mut buffer = [0u8; 223];
+    let bytes_written = serialize(&pgn, &mut buffer, &Pgn126464::PGN_126464_DESCRIPTOR).unwrap();
+    assert_eq!(buffer[..bytes_written], payload[..10]);
+
+    // A full Fast Packet payload is read up to the array's 32 entries.
+    let mut full = [0x11u8; 223];
+    full[0] = 1; // Receive PGN list
+    deserialize_into(&mut pgn, &full, &Pgn126464::PGN_126464_DESCRIPTOR).unwrap();
+    assert_eq!(pgn.function_code, PgnListFunction::ReceivePgnList);
+    assert_eq!(pgn.pgns_count, 32);
+    assert_eq!(pgn.pgns.len(), 32);
+    assert_eq!(pgn.pgns[31].pgn, 0x11_1111);
+}
+
+#[test]
+/// Without a counter field, groups holding a string are read until an element
+/// no longer parses: trailing padding ends the set instead of failing the payload.
+fn test_payload_driven_repetitions_with_strings() {
+    #[derive(Debug, Clone, Copy, Default, PartialEq)]
+    struct Entry {
+        id: u8,
+        name: PgnBytes,
+    }
+
+    #[derive(Debug, Default)]
+    struct PgnNamedList {
+        kind: u8,
+        entries: [Entry; 4],
+        entries_count: usize,
+    }
+
+    impl FieldAccess for PgnNamedList {
+        fn field(&self, id: &'static str) -> Option<PgnValue> {
+            (id == "Kind").then_some(PgnValue::U8(self.kind))
+        }
+
+        fn field_mut(&mut self, id: &'static str, value: PgnValue) -> Option<()> {
+            match (id, value) {
+                ("Kind", PgnValue::U8(kind)) => {
+                    self.kind = kind;
+                    Some(())
+                }
+                _ => None,
+            }
+        }
+
+        fn repetitive_field(
+            &self,
+            array_id: &'static str,
+            index: usize,
+            field_id: &'static str,
+        ) -> Option<PgnValue> {
+            let entry = self.entries.get(index).filter(|_| array_id == "entries")?;
+            match field_id {
+                "Id" => Some(PgnValue::U8(entry.id)),
+                "Name" => Some(PgnValue::Bytes(entry.name)),
+                _ => None,
+            }
+        }
+
+        fn repetitive_field_mut(
+            &mut self,
+            array_id: &'static str,
+            index: usize,
+            field_id: &'static str,
+            value: PgnValue,
+        ) -> Option<()> {
+            let entry = self
+                .entries
+                .get_mut(index)
+                .filter(|_| array_id == "entries")?;
+            match (field_id, value) {
+                ("Id", PgnValue::U8(id)) => entry.id = id,
+                ("Name", PgnValue::Bytes(name)) => entry.name = name,
+                _ => return None,
+            }
+            Some(())
+        }
+
+        fn repetitive_count(&self, array_id: &'static str) -> Option<usize> {
+            (array_id == "entries").then_some(self.entries_count)
+        }
+
+        fn set_repetitive_count(&mut self, array_id: &'static str, count: usize) -> Option<()> {
+            (array_id == "entries" && count <= self.entries.len()).then(|| {
+                self.entries_count = count;
+            })
+        }
+    }
+
+    const fn field(id: &'static str, kind: FieldKind, bits: Option<u32>) -> FieldDescriptor {
+        FieldDescriptor {
+            id,
+            name: id,
+            kind,
+            bits_length: bits,
+            bits_length_var: None,
+            bits_offset: None,
+            is_signed: Some(false),
+            resolution: None,
+            enum_direct_name: None,
+            enum_bit_name: None,
+            enum_indirect_name: None,
+            enum_indirect_field_order: None,
+            physical_unit: None,
+            physical_qtity: None,
+            proprietary_only: false,
+        }
+    }
+
+    static DESCRIPTOR: PgnDescriptor = PgnDescriptor {
+        id: 42422,
+        name: "MockNamedList",
+        description: "Mocked list of named entries without a counter",
+        priority: Some(6),
+        fastpacket: true,
+        length: None,
+        field_count: Some(3),
+        trans_interval: None,
+        trans_irregular: Some(true),
+        fields: &[
+            field("Kind", FieldKind::Number, Some(8)),
+            field("Id", FieldKind::Number, Some(8)),
+            field("Name", FieldKind::StringLau, None),
+        ],
+        repeating_field_sets: &[RepeatingFieldSet {
+            array_id: "entries",
+            count_field_index: None,
+            start_field_index: 1,
+            size: 2,
+            max_repetitions: 4,
+        }],
+        iso_name_fields: &[],
+    };
+
+    let mut list = PgnNamedList {
+        kind: 7,
+        entries_count: 2,
+        ..Default::default()
+    };
+    for (entry, (id, text)) in list.entries.iter_mut().zip([(1, &b"GPS"[..]), (2, b"AIS")]) {
+        let mut name = [1u8; 4]; // ASCII encoding byte, then the text
+        name[1..].copy_from_slice(text);
+        entry.id = id;
+        entry.name.copy_from_slice(&name);
+    }
+
+    let mut buffer = [0xFF; 32];
+    let bytes_written = serialize(&list, &mut buffer, &DESCRIPTOR).unwrap();
+
+    // Two padding bytes follow the last entry: an `Id` reads, its `Name` does not.
+    let mut decoded = PgnNamedList::default();
+    deserialize_into(&mut decoded, &buffer[..bytes_written + 2], &DESCRIPTOR).unwrap();
+    assert_eq!(decoded.kind, 7);
+    assert_eq!(decoded.entries_count, 2);
+    assert_eq!(decoded.entries[..2], list.entries[..2]);
+
+    // Without padding the set ends with the payload.
+    let mut decoded = PgnNamedList::default();
+    deserialize_into(&mut decoded, &buffer[..bytes_written], &DESCRIPTOR).unwrap();
+    assert_eq!(decoded.entries_count, 2);
+    assert_eq!(decoded.entries[..2], list.entries[..2]);
+}
+
 #[test]
 /// PGN 129540: verifies serialization of satellites-in-view repeating data.
+// Angles are wire-resolution samples, not approximations of `PI`.
+#[allow(clippy::approx_constant)]
 fn test_round_trip_pgn_129540_repetitive_fields() {
     let mut pgn = Pgn129540::new();
     pgn.sid = 7;
@@ -793,12 +1128,15 @@ fn test_bitlookup_u8_roundtrip() {
                 is_signed: None,
                 resolution: None,
                 enum_direct_name: None,
+                enum_bit_name: None,
                 enum_indirect_name: None,
                 enum_indirect_field_order: None,
                 physical_unit: None,
                 physical_qtity: None,
+                proprietary_only: false,
             }],
             repeating_field_sets: &[],
+            iso_name_fields: &[],
         };
     }
 
@@ -871,12 +1209,15 @@ fn test_bitlookup_u16_roundtrip() {
                 is_signed: None,
                 resolution: None,
                 enum_direct_name: None,
+                enum_bit_name: None,
                 enum_indirect_name: None,
                 enum_indirect_field_order: None,
                 physical_unit: None,
                 physical_qtity: None,
+                proprietary_only: false,
             }],
             repeating_field_sets: &[],
+            iso_name_fields: &[],
         };
     }
 
@@ -959,12 +1300,15 @@ fn test_bitlookup_u32_roundtrip() {
                 is_signed: None,
                 resolution: None,
                 enum_direct_name: None,
+                enum_bit_name: None,
                 enum_indirect_name: None,
                 enum_indirect_field_order: None,
                 physical_unit: None,
                 physical_qtity: None,
+                proprietary_only: false,
             }],
             repeating_field_sets: &[],
+            iso_name_fields: &[],
         };
     }
 
@@ -1038,12 +1382,15 @@ fn test_bitlookup_edge_cases() {
                 is_signed: None,
                 resolution: None,
                 enum_direct_name: None,
+                enum_bit_name: None,
                 enum_indirect_name: None,
                 enum_indirect_field_order: None,
                 physical_unit: None,
                 physical_qtity: None,
+                proprietary_only: false,
             }],
             repeating_field_sets: &[],
+            iso_name_fields: &[],
         };
     }
 
@@ -1171,3 +1518,276 @@ fn test_round_trip_pgn_127503_repetitive_fields() {
         );
     }
 }
+
+#[test]
+/// PGN 126208: Command parameters are sized and encoded from the commanded PGN.
+fn test_round_trip_pgn_126208_command_parameters() {
+    let message = Pgn126208::command(127250)
+        .parameter(2, PgnValue::F32(1.5))
+        .unwrap()
+        .parameter(5, PgnValue::U8(1))
+        .unwrap()
+        .build();
+
+    let mut buffer = [0u8; 223];
+    let bytes_written = message.to_payload(&mut buffer).unwrap();
+    assert_eq!(
+        buffer[..bytes_written],
+        [
+            0x01, // Command
+            0x12, 0xF1, 0x01, // PGN 127250
+            0xF8, // Priority left unchanged, reserved bits set
+            0x02, // Two parameters
+            0x02, 0x98, 0x3A, // Heading: 15000 * 0.0001 rad
+            0x05, 0xFD, // Reference: Magnetic, trailing bits set
+        ]
+    );
+
+    let decoded = Pgn126208::from_payload(&buffer[..bytes_written]).unwrap();
+    assert_eq!(decoded, message);
+    let Pgn126208::NmeaCommandGroupFunction(command) = decoded else {
+        panic!("unexpected variant");
+    };
+    assert_eq!(command.function_code, GroupFunction::Command);
+    assert_eq!(command.priority, Priority::LeaveUnchanged);
+    let heading = variable_field(127250, 2, None).unwrap().unwrap();
+    let Some(PgnValue::F32(value)) =
+        decode_variable(heading, &command.parameters[0].value).unwrap()
+    else {
+        panic!("unexpected value");
+    };
+    assert!((value - 1.5).abs() < 0.0001);
+}
+
+#[test]
+/// PGN 126208: parameters of 60928 and 65240 keep their CANboat indices, NAME
+/// sub-fields included, though the NAME is one `IsoName` field.
+fn test_pgn_126208_parameters_against_iso_name() {
+    let manufacturer = variable_field(60928, 2, None).unwrap().unwrap();
+    assert_eq!(manufacturer.id, "ManufacturerCode");
+    assert_eq!(manufacturer.bits_length, Some(11));
+    let address = variable_field(65240, 11, None).unwrap().unwrap();
+    assert_eq!(address.id, "NewSourceAddress");
+    assert!(matches!(
+        variable_field(60928, 11, None),
+        Err(DeserializationError::MalformedData)
+    ));
+
+    let message = Pgn126208::request(60928)
+        .parameter(2, PgnValue::U16(u16::from(ManufacturerCode::Airmar)))
+        .unwrap()
+        .build();
+    let mut buffer = [0u8; 223];
+    let bytes_written = message.to_payload(&mut buffer).unwrap();
+    // Manufacturer code 135 on two bytes, trailing bits set.
+    assert_eq!(
+        buffer[bytes_written - 4..bytes_written],
+        [0x01, 0x02, 0x87, 0xF8]
+    );
+    let decoded = Pgn126208::from_payload(&buffer[..bytes_written]).unwrap();
+    let Pgn126208::NmeaRequestGroupFunction(request) = decoded else {
+        panic!("unexpected variant");
+    };
+    assert_eq!(request.pgn, 60928);
+    assert_eq!(request.parameters[0].parameter, 2);
+    assert_eq!(
+        decode_variable(manufacturer, &request.parameters[0].value).unwrap(),
+        Some(PgnValue::U16(135))
+    );
+
+    let message = Pgn126208::command(65240)
+        .parameter(11, PgnValue::U8(42))
+        .unwrap()
+        .build();
+    let bytes_written = message.to_payload(&mut buffer).unwrap();
+    assert_eq!(buffer[bytes_written - 3..bytes_written], [0x01, 0x0B, 0x2A]);
+    assert_eq!(
+        Pgn126208::from_payload(&buffer[..bytes_written]).unwrap(),
+        message
+    );
+}
+
+#[test]
+/// PGN 126208: a bare Request asks for the PGN now, without changing its timing.
+fn test_pgn_126208_request_defaults_and_errors() {
+    let message = Pgn126208::request(129025).build();
+    let mut buffer = [0u8; 223];
+    let bytes_written = message.to_payload(&mut buffer).unwrap();
+    assert_eq!(
+        buffer[..bytes_written],
+        [0x00, 0x01, 0xF8, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00]
+    );
+
+    // Parameter 9 does not exist in PGN 129025.
+    assert!(matches!(
+        Pgn126208::request(129025).parameter(9, PgnValue::F64(0.0)),
+        Err(crate::error::SerializationError::InvalidData)
+    ));
+
+    // Values of an unknown PGN cannot be sized: the first parameter is kept
+    // unresolved and parsing stops there.
+    let payload = [
+        0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02, 0x01, 0x00, 0x02, 0x00,
+    ];
+    let Pgn126208::NmeaRequestGroupFunction(request) = Pgn126208::from_payload(&payload).unwrap()
+    else {
+        panic!("unexpected variant");
+    };
+    assert_eq!(request.pgn, 0);
+    assert_eq!(request.parameters_count, 1);
+    assert_eq!(request.number_of_parameters, 1);
+    assert_eq!(request.parameters[0].parameter, 1);
+    assert!(request.parameters[0].value.is_empty());
+}
+
+#[test]
+/// PGN 126208: Write Fields carries selection pairs and parameters, two repeating
+/// sets; the manufacturer fields are absent for a non-proprietary PGN.
+fn test_round_trip_pgn_126208_write_fields() {
+    let payload = [
+        0x05, // Write Fields
+        0x12, 0xF1, 0x01, // PGN 127250
+        0x2A, // Unique ID
+        0x01, // One selection pair
+        0x01, // One parameter
+        0x01, 0x07, // Selection: SID 7
+        0x05, 0xFD, // Reference: Magnetic, trailing bits set
+    ];
+    let decoded = Pgn126208::from_payload(&payload).unwrap();
+    let Pgn126208::NmeaWriteFieldsGroupFunction(write) = decoded else {
+        panic!("unexpected variant");
+    };
+    assert_eq!(write.function_code, GroupFunction::WriteFields);
+    assert_eq!(write.pgn, 127250);
+    assert_eq!(write.unique_id, 42);
+    assert_eq!(write.selection_parameters_count, 1);
+    assert_eq!(write.selection_parameters[0].selection_parameter, 1);
+    assert_eq!(
+        write.selection_parameters[0].selection_value.as_bytes(),
+        [0x07]
+    );
+    assert_eq!(write.parameters_count, 1);
+    assert_eq!(write.parameters[0].parameter, 5);
+    let reference = variable_field(127250, 5, None).unwrap().unwrap();
+    assert_eq!(
+        decode_variable(reference, &write.parameters[0].value).unwrap(),
+        Some(PgnValue::U8(1))
+    );
+
+    let mut buffer = [0u8; 223];
+    let bytes_written = decoded.to_payload(&mut buffer).unwrap();
+    assert_eq!(buffer[..bytes_written], payload);
+}
+
+#[test]
+/// PGN 126208: parameters of a polymorphic PGN resolve against the variant its
+/// selection pair for parameter 1 matches, and are rejected without one.
+fn test_pgn_126208_write_fields_polymorphic_pgn() {
+    let payload = [
+        0x05, // Write Fields
+        0x05, 0xFF, 0x01, // PGN 130821, proprietary
+        0x3F, 0x9F, // Furuno, marine industry
+        0x01, // Unique ID
+        0x01, // One selection pair
+        0x01, // One parameter
+        0x01, 0x3F, 0xFF, // Selection: manufacturer Furuno
+        0x05, 0x2A, // Furuno variant field 5, one byte
+    ];
+    let Pgn126208::NmeaWriteFieldsGroupFunction(write) = Pgn126208::from_payload(&payload).unwrap()
+    else {
+        panic!("unexpected variant");
+    };
+    assert_eq!(write.manufacturer_code, ManufacturerCode::Furuno);
+    assert_eq!(write.industry_code, IndustryCode::MarineIndustry);
+    assert_eq!(write.parameters_count, 1);
+    let field = variable_field(130821, 5, Some(1855)).unwrap().unwrap();
+    assert_eq!(
+        decode_variable(field, &write.parameters[0].value).unwrap(),
+        Some(PgnValue::U8(42))
+    );
+
+    // Without the selection pair the variant, hence the field, is unknown.
+    let payload = [
+        0x05, 0x05, 0xFF, 0x01, 0x3F, 0x9F, 0x01, 0x00, 0x01, 0x05, 0x2A,
+    ];
+    assert!(matches!(
+        Pgn126208::from_payload(&payload),
+        Err(DeserializationError::UnmatchedVariant { pgn: 130821 })
+    ));
+    assert!(matches!(
+        variable_field(130821, 5, Some(1)),
+        Err(DeserializationError::UnmatchedVariant { pgn: 130821 })
+    ));
+}
+
+#[test]
+/// PGN 126208: string parameters (PGN 126998 installation descriptions) are
+/// sized from their length byte, in Read Fields Reply and Write Fields alike.
+fn test_round_trip_pgn_126208_installation_description() {
+    let mut payload = [0u8; 27];
+    payload[..8].copy_from_slice(&[
+        0x04, // Read Fields Reply
+        0x16, 0xF0, 0x01, // PGN 126998
+        0x07, // Unique ID
+        0x00, // No selection pair
+        0x02, // Two parameters
+        0x01, // Installation description 1
+    ]);
+    payload[8..21].copy_from_slice(b"\x0C\x01Nav station");
+    payload[21..].copy_from_slice(b"\x02\x04\x01Aft"); // Installation description 2
+
+    let decoded = Pgn126208::from_payload(&payload).unwrap();
+    let Pgn126208::NmeaReadFieldsReplyGroupFunction(reply) = decoded else {
+        panic!("unexpected variant");
+    };
+    assert_eq!(reply.pgn, 126998);
+    assert_eq!(reply.parameters_count, 2);
+    let description1 = variable_field(126998, 1, None).unwrap().unwrap();
+    let mut expected = PgnBytes::default();
+    expected.data[..12].copy_from_slice(b"\x01Nav station");
+    expected.len = 12;
+    assert_eq!(
+        decode_variable(description1, &reply.parameters[0].value).unwrap(),
+        Some(PgnValue::Bytes(expected))
+    );
+    assert_eq!(reply.parameters[1].parameter, 2);
+    assert_eq!(reply.parameters[1].value.as_bytes(), b"\x04\x01Aft");
+
+    let mut buffer = [0u8; 223];
+    let bytes_written = decoded.to_payload(&mut buffer).unwrap();
+    assert_eq!(buffer[..bytes_written], payload);
+
+    // Writing the description back carries the value just read.
+    payload[0] = 0x05; // Write Fields
+    let Pgn126208::NmeaWriteFieldsGroupFunction(mut write) =
+        Pgn126208::from_payload(&payload).unwrap()
+    else {
+        panic!("unexpected variant");
+    };
+    write.parameters[0].value = encode_variable(description1, &PgnValue::Bytes(expected)).unwrap();
+    assert_eq!(write.parameters[0].value, reply.parameters[0].value);
+    let bytes_written = Pgn126208::NmeaWriteFieldsGroupFunction(write)
+        .to_payload(&mut buffer)
+        .unwrap();
+    assert_eq!(buffer[..bytes_written], payload);
+}
+
+#[test]
+/// PGN 126208: Acknowledge error codes round-trip, one per parameter.
+fn test_round_trip_pgn_126208_acknowledge() {
+    let message = Pgn126208::acknowledge(127250)
+        .pgn_error_code(PgnErrorCode::Acknowledge)
+        .parameter(ParameterField::Acknowledge)
+        .unwrap()
+        .parameter(ParameterField::ParameterOutOfRange)
+        .unwrap()
+        .build();
+
+    let mut buffer = [0u8; 223];
+    let bytes_written = message.to_payload(&mut buffer).unwrap();
+    assert_eq!(buffer[..6], [0x02, 0x12, 0xF1, 0x01, 0x00, 0x02]);
+    assert_eq!(
+        Pgn126208::from_payload(&buffer[..bytes_written]).unwrap(),
+        message
+    );
+}
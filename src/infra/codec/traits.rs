@@ -1,7 +1,9 @@
 //! Public traits exposed by the codec engine. They decouple generated
 //! PGN structures from the serialization/deserialization logic and provide
 //! a uniform API to upper layers.
-use crate::core::PgnValue;
+// Codec errors carry the offending `PgnValue` by value: the crate does not allocate.
+#![allow(clippy::result_large_err)]
+use crate::core::{FieldDescriptor, PgnDescriptor, PgnValue};
 use crate::error::{DeserializationError, SerializationError};
 
 //==================================================================================PGN_DATA
@@ -133,6 +135,52 @@ pub trait FieldAccess {
     fn set_repetitive_count(&mut self, _array_id: &'static str, _count: usize) -> Option<()> {
         None // Default: no repeating fields
     }
+
+    //==================== Group Function parameters ====================
+
+    /// Resolve the field a Group Function parameter stands for, to size and type
+    /// its `Variable` value.
+    ///
+    /// * `pgn` - PGN referenced by the message
+    /// * `index` - Parameter index (1-based CANboat order of the field)
+    /// * `selector` - Value given to parameter 1, selecting the variant of a polymorphic PGN
+    ///
+    /// Returns `Ok(None)` when `pgn` is unknown: the parameter stays unresolved.
+    ///
+    /// Default implementation returns `Ok(None)` (PGNs without `Variable` fields);
+    /// generated Group Functions resolve against the manifest.
+    fn variable_field(
+        &self,
+        _pgn: u32,
+        _index: u8,
+        _selector: Option<u64>,
+    ) -> Result<Option<&'static FieldDescriptor>, DeserializationError> {
+        Ok(None) // Default: nothing to resolve against
+    }
+}
+//==================================================================================PGN_METADATA
+/// Static metadata of a generated PGN type, taken from its descriptor.
+///
+/// Lets senders derive the PGN number and priority from the type itself, so a
+/// structure can never be sent under the identifier of another PGN.
+///
+/// ```rust, ignore
+/// assert_eq!(Pgn129025::PGN, 129025);
+/// assert_eq!(Pgn129025::DEFAULT_PRIORITY, 2);
+/// assert!(!Pgn129025::DESCRIPTOR.fastpacket);
+/// ```
+pub trait PgnMetadata {
+    /// Binary layout and transport metadata generated from CANboat.
+    ///
+    /// Polymorphic PGNs expose the descriptor of their first variant.
+    const DESCRIPTOR: PgnDescriptor;
+    /// Parameter Group Number.
+    const PGN: u32 = Self::DESCRIPTOR.id;
+    /// Priority recommended by the definition, 6 when the definition has none.
+    const DEFAULT_PRIORITY: u8 = match Self::DESCRIPTOR.priority {
+        Some(priority) => priority & 0x07,
+        None => 6,
+    };
 }
 //==================================================================================TO_PAYLOAD
 /// Serialize a data structure into a sequence of bytes.
@@ -3,6 +3,8 @@
 //! infrastructure modules (codec, CAN bus), protocol logic (address management,
 //! transport, messages), and a few prototypes.
 #![no_std]
+#[cfg(feature = "std")]
+extern crate std;
 //==================================================================================
 // use pgn::Pgn;
 //==================================================================================
@@ -11,9 +13,14 @@ pub mod core;
 /// Domain and low-level errors (CAN identifier construction, serialization,
 /// deserialization, and related issues).
 pub mod error;
+/// Log and gateway formats (candump, Actisense, Yacht Devices, SeaSmart, pcap)
+/// converting captures to and from timestamped CAN frames.
+pub mod formats;
 /// Representation of a raw NMEA 2000 frame as it is read from the CAN bus.
 pub mod infra;
 /// NMEA 2000 protocol implementation: CAN transport, fast packets,
 /// address management, and lookup tables.
 pub mod protocol;
+/// Timed replay of captured frames on any CAN bus.
+pub mod replay;
 //==================================================================================
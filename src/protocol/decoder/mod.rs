@@ -0,0 +1,184 @@
+//! Receive pipeline turning raw CAN frames into complete NMEA 2000 messages.
+//!
+//! [`N2kDecoder`] looks every PGN up in the generated descriptors: Fast Packet PGNs
+//! go through an internal [`FastPacketAssembler`], single-frame PGNs are passed
+//! straight through. The result is a [`RawMessage`] (identifier + payload), which
+//! can then be decoded into the generated [`N2kMessage`].
+//!
+//! PGNs missing from the manifest have no descriptor and are treated as single
+//! frames.
+//!
+//! ```rust,ignore
+//! let mut decoder = N2kDecoder::new();
+//! loop {
+//!     let message = decoder.recv(&mut can_bus, &timer).await?;
+//!     match message.decode() {
+//!         Ok(N2kMessage::Pgn129025(position)) => { /* … */ }
+//!         Ok(_) | Err(_) => {}
+//!     }
+//! }
+//! ```
+use crate::{
+    error::DeserializationError,
+    protocol::{
+        messages::N2kMessage,
+        transport::{
+            can_frame::CanFrame,
+            can_id::CanId,
+            fast_packet::{
+                assembler::{FastPacketAssembler, ProcessResult, DEFAULT_CONCURRENT_SESSIONS},
+                MAX_FAST_PACKET_PAYLOAD,
+            },
+            traits::{can_bus::CanBus, korri_timer::KorriTimer},
+        },
+    },
+};
+
+//==================================================================================Messages
+
+/// Complete message rebuilt from one or several CAN frames, not decoded yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawMessage {
+    /// Identifier of the frame that completed the message.
+    pub id: CanId,
+    /// Message payload; only the first `len` bytes are meaningful.
+    pub payload: [u8; MAX_FAST_PACKET_PAYLOAD],
+    /// Number of valid bytes in `payload`.
+    pub len: usize,
+}
+
+impl RawMessage {
+    /// PGN carried by the message.
+    pub fn pgn(&self) -> u32 {
+        self.id.pgn()
+    }
+
+    /// Valid part of the payload.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload[..self.len]
+    }
+
+    /// Decode the payload into the generated type matching its PGN.
+    #[allow(clippy::result_large_err)]
+    pub fn decode(&self) -> Result<N2kMessage, DeserializationError> {
+        N2kMessage::decode(self.pgn(), self.payload())
+    }
+}
+
+/// Message decoded into its generated type, with the identifier it arrived with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedMessage {
+    /// Identifier of the frame that completed the message (source, priority, destination).
+    pub id: CanId,
+    /// Decoded message.
+    pub message: N2kMessage,
+}
+
+//==================================================================================Decoder
+
+/// Routes incoming frames to the Fast Packet assembler or straight through.
+///
+/// `N` is the number of Fast Packet sessions reassembled in parallel.
+#[derive(Debug, Clone, Copy)]
+pub struct N2kDecoder<const N: usize = DEFAULT_CONCURRENT_SESSIONS> {
+    assembler: FastPacketAssembler<N>,
+}
+
+impl Default for N2kDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl N2kDecoder {
+    /// Create a decoder with the default Fast Packet session pool.
+    pub const fn new() -> Self {
+        Self::empty()
+    }
+}
+
+impl<const N: usize> N2kDecoder<N> {
+    /// Create a decoder with a pool of `N` Fast Packet sessions.
+    pub const fn empty() -> Self {
+        Self {
+            assembler: FastPacketAssembler::empty(),
+        }
+    }
+
+    /// Fast Packet assembler used internally, for pool statistics.
+    pub fn assembler(&self) -> &FastPacketAssembler<N> {
+        &self.assembler
+    }
+
+    /// Feed one received frame.
+    ///
+    /// * `frame` – frame read from the bus
+    /// * `now_ms` – monotonic timestamp of the reception, in milliseconds
+    ///
+    /// Returns the complete message when `frame` is a single-frame PGN or the
+    /// last fragment of a Fast Packet, `None` while a Fast Packet is incomplete
+    /// or when the frame was discarded.
+    pub fn process_frame(&mut self, frame: &CanFrame, now_ms: u64) -> Option<RawMessage> {
+        let is_fast_packet =
+            N2kMessage::descriptor(frame.id.pgn()).is_some_and(|descriptor| descriptor.fastpacket);
+
+        if !is_fast_packet {
+            let len = frame.len.min(frame.data.len());
+            let mut payload = [0; MAX_FAST_PACKET_PAYLOAD];
+            payload[..len].copy_from_slice(&frame.data[..len]);
+            return Some(RawMessage {
+                id: frame.id,
+                payload,
+                len,
+            });
+        }
+
+        match self.assembler.process_frame(frame.id, &frame.data, now_ms) {
+            ProcessResult::MessageComplete(message) => Some(RawMessage {
+                id: frame.id,
+                payload: message.payload,
+                len: message.len,
+            }),
+            ProcessResult::FragmentConsumed | ProcessResult::Ignored => None,
+        }
+    }
+
+    /// Feed one received frame and decode the message it completes, if any.
+    ///
+    /// Returns `None` while no message is complete, otherwise the decoding result.
+    pub fn decode_frame(
+        &mut self,
+        frame: &CanFrame,
+        now_ms: u64,
+    ) -> Option<Result<DecodedMessage, DeserializationError>> {
+        let raw = self.process_frame(frame, now_ms)?;
+        Some(raw.decode().map(|message| DecodedMessage {
+            id: raw.id,
+            message,
+        }))
+    }
+
+    /// Read frames from `can_bus` until a message is complete.
+    ///
+    /// Frames are timestamped with `timer.now_ms()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the bus error when reception fails.
+    pub async fn recv<C, T>(&mut self, can_bus: &mut C, timer: &T) -> Result<RawMessage, C::Error>
+    where
+        C: CanBus,
+        T: KorriTimer,
+    {
+        loop {
+            let frame = can_bus.recv().await?;
+            if let Some(message) = self.process_frame(&frame, timer.now_ms()) {
+                return Ok(message);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,90 @@
+//! Receive pipeline tests: single-frame passthrough, Fast Packet routing, decoding.
+// DECODER
+use super::*;
+use crate::{
+    infra::codec::traits::PgnData,
+    protocol::{
+        messages::{Pgn129025, Pgn129029},
+        transport::fast_packet::{builder::FastPacketBuilder, sequence::SequenceCounter},
+    },
+};
+
+/// Single CAN frame carrying `payload` for `pgn`.
+fn single_frame(pgn: u32, source: u8, payload: &[u8]) -> CanFrame {
+    let mut data = [0xFF; 8];
+    data[..payload.len()].copy_from_slice(payload);
+    CanFrame {
+        id: CanId::builder(pgn, source).build().unwrap(),
+        data,
+        len: payload.len(),
+    }
+}
+
+#[test]
+/// Single-frame PGNs come out immediately and decode into their type.
+fn test_single_frame_pgn_passes_through() {
+    let mut decoder = N2kDecoder::new();
+    let mut position = Pgn129025::new();
+    position.latitude = 47.5;
+    position.longitude = -3.25;
+    let mut buffer = [0u8; 8];
+    let len = position.to_payload(&mut buffer).unwrap();
+
+    let frame = single_frame(129025, 7, &buffer[..len]);
+    let decoded = decoder.decode_frame(&frame, 0).unwrap().unwrap();
+
+    assert_eq!(decoded.id.source_address(), 7);
+    assert!(matches!(decoded.message, N2kMessage::Pgn129025(p) if p == position));
+}
+
+#[test]
+/// Fast Packet PGNs are held until the last fragment arrives.
+fn test_fast_packet_pgn_is_reassembled() {
+    let mut decoder = N2kDecoder::new();
+    let mut gnss = Pgn129029::new();
+    gnss.latitude = 47.5;
+    gnss.longitude = -3.25;
+    let mut buffer = [0u8; MAX_FAST_PACKET_PAYLOAD];
+    let len = gnss.to_payload(&mut buffer).unwrap();
+
+    let mut completed = None;
+    let mut frames = 0;
+    for frame in FastPacketBuilder::new(
+        129029,
+        12,
+        None,
+        &buffer[..len],
+        &mut SequenceCounter::new(),
+    )
+    .build()
+    {
+        let frame = frame.unwrap();
+        frames += 1;
+        if let Some(message) = decoder.process_frame(&frame, 0) {
+            assert!(completed.is_none(), "message completed twice");
+            completed = Some(message);
+        }
+    }
+
+    let message = completed.expect("message never completed");
+    assert!(frames > 1);
+    assert_eq!(message.pgn(), 129029);
+    assert_eq!(message.id.source_address(), 12);
+    assert_eq!(message.payload(), &buffer[..len]);
+    assert!(matches!(message.decode(), Ok(N2kMessage::Pgn129029(p)) if p.latitude == 47.5));
+}
+
+#[test]
+/// PGNs outside the manifest are passed through raw and fail to decode.
+fn test_unknown_pgn_passes_through_raw() {
+    let mut decoder = N2kDecoder::new();
+    let frame = single_frame(65280, 3, &[1, 2, 3]);
+
+    let message = decoder.process_frame(&frame, 0).unwrap();
+    assert_eq!(message.pgn(), 65280);
+    assert_eq!(message.payload(), &[1, 2, 3]);
+    assert!(matches!(
+        decoder.decode_frame(&frame, 0),
+        Some(Err(DeserializationError::UnsupportedPgn { pgn: 65280 }))
+    ));
+}
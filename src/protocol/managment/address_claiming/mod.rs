@@ -10,6 +10,13 @@ use crate::{
 use futures_util::future::{select, Either};
 use futures_util::pin_mut;
 
+pub mod state_machine;
+
+pub use state_machine::{AddressClaimStateMachine, ClaimState};
+
+/// Listening window after each claim before the address is considered owned (ms).
+pub const ADDRESS_CLAIM_TIMEOUT_MS: u64 = 250;
+
 /// Execute a full address-claim cycle and return the acquired address.
 ///
 /// Strategy:
@@ -17,6 +24,9 @@ use futures_util::pin_mut;
 /// 2. If the equipment is Arbitrary Address Capable (AAC), iterate over the 128–247 range.
 /// 3. After each attempt, listen for competing claims for 250 ms.
 /// 4. Defend the address if the local NAME wins, otherwise move to the next one.
+///
+/// Async driver of [`AddressClaimStateMachine`]: frames and timeouts are fed to
+/// the state machine and the frames it returns are sent on `can_bus`.
 pub async fn claim_address<C: CanBus, T: KorriTimer>(
     can_bus: &mut C,
     timer: &mut T,
@@ -26,132 +36,82 @@ pub async fn claim_address<C: CanBus, T: KorriTimer>(
 where
     C::Error: core::fmt::Debug,
 {
-    // Determine AAC capabilities (bit 63 of the NAME).
-    let is_arbitrary_capable = (my_name >> 63) & 1 == 1;
-    // Iterate over allowed addresses (preferred, then 128-247).
-    let addr_iterator = AddressClaimIterator::new(preferred_address, is_arbitrary_capable);
-
-    for address_to_claim in addr_iterator {
-        // Step 1: propose our claim.
-        #[cfg(feature = "defmt")]
-        defmt::info!("Trying to claim address: {}", address_to_claim);
-
-        let claim_frame = build_address_claim_frame(my_name, address_to_claim)?;
-        can_bus
-            .send(&claim_frame)
-            .await
-            .map_err(ClaimError::SendError)?;
+    let mut machine = AddressClaimStateMachine::new(my_name, preferred_address);
+    drive_claim(can_bus, timer, &mut machine).await
+}
 
-        #[cfg(feature = "defmt")]
-        defmt::info!("Sent claim frame, waiting 250ms for conflicts...");
+/// Run `machine` on `can_bus` until it owns an address or gives up.
+///
+/// Starts the claim when the machine is idle and resumes it otherwise (e.g.
+/// after [`AddressClaimStateMachine::on_frame`] reported a lost address).
+pub(crate) async fn drive_claim<C: CanBus, T: KorriTimer>(
+    can_bus: &mut C,
+    timer: &mut T,
+    machine: &mut AddressClaimStateMachine,
+) -> Result<u8, ClaimError<C::Error>>
+where
+    C::Error: core::fmt::Debug,
+{
+    // Time only moves when a listening window elapses: the timer clock may
+    // never move, and received frames must not stretch a window.
+    let mut now_ms = timer.now_ms();
+
+    loop {
+        // Step 1: start the claim or close an elapsed listening window.
+        if let Some(frame) = machine.on_tick(now_ms) {
+            #[cfg(feature = "defmt")]
+            defmt::info!("Trying to claim address: {}", frame.id.source_address());
+            can_bus.send(&frame).await.map_err(ClaimError::SendError)?;
+        }
 
-        // Step 2: 250 ms listening window for conflicts.
-        let timer = timer.delay_ms(250);
-        pin_mut!(timer);
+        let window = machine.state();
+        let deadline_ms = match window {
+            ClaimState::Claiming { deadline_ms, .. } => deadline_ms,
+            ClaimState::Claimed { address } => {
+                #[cfg(feature = "defmt")]
+                defmt::info!("Timer expired, address {} claimed successfully!", address);
+                return Ok(address);
+            }
+            ClaimState::CannotClaim => return Ok(254),
+            ClaimState::Idle | ClaimState::NoAddressAvailable => {
+                return Err(ClaimError::NoAddressAvailable)
+            }
+        };
 
-        'listen_loop: loop {
-            let need_defense = {
+        // Step 2: listen for competing claims until the window closes. One
+        // delay covers the whole window, whatever the frames received meanwhile.
+        let delay = timer.delay_ms(deadline_ms.saturating_sub(now_ms) as u32);
+        pin_mut!(delay);
+        loop {
+            let incoming = {
                 let recv = can_bus.recv();
                 pin_mut!(recv);
-
-                match select(timer.as_mut(), recv).await {
-                    Either::Left(_) => {
-                        #[cfg(feature = "defmt")]
-                        defmt::info!(
-                            "Timer expired, address {} claimed successfully!",
-                            address_to_claim
-                        );
-                        return Ok(address_to_claim);
+                match select(delay.as_mut(), recv).await {
+                    Either::Left(_) => None,
+                    Either::Right((incoming_frame, _)) => {
+                        Some(incoming_frame.map_err(ClaimError::ReceiveError)?)
                     }
-
-                    Either::Right((incoming_frame, _)) => match incoming_frame {
-                        Ok(incoming_frame) => {
-                            // Ignore everything except Address Claim frames (PGN 60928)
-                            if incoming_frame.id.pgn() != 60928 {
-                                #[cfg(feature = "defmt")]
-                                defmt::trace!(
-                                    "Ignoring non-claim frame: PGN={}",
-                                    incoming_frame.id.pgn()
-                                );
-                                false
-                            } else {
-                                #[cfg(feature = "defmt")]
-                                defmt::debug!(
-                                    "Received claim frame: PGN={}, SA={}",
-                                    incoming_frame.id.pgn(),
-                                    incoming_frame.id.source_address()
-                                );
-
-                                let their_name = extract_name_from_claim(&incoming_frame)?;
-
-                                #[cfg(feature = "defmt")]
-                                defmt::debug!(
-                                    "Claim RX: SA={}, Their NAME={:#X}, My NAME={:#X}",
-                                    incoming_frame.id.source_address(),
-                                    their_name,
-                                    my_name
-                                );
-
-                                if is_conflicting_claim(&incoming_frame, address_to_claim, my_name)
-                                {
-                                    #[cfg(feature = "defmt")]
-                                    defmt::warn!(
-                                        "CONFLICT DETECTED! Their name: {:#X}, My name: {:#X}",
-                                        their_name,
-                                        my_name
-                                    );
-
-                                    if my_name > their_name {
-                                        #[cfg(feature = "defmt")]
-                                        defmt::warn!(
-                                            "I LOSE (higher name), trying next address..."
-                                        );
-
-                                        if is_arbitrary_capable {
-                                            // Lost arbitration, try the next address
-                                            break 'listen_loop;
-                                        } else {
-                                            return Ok(254);
-                                        }
-                                    } else {
-                                        #[cfg(feature = "defmt")]
-                                        defmt::info!("I WIN (lower name), defending address...");
-                                        true
-                                    }
-                                } else {
-                                    #[cfg(feature = "defmt")]
-                                    defmt::debug!("NOT a conflict (same NAME or different SA)");
-                                    false
-                                }
-                            }
-                        }
-
-                        Err(e) => {
-                            #[cfg(feature = "defmt")]
-                            defmt::error!("Receive error occurred");
-                            return Err(ClaimError::ReceiveError(e));
-                        }
-                    },
                 }
-            }; // recv borrow is dropped here
-
-            // Optional defensive transmission (outside the `recv` borrow scope).
-            if need_defense {
-                let defense_frame = build_address_claim_frame(my_name, address_to_claim)?;
-                can_bus
-                    .send(&defense_frame)
-                    .await
-                    .map_err(ClaimError::SendError)?;
+            };
+            let Some(incoming_frame) = incoming else {
+                now_ms = now_ms.max(deadline_ms);
+                break;
+            };
+            // Defence claim, or claim for the next candidate address.
+            if let Some(frame) = machine.on_frame(&incoming_frame, now_ms) {
+                can_bus.send(&frame).await.map_err(ClaimError::SendError)?;
+            }
+            // A new candidate address opens a new window.
+            if machine.state() != window {
+                break;
             }
         }
     }
-
-    // Iterator exhausted: no address available.
-    Err(ClaimError::NoAddressAvailable)
 }
 
 //==================================================================================ADDRESS_CLAIM_ITERATOR
 /// Generates candidate addresses following the J1939 rules.
+#[derive(Debug, Clone)]
 struct AddressClaimIterator {
     preferred: u8,
     next_arbitrary: u16,
@@ -159,7 +119,7 @@ struct AddressClaimIterator {
     arbitrary_capable: bool,
 }
 
-#[derive(PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 /// Iteration states (preferred address, then AAC range).
 enum AddressClaimState {
     TryPreferred,
@@ -250,15 +210,6 @@ pub fn build_address_claim_frame(
     })
 }
 
-/// Check whether an incoming claim frame conflicts with our current address.
-fn is_conflicting_claim(incoming_frame: &CanFrame, my_claimed_address: u8, my_name: u64) -> bool {
-    // All three conditions must be true for a conflict.
-    // The `&&` operator ensures every predicate is checked in one expression.
-    incoming_frame.id.pgn() == 60928
-        && incoming_frame.id.source_address() == my_claimed_address
-        && extract_name_from_claim(incoming_frame).is_ok_and(|their_name| their_name != my_name)
-}
-
 /// Extracts the NAME from an Address Claim frame (PGN 60928).
 pub(super) fn extract_name_from_claim(frame: &CanFrame) -> Result<u64, ExtractionError> {
     if frame.id.pgn() != 60928 {
@@ -0,0 +1,187 @@
+//! Poll-driven address-claim procedure, free of any executor or timer.
+//!
+//! [`AddressClaimStateMachine`] holds the whole claim/defend logic. The caller
+//! feeds it every received frame ([`on_frame`](AddressClaimStateMachine::on_frame))
+//! and the current time ([`on_tick`](AddressClaimStateMachine::on_tick)), and
+//! transmits the frames it returns. It fits RTIC tasks, interrupt handlers, and
+//! superloops alike; the async [`claim_address`](super::claim_address) and
+//! [`AddressManager`](crate::protocol::managment::address_manager::AddressManager)
+//! are thin wrappers around it.
+//!
+//! ```rust,ignore
+//! let mut claim = AddressClaimStateMachine::new(my_name, 42);
+//! loop {
+//!     let now = monotonic_ms();
+//!     if let Some(frame) = can.try_receive() {
+//!         if let Some(reply) = claim.on_frame(&frame, now) {
+//!             can.transmit(&reply);
+//!         }
+//!     }
+//!     if let Some(frame) = claim.on_tick(now) {
+//!         can.transmit(&frame);
+//!     }
+//!     if let Some(address) = claim.address() {
+//!         // … application traffic with `address` as source …
+//!     }
+//! }
+//! ```
+use crate::protocol::{
+    managment::address_claiming::{
+        build_address_claim_frame, extract_name_from_claim, AddressClaimIterator,
+        ADDRESS_CLAIM_TIMEOUT_MS,
+    },
+    transport::can_frame::CanFrame,
+};
+
+/// Progress of the claim procedure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimState {
+    /// Nothing sent yet; the next [`on_tick`](AddressClaimStateMachine::on_tick) starts the claim.
+    Idle,
+    /// A claim for `address` was sent; competing claims are watched until `deadline_ms`.
+    Claiming { address: u8, deadline_ms: u64 },
+    /// `address` is owned and defended against higher NAMEs.
+    Claimed { address: u8 },
+    /// A non arbitrary-address-capable node lost its only address (NULL address 254).
+    CannotClaim,
+    /// Every candidate address was lost to a lower NAME.
+    NoAddressAvailable,
+}
+
+/// Address claim and defence as a synchronous state machine.
+#[derive(Debug, Clone)]
+pub struct AddressClaimStateMachine {
+    my_name: u64,
+    preferred_address: u8,
+    candidates: AddressClaimIterator,
+    state: ClaimState,
+}
+
+impl AddressClaimStateMachine {
+    /// Prepare a claim for `preferred_address`; nothing is sent before the first tick.
+    ///
+    /// Bit 63 of `my_name` (Arbitrary Address Capable) allows falling back on
+    /// the 128–247 range.
+    pub fn new(my_name: u64, preferred_address: u8) -> Self {
+        Self {
+            my_name,
+            preferred_address,
+            candidates: AddressClaimIterator::new(preferred_address, is_arbitrary_capable(my_name)),
+            state: ClaimState::Idle,
+        }
+    }
+
+    /// Current progress of the procedure.
+    pub fn state(&self) -> ClaimState {
+        self.state
+    }
+
+    /// Owned address, once the listening window elapsed without losing.
+    pub fn address(&self) -> Option<u8> {
+        match self.state {
+            ClaimState::Claimed { address } => Some(address),
+            _ => None,
+        }
+    }
+
+    /// NAME announced in the claims.
+    pub fn name(&self) -> u64 {
+        self.my_name
+    }
+
+    /// Time at which [`on_tick`](Self::on_tick) must next be called, if any.
+    pub fn next_deadline(&self) -> Option<u64> {
+        match self.state {
+            ClaimState::Claiming { deadline_ms, .. } => Some(deadline_ms),
+            _ => None,
+        }
+    }
+
+    /// Advance the procedure to `now_ms` and return the frame to send, if any.
+    ///
+    /// Starts the claim on the first call and confirms the address once its
+    /// 250 ms listening window elapsed.
+    pub fn on_tick(&mut self, now_ms: u64) -> Option<CanFrame> {
+        match self.state {
+            ClaimState::Idle => self.claim_next(now_ms),
+            ClaimState::Claiming {
+                address,
+                deadline_ms,
+            } if now_ms >= deadline_ms => {
+                self.state = ClaimState::Claimed { address };
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Handle a received frame and return the frame to send, if any.
+    ///
+    /// Only well-formed Address Claims (PGN 60928) for the address being
+    /// claimed or owned matter: a higher NAME is answered with a defence
+    /// claim, a lower NAME makes the node move to its next candidate. A lost
+    /// owned address restarts the whole procedure from the preferred address.
+    pub fn on_frame(&mut self, frame: &CanFrame, now_ms: u64) -> Option<CanFrame> {
+        let address = match self.state {
+            ClaimState::Claiming { address, .. } | ClaimState::Claimed { address } => address,
+            _ => return None,
+        };
+        if frame.id.source_address() != address {
+            return None;
+        }
+        let their_name = extract_name_from_claim(frame).ok()?;
+
+        if their_name == self.my_name {
+            // Our own claim echoed back.
+            None
+        } else if self.my_name < their_name {
+            // Lowest NAME wins: defend the address.
+            Some(claim_frame(self.my_name, address))
+        } else if matches!(self.state, ClaimState::Claimed { .. }) {
+            self.restart(now_ms)
+        } else if is_arbitrary_capable(self.my_name) {
+            self.claim_next(now_ms)
+        } else {
+            self.state = ClaimState::CannotClaim;
+            None
+        }
+    }
+
+    /// Start over from the preferred address, as after losing an owned address.
+    pub fn restart(&mut self, now_ms: u64) -> Option<CanFrame> {
+        self.candidates =
+            AddressClaimIterator::new(self.preferred_address, is_arbitrary_capable(self.my_name));
+        self.claim_next(now_ms)
+    }
+
+    /// Claim the next candidate address, or give up when none is left.
+    fn claim_next(&mut self, now_ms: u64) -> Option<CanFrame> {
+        match self.candidates.next() {
+            Some(address) => {
+                self.state = ClaimState::Claiming {
+                    address,
+                    deadline_ms: now_ms.saturating_add(ADDRESS_CLAIM_TIMEOUT_MS),
+                };
+                Some(claim_frame(self.my_name, address))
+            }
+            None => {
+                self.state = ClaimState::NoAddressAvailable;
+                None
+            }
+        }
+    }
+}
+
+/// Bit 63 of the NAME: Arbitrary Address Capable.
+fn is_arbitrary_capable(name: u64) -> bool {
+    (name >> 63) & 1 == 1
+}
+
+fn claim_frame(my_name: u64, address: u8) -> CanFrame {
+    build_address_claim_frame(my_name, address)
+        .expect("PGN 60928 with destination 255 must always produce a valid CanId")
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,120 @@
+//! Address claim state machine tests: claim, defence, fallback, and exhaustion.
+// STATE_MACHINE
+use super::*;
+use crate::protocol::transport::can_id::CanId;
+
+const AAC: u64 = 1 << 63;
+
+fn claim_from(name: u64, address: u8) -> CanFrame {
+    build_address_claim_frame(name, address).unwrap()
+}
+
+#[test]
+/// The first tick sends the claim; the address is owned 250 ms later.
+fn test_claim_without_conflict() {
+    let mut machine = AddressClaimStateMachine::new(AAC | 0x10, 42);
+    assert_eq!(machine.state(), ClaimState::Idle);
+
+    let claim = machine.on_tick(1000).unwrap();
+    assert_eq!(claim.id.pgn(), 60928);
+    assert_eq!(claim.id.source_address(), 42);
+    assert_eq!(claim.data, (AAC | 0x10).to_le_bytes());
+    assert_eq!(machine.next_deadline(), Some(1250));
+
+    assert!(machine.on_tick(1249).is_none());
+    assert_eq!(machine.address(), None);
+    assert!(machine.on_tick(1250).is_none());
+    assert_eq!(machine.address(), Some(42));
+    assert_eq!(machine.next_deadline(), None);
+}
+
+#[test]
+/// A higher NAME is answered with a defence claim, while claiming and once owned.
+fn test_defends_against_higher_name() {
+    let mut machine = AddressClaimStateMachine::new(0x10, 42);
+    machine.on_tick(0);
+
+    let defence = machine.on_frame(&claim_from(0x20, 42), 100).unwrap();
+    assert_eq!(defence.id.source_address(), 42);
+    assert_eq!(
+        machine.next_deadline(),
+        Some(250),
+        "the window is not reset"
+    );
+
+    machine.on_tick(250);
+    assert!(machine.on_frame(&claim_from(0x20, 42), 300).is_some());
+    assert_eq!(machine.address(), Some(42));
+}
+
+#[test]
+/// Our own claim, other addresses, and other PGNs are ignored.
+fn test_ignores_unrelated_frames() {
+    let mut machine = AddressClaimStateMachine::new(0x10, 42);
+    machine.on_tick(0);
+
+    assert!(machine.on_frame(&claim_from(0x10, 42), 10).is_none());
+    assert!(machine.on_frame(&claim_from(0x01, 43), 10).is_none());
+
+    let mut truncated = claim_from(0x01, 42);
+    truncated.len = 4;
+    assert!(machine.on_frame(&truncated, 10).is_none());
+
+    let mut other_pgn = claim_from(0x01, 42);
+    other_pgn.id = CanId::builder(129025, 42).build().unwrap();
+    assert!(machine.on_frame(&other_pgn, 10).is_none());
+
+    assert!(matches!(
+        machine.state(),
+        ClaimState::Claiming { address: 42, .. }
+    ));
+}
+
+#[test]
+/// An AAC node losing to a lower NAME claims the next address at once.
+fn test_arbitrary_capable_moves_to_next_address() {
+    let mut machine = AddressClaimStateMachine::new(AAC | 0x20, 42);
+    machine.on_tick(0);
+
+    let next = machine.on_frame(&claim_from(0x10, 42), 100).unwrap();
+    assert_eq!(next.id.source_address(), 128);
+    assert_eq!(machine.next_deadline(), Some(350));
+
+    machine.on_tick(350);
+    assert_eq!(machine.address(), Some(128));
+}
+
+#[test]
+/// A node without AAC falls back to the NULL address.
+fn test_non_arbitrary_cannot_claim() {
+    let mut machine = AddressClaimStateMachine::new(0x20, 42);
+    machine.on_tick(0);
+
+    assert!(machine.on_frame(&claim_from(0x10, 42), 100).is_none());
+    assert_eq!(machine.state(), ClaimState::CannotClaim);
+    assert!(machine.on_tick(1000).is_none());
+}
+
+#[test]
+/// Losing an owned address restarts the claim from the preferred address.
+fn test_lost_address_restarts_claim() {
+    let mut machine = AddressClaimStateMachine::new(AAC | 0x20, 42);
+    machine.on_tick(0);
+    machine.on_tick(250);
+
+    let reclaim = machine.on_frame(&claim_from(0x10, 42), 1000).unwrap();
+    assert_eq!(reclaim.id.source_address(), 42);
+    assert_eq!(machine.address(), None);
+    assert_eq!(machine.next_deadline(), Some(1250));
+}
+
+#[test]
+/// Every candidate lost: the procedure gives up.
+fn test_exhausts_candidates() {
+    let mut machine = AddressClaimStateMachine::new(AAC | 0xFFFF, 128);
+    let mut frame = machine.on_tick(0);
+    while let Some(claim) = frame {
+        frame = machine.on_frame(&claim_from(0x01, claim.id.source_address()), 0);
+    }
+    assert_eq!(machine.state(), ClaimState::NoAddressAvailable);
+}
@@ -1,33 +1,72 @@
 //! Automated lifecycle management for NMEA 2000 logical addresses:
 //! initial claim, conflict detection, defense, and reclaim.
+//!
+//! Connection-mode transport sessions (TP.CM / TP.DT) are opt-in: a manager
+//! built with `TP_SESSIONS > 0` answers the sessions addressed to its current
+//! address, so larger point-to-point transfers work without extra plumbing.
+//!
+//! ```rust,ignore
+//! let mut node: AddressManager<_, _, 2> =
+//!     AddressManager::claim(bus, timer, my_name, 42).await?;
+//! node.send_transport(126208, 35, &command).await?;
+//! ```
 use crate::{
-    error::{ClaimError, SendPgnError},
-    infra::codec::traits::PgnData,
+    error::{ClaimError, SendPgnError, TransportError},
+    infra::codec::traits::{PgnData, PgnMetadata},
     protocol::{
-        managment::address_claiming::claim_address,
+        managment::address_claiming::{drive_claim, AddressClaimStateMachine, ClaimState},
         transport::{
             can_frame::CanFrame,
-            can_id::CanId,
-            fast_packet::builder::FastPacketBuilder,
-            traits::{can_bus::CanBus, korri_timer::KorriTimer, pgn_sender::PgnSender},
-            FAST_PACKET_INTER_FRAME_DELAY_MS,
+            fast_packet::{sequence::SequenceCounter, transmission::PgnTransmission},
+            iso_tp::{
+                assembler::CompletedMessage,
+                connection::{ProcessResult, SessionTable},
+                TP_CM_PGN, TP_DT_INTER_PACKET_DELAY_MS, TP_DT_PGN, TP_T3_TIMEOUT_MS,
+            },
+            traits::{
+                can_bus::CanBus,
+                korri_timer::KorriTimer,
+                pgn_sender::{send_transmission, PgnSender},
+            },
         },
     },
 };
+use futures_util::{
+    future::{select, Either},
+    pin_mut,
+};
+
+//==================================================================================Constants
+/// Application frames kept while [`AddressManager::send_transport`] waits for
+/// its peer; they are handed out by the next [`AddressManager::recv`] calls.
+pub const MAX_PENDING_FRAMES: usize = 8;
 
+//==================================================================================AddressManager
 /// NMEA2000/J1939-compliant address manager.
 /// Handles address defense and automatic reclaim.
-pub struct AddressManager<C: CanBus, T: KorriTimer> {
+///
+/// `TP_SESSIONS` is the number of connection-mode transport sessions handled
+/// in parallel. The default, zero, leaves TP.CM / TP.DT frames to the
+/// application and embeds no session buffer.
+pub struct AddressManager<C: CanBus, T: KorriTimer, const TP_SESSIONS: usize = 0> {
     /// CAN bus implementation used to send/receive frames.
     can_bus: C,
     /// Asynchronous timer enforcing delays between claim attempts.
     timer: T,
-    /// Node NAME identifier (64 bits).
-    my_name: u64,
-    /// Preferred address used during the initial claim and subsequent reclaims.
-    preferred_address: u8,
-    /// Active address currently owned by the node.
-    current_address: u8,
+    /// Claim/defence logic for the node NAME and its preferred address.
+    claim: AddressClaimStateMachine,
+    /// Fast Packet sequence identifiers of the messages sent by this node.
+    sequences: SequenceCounter,
+    /// Application frames received during a transport transfer.
+    pending_frames: Queue<CanFrame, MAX_PENDING_FRAMES>,
+    /// Application frames lost because `pending_frames` was full.
+    dropped_frames: u32,
+    /// TP.CM_Abort frames of timed-out sessions that could not be built.
+    unsent_aborts: u32,
+    /// Connection-mode transport sessions (RTS/CTS) in both directions.
+    transport: SessionTable<TP_SESSIONS>,
+    /// Transport messages received and not yet taken by the application.
+    transport_inbox: Queue<CompletedMessage, TP_SESSIONS>,
 }
 
 impl<C: CanBus, T: KorriTimer> AddressManager<C, T>
@@ -38,28 +77,59 @@ where
     ///
     /// This async constructor waits until a valid address is claimed or an unrecoverable
     /// CAN bus error occurs. It only returns once the claim succeeds or fails definitively.
+    ///
+    /// The manager handles no transport session; use [`claim`](Self::claim)
+    /// with `TP_SESSIONS > 0` to answer them.
     pub async fn new(
+        can_bus: C,
+        timer: T,
+        my_name: u64,
+        preferred_address: u8,
+    ) -> Result<Self, ClaimError<C::Error>> {
+        Self::claim(can_bus, timer, my_name, preferred_address).await
+    }
+}
+
+impl<C: CanBus, T: KorriTimer, const TP_SESSIONS: usize> AddressManager<C, T, TP_SESSIONS>
+where
+    C::Error: core::fmt::Debug,
+{
+    /// Perform the initial claim, for a manager of any transport capacity.
+    ///
+    /// Same as [`new`](AddressManager::new); the session count comes from the
+    /// annotated type, e.g. `AddressManager<_, _, 2>`.
+    pub async fn claim(
         mut can_bus: C,
         mut timer: T,
         my_name: u64,
         preferred_address: u8,
     ) -> Result<Self, ClaimError<C::Error>> {
         // Perform the initial claim
-        let current_address =
-            claim_address(&mut can_bus, &mut timer, my_name, preferred_address).await?;
+        let mut claim = AddressClaimStateMachine::new(my_name, preferred_address);
+        drive_claim(&mut can_bus, &mut timer, &mut claim).await?;
 
         Ok(Self {
             can_bus,
             timer,
-            my_name,
-            preferred_address,
-            current_address,
+            claim,
+            sequences: SequenceCounter::new(),
+            pending_frames: Queue::new(),
+            dropped_frames: 0,
+            unsent_aborts: 0,
+            transport: SessionTable::empty(),
+            transport_inbox: Queue::new(),
         })
     }
 
     /// Return the address currently held by the manager.
+    ///
+    /// 254 when the node cannot claim any address, 255 while it has none.
     pub fn current_address(&self) -> u8 {
-        self.current_address
+        match self.claim.state() {
+            ClaimState::Claimed { address } => address,
+            ClaimState::CannotClaim => 254,
+            _ => 255,
+        }
     }
 
     /// Send a frame on the CAN bus using the current address as source.
@@ -83,36 +153,233 @@ where
         pgn: u32,
         destination: Option<u8>,
     ) -> Result<(), SendPgnError<C::Error>> {
-        let source_address = self.current_address;
+        let source_address = self.current_address();
+        self.can_bus
+            .send_pgn(
+                pgn_data,
+                pgn,
+                source_address,
+                destination,
+                &mut self.sequences,
+                &mut self.timer,
+            )
+            .await
+    }
+
+    /// Send a generated PGN type; the PGN number and priority come from its
+    /// [`PgnMetadata`] instead of separate arguments.
+    ///
+    /// Returns [`SendPgnError`] when serialization, Fast Packet construction,
+    /// or CAN bus transmission fails.
+    pub async fn send_message<P: PgnData + PgnMetadata>(
+        &mut self,
+        pgn_data: &P,
+        destination: Option<u8>,
+    ) -> Result<(), SendPgnError<C::Error>> {
+        let source_address = self.current_address();
         self.can_bus
-            .send_pgn(pgn_data, pgn, source_address, destination, &mut self.timer)
+            .send_message(
+                pgn_data,
+                source_address,
+                destination,
+                &mut self.sequences,
+                &mut self.timer,
+            )
             .await
     }
 
+    /// Send `payload` (9 to 1785 bytes) to `destination` through a
+    /// connection-mode transport session (RTS/CTS).
+    ///
+    /// Waits until the peer acknowledges the whole message, aborts, or a
+    /// T3/T4 timeout elapses. Incoming frames are processed meanwhile
+    /// (address claims, other transport sessions); up to
+    /// [`MAX_PENDING_FRAMES`] application frames received during the transfer
+    /// are kept for [`recv`](Self::recv), later ones are counted by
+    /// [`dropped_frames`](Self::dropped_frames).
+    ///
+    /// Returns [`TransportError::Session`] with the typed abort reason when
+    /// the transfer fails, or [`TpSessionError::NoSessionAvailable`] when the
+    /// manager handles no transport session.
+    ///
+    /// [`TpSessionError::NoSessionAvailable`]: crate::error::TpSessionError::NoSessionAvailable
+    pub async fn send_transport(
+        &mut self,
+        pgn: u32,
+        destination: u8,
+        payload: &[u8],
+    ) -> Result<(), TransportError<C::Error>> {
+        let source_address = self.current_address();
+        let rts = self.transport.open_transfer(
+            pgn,
+            source_address,
+            destination,
+            payload,
+            self.timer.now_ms(),
+        )?;
+        self.can_bus
+            .send(&rts)
+            .await
+            .map_err(TransportError::Send)?;
+
+        loop {
+            // Step 1: enforce T1–T4, our own transfer included.
+            let now_ms = self.timer.now_ms();
+            while let Some(result) = self.transport.poll_timeouts(now_ms)? {
+                if let Some(reply) = result.reply() {
+                    self.can_bus
+                        .send(reply)
+                        .await
+                        .map_err(TransportError::Send)?;
+                }
+                if let ProcessResult::Aborted {
+                    peer,
+                    pgn: aborted_pgn,
+                    error,
+                    ..
+                } = result
+                {
+                    if peer == destination && aborted_pgn == pgn {
+                        return Err(error.into());
+                    }
+                }
+            }
+
+            // Step 2: wait for a frame until the nearest deadline.
+            let wait_ms = self
+                .transport
+                .next_deadline()
+                .map_or(TP_T3_TIMEOUT_MS, |deadline| deadline.saturating_sub(now_ms))
+                + 1;
+            let frame = {
+                let recv = self.can_bus.recv();
+                pin_mut!(recv);
+                let delay = self.timer.delay_ms(wait_ms as u32);
+                pin_mut!(delay);
+
+                match select(recv, delay).await {
+                    Either::Left((frame, _)) => frame.map_err(TransportError::Receive)?,
+                    Either::Right(_) => continue,
+                }
+            };
+
+            if !self.is_transport_frame(&frame) {
+                if let Some(app_frame) = self
+                    .handle_frame(&frame)
+                    .await
+                    .map_err(TransportError::Send)?
+                {
+                    if self.pending_frames.push(app_frame).is_err() {
+                        self.dropped_frames = self.dropped_frames.saturating_add(1);
+                    }
+                }
+                continue;
+            }
+
+            // Step 3: advance the sessions.
+            match self.process_transport_frame(&frame).await? {
+                ProcessResult::TransmitReady { destination: peer } if peer == destination => {
+                    let mut is_first = true;
+                    while let Some(packet) = self.transport.next_data_frame(
+                        source_address,
+                        destination,
+                        self.timer.now_ms(),
+                    )? {
+                        if !is_first {
+                            self.timer.delay_ms(TP_DT_INTER_PACKET_DELAY_MS).await;
+                        }
+                        self.can_bus
+                            .send(&packet)
+                            .await
+                            .map_err(TransportError::Send)?;
+                        is_first = false;
+                    }
+                }
+                ProcessResult::TransferComplete {
+                    pgn: done_pgn,
+                    destination: peer,
+                } if peer == destination && done_pgn == pgn => return Ok(()),
+                ProcessResult::Aborted {
+                    peer,
+                    pgn: aborted_pgn,
+                    error,
+                    ..
+                } if peer == destination && aborted_pgn == pgn => return Err(error.into()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Take the oldest message received through a connection-mode transport session.
+    ///
+    /// Up to `TP_SESSIONS` messages wait here; while the inbox is full, new
+    /// transfers are refused with [`TpAbortReason::ResourcesUnavailable`].
+    ///
+    /// [`TpAbortReason::ResourcesUnavailable`]: crate::error::TpAbortReason::ResourcesUnavailable
+    pub fn take_transport_message(&mut self) -> Option<CompletedMessage> {
+        self.transport_inbox.pop()
+    }
+
+    /// Application frames dropped because they arrived during a transport
+    /// transfer while [`MAX_PENDING_FRAMES`] were already waiting.
+    pub fn dropped_frames(&self) -> u32 {
+        self.dropped_frames
+    }
+
+    /// Timed-out transport sessions released without their TP.CM_Abort,
+    /// because the frame could not be addressed.
+    pub fn unsent_aborts(&self) -> u32 {
+        self.unsent_aborts
+    }
+
+    /// Abort every transport session whose T1–T4 timer elapsed at `now_ms`
+    /// and send the matching TP.CM_Abort frames.
+    ///
+    /// [`recv`](Self::recv) calls it while waiting; applications that feed
+    /// frames through [`handle_frame`](Self::handle_frame) call it periodically.
+    pub async fn poll_timeouts(&mut self, now_ms: u64) -> Result<(), C::Error> {
+        loop {
+            match self.transport.poll_timeouts(now_ms) {
+                Ok(Some(result)) => {
+                    if let Some(reply) = result.reply() {
+                        self.can_bus.send(reply).await?;
+                    }
+                }
+                // The session is released; only its abort frame is lost.
+                Err(_) => self.unsent_aborts = self.unsent_aborts.saturating_add(1),
+                Ok(None) => return Ok(()),
+            }
+        }
+    }
+
     /// Process an incoming frame and apply address management rules.
     ///
     /// Returns `Ok(Some(frame))` for application frames or `Ok(None)` for consumed
-    /// frames (claim/defense).
+    /// frames (claim/defense, transport sessions addressed to us). A completed
+    /// transport message is kept until [`take_transport_message`](Self::take_transport_message).
     pub async fn handle_frame(&mut self, frame: &CanFrame) -> Result<Option<CanFrame>, C::Error> {
         // Check if this is a claim frame targeting our address
-        if frame.id.pgn() == 60928
-            && frame.id.source_address() == self.current_address
-            && frame.len == 8
-        {
-            let their_name = u64::from_le_bytes(frame.data);
-
-            // In J1939/NMEA2000 the lowest NAME wins
-            if self.my_name > their_name {
+        if frame.id.pgn() == 60928 && frame.id.source_address() == self.current_address() {
+            // The state machine defends the address (lowest NAME wins) or
+            // starts over when we lose it; our own NAME is ignored.
+            let now_ms = self.timer.now_ms();
+            if let Some(reply) = self.claim.on_frame(frame, now_ms) {
+                self.can_bus.send(&reply).await?;
+            }
+            if self.claim.address().is_none() {
                 // We lose, reclaim a new address
                 self.reclaim().await.ok();
-                Ok(None)
-            } else if their_name != self.my_name {
-                // We win, defend our address
-                self.defend().await?;
-                Ok(None)
-            } else {
-                // Same NAME (ours), ignore
-                Ok(None)
+            }
+            Ok(None)
+        } else if self.is_transport_frame(frame) {
+            // Transport session addressed to us: answer it automatically
+            let now_ms = self.timer.now_ms();
+            self.poll_timeouts(now_ms).await?;
+            match self.process_transport_frame(frame).await {
+                Ok(_) => Ok(None),
+                Err(TransportError::Send(err) | TransportError::Receive(err)) => Err(err),
+                // No reply could be addressed: the frame is dropped.
+                Err(TransportError::Session(_)) => Ok(None),
             }
         } else {
             // Regular frame, forward to the application
@@ -121,29 +388,99 @@ where
     }
 
     /// Blocking receive loop that filters out address management frames.
+    ///
+    /// Frames kept during [`send_transport`](Self::send_transport) come first.
+    /// While transport sessions are open, their timeouts are enforced even if
+    /// the bus stays silent.
+    ///
+    /// Returns `Ok(None)` when a connection-mode transport message completed;
+    /// fetch it with [`take_transport_message`](Self::take_transport_message).
     pub async fn recv(&mut self) -> Result<Option<CanFrame>, C::Error> {
+        if let Some(frame) = self.pending_frames.pop() {
+            return Ok(Some(frame));
+        }
+
         loop {
-            let frame = self.can_bus.recv().await?;
+            let now_ms = self.timer.now_ms();
+            self.poll_timeouts(now_ms).await?;
+
+            let frame = match self.transport.next_deadline() {
+                None => self.can_bus.recv().await?,
+                Some(deadline) => {
+                    let wait_ms = deadline.saturating_sub(now_ms) + 1;
+                    let recv = self.can_bus.recv();
+                    pin_mut!(recv);
+                    let delay = self.timer.delay_ms(wait_ms as u32);
+                    pin_mut!(delay);
+
+                    match select(recv, delay).await {
+                        Either::Left((frame, _)) => frame?,
+                        Either::Right(_) => continue,
+                    }
+                }
+            };
+
             if let Some(app_frame) = self.handle_frame(&frame).await? {
                 return Ok(Some(app_frame));
             }
+            if !self.transport_inbox.is_empty() {
+                return Ok(None);
+            }
             // Otherwise it was absorbed by address management, continue listening
         }
     }
 
-    /// Re-issue a claim to defend the current address (PGN 60928).
-    async fn defend(&mut self) -> Result<(), C::Error> {
-        let claim_frame = CanFrame {
-            id: CanId::builder(60928, self.current_address)
-                .to_destination(255)
-                .with_priority(6)
-                .build()
-                .expect("PGN 60928 with destination 255 must always produce a valid CanId"),
-            data: self.my_name.to_le_bytes(),
-            len: 8,
-        };
+    /// True for TP.CM / TP.DT frames addressed to the current address, when
+    /// the manager handles transport sessions.
+    fn is_transport_frame(&self, frame: &CanFrame) -> bool {
+        TP_SESSIONS > 0
+            && matches!(frame.id.pgn(), TP_CM_PGN | TP_DT_PGN)
+            && frame.id.destination() == Some(self.current_address())
+    }
 
-        self.can_bus.send(&claim_frame).await
+    /// Feed a transport frame to the session table and send its reply.
+    ///
+    /// A completed message moves to the inbox and is reported as
+    /// [`ProcessResult::Consumed`]. New transfers are refused while the inbox
+    /// could not hold their message; a peer restarting its transfer keeps
+    /// its session.
+    async fn process_transport_frame(
+        &mut self,
+        frame: &CanFrame,
+    ) -> Result<ProcessResult, TransportError<C::Error>> {
+        if self.transport_inbox.len() + self.transport.receiving_sessions() >= TP_SESSIONS
+            && !self
+                .transport
+                .restarts_transfer(frame, self.current_address())
+        {
+            if let Some(refusal) =
+                SessionTable::<TP_SESSIONS>::refuse_request(frame, self.current_address())?
+            {
+                self.can_bus
+                    .send(&refusal)
+                    .await
+                    .map_err(TransportError::Send)?;
+                return Ok(ProcessResult::Consumed);
+            }
+        }
+
+        let result =
+            self.transport
+                .process_frame(frame, self.current_address(), self.timer.now_ms())?;
+        if let Some(reply) = result.reply() {
+            self.can_bus
+                .send(reply)
+                .await
+                .map_err(TransportError::Send)?;
+        }
+        match result {
+            ProcessResult::MessageComplete { message, .. } => {
+                // Room was checked when the transfer was accepted.
+                let _ = self.transport_inbox.push(message);
+                Ok(ProcessResult::Consumed)
+            }
+            other => Ok(other),
+        }
     }
 
     /// Send a pre-built payload using the current logical address.
@@ -154,44 +491,68 @@ where
         destination: Option<u8>,
         payload: &[u8],
     ) -> Result<(), SendPgnError<C::Error>> {
-        let source_address = self.current_address;
-        let builder = FastPacketBuilder::new(pgn, source_address, destination, payload);
-        let mut is_first = true;
-
-        for frame in builder.build() {
-            let mut frame = frame.map_err(SendPgnError::Build)?;
-            frame.id.0 = (frame.id.0 & !(0x7 << 26)) | (((priority & 0x07) as u32) << 26);
+        let transmission = PgnTransmission::from_payload(
+            pgn,
+            self.current_address(),
+            destination,
+            payload,
+            &mut self.sequences,
+        )
+        .map_err(SendPgnError::Build)?
+        .with_priority(priority);
+        send_transmission(&mut self.can_bus, &mut self.timer, transmission).await
+    }
 
-            if !is_first && payload.len() > 8 {
-                self.timer.delay_ms(FAST_PACKET_INTER_FRAME_DELAY_MS).await;
-            }
+    /// Attempt to acquire a new address after losing the previous one.
+    async fn reclaim(&mut self) -> Result<u8, ClaimError<C::Error>> {
+        // Resume the claim started by the state machine
+        drive_claim(&mut self.can_bus, &mut self.timer, &mut self.claim).await
+    }
+}
 
-            self.can_bus
-                .send(&frame)
-                .await
-                .map_err(SendPgnError::Send)?;
+//==================================================================================Queue
+/// Fixed-capacity FIFO keeping items until the application takes them.
+struct Queue<T, const N: usize> {
+    items: [Option<T>; N],
+    head: usize,
+    len: usize,
+}
 
-            is_first = false;
+impl<T, const N: usize> Queue<T, N> {
+    fn new() -> Self {
+        Self {
+            items: core::array::from_fn(|_| None),
+            head: 0,
+            len: 0,
         }
+    }
 
-        Ok(())
+    fn len(&self) -> usize {
+        self.len
     }
 
-    /// Attempt to acquire a new address after losing the previous one.
-    async fn reclaim(&mut self) -> Result<(), ClaimError<C::Error>> {
-        // Move to the NULL address temporarily
-        self.current_address = 255;
-
-        // Reclaim a new address
-        let new_address = claim_address(
-            &mut self.can_bus,
-            &mut self.timer,
-            self.my_name,
-            self.preferred_address,
-        )
-        .await?;
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 
-        self.current_address = new_address;
+    /// Append `item`, or hand it back when the queue is full.
+    fn push(&mut self, item: T) -> Result<(), T> {
+        if self.len == N {
+            return Err(item);
+        }
+        self.items[(self.head + self.len) % N] = Some(item);
+        self.len += 1;
         Ok(())
     }
+
+    /// Remove the oldest item.
+    fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let item = self.items[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        item
+    }
 }
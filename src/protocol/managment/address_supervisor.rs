@@ -18,7 +18,7 @@ use embassy_sync::{
 use futures_util::{future::select, future::Either, pin_mut};
 
 use crate::error::{ClaimError, SendPgnError};
-use crate::infra::codec::traits::PgnData;
+use crate::infra::codec::traits::{PgnData, PgnMetadata};
 use crate::protocol::managment::address_manager::AddressManager;
 use crate::protocol::transport::can_frame::CanFrame;
 use crate::protocol::transport::fast_packet::MAX_FAST_PACKET_PAYLOAD;
@@ -142,17 +142,13 @@ where
                         pin_mut!(recv_future);
 
                         match select(recv_future, cmd_future).await {
-                            Either::Left((result, pending_cmd)) => {
-                                match result {
-                                    Ok(Some(frame)) => frame_to_forward = Some(frame),
-                                    Ok(None) => {}
-                                    Err(err) => recv_error = Some(err),
-                                }
-                                drop(pending_cmd);
-                            }
-                            Either::Right((command, pending_recv)) => {
+                            Either::Left((result, _)) => match result {
+                                Ok(Some(frame)) => frame_to_forward = Some(frame),
+                                Ok(None) => {}
+                                Err(err) => recv_error = Some(err),
+                            },
+                            Either::Right((command, _)) => {
                                 command_to_process = Some(command);
-                                drop(pending_recv);
                             }
                         }
                     }
@@ -225,6 +221,16 @@ impl<'a, const CMD_CAP: usize> AddressHandle<'a, CMD_CAP> {
         self.sender.send(command).await;
         Ok(())
     }
+
+    /// Queue a generated PGN type, using the PGN number and default priority of the type.
+    pub async fn send_message<P: PgnData + PgnMetadata>(
+        &self,
+        pgn_data: &P,
+        destination: Option<u8>,
+    ) -> Result<(), AddressHandleError> {
+        self.send_pgn(pgn_data, P::PGN, P::DEFAULT_PRIORITY, destination)
+            .await
+    }
 }
 
 /// Optional receiver returning application frames filtered by the supervisor.
@@ -239,6 +245,8 @@ impl<'a, const FRAME_CAP: usize> AddressFrames<'a, FRAME_CAP> {
 }
 
 /// Commands queued by producer tasks.
+// Payloads travel by value through the channel: the crate does not allocate.
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone)]
 pub enum SupervisorCommand {
     SendFrame(CanFrame),
@@ -18,7 +18,6 @@
 //! Bit  63     ( 1 bit ) : Arbitrary Address Capable
 //! ```
 
-use crate::protocol::lookups::*;
 use crate::protocol::messages::Pgn60928;
 use core::fmt;
 
@@ -98,7 +97,7 @@ impl IsoName {
     /// Full 8-bit instance (merge of upper and lower parts).
     #[inline]
     pub const fn device_instance(&self) -> u8 {
-        (self.device_instance_lower() | (self.device_instance_upper() << 3)) & 0xFF
+        self.device_instance_lower() | (self.device_instance_upper() << 3)
     }
 
     /// Device function (bits 40-47, 8 bits).
@@ -163,48 +162,19 @@ impl From<IsoName> for u64 {
 }
 
 impl From<Pgn60928> for IsoName {
-    /// Convert a generated PGN 60928 structure into a compact `IsoName`.
-    ///
-    /// The reserved bit is forced to `false` because it is not exposed by the PGN.
-    /// Note: `device_function` is an INDIRECT_LOOKUP stored as `u8` inside `Pgn60928`.
+    /// Extract the NAME carried by a PGN 60928 address claim.
+    #[inline]
     fn from(pgn: Pgn60928) -> Self {
-        Self::builder()
-            .unique_number(pgn.unique_number)
-            .manufacturer_code(u16::from(pgn.manufacturer_code))
-            .device_instance_lower(pgn.device_instance_lower)
-            .device_instance_upper(pgn.device_instance_upper)
-            .device_function(pgn.device_function) // device_function est u8 (INDIRECT_LOOKUP)
-            .spare(false) // Reserved field, always false
-            .device_class(u8::from(pgn.device_class))
-            .system_instance(pgn.system_instance)
-            .industry_group(u8::from(pgn.industry_group))
-            .arbitrary_address_capable(pgn.arbitrary_address_capable != YesNo::No)
-            .build()
+        pgn.name
     }
 }
 
 impl From<IsoName> for Pgn60928 {
-    /// Convert a compact `IsoName` into the generated PGN 60928 structure.
-    ///
-    /// Uses `new()` to ensure the reserved field is initialized.
-    /// Note: `device_function` is an INDIRECT_LOOKUP stored as `u8` in `Pgn60928`.
+    /// Build the PGN 60928 address claim announcing `name`.
+    #[inline]
     fn from(name: IsoName) -> Self {
         let mut pgn = Pgn60928::new();
-        pgn.unique_number = name.unique_number();
-        pgn.manufacturer_code =
-            ManufacturerCode::try_from(name.manufacturer_code()).unwrap_or_default();
-        pgn.device_instance_lower = name.device_instance_lower();
-        pgn.device_instance_upper = name.device_instance_upper();
-        pgn.device_function = name.device_function(); // device_function stays as u8 (INDIRECT_LOOKUP)
-                                                      // Reserved field remains zero thanks to `new()`.
-        pgn.device_class = DeviceClass::try_from(name.device_class()).unwrap_or_default();
-        pgn.system_instance = name.system_instance();
-        pgn.industry_group = IndustryCode::try_from(name.industry_group()).unwrap_or_default();
-        pgn.arbitrary_address_capable = if name.is_arbitrary_address_capable() {
-            YesNo::Yes
-        } else {
-            YesNo::No
-        };
+        pgn.name = name;
         pgn
     }
 }
@@ -493,111 +463,22 @@ mod tests {
     }
 
     #[test]
-    fn test_pgn60928_to_isoname_conversion() {
-        // Create a Pgn60928 with known values (using valid enum values)
-        // Note: device_function is an INDIRECT_LOOKUP encoded across two u8 values
-        let mut pgn = Pgn60928::new();
-        pgn.unique_number = 123456;
-        pgn.manufacturer_code = ManufacturerCode::try_from(69).unwrap(); // ArksEnterprisesInc
-        pgn.device_instance_lower = 3;
-        pgn.device_instance_upper = 5;
-        // Use the helper to assign DeviceFunction
-        pgn.set_device_function(DeviceFunction::Diagnostic); // 2690 = 0x0A82
-        pgn.device_class = DeviceClass::try_from(25).unwrap(); // InternetworkDevice
-        pgn.system_instance = 7;
-        pgn.arbitrary_address_capable = YesNo::Yes;
-
-        // Convert to IsoName
-        let iso_name: IsoName = pgn.into();
-
-        // Verify all fields match (device_function corresponds to the low byte)
-        assert_eq!(iso_name.unique_number(), 123456);
-        assert_eq!(iso_name.manufacturer_code(), 69);
-        assert_eq!(iso_name.device_instance_lower(), 3);
-        assert_eq!(iso_name.device_instance_upper(), 5);
-        assert_eq!(iso_name.device_function(), 0x82); // 2690 = 0x0A82, low byte = 0x82 = 130
-        assert!(!iso_name.spare()); // spare is always false from Pgn60928
-        assert_eq!(iso_name.device_class(), 25);
-        assert_eq!(iso_name.system_instance(), 7);
-        assert!(iso_name.is_arbitrary_address_capable());
-    }
-
-    #[test]
-    fn test_isoname_to_pgn60928_conversion() {
-        // Create an IsoName with known values (using u8 for device_function)
-        // Since ISO NAME only uses 8 bits for device_function, use a value < 256
+    fn test_pgn60928_isoname_round_trip() {
+        // Spare bit and a manufacturer code absent from the lookup survive the trip.
         let iso_name = IsoName::builder()
             .unique_number(654321)
-            .manufacturer_code(78) // FwMurphyEnovationControls
-            .device_instance_lower(7)
-            .device_instance_upper(15)
-            .device_function(200) // Raw value (no standard DeviceFunction < 256)
+            .manufacturer_code(0x7FE)
+            .device_instance(0xFF)
+            .device_function(200)
             .spare(true)
-            .device_class(20) // SafetySystems
+            .device_class(20)
             .system_instance(10)
-            .industry_group(4) // MarineIndustry
+            .industry_group(4)
             .arbitrary_address_capable(false)
             .build();
 
-        // Convert to Pgn60928
         let pgn: Pgn60928 = iso_name.into();
-
-        // Verify all fields match (spare is private and not tested)
-        assert_eq!(pgn.unique_number, 654321);
-        assert_eq!(u16::from(pgn.manufacturer_code), 78);
-        assert_eq!(pgn.device_instance_lower, 7);
-        assert_eq!(pgn.device_instance_upper, 15);
-        assert_eq!(u16::from(pgn.device_function), 200);
-        // spare field is private, cannot be tested
-        assert_eq!(u8::from(pgn.device_class), 20);
-        assert_eq!(pgn.system_instance, 10);
-        assert_eq!(u8::from(pgn.industry_group), 4);
-        assert_eq!(pgn.arbitrary_address_capable, YesNo::No);
-    }
-
-    #[test]
-    fn test_pgn60928_isoname_round_trip() {
-        // Create a Pgn60928 (using valid enum values)
-        // Note: device_function is an INDIRECT_LOOKUP
-        let mut original_pgn = Pgn60928::new();
-        original_pgn.unique_number = 999888;
-        original_pgn.manufacturer_code = ManufacturerCode::try_from(88).unwrap(); // HemisphereGpsInc
-        original_pgn.device_instance_lower = 1;
-        original_pgn.device_instance_upper = 31;
-        // Use helper to assign DeviceFunction
-        original_pgn.set_device_function(DeviceFunction::AlarmEnunciator5230); // 5230 = 0x146E
-        original_pgn.device_class = DeviceClass::try_from(30).unwrap(); // ElectricalDistribution
-        original_pgn.system_instance = 15;
-        original_pgn.arbitrary_address_capable = YesNo::Yes;
-
-        // Convert to IsoName and back
-        let iso_name: IsoName = original_pgn.into();
-        let restored_pgn: Pgn60928 = iso_name.into();
-
-        // Verify round-trip preservation (spare is private and not tested)
-        assert_eq!(original_pgn.unique_number, restored_pgn.unique_number);
-        assert_eq!(
-            original_pgn.manufacturer_code,
-            restored_pgn.manufacturer_code
-        );
-        assert_eq!(
-            original_pgn.device_instance_lower,
-            restored_pgn.device_instance_lower
-        );
-        assert_eq!(
-            original_pgn.device_instance_upper,
-            restored_pgn.device_instance_upper
-        );
-        // device_function is an INDIRECT_LOOKUP stored as two u8 values.
-        // The low byte must match.
-        assert_eq!(original_pgn.device_function, restored_pgn.device_function);
-        // spare field is private, cannot be tested
-        assert_eq!(original_pgn.device_class, restored_pgn.device_class);
-        assert_eq!(original_pgn.system_instance, restored_pgn.system_instance);
-        assert_eq!(original_pgn.industry_group, restored_pgn.industry_group);
-        assert_eq!(
-            original_pgn.arbitrary_address_capable,
-            restored_pgn.arbitrary_address_capable
-        );
+        assert_eq!(pgn.name, iso_name);
+        assert_eq!(IsoName::from(pgn), iso_name);
     }
 }
@@ -0,0 +1,146 @@
+//! Poll-driven network discovery, free of any executor or timer.
+//!
+//! [`NetworkDiscovery`] broadcasts an ISO Request for the Address Claim PGN
+//! on its first [`on_tick`](NetworkDiscovery::on_tick), then records the claims
+//! fed through [`on_frame`](NetworkDiscovery::on_frame) until its listening
+//! window elapses. The async
+//! [`request_network_discovery`](super::request_network_discovery) is a thin
+//! wrapper around it.
+//!
+//! ```rust,ignore
+//! let mut devices = [(0u8, 0u64); 16];
+//! let mut discovery = NetworkDiscovery::new(&mut devices);
+//! while !discovery.is_complete() {
+//!     let now = monotonic_ms();
+//!     if let Some(request) = discovery.on_tick(now) {
+//!         can.transmit(&request);
+//!     }
+//!     if let Some(frame) = can.try_receive() {
+//!         discovery.on_frame(&frame);
+//!     }
+//! }
+//! for (address, name) in discovery.devices() { /* … */ }
+//! ```
+use crate::protocol::{
+    managment::address_claiming::extract_name_from_claim,
+    transport::{can_frame::CanFrame, can_id::CanId},
+};
+
+/// Listening window for Address Claim responses after the request (ms).
+///
+/// 300 ms balances completeness and responsiveness.
+pub const DISCOVERY_WINDOW_MS: u64 = 300;
+
+/// Progress of the discovery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscoveryState {
+    /// Nothing sent yet; the next [`on_tick`](NetworkDiscovery::on_tick) sends the request.
+    Idle,
+    /// The request was sent; claims are recorded until `deadline_ms`.
+    Listening { deadline_ms: u64 },
+    /// The listening window elapsed.
+    Complete,
+}
+
+/// Neighbour enumeration as a synchronous state machine.
+///
+/// Devices are stored in the caller-provided buffer, one entry per source
+/// address; claims past its capacity are ignored.
+#[derive(Debug)]
+pub struct NetworkDiscovery<'a> {
+    devices: &'a mut [(u8, u64)],
+    count: usize,
+    state: DiscoveryState,
+}
+
+impl<'a> NetworkDiscovery<'a> {
+    /// Prepare a discovery storing `(address, NAME)` pairs in `devices`.
+    pub fn new(devices: &'a mut [(u8, u64)]) -> Self {
+        Self {
+            devices,
+            count: 0,
+            state: DiscoveryState::Idle,
+        }
+    }
+
+    /// Current progress of the discovery.
+    pub fn state(&self) -> DiscoveryState {
+        self.state
+    }
+
+    /// True once the listening window elapsed.
+    pub fn is_complete(&self) -> bool {
+        self.state == DiscoveryState::Complete
+    }
+
+    /// Time at which [`on_tick`](Self::on_tick) must next be called, if any.
+    pub fn next_deadline(&self) -> Option<u64> {
+        match self.state {
+            DiscoveryState::Listening { deadline_ms } => Some(deadline_ms),
+            _ => None,
+        }
+    }
+
+    /// Devices discovered so far, in response order.
+    pub fn devices(&self) -> &[(u8, u64)] {
+        &self.devices[..self.count]
+    }
+
+    /// Advance the discovery to `now_ms` and return the frame to send, if any.
+    ///
+    /// Returns the ISO Request on the first call and closes the listening
+    /// window once it elapsed.
+    pub fn on_tick(&mut self, now_ms: u64) -> Option<CanFrame> {
+        match self.state {
+            DiscoveryState::Idle => {
+                self.state = DiscoveryState::Listening {
+                    deadline_ms: now_ms.saturating_add(DISCOVERY_WINDOW_MS),
+                };
+                Some(request_frame())
+            }
+            DiscoveryState::Listening { deadline_ms } if now_ms >= deadline_ms => {
+                self.state = DiscoveryState::Complete;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Record the sender of a well-formed Address Claim received while listening.
+    ///
+    /// Some devices answer several times; only their first claim is kept.
+    pub fn on_frame(&mut self, frame: &CanFrame) {
+        if !matches!(self.state, DiscoveryState::Listening { .. }) {
+            return;
+        }
+        let Ok(name) = extract_name_from_claim(frame) else {
+            return;
+        };
+        let address = frame.id.source_address();
+        if self.count < self.devices.len() && !self.devices().iter().any(|(a, _)| *a == address) {
+            self.devices[self.count] = (address, name);
+            self.count += 1;
+        }
+    }
+}
+
+/// ISO Request (PGN 59904) for the Address Claim PGN, sent to every node.
+fn request_frame() -> CanFrame {
+    // ISO Request payload stores the target PGN on 3 bytes.
+    let mut data = [0xFFu8; 8]; // Remaining bytes padded with 0xFF.
+    data[0..3].copy_from_slice(&60928u32.to_le_bytes()[0..3]);
+
+    CanFrame {
+        id: CanId::builder(59904, 255) // Source 255: global address.
+            .to_destination(255)
+            .with_priority(6) // Standard priority for network requests.
+            .build()
+            .expect("PGN 59904 with destination 255 must always produce a valid CanId"),
+        data,
+        len: 3, // Only the first three bytes are meaningful.
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,68 @@
+//! Network discovery state machine tests: request, collection, and window.
+// STATE_MACHINE
+use super::*;
+use crate::protocol::managment::address_claiming::build_address_claim_frame;
+
+fn claim_from(name: u64, address: u8) -> CanFrame {
+    build_address_claim_frame(name, address).unwrap()
+}
+
+#[test]
+/// The first tick requests the claims; the window closes 300 ms later.
+fn test_request_and_window() {
+    let mut devices = [(0, 0); 4];
+    let mut discovery = NetworkDiscovery::new(&mut devices);
+    assert_eq!(discovery.state(), DiscoveryState::Idle);
+
+    let request = discovery.on_tick(1000).unwrap();
+    assert_eq!(request.id.pgn(), 59904);
+    assert_eq!(request.id.destination(), Some(255));
+    assert_eq!(&request.data[..request.len], &[0x00, 0xEE, 0x00]);
+    assert_eq!(discovery.next_deadline(), Some(1300));
+
+    assert!(discovery.on_tick(1299).is_none());
+    assert!(!discovery.is_complete());
+    assert!(discovery.on_tick(1300).is_none());
+    assert!(discovery.is_complete());
+    assert_eq!(discovery.next_deadline(), None);
+
+    // Claims after the window are ignored.
+    discovery.on_frame(&claim_from(0x10, 10));
+    assert!(discovery.devices().is_empty());
+}
+
+#[test]
+/// Claims are recorded once per address, within the buffer capacity.
+fn test_collects_unique_claims() {
+    let mut devices = [(0, 0); 2];
+    let mut discovery = NetworkDiscovery::new(&mut devices);
+
+    // Not listening yet.
+    discovery.on_frame(&claim_from(0x10, 10));
+    assert!(discovery.devices().is_empty());
+
+    discovery.on_tick(0);
+    discovery.on_frame(&claim_from(0x10, 10));
+    discovery.on_frame(&claim_from(0x10, 10));
+    discovery.on_frame(&claim_from(0x20, 20));
+    discovery.on_frame(&claim_from(0x30, 30));
+    assert_eq!(discovery.devices(), &[(10, 0x10), (20, 0x20)]);
+}
+
+#[test]
+/// Frames other than well-formed Address Claims are ignored.
+fn test_ignores_other_frames() {
+    let mut devices = [(0, 0); 2];
+    let mut discovery = NetworkDiscovery::new(&mut devices);
+    discovery.on_tick(0);
+
+    let mut short = claim_from(0x10, 10);
+    short.len = 4;
+    discovery.on_frame(&short);
+
+    let mut other_pgn = claim_from(0x10, 10);
+    other_pgn.id = CanId::builder(127250, 10).build().unwrap();
+    discovery.on_frame(&other_pgn);
+
+    assert!(discovery.devices().is_empty());
+}
@@ -4,7 +4,41 @@
 include!(concat!(env!("OUT_DIR"), "/generated_pgns.rs"));
 use crate::{
     error::DeserializationError,
-    infra::codec::traits::{FieldAccess, PgnData},
+    infra::codec::traits::{FieldAccess, PgnData, PgnMetadata},
 };
 
 use crate::core::{FieldDescriptor, FieldKind};
+
+//==================================================================================VARIABLE
+/// Field of `pgn` addressed by a Group Function parameter index (its 1-based
+/// CANboat order), sizing and typing the `Variable` value that follows it.
+///
+/// A polymorphic PGN resolves against the variant whose first field holds
+/// `selector`, the value given to parameter 1; without one, only index 1 (the
+/// selecting field itself) resolves. Returns `Ok(None)` when `pgn` is not
+/// generated, leaving the parameter unresolved.
+#[allow(clippy::result_large_err)]
+pub fn variable_field(
+    pgn: u32,
+    index: u8,
+    selector: Option<u64>,
+) -> Result<Option<&'static FieldDescriptor>, DeserializationError> {
+    let Some(descriptor) = N2kMessage::descriptor(pgn) else {
+        return Ok(None);
+    };
+    let variants = N2kMessage::variant_descriptors(pgn);
+    let descriptor = match selector {
+        _ if variants.is_empty() => descriptor,
+        Some(selector) => variants
+            .iter()
+            .find(|(value, _)| *value == selector)
+            .map(|(_, variant)| *variant)
+            .ok_or(DeserializationError::UnmatchedVariant { pgn })?,
+        None if index == 1 => descriptor,
+        None => return Err(DeserializationError::UnmatchedVariant { pgn }),
+    };
+    descriptor
+        .indexed_field(index)
+        .map(Some)
+        .ok_or(DeserializationError::MalformedData)
+}
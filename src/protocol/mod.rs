@@ -1,5 +1,7 @@
 //! High-level components of the NMEA 2000 protocol: lookup tables,
-//! network management, message structures, and CAN/Fast Packet transport.
+//! network management, message structures, CAN/Fast Packet transport, and the
+//! receive pipeline decoding incoming frames.
+pub mod decoder;
 pub mod lookups;
 pub mod managment;
 pub mod messages;
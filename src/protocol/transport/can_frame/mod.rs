@@ -1,5 +1,12 @@
 //! In-memory representation of an SAE J1939 / NMEA 2000 CAN frame.
-use crate::protocol::transport::can_id::CanId;
+//!
+//! [`CanFrame`] implements [`embedded_can::Frame`], and converts from and to the
+//! frame type of any `embedded-can` driver with [`CanFrame::from_frame`] and
+//! [`CanFrame::to_frame`]. Standard identifiers and remote frames are rejected,
+//! as NMEA 2000 never uses them.
+use embedded_can::{ExtendedId, Frame, Id};
+
+use crate::{error::FrameConversionError, protocol::transport::can_id::CanId};
 
 #[derive(Clone, Debug)]
 /// Raw NMEA 2000 frame as read from the CAN bus.
@@ -11,3 +18,104 @@ pub struct CanFrame {
     /// Number of valid payload bytes (Data Length Code, 0 to 8).
     pub len: usize,
 }
+
+impl CanFrame {
+    /// Convert a frame received from an `embedded-can` driver.
+    ///
+    /// # Errors
+    ///
+    /// - [`FrameConversionError::StandardId`] for 11-bit identifiers
+    /// - [`FrameConversionError::RemoteFrame`] for remote frames
+    /// - [`FrameConversionError::InvalidData`] for payloads over eight bytes
+    pub fn from_frame<F: Frame>(frame: &F) -> Result<Self, FrameConversionError> {
+        if frame.is_remote_frame() {
+            return Err(FrameConversionError::RemoteFrame);
+        }
+        let Id::Extended(id) = frame.id() else {
+            return Err(FrameConversionError::StandardId);
+        };
+
+        let payload = frame.data();
+        if payload.len() > 8 {
+            return Err(FrameConversionError::InvalidData);
+        }
+        let mut data = [0; 8];
+        data[..payload.len()].copy_from_slice(payload);
+
+        Ok(Self {
+            id: CanId(id.as_raw()),
+            data,
+            len: payload.len(),
+        })
+    }
+
+    /// Build the equivalent frame for an `embedded-can` driver.
+    ///
+    /// # Errors
+    ///
+    /// [`FrameConversionError::InvalidData`] when the target type refuses the
+    /// identifier or the payload.
+    pub fn to_frame<F: Frame>(&self) -> Result<F, FrameConversionError> {
+        F::new(self.extended_id()?, self.payload()).ok_or(FrameConversionError::InvalidData)
+    }
+
+    /// Valid payload bytes (`data[..len]`, clamped to eight bytes).
+    fn payload(&self) -> &[u8] {
+        &self.data[..self.len.min(self.data.len())]
+    }
+
+    fn extended_id(&self) -> Result<ExtendedId, FrameConversionError> {
+        ExtendedId::new(self.id.0).ok_or(FrameConversionError::InvalidData)
+    }
+}
+
+impl Frame for CanFrame {
+    /// Only extended identifiers and payloads up to eight bytes are accepted.
+    fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
+        let Id::Extended(id) = id.into() else {
+            return None;
+        };
+        if data.len() > 8 {
+            return None;
+        }
+        let mut buffer = [0; 8];
+        buffer[..data.len()].copy_from_slice(data);
+        Some(Self {
+            id: CanId(id.as_raw()),
+            data: buffer,
+            len: data.len(),
+        })
+    }
+
+    /// Remote frames do not exist in NMEA 2000: always `None`.
+    fn new_remote(_id: impl Into<Id>, _dlc: usize) -> Option<Self> {
+        None
+    }
+
+    fn is_extended(&self) -> bool {
+        true
+    }
+
+    fn is_remote_frame(&self) -> bool {
+        false
+    }
+
+    fn id(&self) -> Id {
+        // Identifiers wider than 29 bits cannot come from `CanIdBuilder`; mask defensively.
+        Id::Extended(
+            ExtendedId::new(self.id.0 & ExtendedId::MAX.as_raw()).unwrap_or(ExtendedId::ZERO),
+        )
+    }
+
+    fn dlc(&self) -> usize {
+        self.payload().len()
+    }
+
+    fn data(&self) -> &[u8] {
+        self.payload()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
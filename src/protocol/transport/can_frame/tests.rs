@@ -0,0 +1,56 @@
+//! `embedded-can` frame conversion tests.
+// CAN_FRAME
+use super::*;
+use crate::protocol::transport::test_support::HalFrame;
+use embedded_can::StandardId;
+
+#[test]
+/// Identifier, DLC, and payload survive a round trip through a driver frame.
+fn test_driver_frame_roundtrip() {
+    let frame = CanFrame {
+        id: CanId::builder(129025, 42).with_priority(2).build().unwrap(),
+        data: [1, 2, 3, 4, 5, 0, 0, 0],
+        len: 5,
+    };
+
+    let hal: HalFrame = frame.to_frame().unwrap();
+    assert_eq!(hal.id, Id::Extended(ExtendedId::new(frame.id.0).unwrap()));
+    assert_eq!(hal.data(), &[1, 2, 3, 4, 5]);
+
+    let back = CanFrame::from_frame(&hal).unwrap();
+    assert_eq!(back.id, frame.id);
+    assert_eq!(back.len, 5);
+    assert_eq!(back.data, frame.data);
+}
+
+#[test]
+/// Standard identifiers and remote frames are rejected.
+fn test_rejects_standard_and_remote_frames() {
+    let standard = HalFrame::new(StandardId::new(0x123).unwrap(), &[0; 8]).unwrap();
+    assert_eq!(
+        CanFrame::from_frame(&standard).unwrap_err(),
+        FrameConversionError::StandardId
+    );
+
+    let remote = HalFrame::new_remote(ExtendedId::new(0x1234).unwrap(), 8).unwrap();
+    assert_eq!(
+        CanFrame::from_frame(&remote).unwrap_err(),
+        FrameConversionError::RemoteFrame
+    );
+
+    assert!(<CanFrame as Frame>::new(StandardId::new(0x123).unwrap(), &[]).is_none());
+    assert!(CanFrame::new_remote(ExtendedId::new(0x1234).unwrap(), 0).is_none());
+}
+
+#[test]
+/// The `Frame` view of a `CanFrame` exposes only the valid bytes.
+fn test_frame_trait_view() {
+    let frame =
+        <CanFrame as Frame>::new(ExtendedId::new(0x09F8_0142).unwrap(), &[9, 8, 7]).unwrap();
+    assert!(frame.is_extended());
+    assert!(!frame.is_remote_frame());
+    assert_eq!(frame.dlc(), 3);
+    assert_eq!(frame.data(), &[9, 8, 7]);
+    assert_eq!(frame.id.0, 0x09F8_0142);
+    assert!(<CanFrame as Frame>::new(ExtendedId::ZERO, &[0; 9]).is_none());
+}
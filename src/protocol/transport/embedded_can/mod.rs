@@ -0,0 +1,128 @@
+//! [`CanBus`] adapters for synchronous `embedded-can` drivers.
+//!
+//! Boards whose HAL only offers the `embedded-can` traits can still run the
+//! stack:
+//! - [`NbCanBus`] wraps an [`embedded_can::nb::Can`] driver. `WouldBlock` is
+//!   answered by yielding to the executor and retrying, so the task keeps
+//!   polling the peripheral while it waits (busy polling).
+//! - [`BlockingCanBus`] wraps an [`embedded_can::blocking::Can`] driver. Each
+//!   call blocks the executor until the driver returns; reserve it for
+//!   single-task firmware or dedicated executors.
+//!
+//! Received frames with a standard identifier or the remote flag are skipped,
+//! as NMEA 2000 never uses them.
+//!
+//! ```rust,ignore
+//! let can = hal::can::Can::new(peripherals.CAN1, pins, 250_000);
+//! let bus = NbCanBus::new(can);
+//! let manager = AddressManager::new(bus, EmbassyTimer::new(), my_name, 42).await?;
+//! ```
+use ::embedded_can::{blocking, nb as nb_can};
+
+use crate::{
+    error::EmbeddedCanError,
+    protocol::transport::{can_frame::CanFrame, timers::YieldOnce, traits::can_bus::CanBus},
+};
+
+//==================================================================================NB
+/// [`CanBus`] over a non-blocking [`embedded_can::nb::Can`] driver.
+#[derive(Debug)]
+pub struct NbCanBus<D> {
+    driver: D,
+}
+
+impl<D: nb_can::Can> NbCanBus<D> {
+    /// Wrap `driver`.
+    pub const fn new(driver: D) -> Self {
+        Self { driver }
+    }
+
+    /// Access the wrapped driver.
+    pub fn driver(&mut self) -> &mut D {
+        &mut self.driver
+    }
+
+    /// Release the wrapped driver.
+    pub fn into_inner(self) -> D {
+        self.driver
+    }
+}
+
+impl<D: nb_can::Can> CanBus for NbCanBus<D> {
+    type Error = EmbeddedCanError<D::Error>;
+
+    async fn send<'a>(&'a mut self, frame: &'a CanFrame) -> Result<(), Self::Error> {
+        let mut outgoing: D::Frame = frame.to_frame()?;
+        loop {
+            match self.driver.transmit(&outgoing) {
+                // The driver displaced a lower-priority pending frame: queue it again.
+                Ok(Some(replaced)) => outgoing = replaced,
+                Ok(None) => return Ok(()),
+                Err(nb::Error::WouldBlock) => YieldOnce::new().await,
+                Err(nb::Error::Other(error)) => return Err(EmbeddedCanError::Driver(error)),
+            }
+        }
+    }
+
+    async fn recv(&mut self) -> Result<CanFrame, Self::Error> {
+        loop {
+            match self.driver.receive() {
+                Ok(frame) => {
+                    if let Ok(frame) = CanFrame::from_frame(&frame) {
+                        return Ok(frame);
+                    }
+                }
+                Err(nb::Error::WouldBlock) => YieldOnce::new().await,
+                Err(nb::Error::Other(error)) => return Err(EmbeddedCanError::Driver(error)),
+            }
+        }
+    }
+}
+
+//==================================================================================BLOCKING
+/// [`CanBus`] over a blocking [`embedded_can::blocking::Can`] driver.
+#[derive(Debug)]
+pub struct BlockingCanBus<D> {
+    driver: D,
+}
+
+impl<D: blocking::Can> BlockingCanBus<D> {
+    /// Wrap `driver`.
+    pub const fn new(driver: D) -> Self {
+        Self { driver }
+    }
+
+    /// Access the wrapped driver.
+    pub fn driver(&mut self) -> &mut D {
+        &mut self.driver
+    }
+
+    /// Release the wrapped driver.
+    pub fn into_inner(self) -> D {
+        self.driver
+    }
+}
+
+impl<D: blocking::Can> CanBus for BlockingCanBus<D> {
+    type Error = EmbeddedCanError<D::Error>;
+
+    async fn send<'a>(&'a mut self, frame: &'a CanFrame) -> Result<(), Self::Error> {
+        let outgoing: D::Frame = frame.to_frame()?;
+        self.driver
+            .transmit(&outgoing)
+            .map_err(EmbeddedCanError::Driver)
+    }
+
+    async fn recv(&mut self) -> Result<CanFrame, Self::Error> {
+        loop {
+            let frame = self.driver.receive().map_err(EmbeddedCanError::Driver)?;
+            if let Ok(frame) = CanFrame::from_frame(&frame) {
+                return Ok(frame);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
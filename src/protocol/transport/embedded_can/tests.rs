@@ -0,0 +1,157 @@
+//! `embedded-can` adapter tests against scripted drivers.
+// EMBEDDED_CAN
+use super::*;
+use crate::protocol::transport::{
+    can_id::CanId,
+    test_support::{block_on, HalFrame},
+};
+use ::embedded_can::{ErrorKind, ExtendedId, Frame, StandardId};
+
+/// Scripted driver: busy for `busy` calls, then serves `rx` and records sends.
+struct ScriptedDriver {
+    busy: usize,
+    rx: [Option<HalFrame>; 3],
+    sent: [Option<HalFrame>; 3],
+    fail: bool,
+}
+
+impl ScriptedDriver {
+    fn new(busy: usize, rx: [Option<HalFrame>; 3]) -> Self {
+        Self {
+            busy,
+            rx,
+            sent: [None, None, None],
+            fail: false,
+        }
+    }
+
+    fn record(&mut self, frame: &HalFrame) {
+        let slot = self.sent.iter_mut().find(|slot| slot.is_none()).unwrap();
+        *slot = Some(frame.clone());
+    }
+
+    fn next_rx(&mut self) -> Option<HalFrame> {
+        self.rx.iter_mut().find_map(Option::take)
+    }
+}
+
+impl nb_can::Can for ScriptedDriver {
+    type Frame = HalFrame;
+    type Error = ErrorKind;
+
+    fn transmit(&mut self, frame: &HalFrame) -> nb::Result<Option<HalFrame>, ErrorKind> {
+        if self.fail {
+            return Err(nb::Error::Other(ErrorKind::Bit));
+        }
+        if self.busy > 0 {
+            self.busy -= 1;
+            return Err(nb::Error::WouldBlock);
+        }
+        self.record(frame);
+        Ok(None)
+    }
+
+    fn receive(&mut self) -> nb::Result<HalFrame, ErrorKind> {
+        if self.busy > 0 {
+            self.busy -= 1;
+            return Err(nb::Error::WouldBlock);
+        }
+        self.next_rx().ok_or(nb::Error::Other(ErrorKind::Overrun))
+    }
+}
+
+impl blocking::Can for ScriptedDriver {
+    type Frame = HalFrame;
+    type Error = ErrorKind;
+
+    fn transmit(&mut self, frame: &HalFrame) -> Result<(), ErrorKind> {
+        self.record(frame);
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<HalFrame, ErrorKind> {
+        self.next_rx().ok_or(ErrorKind::Overrun)
+    }
+}
+
+fn n2k_frame() -> CanFrame {
+    CanFrame {
+        id: CanId::builder(127250, 7).with_priority(2).build().unwrap(),
+        data: [1, 2, 3, 4, 5, 6, 7, 8],
+        len: 8,
+    }
+}
+
+fn standard_frame() -> HalFrame {
+    HalFrame::new(StandardId::new(0x100).unwrap(), &[0xAA]).unwrap()
+}
+
+#[test]
+/// `WouldBlock` is retried after yielding until the driver accepts the frame.
+fn test_nb_send_retries_until_accepted() {
+    let mut bus = NbCanBus::new(ScriptedDriver::new(3, [None, None, None]));
+
+    let (result, polls) = block_on(bus.send(&n2k_frame()));
+    assert!(result.is_ok());
+    assert_eq!(polls, 4);
+
+    let sent = bus.driver().sent[0].clone().unwrap();
+    assert_eq!(
+        sent.id(),
+        ::embedded_can::Id::Extended(ExtendedId::new(n2k_frame().id.0).unwrap())
+    );
+    assert_eq!(sent.data(), &n2k_frame().data);
+}
+
+#[test]
+/// Standard and remote frames are skipped on reception.
+fn test_nb_recv_skips_non_n2k_frames() {
+    let remote = HalFrame::new_remote(ExtendedId::new(0x1234).unwrap(), 0).unwrap();
+    let wanted = n2k_frame().to_frame::<HalFrame>().unwrap();
+    let mut bus = NbCanBus::new(ScriptedDriver::new(
+        1,
+        [Some(standard_frame()), Some(remote), Some(wanted)],
+    ));
+
+    let (frame, _) = block_on(bus.recv());
+    assert_eq!(frame.unwrap().id, n2k_frame().id);
+}
+
+#[test]
+/// Driver errors are surfaced unchanged.
+fn test_nb_driver_error() {
+    let mut driver = ScriptedDriver::new(0, [None, None, None]);
+    driver.fail = true;
+    let mut bus = NbCanBus::new(driver);
+
+    let (result, _) = block_on(bus.send(&n2k_frame()));
+    assert!(matches!(
+        result,
+        Err(EmbeddedCanError::Driver(ErrorKind::Bit))
+    ));
+}
+
+#[test]
+/// The blocking adapter completes in a single poll.
+fn test_blocking_adapter() {
+    let wanted = n2k_frame().to_frame::<HalFrame>().unwrap();
+    let mut bus = BlockingCanBus::new(ScriptedDriver::new(
+        0,
+        [Some(standard_frame()), Some(wanted), None],
+    ));
+
+    let (result, polls) = block_on(bus.send(&n2k_frame()));
+    assert!(result.is_ok());
+    assert_eq!(polls, 1);
+    assert!(bus.driver().sent[0].is_some());
+
+    let (frame, polls) = block_on(bus.recv());
+    assert_eq!(frame.unwrap().id, n2k_frame().id);
+    assert_eq!(polls, 1);
+
+    let (result, _) = block_on(bus.recv());
+    assert!(matches!(
+        result,
+        Err(EmbeddedCanError::Driver(ErrorKind::Overrun))
+    ));
+}
@@ -1,30 +1,35 @@
 //! NMEA 2000 Fast Packet assembler: rebuilds application messages by
 //! aggregating the CAN frames of a multi-packet session.
-use super::MAX_FAST_PACKET_PAYLOAD;
+use super::{FAST_PACKET_SESSION_TIMEOUT_MS, MAX_FAST_PACKET_PAYLOAD};
+use crate::protocol::transport::can_id::CanId;
 
 //==================================================================================Constants
 
-/// Maximum number of Fast Packet sessions handled in parallel (distinct sources).
-const MAX_CONCURRENT_SESSIONS: usize = 4;
+/// Default number of Fast Packet sessions handled in parallel (distinct source/PGN pairs).
+pub const DEFAULT_CONCURRENT_SESSIONS: usize = 4;
 
 //==================================================================================Enums and Structs
 #[derive(Debug)]
-pub enum ProcessResult {
+pub enum ProcessResult<const CAP: usize = MAX_FAST_PACKET_PAYLOAD> {
     /// Frame not recognized as Fast Packet or discarded (invalid sequence,
-    /// session pool exhausted, etc.).
+    /// unknown or expired session, etc.).
     Ignored,
     /// Frame successfully integrated but additional fragments are still missing.
     FragmentConsumed,
     /// All expected fragments were received; the complete message is now available.
-    MessageComplete(CompletedMessage),
+    MessageComplete(CompletedMessage<CAP>),
 }
 
 /// Safe container returning a reassembled message without exposing
 /// the assembler's internal buffer.
 #[derive(Debug, PartialEq, Eq)]
-pub struct CompletedMessage {
+pub struct CompletedMessage<const CAP: usize = MAX_FAST_PACKET_PAYLOAD> {
+    /// PGN carried by the message.
+    pub pgn: u32,
+    /// Address of the node that sent the message.
+    pub source_address: u8,
     /// Reassembled payload.
-    pub payload: [u8; MAX_FAST_PACKET_PAYLOAD],
+    pub payload: [u8; CAP],
     /// Effective message length (number of valid bytes).
     pub len: usize,
 }
@@ -38,70 +43,147 @@ enum SessionState {
 
 /// Internal structure tracking the state of a Fast Packet session.
 #[derive(Debug, Clone, Copy)]
-struct FastPacketSession {
+struct FastPacketSession<const CAP: usize> {
     state: SessionState,
     source_address: u8,
+    pgn: u32,
     sequence_id: u8,
-    buffer: [u8; MAX_FAST_PACKET_PAYLOAD],
+    buffer: [u8; CAP],
     expected_size: usize,
     current_size: usize,
     last_frame_index: u8,
+    /// Reception time of the latest frame, used for expiry and LRU eviction.
+    last_frame_ms: u64,
 }
 
-impl FastPacketSession {
+impl<const CAP: usize> FastPacketSession<CAP> {
     /// Create a session in the inactive state.
     const fn new() -> Self {
         Self {
             state: SessionState::Inactive,
             source_address: 0,
+            pgn: 0,
             sequence_id: 0,
-            buffer: [0; MAX_FAST_PACKET_PAYLOAD],
+            buffer: [0; CAP],
             expected_size: 0,
             current_size: 0,
             last_frame_index: 0,
+            last_frame_ms: 0,
         }
     }
 
     /// Reset the session and make it available again.
     fn reset(&mut self) {
         self.state = SessionState::Inactive;
+        self.pgn = 0;
         self.sequence_id = 0;
         self.expected_size = 0;
         self.current_size = 0;
         self.last_frame_index = 0;
         // No need to wipe the buffer; upcoming copies will overwrite it.
     }
+
+    /// True when no frame arrived within the session timeout.
+    fn is_expired(&self, now_ms: u64) -> bool {
+        now_ms.saturating_sub(self.last_frame_ms) > FAST_PACKET_SESSION_TIMEOUT_MS
+    }
 }
 
 /// Main assembler: owns a fixed pool of reusable sessions.
+///
+/// * `N` – number of sessions reassembled in parallel (defaults to
+///   [`DEFAULT_CONCURRENT_SESSIONS`])
+/// * `CAP` – payload capacity of each session, in bytes (defaults to
+///   [`MAX_FAST_PACKET_PAYLOAD`]); longer messages are ignored
+///
+/// The pool costs roughly `N × CAP` bytes of RAM: a busy bus gateway raises `N`,
+/// while a small sensor node listening to a few short PGNs lowers both.
+///
+/// ```rust,ignore
+/// // Gateway following many talkers.
+/// let mut gateway: FastPacketAssembler<24> = FastPacketAssembler::empty();
+/// // Sensor node only interested in short messages.
+/// let mut sensor: FastPacketAssembler<1, 32> = FastPacketAssembler::empty();
+/// ```
 #[derive(Debug, Copy, Clone)]
-pub struct FastPacketAssembler {
-    sessions: [FastPacketSession; MAX_CONCURRENT_SESSIONS],
+pub struct FastPacketAssembler<
+    const N: usize = DEFAULT_CONCURRENT_SESSIONS,
+    const CAP: usize = MAX_FAST_PACKET_PAYLOAD,
+> {
+    sessions: [FastPacketSession<CAP>; N],
+    /// Messages lost because no session was available for them.
+    dropped_messages: u32,
 }
 
-impl Default for FastPacketAssembler {
+impl<const N: usize, const CAP: usize> Default for FastPacketAssembler<N, CAP> {
     fn default() -> Self {
-        Self::new()
+        Self::empty()
     }
 }
 
 impl FastPacketAssembler {
-    /// Instantiate the assembler with an inactive session pool.
+    /// Instantiate the default-sized assembler with an inactive session pool.
     pub const fn new() -> Self {
+        Self::empty()
+    }
+}
+
+impl<const N: usize, const CAP: usize> FastPacketAssembler<N, CAP> {
+    /// Instantiate an assembler of any size with an inactive session pool.
+    pub const fn empty() -> Self {
         Self {
-            sessions: [FastPacketSession::new(); MAX_CONCURRENT_SESSIONS],
+            sessions: [FastPacketSession::new(); N],
+            dropped_messages: 0,
         }
     }
 
+    //==================================================================================Pool Statistics
+    /// Number of sessions in the pool (`N`).
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Largest payload a session can reassemble (`CAP`).
+    pub const fn payload_capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Number of sessions currently reassembling a message.
+    pub fn active_sessions(&self) -> usize {
+        self.sessions
+            .iter()
+            .filter(|s| s.state == SessionState::InProgress)
+            .count()
+    }
+
+    /// Number of messages lost for lack of sessions since creation: in-progress
+    /// messages evicted by a newcomer, or newcomers refused by an empty pool.
+    ///
+    /// Saturates at `u32::MAX`. A steadily growing value means `N` is too small
+    /// for the bus.
+    pub const fn dropped_messages(&self) -> u32 {
+        self.dropped_messages
+    }
+
     //==================================================================================Process Functions
     /// Process a CAN frame that may belong to a Fast Packet session.
     ///
-    /// * `source_address` – logical address of the sender (session key)
+    /// * `id` – identifier of the received CAN frame; its source address and PGN,
+    ///   together with the sequence identifier, key the session
     /// * `data` – raw 8-byte payload of the received CAN frame
+    /// * `now_ms` – monotonic timestamp of the reception, in milliseconds
+    ///
+    /// Sessions silent for longer than [`FAST_PACKET_SESSION_TIMEOUT_MS`] are dropped
+    /// first. When every slot is busy, a new message evicts the least recently
+    /// updated session, which counts as a dropped message.
     ///
     /// Returns a `ProcessResult` indicating whether the frame was ignored,
     /// consumed, or completed the message.
-    pub fn process_frame(&mut self, source_address: u8, data: &[u8; 8]) -> ProcessResult {
+    pub fn process_frame(&mut self, id: CanId, data: &[u8; 8], now_ms: u64) -> ProcessResult<CAP> {
+        self.evict_expired(now_ms);
+
+        let source_address = id.source_address();
+        let pgn = id.pgn();
         let frame_index = data[0] & 0x1F;
         let sequence_id = (data[0] >> 5) & 0x07;
 
@@ -109,19 +191,30 @@ impl FastPacketAssembler {
             // First frame: carries the total expected size.
             let expected_size = data[1] as usize;
 
-            if !(8..=MAX_FAST_PACKET_PAYLOAD).contains(&expected_size) {
+            if !(8..=MAX_FAST_PACKET_PAYLOAD.min(CAP)).contains(&expected_size) {
                 return ProcessResult::Ignored;
             }
 
-            let ideal_session_index = self.sessions.iter().position(|s| {
-                s.source_address == source_address && s.state == SessionState::Inactive
+            // A sender restarting the same sequence takes its previous session over.
+            let restarted_session_index = self.sessions.iter().position(|s| {
+                s.state == SessionState::InProgress
+                    && s.source_address == source_address
+                    && s.pgn == pgn
+                    && s.sequence_id == sequence_id
             });
 
-            let session_index = ideal_session_index.or_else(|| {
-                self.sessions
-                    .iter()
-                    .position(|s| s.state == SessionState::Inactive)
-            });
+            let session_index = restarted_session_index
+                .or_else(|| {
+                    self.sessions
+                        .iter()
+                        .position(|s| s.state == SessionState::Inactive)
+                })
+                .or_else(|| {
+                    // Evicting an in-progress session loses its message.
+                    let evicted = self.least_recently_used();
+                    self.dropped_messages = self.dropped_messages.saturating_add(1);
+                    evicted
+                });
 
             if let Some(index) = session_index {
                 let session = &mut self.sessions[index];
@@ -129,9 +222,11 @@ impl FastPacketAssembler {
                 // Initialize the session.
                 session.state = SessionState::InProgress;
                 session.source_address = source_address;
+                session.pgn = pgn;
                 session.expected_size = expected_size;
-                session.sequence_id = sequence_id as u8;
+                session.sequence_id = sequence_id;
                 session.last_frame_index = 0;
+                session.last_frame_ms = now_ms;
 
                 // First frame transports six useful bytes after the header.
                 let data_len = 6;
@@ -147,7 +242,8 @@ impl FastPacketAssembler {
             if let Some(session) = self.sessions.iter_mut().find(|s| {
                 s.state == SessionState::InProgress
                     && s.source_address == source_address
-                    && s.sequence_id == sequence_id as u8
+                    && s.pgn == pgn
+                    && s.sequence_id == sequence_id
             }) {
                 if frame_index != session.last_frame_index.wrapping_add(1) {
                     session.reset();
@@ -155,6 +251,7 @@ impl FastPacketAssembler {
                 }
 
                 session.last_frame_index = frame_index;
+                session.last_frame_ms = now_ms;
 
                 let bytes_needed = session.expected_size - session.current_size;
                 // Subsequent frames provide up to seven bytes of payload.
@@ -170,11 +267,13 @@ impl FastPacketAssembler {
 
                 if session.current_size >= session.expected_size {
                     // Copy the complete message into a dedicated return structure.
-                    let mut payload_buffer = [0; MAX_FAST_PACKET_PAYLOAD];
+                    let mut payload_buffer = [0; CAP];
                     let payload_len = session.expected_size;
                     payload_buffer[..payload_len].copy_from_slice(&session.buffer[..payload_len]);
 
                     let completed_message = CompletedMessage {
+                        pgn,
+                        source_address,
                         payload: payload_buffer,
                         len: payload_len,
                     };
@@ -191,6 +290,25 @@ impl FastPacketAssembler {
 
         ProcessResult::Ignored
     }
+
+    /// Drop every session silent for longer than [`FAST_PACKET_SESSION_TIMEOUT_MS`].
+    pub fn evict_expired(&mut self, now_ms: u64) {
+        for session in self.sessions.iter_mut() {
+            if session.state == SessionState::InProgress && session.is_expired(now_ms) {
+                session.reset();
+            }
+        }
+    }
+
+    /// Index of the in-progress session with the oldest activity.
+    fn least_recently_used(&self) -> Option<usize> {
+        self.sessions
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.state == SessionState::InProgress)
+            .min_by_key(|(_, s)| s.last_frame_ms)
+            .map(|(index, _)| index)
+    }
 }
 
 #[cfg(test)]
@@ -2,8 +2,16 @@
 // ASSEMBLER
 use super::*;
 
+/// Broadcast Fast Packet PGN used by the hand-written frames below.
+const TEST_PGN: u32 = 129029;
+
+/// Identifier of `TEST_PGN` sent by `source`.
+fn fast_packet_id(source: u8) -> CanId {
+    CanId::builder(TEST_PGN, source).build().unwrap()
+}
+
 // Helper to make test assertions easier to read
-impl PartialEq for ProcessResult {
+impl<const CAP: usize> PartialEq for ProcessResult<CAP> {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (ProcessResult::Ignored, ProcessResult::Ignored) => true,
@@ -13,7 +21,7 @@ impl PartialEq for ProcessResult {
         }
     }
 }
-impl Eq for ProcessResult {}
+impl<const CAP: usize> Eq for ProcessResult<CAP> {}
 
 #[test]
 /// Rebuild a complete message from three valid fragments.
@@ -24,18 +32,18 @@ fn test_full_fast_packet_reassembly() {
     // Total length = 15 bytes
     // Data: 6 bytes
     let frame0: [u8; 8] = [0b000_00000, 15, 1, 2, 3, 4, 5, 6];
-    let result = assembler.process_frame(source_address, &frame0);
+    let result = assembler.process_frame(fast_packet_id(source_address), &frame0, 0);
     assert_eq!(result, ProcessResult::FragmentConsumed);
 
     // --- Frame 2 (continuation) ---
     // Data: 7 bytes
     let frame1: [u8; 8] = [0b000_00001, 7, 8, 9, 10, 11, 12, 13];
-    let result = assembler.process_frame(source_address, &frame1);
+    let result = assembler.process_frame(fast_packet_id(source_address), &frame1, 0);
     assert_eq!(result, ProcessResult::FragmentConsumed);
     // --- Frame 3 (final) ---
     // Data: 2 bytes (remaining bytes are padding)
     let frame2: [u8; 8] = [0b000_00010, 14, 15, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
-    let result = assembler.process_frame(source_address, &frame2);
+    let result = assembler.process_frame(fast_packet_id(source_address), &frame2, 0);
 
     // --- Verification ---
     let mut expected_payload_array = [0; MAX_FAST_PACKET_PAYLOAD];
@@ -43,6 +51,8 @@ fn test_full_fast_packet_reassembly() {
     expected_payload_array[..15].copy_from_slice(&expected_data);
 
     let expected_message = CompletedMessage {
+        pgn: TEST_PGN,
+        source_address,
         payload: expected_payload_array,
         len: 15,
     };
@@ -56,10 +66,10 @@ fn test_out_of_sequence_packet() {
     let mut assembler = FastPacketAssembler::new();
     let source_address = 10;
     let frame0: [u8; 8] = [0b000_00000, 15, 1, 2, 3, 4, 5, 6];
-    assembler.process_frame(source_address, &frame0);
+    assembler.process_frame(fast_packet_id(source_address), &frame0, 0);
     // Send frame index 2 while skipping frame index 1
     let frame2: [u8; 8] = [0b000_00010, 14, 15, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
-    let result = assembler.process_frame(source_address, &frame2);
+    let result = assembler.process_frame(fast_packet_id(source_address), &frame2, 0);
     // The assembler must drop the frame and abandon the message
     assert_eq!(result, ProcessResult::Ignored);
     // Ensure the session was released
@@ -75,13 +85,13 @@ fn test_multiple_concurrent_sessions() {
     // Start message A
     let frame_a0: [u8; 8] = [0, 10, 1, 2, 3, 4, 5, 6];
     assert_eq!(
-        assembler.process_frame(source_a, &frame_a0),
+        assembler.process_frame(fast_packet_id(source_a), &frame_a0, 0),
         ProcessResult::FragmentConsumed
     );
     // Start message B
     let frame_b0: [u8; 8] = [0, 9, 100, 101, 102, 103, 104, 105];
     assert_eq!(
-        assembler.process_frame(source_b, &frame_b0),
+        assembler.process_frame(fast_packet_id(source_b), &frame_b0, 0),
         ProcessResult::FragmentConsumed
     );
     // Finish message A
@@ -89,11 +99,13 @@ fn test_multiple_concurrent_sessions() {
     let mut payload_a = [0; MAX_FAST_PACKET_PAYLOAD];
     payload_a[..10].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
     let expected_a = CompletedMessage {
+        pgn: TEST_PGN,
+        source_address: source_a,
         payload: payload_a,
         len: 10,
     };
     assert_eq!(
-        assembler.process_frame(source_a, &frame_a1),
+        assembler.process_frame(fast_packet_id(source_a), &frame_a1, 0),
         ProcessResult::MessageComplete(expected_a)
     );
     // Finish message B
@@ -101,11 +113,13 @@ fn test_multiple_concurrent_sessions() {
     let mut payload_b = [0; MAX_FAST_PACKET_PAYLOAD];
     payload_b[..9].copy_from_slice(&[100, 101, 102, 103, 104, 105, 106, 107, 108]);
     let expected_b = CompletedMessage {
+        pgn: TEST_PGN,
+        source_address: source_b,
         payload: payload_b,
         len: 9,
     };
     assert_eq!(
-        assembler.process_frame(source_b, &frame_b1),
+        assembler.process_frame(fast_packet_id(source_b), &frame_b1, 0),
         ProcessResult::MessageComplete(expected_b)
     );
 }
@@ -119,14 +133,14 @@ fn test_interleaved_sequences_same_source() {
     // Message A: sequence 1 (upper bits = 0b001)
     let frame_a0: [u8; 8] = [0b001_00000, 10, 1, 2, 3, 4, 5, 6];
     assert_eq!(
-        assembler.process_frame(source, &frame_a0),
+        assembler.process_frame(fast_packet_id(source), &frame_a0, 0),
         ProcessResult::FragmentConsumed
     );
 
     // Message B: sequence 2 (upper bits = 0b010)
     let frame_b0: [u8; 8] = [0b010_00000, 9, 21, 22, 23, 24, 25, 26];
     assert_eq!(
-        assembler.process_frame(source, &frame_b0),
+        assembler.process_frame(fast_packet_id(source), &frame_b0, 0),
         ProcessResult::FragmentConsumed
     );
 
@@ -135,11 +149,13 @@ fn test_interleaved_sequences_same_source() {
     let mut payload_b = [0; MAX_FAST_PACKET_PAYLOAD];
     payload_b[..9].copy_from_slice(&[21, 22, 23, 24, 25, 26, 27, 28, 29]);
     let expected_b = CompletedMessage {
+        pgn: TEST_PGN,
+        source_address: source,
         payload: payload_b,
         len: 9,
     };
     assert_eq!(
-        assembler.process_frame(source, &frame_b1),
+        assembler.process_frame(fast_packet_id(source), &frame_b1, 0),
         ProcessResult::MessageComplete(expected_b)
     );
 
@@ -148,11 +164,180 @@ fn test_interleaved_sequences_same_source() {
     let mut payload_a = [0; MAX_FAST_PACKET_PAYLOAD];
     payload_a[..10].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
     let expected_a = CompletedMessage {
+        pgn: TEST_PGN,
+        source_address: source,
         payload: payload_a,
         len: 10,
     };
     assert_eq!(
-        assembler.process_frame(source, &frame_a1),
+        assembler.process_frame(fast_packet_id(source), &frame_a1, 0),
         ProcessResult::MessageComplete(expected_a)
     );
 }
+
+#[test]
+/// Lost final frames no longer exhaust the pool: expired sessions are released.
+fn test_expired_sessions_are_released() {
+    let mut assembler = FastPacketAssembler::new();
+
+    // Four senders start a message and never finish it.
+    for source in 1..=DEFAULT_CONCURRENT_SESSIONS as u8 {
+        let frame0: [u8; 8] = [0b000_00000, 15, 1, 2, 3, 4, 5, 6];
+        assembler.process_frame(fast_packet_id(source), &frame0, 0);
+    }
+
+    // Much later a fifth sender gets a fresh slot and completes normally.
+    let now = FAST_PACKET_SESSION_TIMEOUT_MS + 1;
+    let frame0: [u8; 8] = [0b000_00000, 9, 1, 2, 3, 4, 5, 6];
+    let frame1: [u8; 8] = [0b000_00001, 7, 8, 9, 0xFF, 0xFF, 0xFF, 0xFF];
+    assert_eq!(
+        assembler.process_frame(fast_packet_id(50), &frame0, now),
+        ProcessResult::FragmentConsumed
+    );
+    assert!(matches!(
+        assembler.process_frame(fast_packet_id(50), &frame1, now),
+        ProcessResult::MessageComplete(_)
+    ));
+    assert!(assembler
+        .sessions
+        .iter()
+        .all(|s| s.state == SessionState::Inactive));
+}
+
+#[test]
+/// Frames within the timeout window keep the session alive.
+fn test_session_refreshed_by_each_frame() {
+    let mut assembler = FastPacketAssembler::new();
+    let source = 12;
+
+    let frame0: [u8; 8] = [0b000_00000, 15, 1, 2, 3, 4, 5, 6];
+    let frame1: [u8; 8] = [0b000_00001, 7, 8, 9, 10, 11, 12, 13];
+    let frame2: [u8; 8] = [0b000_00010, 14, 15, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+
+    assembler.process_frame(fast_packet_id(source), &frame0, 0);
+    assembler.process_frame(fast_packet_id(source), &frame1, 700);
+    assert!(matches!(
+        assembler.process_frame(fast_packet_id(source), &frame2, 1_400),
+        ProcessResult::MessageComplete(_)
+    ));
+}
+
+#[test]
+/// A full pool evicts the least recently updated session for a new message.
+fn test_lru_eviction_when_pool_full() {
+    let mut assembler = FastPacketAssembler::new();
+    let frame0: [u8; 8] = [0b000_00000, 15, 1, 2, 3, 4, 5, 6];
+    let frame1: [u8; 8] = [0b000_00001, 7, 8, 9, 10, 11, 12, 13];
+
+    for source in 1..=DEFAULT_CONCURRENT_SESSIONS as u8 {
+        assembler.process_frame(fast_packet_id(source), &frame0, source as u64);
+    }
+    // Source 1 refreshes its session: source 2 becomes the oldest.
+    assembler.process_frame(fast_packet_id(1), &frame1, 10);
+
+    assert_eq!(
+        assembler.process_frame(fast_packet_id(99), &frame0, 20),
+        ProcessResult::FragmentConsumed
+    );
+    assert!(assembler
+        .sessions
+        .iter()
+        .all(|s| s.source_address != 2 || s.state == SessionState::Inactive));
+    assert!(assembler.sessions.iter().any(|s| s.source_address == 1));
+    assert_eq!(assembler.dropped_messages(), 1);
+}
+
+#[test]
+/// One device interleaving two PGNs with the same sequence identifier.
+///
+/// Sessions are keyed by PGN as well, so frames never leak into the other message.
+fn test_interleaved_pgns_same_source() {
+    let mut assembler = FastPacketAssembler::new();
+    let source = 3;
+    let gnss = CanId::builder(129029, source).build().unwrap();
+    let sats = CanId::builder(129540, source).build().unwrap();
+
+    let gnss0: [u8; 8] = [0b000_00000, 9, 1, 2, 3, 4, 5, 6];
+    let sats0: [u8; 8] = [0b000_00000, 9, 11, 12, 13, 14, 15, 16];
+    let gnss1: [u8; 8] = [0b000_00001, 7, 8, 9, 0xFF, 0xFF, 0xFF, 0xFF];
+    let sats1: [u8; 8] = [0b000_00001, 17, 18, 19, 0xFF, 0xFF, 0xFF, 0xFF];
+
+    assembler.process_frame(gnss, &gnss0, 0);
+    assembler.process_frame(sats, &sats0, 0);
+
+    match assembler.process_frame(sats, &sats1, 0) {
+        ProcessResult::MessageComplete(msg) => {
+            assert_eq!(msg.pgn, 129540);
+            assert_eq!(msg.source_address, source);
+            assert_eq!(&msg.payload[..9], &[11, 12, 13, 14, 15, 16, 17, 18, 19]);
+        }
+        other => panic!("expected 129540 to complete, got {other:?}"),
+    }
+    match assembler.process_frame(gnss, &gnss1, 0) {
+        ProcessResult::MessageComplete(msg) => {
+            assert_eq!(msg.pgn, 129029);
+            assert_eq!(&msg.payload[..9], &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        }
+        other => panic!("expected 129029 to complete, got {other:?}"),
+    }
+}
+
+#[test]
+/// A single-session pool reports its occupancy and counts evicted messages.
+fn test_small_pool_occupancy_and_drops() {
+    let mut assembler: FastPacketAssembler<1, 32> = FastPacketAssembler::empty();
+    assert_eq!(assembler.capacity(), 1);
+    assert_eq!(assembler.payload_capacity(), 32);
+    assert_eq!(assembler.active_sessions(), 0);
+
+    let frame0: [u8; 8] = [0b000_00000, 9, 1, 2, 3, 4, 5, 6];
+    let frame1: [u8; 8] = [0b000_00001, 7, 8, 9, 0xFF, 0xFF, 0xFF, 0xFF];
+
+    assembler.process_frame(fast_packet_id(1), &frame0, 0);
+    assert_eq!(assembler.active_sessions(), 1);
+
+    // Source 2 takes the only slot over; the message from source 1 is lost.
+    assembler.process_frame(fast_packet_id(2), &frame0, 1);
+    assert_eq!(assembler.dropped_messages(), 1);
+    assert_eq!(
+        assembler.process_frame(fast_packet_id(1), &frame1, 2),
+        ProcessResult::Ignored
+    );
+
+    match assembler.process_frame(fast_packet_id(2), &frame1, 2) {
+        ProcessResult::MessageComplete(msg) => {
+            assert_eq!(msg.payload.len(), 32);
+            assert_eq!(&msg.payload[..msg.len], &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        }
+        other => panic!("expected a complete message, got {other:?}"),
+    }
+    assert_eq!(assembler.active_sessions(), 0);
+    assert_eq!(assembler.dropped_messages(), 1);
+}
+
+#[test]
+/// Messages longer than the payload capacity are ignored without using a session.
+fn test_payload_capacity_limits_messages() {
+    let mut assembler: FastPacketAssembler<2, 16> = FastPacketAssembler::empty();
+    let too_long: [u8; 8] = [0b000_00000, 17, 1, 2, 3, 4, 5, 6];
+
+    assert_eq!(
+        assembler.process_frame(fast_packet_id(1), &too_long, 0),
+        ProcessResult::Ignored
+    );
+    assert_eq!(assembler.active_sessions(), 0);
+    assert_eq!(assembler.dropped_messages(), 0);
+}
+
+#[test]
+/// An empty pool refuses every message and counts each one as dropped.
+fn test_empty_pool_drops_everything() {
+    let mut assembler: FastPacketAssembler<0> = FastPacketAssembler::empty();
+    let frame0: [u8; 8] = [0b000_00000, 9, 1, 2, 3, 4, 5, 6];
+
+    assert_eq!(
+        assembler.process_frame(fast_packet_id(1), &frame0, 0),
+        ProcessResult::Ignored
+    );
+    assert_eq!(assembler.dropped_messages(), 1);
+}
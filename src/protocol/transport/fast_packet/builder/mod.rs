@@ -3,42 +3,13 @@
 use crate::error::CanIdBuildError;
 use crate::protocol::transport::can_frame::CanFrame;
 use crate::protocol::transport::can_id::CanId;
-use crate::protocol::transport::fast_packet::MAX_FAST_PACKET_PAYLOAD;
-#[cfg(target_has_atomic = "8")]
-use core::sync::atomic::{AtomicU8, Ordering};
-
-#[cfg(target_has_atomic = "8")]
-static GLOBAL_SEQUENCE_ID: AtomicU8 = AtomicU8::new(0);
-
-#[cfg(not(target_has_atomic = "8"))]
-// Warning: this branch is only safe when the caller guarantees exclusive access
-// (single-thread execution or interrupts disabled during construction). On MCUs without
-// atomics, wrap the call in a critical section if multiple contexts can emit concurrently.
-static mut GLOBAL_SEQUENCE_ID: u8 = 0;
-
-fn next_sequence_id() -> u8 {
-    #[cfg(target_has_atomic = "8")]
-    {
-        GLOBAL_SEQUENCE_ID
-            .fetch_update(Ordering::AcqRel, Ordering::Relaxed, |prev| {
-                Some((prev + 1) & 0x07)
-            })
-            .unwrap()
-            & 0x07
-    }
-
-    #[cfg(not(target_has_atomic = "8"))]
-    unsafe {
-        let current = GLOBAL_SEQUENCE_ID & 0x07;
-        GLOBAL_SEQUENCE_ID = (current + 1) & 0x07;
-        current
-    }
-}
+use crate::protocol::transport::fast_packet::{sequence::SequenceCounter, MAX_FAST_PACKET_PAYLOAD};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 /// Shared parameters for all frames composing a Fast Packet message.
 pub struct FastPacketBuilder<'a> {
     pgn: u32,
+    priority: u8,
     source_address: u8,
     destination: Option<u8>,
     payload: &'a [u8],
@@ -48,19 +19,38 @@ pub struct FastPacketBuilder<'a> {
 /// Lazy iterator returning frames one by one as they are encoded.
 pub struct FrameIterator<'a> {
     builder: FastPacketBuilder<'a>,
+    position: FramePosition,
+}
+
+/// Progress of a [`FrameIterator`], to resume it later over the same payload.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct FramePosition {
     frame_index: u8,
     bytes_sent: usize,
 }
 
+impl FrameIterator<'_> {
+    /// Where the next frame starts.
+    pub(crate) fn position(&self) -> FramePosition {
+        self.position
+    }
+
+    /// True once every payload byte was encoded.
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.position.bytes_sent >= self.builder.payload.len()
+    }
+}
+
 impl<'a> Iterator for FrameIterator<'a> {
     type Item = Result<CanFrame, CanIdBuildError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.bytes_sent >= self.builder.payload.len() {
+        if self.position.bytes_sent >= self.builder.payload.len() {
             return None;
         }
 
-        let mut id_builder = CanId::builder(self.builder.pgn, self.builder.source_address);
+        let mut id_builder = CanId::builder(self.builder.pgn, self.builder.source_address)
+            .with_priority(self.builder.priority);
 
         if let Some(destination) = self.builder.destination {
             id_builder = id_builder.to_destination(destination);
@@ -73,7 +63,7 @@ impl<'a> Iterator for FrameIterator<'a> {
         let total_len = self.builder.payload.len();
 
         if total_len > MAX_FAST_PACKET_PAYLOAD {
-            self.bytes_sent = total_len;
+            self.position.bytes_sent = total_len;
             return Some(Err(CanIdBuildError::InvalidData));
         }
 
@@ -82,7 +72,7 @@ impl<'a> Iterator for FrameIterator<'a> {
             let mut data = [0xFF; 8];
             data[..total_len].copy_from_slice(self.builder.payload);
 
-            self.bytes_sent = total_len;
+            self.position.bytes_sent = total_len;
 
             return Some(Ok(CanFrame {
                 id,
@@ -92,8 +82,8 @@ impl<'a> Iterator for FrameIterator<'a> {
         }
 
         // Fast Packet case: segment the message.
-        let header = ((self.builder.sequence_id & 0x07) << 5) | (self.frame_index & 0x1F);
-        let frame = if self.bytes_sent == 0 {
+        let header = ((self.builder.sequence_id & 0x07) << 5) | (self.position.frame_index & 0x1F);
+        let frame = if self.position.bytes_sent == 0 {
             // First frame: header + six data bytes.
             let mut data = [0xFF; 8];
             // Byte 0: sequence identifier.
@@ -104,7 +94,7 @@ impl<'a> Iterator for FrameIterator<'a> {
             let bytes_to_copy = 6.min(self.builder.payload.len());
             data[2..2 + bytes_to_copy].copy_from_slice(&self.builder.payload[0..bytes_to_copy]);
 
-            self.bytes_sent += bytes_to_copy;
+            self.position.bytes_sent += bytes_to_copy;
 
             CanFrame {
                 id,
@@ -115,13 +105,13 @@ impl<'a> Iterator for FrameIterator<'a> {
             let mut data = [0xFF; 8];
             data[0] = header;
 
-            let remaining_bytes = self.builder.payload.len() - self.bytes_sent;
+            let remaining_bytes = self.builder.payload.len() - self.position.bytes_sent;
             let bytes_to_copy = 7.min(remaining_bytes);
-            let payload_slice =
-                &self.builder.payload[self.bytes_sent..self.bytes_sent + bytes_to_copy];
+            let payload_slice = &self.builder.payload
+                [self.position.bytes_sent..self.position.bytes_sent + bytes_to_copy];
             data[1..1 + bytes_to_copy].copy_from_slice(payload_slice);
 
-            self.bytes_sent += bytes_to_copy;
+            self.position.bytes_sent += bytes_to_copy;
 
             CanFrame {
                 id,
@@ -130,7 +120,7 @@ impl<'a> Iterator for FrameIterator<'a> {
             }
         };
 
-        self.frame_index = self.frame_index.wrapping_add(1);
+        self.position.frame_index = self.position.frame_index.wrapping_add(1);
 
         Some(Ok(frame))
     }
@@ -139,25 +129,51 @@ impl<'a> Iterator for FrameIterator<'a> {
 impl<'a> FastPacketBuilder<'a> {
     /// Create a Fast Packet encoder (or single-frame builder) depending on payload size.
     ///
-    /// # Concurrency
-    /// On platforms without 8-bit atomics, the sequence identifier relies on a `static mut`.
-    /// Callers must ensure no concurrent Fast Packet builder runs at the same time (disable
-    /// interrupts or enter a critical section).
-    pub fn new(pgn: u32, source_address: u8, destination: Option<u8>, payload: &'a [u8]) -> Self {
+    /// The sequence identifier is the next one of this PGN and source in the
+    /// sender's `sequences`, so consecutive messages of the same PGN stay
+    /// distinguishable. Single-frame payloads carry no sequence identifier and
+    /// leave the counter untouched.
+    pub fn new<const N: usize>(
+        pgn: u32,
+        source_address: u8,
+        destination: Option<u8>,
+        payload: &'a [u8],
+        sequences: &mut SequenceCounter<N>,
+    ) -> Self {
+        let sequence_id = next_sequence_id(pgn, source_address, payload.len(), sequences);
+        Self::from_parts(pgn, source_address, destination, payload, sequence_id)
+    }
+
+    /// Builder with the default priority and an already drawn sequence identifier.
+    pub(crate) fn from_parts(
+        pgn: u32,
+        source_address: u8,
+        destination: Option<u8>,
+        payload: &'a [u8],
+        sequence_id: u8,
+    ) -> Self {
         Self {
             pgn,
+            priority: 6, // Default priority
             source_address,
             destination,
             payload,
-            sequence_id: next_sequence_id(),
+            sequence_id,
         }
     }
 
+    /// Set the priority (3 bits) of every frame; 6 when not specified.
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority & 0x07;
+        self
+    }
+
     /// Override the 3-bit Fast Packet sequence identifier.
     ///
     /// # Recommended usage
     /// Testing, replaying captured traffic, or controlled playback scenarios.
-    /// In production let `FastPacketBuilder::new` handle auto-increment to avoid collisions.
+    /// In production keep the identifier drawn from the [`SequenceCounter`]
+    /// to avoid collisions.
     pub fn with_sequence_id(mut self, sequence_id: u8) -> Self {
         self.sequence_id = sequence_id & 0x07;
         self
@@ -165,14 +181,33 @@ impl<'a> FastPacketBuilder<'a> {
 
     /// Start the iteration; each call to `next` yields the next frame.
     pub fn build(self) -> FrameIterator<'a> {
+        self.resume(FramePosition::default())
+    }
+
+    /// Continue an iteration stopped at `position`, over the same payload.
+    pub(crate) fn resume(self, position: FramePosition) -> FrameIterator<'a> {
         FrameIterator {
             builder: self,
-            frame_index: 0,
-            bytes_sent: 0,
+            position,
         }
     }
 }
 
+/// Sequence identifier of a `len`-byte message: the next one of this PGN and
+/// source for a Fast Packet, 0 for a single frame.
+pub(crate) fn next_sequence_id<const N: usize>(
+    pgn: u32,
+    source_address: u8,
+    len: usize,
+    sequences: &mut SequenceCounter<N>,
+) -> u8 {
+    if len > 8 {
+        sequences.next(pgn, source_address)
+    } else {
+        0
+    }
+}
+
 #[cfg(test)]
 #[path = "tests.rs"]
 mod tests;
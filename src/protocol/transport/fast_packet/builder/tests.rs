@@ -7,7 +7,7 @@ use crate::{error::CanIdBuildError, protocol::transport::fast_packet::MAX_FAST_P
 /// Short payload: remains a single classic CAN frame (no Fast Packet).
 fn test_builder_single_frame() {
     let payload = [1, 2, 3, 4, 5];
-    let builder = FastPacketBuilder::new(129025, 42, None, &payload);
+    let builder = FastPacketBuilder::new(129025, 42, None, &payload, &mut SequenceCounter::new());
     let mut iter = builder.build();
 
     let frame = iter.next().unwrap().unwrap();
@@ -23,7 +23,8 @@ fn test_builder_single_frame() {
 fn test_builder_two_frames() {
     // 10 bytes → 2 frames (6+4)
     let payload = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
-    let builder = FastPacketBuilder::new(129040, 50, None, &payload).with_sequence_id(0);
+    let builder = FastPacketBuilder::new(129040, 50, None, &payload, &mut SequenceCounter::new())
+        .with_sequence_id(0);
     let mut iter = builder.build();
 
     // Trame 0
@@ -47,7 +48,7 @@ fn test_builder_two_frames() {
 fn test_builder_max_payload() {
     let payload = [0xAA; 223]; // Max Fast Packet
                                // PGN 129540 (GNSS Sats) is PDU2 (broadcast), no destination needed
-    let builder = FastPacketBuilder::new(129540, 25, None, &payload);
+    let builder = FastPacketBuilder::new(129540, 25, None, &payload, &mut SequenceCounter::new());
     let mut iter = builder.build();
 
     // First frame
@@ -68,7 +69,8 @@ fn test_builder_max_payload() {
 /// Destination-aware PGNs keep their target in the generated frames.
 fn test_builder_with_destination() {
     let payload = [1, 2, 3];
-    let builder = FastPacketBuilder::new(59904, 42, Some(50), &payload);
+    let builder =
+        FastPacketBuilder::new(59904, 42, Some(50), &payload, &mut SequenceCounter::new());
     let mut iter = builder.build();
 
     let frame = iter.next().unwrap().unwrap();
@@ -79,7 +81,7 @@ fn test_builder_with_destination() {
 /// Oversized payload: returns an error and stops the iteration.
 fn test_builder_payload_too_large() {
     let payload = [0x11; MAX_FAST_PACKET_PAYLOAD + 1];
-    let builder = FastPacketBuilder::new(129540, 42, None, &payload);
+    let builder = FastPacketBuilder::new(129540, 42, None, &payload, &mut SequenceCounter::new());
     let mut iter = builder.build();
 
     let err = iter.next().unwrap().unwrap_err();
@@ -91,11 +93,13 @@ fn test_builder_payload_too_large() {
 /// Consecutive messages must receive distinct Fast Packet sequence identifiers.
 fn test_builder_sequence_id_progresses_between_messages() {
     let payload = [0x55; 10];
+    let mut sequences = SequenceCounter::new();
 
-    let mut first_iter = FastPacketBuilder::new(129040, 50, None, &payload).build();
+    let mut first_iter = FastPacketBuilder::new(129040, 50, None, &payload, &mut sequences).build();
     let first_header = first_iter.next().unwrap().unwrap().data[0];
 
-    let mut second_iter = FastPacketBuilder::new(129040, 50, None, &payload).build();
+    let mut second_iter =
+        FastPacketBuilder::new(129040, 50, None, &payload, &mut sequences).build();
     let second_header = second_iter.next().unwrap().unwrap().data[0];
 
     // Bits 5-7: sequence identifier, bits 0-4: frame index (0 for the first frame)
@@ -103,3 +107,36 @@ fn test_builder_sequence_id_progresses_between_messages() {
     assert_eq!(second_header & 0x1F, 0);
     assert_ne!(first_header & 0xE0, second_header & 0xE0);
 }
+
+#[test]
+/// Another PGN does not advance the counter of the first one.
+fn test_builder_sequence_id_is_per_pgn() {
+    let payload = [0x55; 10];
+    let mut sequences = SequenceCounter::new();
+
+    let header = |pgn, sequences: &mut SequenceCounter| {
+        FastPacketBuilder::new(pgn, 50, None, &payload, sequences)
+            .build()
+            .next()
+            .unwrap()
+            .unwrap()
+            .data[0]
+    };
+
+    assert_eq!(header(129040, &mut sequences) >> 5, 0);
+    assert_eq!(header(129029, &mut sequences) >> 5, 0);
+    assert_eq!(header(129040, &mut sequences) >> 5, 1);
+}
+
+#[test]
+/// Every frame carries the requested priority.
+fn test_builder_priority_applies_to_all_frames() {
+    let payload = [0x55; 20];
+
+    for frame in FastPacketBuilder::new(129029, 50, None, &payload, &mut SequenceCounter::new())
+        .with_priority(3)
+        .build()
+    {
+        assert_eq!(frame.unwrap().id.priority(), 3);
+    }
+}
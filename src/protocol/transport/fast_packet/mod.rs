@@ -2,9 +2,16 @@
 //! across successive CAN frames.
 /// Maximum payload a Fast Packet can transport once reassembled.
 pub const MAX_FAST_PACKET_PAYLOAD: usize = 223;
+/// Maximum silence tolerated between two frames of the same Fast Packet session (ms).
+///
+/// Matches the 750 ms receive window used by ISO 11783-3 (T1). Past this delay the
+/// session is considered lost and its slot is released.
+pub const FAST_PACKET_SESSION_TIMEOUT_MS: u64 = 750;
 
 pub mod assembler;
 pub mod builder;
+pub mod sequence;
+pub mod transmission;
 
 #[cfg(test)]
 pub mod tests;
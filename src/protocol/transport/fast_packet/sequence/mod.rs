@@ -0,0 +1,126 @@
+//! Fast Packet sequence identifiers, tracked per PGN and source address.
+//!
+//! Receivers separate consecutive messages of the same PGN by the 3-bit sequence
+//! identifier carried in the first byte of every frame. The counter must therefore
+//! advance independently for each PGN (and for each source when a device speaks
+//! under several addresses) instead of being shared by the whole node.
+
+//==================================================================================Constants
+
+/// Default number of (PGN, source) pairs tracked by a [`SequenceCounter`].
+pub const DEFAULT_SEQUENCE_SLOTS: usize = 16;
+
+//==================================================================================Structs
+
+/// Counter state for one (PGN, source) pair.
+#[derive(Debug, Clone, Copy)]
+struct SequenceSlot {
+    pgn: u32,
+    source_address: u8,
+    /// Identifier handed out by the next call to [`SequenceCounter::next`].
+    next_id: u8,
+    /// Value of the store's use counter at the last access, for LRU replacement.
+    last_use: u32,
+    in_use: bool,
+}
+
+impl SequenceSlot {
+    const fn new() -> Self {
+        Self {
+            pgn: 0,
+            source_address: 0,
+            next_id: 0,
+            last_use: 0,
+            in_use: false,
+        }
+    }
+}
+
+/// Bounded store of Fast Packet sequence identifiers keyed by (PGN, source).
+///
+/// Owned by whoever sends the frames (for example `AddressManager`), so no global
+/// state or critical section is involved. When all `N` slots are taken, the pair
+/// unused for the longest time is forgotten and its counter restarts at zero.
+///
+/// ```rust,ignore
+/// let mut sequences = SequenceCounter::new();
+/// let frames = FastPacketBuilder::new(129029, my_address, None, &payload, &mut sequences).build();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SequenceCounter<const N: usize = DEFAULT_SEQUENCE_SLOTS> {
+    slots: [SequenceSlot; N],
+    uses: u32,
+}
+
+impl Default for SequenceCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SequenceCounter {
+    /// Create a default-sized store with every counter at zero.
+    pub const fn new() -> Self {
+        Self::empty()
+    }
+}
+
+impl<const N: usize> SequenceCounter<N> {
+    /// Create a store of any size with every counter at zero.
+    pub const fn empty() -> Self {
+        Self {
+            slots: [SequenceSlot::new(); N],
+            uses: 0,
+        }
+    }
+
+    /// Return the sequence identifier (0-7) for the next message of `pgn` sent
+    /// by `source_address`, and advance that pair's counter.
+    pub fn next(&mut self, pgn: u32, source_address: u8) -> u8 {
+        self.uses = self.uses.wrapping_add(1);
+        let uses = self.uses;
+
+        let index = self
+            .slots
+            .iter()
+            .position(|s| s.in_use && s.pgn == pgn && s.source_address == source_address)
+            .or_else(|| self.slots.iter().position(|s| !s.in_use))
+            .or_else(|| self.least_recently_used());
+
+        let Some(index) = index else {
+            // Zero-sized store: nothing to remember.
+            return 0;
+        };
+
+        let slot = &mut self.slots[index];
+        if !slot.in_use || slot.pgn != pgn || slot.source_address != source_address {
+            *slot = SequenceSlot {
+                pgn,
+                source_address,
+                next_id: 0,
+                last_use: uses,
+                in_use: true,
+            };
+        }
+
+        let id = slot.next_id;
+        slot.next_id = (id + 1) & 0x07;
+        slot.last_use = uses;
+        id
+    }
+
+    /// Index of the slot accessed the longest time ago.
+    fn least_recently_used(&self) -> Option<usize> {
+        let uses = self.uses;
+        self.slots
+            .iter()
+            .enumerate()
+            // Age relative to the current use survives the counter wrapping around.
+            .max_by_key(|(_, s)| uses.wrapping_sub(s.last_use))
+            .map(|(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
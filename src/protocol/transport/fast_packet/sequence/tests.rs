@@ -0,0 +1,51 @@
+//! Sequence counter tests: per-key progression, wrap-around, and slot reuse.
+// SEQUENCE
+use super::*;
+
+#[test]
+/// Each PGN advances its own counter.
+fn test_counters_are_independent_per_pgn() {
+    let mut sequences = SequenceCounter::new();
+
+    assert_eq!(sequences.next(129029, 10), 0);
+    assert_eq!(sequences.next(129029, 10), 1);
+    assert_eq!(sequences.next(129540, 10), 0);
+    assert_eq!(sequences.next(129029, 10), 2);
+    assert_eq!(sequences.next(129540, 10), 1);
+}
+
+#[test]
+/// Virtual sources sharing a PGN get distinct counters.
+fn test_counters_are_independent_per_source() {
+    let mut sequences = SequenceCounter::new();
+
+    assert_eq!(sequences.next(129029, 10), 0);
+    assert_eq!(sequences.next(129029, 11), 0);
+    assert_eq!(sequences.next(129029, 10), 1);
+}
+
+#[test]
+/// The identifier stays on three bits.
+fn test_counter_wraps_after_seven() {
+    let mut sequences = SequenceCounter::new();
+
+    for expected in 0..8 {
+        assert_eq!(sequences.next(126996, 1), expected);
+    }
+    assert_eq!(sequences.next(126996, 1), 0);
+}
+
+#[test]
+/// A full store forgets the least recently used pair.
+fn test_full_store_reuses_oldest_slot() {
+    let mut sequences: SequenceCounter<2> = SequenceCounter::empty();
+
+    sequences.next(1, 1);
+    sequences.next(2, 1);
+    sequences.next(1, 1); // pair (2, 1) is now the oldest
+
+    assert_eq!(sequences.next(3, 1), 0);
+    // Pair (1, 1) kept its progression; pair (2, 1) starts over.
+    assert_eq!(sequences.next(1, 1), 2);
+    assert_eq!(sequences.next(2, 1), 0);
+}
@@ -2,6 +2,7 @@
 use crate::protocol::transport::fast_packet::{
     assembler::{FastPacketAssembler, ProcessResult},
     builder::FastPacketBuilder,
+    sequence::SequenceCounter,
 };
 
 #[test]
@@ -10,16 +11,18 @@ fn test_roundtrip_15_bytes() {
     let original = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
 
     // Fragmentation
-    let builder = FastPacketBuilder::new(129540, 42, None, &original);
-    let mut iter = builder.build();
+    let builder = FastPacketBuilder::new(129540, 42, None, &original, &mut SequenceCounter::new());
+    let iter = builder.build();
 
     // Reassembly
     let mut assembler = FastPacketAssembler::new();
     let mut result = None;
 
-    while let Some(frame_result) = iter.next() {
+    for frame_result in iter {
         let frame = frame_result.unwrap();
-        if let ProcessResult::MessageComplete(msg) = assembler.process_frame(42, &frame.data) {
+        if let ProcessResult::MessageComplete(msg) =
+            assembler.process_frame(frame.id, &frame.data, 0)
+        {
             result = Some(msg);
             break;
         }
@@ -36,15 +39,17 @@ fn test_roundtrip_max_payload() {
     let original = [0x42; 223];
 
     // PGN 129540 is PDU2 (broadcast)
-    let builder = FastPacketBuilder::new(129540, 30, None, &original);
-    let mut iter = builder.build();
+    let builder = FastPacketBuilder::new(129540, 30, None, &original, &mut SequenceCounter::new());
+    let iter = builder.build();
 
     let mut assembler = FastPacketAssembler::new();
     let mut result = None;
 
-    while let Some(frame_result) = iter.next() {
+    for frame_result in iter {
         let frame = frame_result.unwrap();
-        if let ProcessResult::MessageComplete(msg) = assembler.process_frame(30, &frame.data) {
+        if let ProcessResult::MessageComplete(msg) =
+            assembler.process_frame(frame.id, &frame.data, 0)
+        {
             result = Some(msg);
             break;
         }
@@ -63,8 +68,10 @@ fn test_roundtrip_with_interleaved_frames() {
         0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
     ];
 
-    let builder_a = FastPacketBuilder::new(129540, 10, None, &payload_a);
-    let builder_b = FastPacketBuilder::new(129025, 20, None, &payload_b);
+    let builder_a =
+        FastPacketBuilder::new(129540, 10, None, &payload_a, &mut SequenceCounter::new());
+    let builder_b =
+        FastPacketBuilder::new(129025, 20, None, &payload_b, &mut SequenceCounter::new());
 
     let mut iter_a = builder_a.build();
     let mut iter_b = builder_b.build();
@@ -77,13 +84,15 @@ fn test_roundtrip_with_interleaved_frames() {
         let mut done_b = false;
 
         if let Some(frame_result) = iter_a.next() {
-            assembler.process_frame(10, &frame_result.unwrap().data);
+            let frame = frame_result.unwrap();
+            assembler.process_frame(frame.id, &frame.data, 0);
         } else {
             done_a = true;
         }
 
         if let Some(frame_result) = iter_b.next() {
-            let result = assembler.process_frame(20, &frame_result.unwrap().data);
+            let frame = frame_result.unwrap();
+            let result = assembler.process_frame(frame.id, &frame.data, 0);
             if let ProcessResult::MessageComplete(msg) = result {
                 // Stream B completes first (shorter payload)
                 assert_eq!(msg.len, 15);
@@ -0,0 +1,182 @@
+//! Poll-driven transmission of one PGN, free of any executor or timer.
+//!
+//! [`PgnTransmission`] owns a serialized payload and hands out its frames
+//! through [`on_tick`](PgnTransmission::on_tick), spacing the frames of a
+//! Fast Packet by [`FAST_PACKET_INTER_FRAME_DELAY_MS`]. The async
+//! [`PgnSender`](crate::protocol::transport::traits::pgn_sender::PgnSender) and
+//! [`AddressManager`](crate::protocol::managment::address_manager::AddressManager)
+//! are thin wrappers around it.
+//!
+//! ```rust,ignore
+//! let mut transmission =
+//!     PgnTransmission::new(&heading, 127250, my_address, None, &mut sequences)?;
+//! while !transmission.is_complete() {
+//!     if let Some(frame) = transmission.on_tick(monotonic_ms()) {
+//!         can.transmit(&frame?);
+//!     }
+//! }
+//! ```
+use crate::{
+    error::{CanIdBuildError, SerializationError},
+    infra::codec::traits::PgnData,
+    protocol::transport::{
+        can_frame::CanFrame,
+        fast_packet::{
+            builder::{next_sequence_id, FastPacketBuilder, FramePosition},
+            sequence::SequenceCounter,
+            MAX_FAST_PACKET_PAYLOAD,
+        },
+        FAST_PACKET_INTER_FRAME_DELAY_MS,
+    },
+};
+
+/// One PGN being transmitted, frame by frame.
+#[derive(Debug, Clone)]
+pub struct PgnTransmission {
+    pgn: u32,
+    priority: u8,
+    source_address: u8,
+    destination: Option<u8>,
+    /// Fast Packet sequence identifier, drawn when the transmission is prepared.
+    sequence_id: u8,
+    payload: [u8; MAX_FAST_PACKET_PAYLOAD],
+    len: usize,
+    position: FramePosition,
+    /// Earliest time of the next frame; `None` sends it at once.
+    next_frame_ms: Option<u64>,
+    complete: bool,
+}
+
+impl PgnTransmission {
+    /// Serialize `pgn_data` and prepare its frames with the default priority (6).
+    ///
+    /// Multi-frame messages take the next sequence identifier of this PGN and
+    /// source in `sequences`.
+    #[allow(clippy::result_large_err)]
+    pub fn new<P: PgnData, const N: usize>(
+        pgn_data: &P,
+        pgn: u32,
+        source_address: u8,
+        destination: Option<u8>,
+        sequences: &mut SequenceCounter<N>,
+    ) -> Result<Self, SerializationError> {
+        let mut payload = [0u8; MAX_FAST_PACKET_PAYLOAD];
+        let len = pgn_data.to_payload(&mut payload)?;
+        Ok(Self::prepare(
+            pgn,
+            source_address,
+            destination,
+            payload,
+            len,
+            sequences,
+        ))
+    }
+
+    /// Prepare the frames of an already serialized payload (up to 223 bytes).
+    ///
+    /// Returns [`CanIdBuildError::InvalidData`] when `payload` does not fit in
+    /// a Fast Packet.
+    pub fn from_payload<const N: usize>(
+        pgn: u32,
+        source_address: u8,
+        destination: Option<u8>,
+        payload: &[u8],
+        sequences: &mut SequenceCounter<N>,
+    ) -> Result<Self, CanIdBuildError> {
+        let mut buffer = [0u8; MAX_FAST_PACKET_PAYLOAD];
+        buffer
+            .get_mut(..payload.len())
+            .ok_or(CanIdBuildError::InvalidData)?
+            .copy_from_slice(payload);
+        Ok(Self::prepare(
+            pgn,
+            source_address,
+            destination,
+            buffer,
+            payload.len(),
+            sequences,
+        ))
+    }
+
+    /// Set the priority (3 bits) of every frame; 6 when not specified.
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority & 0x07;
+        self
+    }
+
+    /// True once every frame was handed out.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Time at which [`on_tick`](Self::on_tick) yields the next frame, if any.
+    pub fn next_deadline(&self) -> Option<u64> {
+        if self.complete {
+            None
+        } else {
+            Some(self.next_frame_ms.unwrap_or(0))
+        }
+    }
+
+    /// Return the next frame once it is due at `now_ms`.
+    ///
+    /// The first frame is due at once, each following one
+    /// [`FAST_PACKET_INTER_FRAME_DELAY_MS`] after its predecessor. A frame
+    /// that cannot be built ends the transmission.
+    pub fn on_tick(&mut self, now_ms: u64) -> Option<Result<CanFrame, CanIdBuildError>> {
+        if self.complete || self.next_frame_ms.is_some_and(|due_ms| now_ms < due_ms) {
+            return None;
+        }
+
+        let mut frames = FastPacketBuilder::from_parts(
+            self.pgn,
+            self.source_address,
+            self.destination,
+            &self.payload[..self.len],
+            self.sequence_id,
+        )
+        .with_priority(self.priority)
+        .resume(self.position);
+        let frame = frames.next();
+        self.position = frames.position();
+
+        match frame {
+            Some(Ok(frame)) => {
+                self.complete = frames.is_exhausted();
+                self.next_frame_ms =
+                    Some(now_ms.saturating_add(FAST_PACKET_INTER_FRAME_DELAY_MS as u64));
+                Some(Ok(frame))
+            }
+            other => {
+                self.complete = true;
+                other
+            }
+        }
+    }
+
+    fn prepare<const N: usize>(
+        pgn: u32,
+        source_address: u8,
+        destination: Option<u8>,
+        payload: [u8; MAX_FAST_PACKET_PAYLOAD],
+        len: usize,
+        sequences: &mut SequenceCounter<N>,
+    ) -> Self {
+        Self {
+            pgn,
+            priority: 6,
+            source_address,
+            destination,
+            sequence_id: next_sequence_id(pgn, source_address, len, sequences),
+            payload,
+            len,
+            position: FramePosition::default(),
+            next_frame_ms: None,
+            complete: false,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,72 @@
+//! Poll-driven PGN transmission tests: pacing, sequence, and limits.
+// TRANSMISSION
+use super::*;
+
+#[test]
+/// A single-frame payload goes out at once and completes the transmission.
+fn test_single_frame() {
+    let mut sequences = SequenceCounter::new();
+    let mut transmission =
+        PgnTransmission::from_payload(127250, 42, None, &[1, 2, 3], &mut sequences).unwrap();
+    assert_eq!(transmission.next_deadline(), Some(0));
+
+    let frame = transmission.on_tick(1000).unwrap().unwrap();
+    assert_eq!(frame.id.pgn(), 127250);
+    assert_eq!(&frame.data[..frame.len], &[1, 2, 3]);
+    assert!(transmission.is_complete());
+    assert!(transmission.on_tick(2000).is_none());
+    assert_eq!(transmission.next_deadline(), None);
+}
+
+#[test]
+/// Fast Packet frames are spaced by the inter-frame delay.
+fn test_fast_packet_pacing() {
+    let payload: [u8; 20] = core::array::from_fn(|i| i as u8);
+    let mut sequences = SequenceCounter::new();
+    let mut transmission =
+        PgnTransmission::from_payload(129029, 42, None, &payload, &mut sequences)
+            .unwrap()
+            .with_priority(3);
+
+    let first = transmission.on_tick(100).unwrap().unwrap();
+    assert_eq!(first.id.priority(), 3);
+    assert_eq!(first.data[1], 20, "first frame carries the length");
+
+    let due = 100 + FAST_PACKET_INTER_FRAME_DELAY_MS as u64;
+    assert_eq!(transmission.next_deadline(), Some(due));
+    assert!(transmission.on_tick(due - 1).is_none());
+    let second = transmission.on_tick(due).unwrap().unwrap();
+    assert_eq!(second.data[0] & 0x1F, 1);
+    assert!(!transmission.is_complete());
+
+    let third = transmission.on_tick(due + 10).unwrap().unwrap();
+    assert_eq!(third.data[0] & 0x1F, 2);
+    assert_eq!(&third.data[1..third.len], &payload[13..]);
+    assert!(transmission.is_complete());
+}
+
+#[test]
+/// Consecutive transmissions of the same PGN take consecutive sequence identifiers.
+fn test_sequence_from_counter() {
+    let payload = [0u8; 12];
+    let mut sequences = SequenceCounter::new();
+
+    let mut first =
+        PgnTransmission::from_payload(129029, 42, None, &payload, &mut sequences).unwrap();
+    let mut second =
+        PgnTransmission::from_payload(129029, 42, None, &payload, &mut sequences).unwrap();
+
+    let first_id = first.on_tick(0).unwrap().unwrap().data[0] >> 5;
+    let second_id = second.on_tick(0).unwrap().unwrap().data[0] >> 5;
+    assert_eq!(second_id, (first_id + 1) & 0x07);
+}
+
+#[test]
+/// Payloads beyond the Fast Packet capacity are rejected.
+fn test_oversized_payload() {
+    let payload = [0u8; MAX_FAST_PACKET_PAYLOAD + 1];
+    assert!(matches!(
+        PgnTransmission::from_payload(129029, 42, None, &payload, &mut SequenceCounter::new()),
+        Err(CanIdBuildError::InvalidData)
+    ));
+}
@@ -0,0 +1,235 @@
+//! ISO transport BAM assembler: rebuilds broadcast multi-packet messages from
+//! the TP.CM_BAM announcement and the TP.DT packets that follow it.
+use crate::protocol::transport::can_frame::CanFrame;
+use crate::protocol::transport::iso_tp::{
+    packet_count, MAX_ISO_TP_PAYLOAD, MIN_ISO_TP_PAYLOAD, TP_CM_BAM, TP_CM_PGN, TP_DT_DATA_BYTES,
+    TP_DT_PGN, TP_GLOBAL_ADDRESS, TP_T1_TIMEOUT_MS,
+};
+
+//==================================================================================Constants
+
+/// Maximum number of BAM sessions handled in parallel (distinct sources).
+///
+/// Each session owns a 1785-byte buffer; a sender runs at most one BAM at a time.
+const MAX_CONCURRENT_SESSIONS: usize = 2;
+
+//==================================================================================Enums and Structs
+// The completed message is handed over by value: the crate does not allocate.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum ProcessResult {
+    /// Frame is not a broadcast TP.CM / TP.DT frame, or was discarded
+    /// (unexpected sequence, session pool exhausted, etc.).
+    Ignored,
+    /// Frame successfully integrated but additional packets are still missing.
+    FragmentConsumed,
+    /// Every announced packet was received; the complete message is now available.
+    MessageComplete(CompletedMessage),
+}
+
+/// Reassembled broadcast message, detached from the assembler's internal buffer.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CompletedMessage {
+    /// PGN announced in the TP.CM_BAM frame.
+    pub pgn: u32,
+    /// Address of the node that broadcast the message.
+    pub source_address: u8,
+    /// Reassembled payload.
+    pub payload: [u8; MAX_ISO_TP_PAYLOAD],
+    /// Effective message length (number of valid bytes).
+    pub len: usize,
+}
+
+/// Possible states for a reassembly session.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum SessionState {
+    Inactive,
+    InProgress,
+}
+
+/// Internal structure tracking the state of a BAM session.
+#[derive(Debug, Clone, Copy)]
+struct BamSession {
+    state: SessionState,
+    source_address: u8,
+    pgn: u32,
+    buffer: [u8; MAX_ISO_TP_PAYLOAD],
+    expected_size: usize,
+    total_packets: u8,
+    last_sequence: u8,
+    last_frame_ms: u64,
+}
+
+impl BamSession {
+    /// Create a session in the inactive state.
+    const fn new() -> Self {
+        Self {
+            state: SessionState::Inactive,
+            source_address: 0,
+            pgn: 0,
+            buffer: [0; MAX_ISO_TP_PAYLOAD],
+            expected_size: 0,
+            total_packets: 0,
+            last_sequence: 0,
+            last_frame_ms: 0,
+        }
+    }
+
+    /// Reset the session and make it available again.
+    fn reset(&mut self) {
+        self.state = SessionState::Inactive;
+        self.pgn = 0;
+        self.expected_size = 0;
+        self.total_packets = 0;
+        self.last_sequence = 0;
+        // No need to wipe the buffer; upcoming copies will overwrite it.
+    }
+
+    /// True when the T1 timer elapsed since the last packet.
+    fn is_expired(&self, now_ms: u64) -> bool {
+        now_ms.saturating_sub(self.last_frame_ms) > TP_T1_TIMEOUT_MS
+    }
+}
+
+/// Main assembler: owns a fixed pool of reusable sessions.
+#[derive(Debug, Clone)]
+pub struct BamAssembler {
+    sessions: [BamSession; MAX_CONCURRENT_SESSIONS],
+}
+
+impl Default for BamAssembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BamAssembler {
+    /// Instantiate the assembler with an inactive session pool.
+    pub const fn new() -> Self {
+        Self {
+            sessions: [BamSession::new(); MAX_CONCURRENT_SESSIONS],
+        }
+    }
+
+    //==================================================================================Process Functions
+    /// Process a CAN frame that may belong to a BAM session.
+    ///
+    /// * `frame` – received CAN frame (any PGN; non-transport frames are ignored)
+    /// * `now_ms` – monotonic timestamp of the reception, in milliseconds
+    ///
+    /// Sessions silent for longer than [`TP_T1_TIMEOUT_MS`] are dropped before
+    /// the frame is handled.
+    pub fn process_frame(&mut self, frame: &CanFrame, now_ms: u64) -> ProcessResult {
+        self.evict_expired(now_ms);
+
+        if frame.len < 8 || frame.id.destination() != Some(TP_GLOBAL_ADDRESS) {
+            return ProcessResult::Ignored;
+        }
+
+        let source_address = frame.id.source_address();
+        match frame.id.pgn() {
+            TP_CM_PGN if frame.data[0] == TP_CM_BAM => {
+                self.open_session(source_address, &frame.data, now_ms)
+            }
+            TP_DT_PGN => self.consume_packet(source_address, &frame.data, now_ms),
+            _ => ProcessResult::Ignored,
+        }
+    }
+
+    /// Drop every session whose T1 timer elapsed.
+    pub fn evict_expired(&mut self, now_ms: u64) {
+        for session in self.sessions.iter_mut() {
+            if session.state == SessionState::InProgress && session.is_expired(now_ms) {
+                session.reset();
+            }
+        }
+    }
+
+    /// Handle a TP.CM_BAM announcement.
+    fn open_session(&mut self, source_address: u8, data: &[u8; 8], now_ms: u64) -> ProcessResult {
+        let expected_size = u16::from_le_bytes([data[1], data[2]]) as usize;
+        let total_packets = data[3];
+        let pgn = u32::from_le_bytes([data[5], data[6], data[7], 0]);
+
+        if !(MIN_ISO_TP_PAYLOAD..=MAX_ISO_TP_PAYLOAD).contains(&expected_size)
+            || total_packets as usize != packet_count(expected_size)
+        {
+            return ProcessResult::Ignored;
+        }
+
+        // A new announcement from the same source supersedes the running session.
+        let session_index = self
+            .sessions
+            .iter()
+            .position(|s| s.state == SessionState::InProgress && s.source_address == source_address)
+            .or_else(|| {
+                self.sessions
+                    .iter()
+                    .position(|s| s.state == SessionState::Inactive)
+            });
+
+        let Some(index) = session_index else {
+            return ProcessResult::Ignored;
+        };
+
+        let session = &mut self.sessions[index];
+        session.state = SessionState::InProgress;
+        session.source_address = source_address;
+        session.pgn = pgn;
+        session.expected_size = expected_size;
+        session.total_packets = total_packets;
+        session.last_sequence = 0;
+        session.last_frame_ms = now_ms;
+
+        ProcessResult::FragmentConsumed
+    }
+
+    /// Handle a TP.DT packet.
+    fn consume_packet(&mut self, source_address: u8, data: &[u8; 8], now_ms: u64) -> ProcessResult {
+        let Some(session) = self
+            .sessions
+            .iter_mut()
+            .find(|s| s.state == SessionState::InProgress && s.source_address == source_address)
+        else {
+            return ProcessResult::Ignored;
+        };
+
+        let sequence = data[0];
+        if sequence != session.last_sequence.wrapping_add(1) || sequence > session.total_packets {
+            session.reset();
+            return ProcessResult::Ignored;
+        }
+
+        session.last_sequence = sequence;
+        session.last_frame_ms = now_ms;
+
+        let offset = (sequence as usize - 1) * TP_DT_DATA_BYTES;
+        let copy_len = (session.expected_size - offset).min(TP_DT_DATA_BYTES);
+        session.buffer[offset..offset + copy_len].copy_from_slice(&data[1..1 + copy_len]);
+
+        if sequence < session.total_packets {
+            return ProcessResult::FragmentConsumed;
+        }
+
+        // Copy the complete message into a dedicated return structure.
+        let mut payload = [0; MAX_ISO_TP_PAYLOAD];
+        let len = session.expected_size;
+        payload[..len].copy_from_slice(&session.buffer[..len]);
+
+        let completed_message = CompletedMessage {
+            pgn: session.pgn,
+            source_address,
+            payload,
+            len,
+        };
+
+        // Release the session for future messages.
+        session.reset();
+
+        ProcessResult::MessageComplete(completed_message)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
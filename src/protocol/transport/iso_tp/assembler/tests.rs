@@ -0,0 +1,148 @@
+//! BAM reassembly tests covering sequencing, T1 timeout, and session handling.
+// ASSEMBLER
+use super::*;
+use crate::protocol::transport::can_id::CanId;
+
+/// Build a broadcast TP.CM or TP.DT frame from `source`.
+fn tp_frame(pgn: u32, source: u8, data: [u8; 8]) -> CanFrame {
+    CanFrame {
+        id: CanId::builder(pgn, source)
+            .with_priority(7)
+            .to_destination(TP_GLOBAL_ADDRESS)
+            .build()
+            .unwrap(),
+        data,
+        len: 8,
+    }
+}
+
+/// TP.CM_BAM announcing a 10-byte PGN 126464 in two packets.
+fn bam_10_bytes(source: u8) -> CanFrame {
+    tp_frame(TP_CM_PGN, source, [32, 10, 0, 2, 0xFF, 0x00, 0xEE, 0x01])
+}
+
+#[test]
+/// Rebuild a 10-byte message from an announcement and two packets.
+fn test_bam_reassembly() {
+    let mut assembler = BamAssembler::new();
+
+    let result = assembler.process_frame(&bam_10_bytes(42), 0);
+    assert!(matches!(result, ProcessResult::FragmentConsumed));
+
+    let dt1 = tp_frame(TP_DT_PGN, 42, [1, 1, 2, 3, 4, 5, 6, 7]);
+    let result = assembler.process_frame(&dt1, 50);
+    assert!(matches!(result, ProcessResult::FragmentConsumed));
+
+    let dt2 = tp_frame(TP_DT_PGN, 42, [2, 8, 9, 10, 0xFF, 0xFF, 0xFF, 0xFF]);
+    match assembler.process_frame(&dt2, 100) {
+        ProcessResult::MessageComplete(msg) => {
+            assert_eq!(msg.pgn, 126464);
+            assert_eq!(msg.source_address, 42);
+            assert_eq!(msg.len, 10);
+            assert_eq!(&msg.payload[..10], &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        }
+        other => panic!("expected a complete message, got {other:?}"),
+    }
+}
+
+#[test]
+/// A skipped sequence number aborts the session.
+fn test_bam_out_of_sequence_packet() {
+    let mut assembler = BamAssembler::new();
+    assembler.process_frame(&bam_10_bytes(42), 0);
+
+    let dt2 = tp_frame(TP_DT_PGN, 42, [2, 8, 9, 10, 0xFF, 0xFF, 0xFF, 0xFF]);
+    assert!(matches!(
+        assembler.process_frame(&dt2, 50),
+        ProcessResult::Ignored
+    ));
+
+    // The session is gone: even the right packet is now ignored.
+    let dt1 = tp_frame(TP_DT_PGN, 42, [1, 1, 2, 3, 4, 5, 6, 7]);
+    assert!(matches!(
+        assembler.process_frame(&dt1, 60),
+        ProcessResult::Ignored
+    ));
+}
+
+#[test]
+/// A session silent for more than T1 is dropped.
+fn test_bam_t1_timeout() {
+    let mut assembler = BamAssembler::new();
+    assembler.process_frame(&bam_10_bytes(42), 0);
+
+    let dt1 = tp_frame(TP_DT_PGN, 42, [1, 1, 2, 3, 4, 5, 6, 7]);
+    assembler.process_frame(&dt1, 100);
+
+    let dt2 = tp_frame(TP_DT_PGN, 42, [2, 8, 9, 10, 0xFF, 0xFF, 0xFF, 0xFF]);
+    assert!(matches!(
+        assembler.process_frame(&dt2, 100 + TP_T1_TIMEOUT_MS + 1),
+        ProcessResult::Ignored
+    ));
+}
+
+#[test]
+/// Announcements with an inconsistent size or packet count are rejected.
+fn test_bam_invalid_announcement() {
+    let mut assembler = BamAssembler::new();
+
+    // 10 bytes announced in 3 packets.
+    let bad_count = tp_frame(TP_CM_PGN, 1, [32, 10, 0, 3, 0xFF, 0x00, 0xEE, 0x01]);
+    assert!(matches!(
+        assembler.process_frame(&bad_count, 0),
+        ProcessResult::Ignored
+    ));
+
+    // 1786 bytes exceeds the transport limit.
+    let too_big = tp_frame(TP_CM_PGN, 1, [32, 0xFA, 0x06, 255, 0xFF, 0x00, 0xEE, 0x01]);
+    assert!(matches!(
+        assembler.process_frame(&too_big, 0),
+        ProcessResult::Ignored
+    ));
+}
+
+#[test]
+/// Frames addressed to a specific node belong to connection mode, not BAM.
+fn test_bam_ignores_addressed_frames() {
+    let mut assembler = BamAssembler::new();
+    let frame = CanFrame {
+        id: CanId::builder(TP_CM_PGN, 42)
+            .to_destination(10)
+            .build()
+            .unwrap(),
+        data: [32, 10, 0, 2, 0xFF, 0x00, 0xEE, 0x01],
+        len: 8,
+    };
+    assert!(matches!(
+        assembler.process_frame(&frame, 0),
+        ProcessResult::Ignored
+    ));
+}
+
+#[test]
+/// Two sources broadcast interleaved messages; both complete independently.
+fn test_bam_concurrent_sources() {
+    let mut assembler = BamAssembler::new();
+    assembler.process_frame(&bam_10_bytes(1), 0);
+    assembler.process_frame(&bam_10_bytes(2), 0);
+
+    for source in [1, 2] {
+        let dt1 = tp_frame(TP_DT_PGN, source, [1, source, 0, 0, 0, 0, 0, 0]);
+        assert!(matches!(
+            assembler.process_frame(&dt1, 10),
+            ProcessResult::FragmentConsumed
+        ));
+    }
+
+    for source in [2, 1] {
+        let dt2 = tp_frame(TP_DT_PGN, source, [2, 0, 0, source, 0xFF, 0xFF, 0xFF, 0xFF]);
+        match assembler.process_frame(&dt2, 20) {
+            ProcessResult::MessageComplete(msg) => {
+                assert_eq!(msg.source_address, source);
+                assert_eq!(msg.payload[0], source);
+                assert_eq!(msg.payload[9], source);
+            }
+            other => panic!("expected a complete message, got {other:?}"),
+        }
+    }
+}
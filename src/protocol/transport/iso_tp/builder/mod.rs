@@ -0,0 +1,149 @@
+//! CAN frame generator for ISO transport BAM sessions: yields the TP.CM_BAM
+//! announcement followed by the numbered TP.DT packets.
+use crate::error::CanIdBuildError;
+use crate::protocol::transport::can_frame::CanFrame;
+use crate::protocol::transport::can_id::CanId;
+use crate::protocol::transport::iso_tp::{
+    packet_count, MAX_ISO_TP_PAYLOAD, MIN_ISO_TP_PAYLOAD, TP_CM_BAM, TP_CM_PGN, TP_DT_DATA_BYTES,
+    TP_DT_PGN, TP_GLOBAL_ADDRESS, TP_PRIORITY,
+};
+
+#[derive(Debug)]
+/// Parameters shared by every frame of a broadcast transport session.
+pub struct BamBuilder<'a> {
+    pgn: u32,
+    source_address: u8,
+    payload: &'a [u8],
+    priority: u8,
+}
+
+/// Lazy iterator returning the announcement, then each data packet.
+pub struct BamFrameIterator<'a> {
+    builder: BamBuilder<'a>,
+    /// `0` for the TP.CM_BAM frame, then the TP.DT sequence number.
+    next_packet: usize,
+    done: bool,
+}
+
+impl<'a> Iterator for BamFrameIterator<'a> {
+    type Item = Result<CanFrame, CanIdBuildError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let payload = self.builder.payload;
+        if payload.is_empty() {
+            self.done = true;
+            return Some(Err(CanIdBuildError::EmptyPayload));
+        }
+        if !(MIN_ISO_TP_PAYLOAD..=MAX_ISO_TP_PAYLOAD).contains(&payload.len()) {
+            self.done = true;
+            return Some(Err(CanIdBuildError::InvalidData));
+        }
+
+        let total_packets = packet_count(payload.len());
+
+        if self.next_packet == 0 {
+            let id = match CanId::builder(TP_CM_PGN, self.builder.source_address)
+                .with_priority(self.builder.priority)
+                .to_destination(TP_GLOBAL_ADDRESS)
+                .build()
+            {
+                Ok(id) => id,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+
+            self.next_packet = 1;
+            return Some(Ok(CanFrame {
+                id,
+                data: encode_bam(payload.len(), self.builder.pgn),
+                len: 8,
+            }));
+        }
+
+        let id = match CanId::builder(TP_DT_PGN, self.builder.source_address)
+            .with_priority(self.builder.priority)
+            .to_destination(TP_GLOBAL_ADDRESS)
+            .build()
+        {
+            Ok(id) => id,
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        };
+
+        let sequence = self.next_packet;
+        let start = (sequence - 1) * TP_DT_DATA_BYTES;
+        let end = (start + TP_DT_DATA_BYTES).min(payload.len());
+
+        // Unused bytes of the last packet are padded with 0xFF.
+        let mut data = [0xFF; 8];
+        data[0] = sequence as u8;
+        data[1..1 + (end - start)].copy_from_slice(&payload[start..end]);
+
+        if sequence >= total_packets {
+            self.done = true;
+        } else {
+            self.next_packet += 1;
+        }
+
+        Some(Ok(CanFrame { id, data, len: 8 }))
+    }
+}
+
+impl<'a> BamBuilder<'a> {
+    /// Prepare a broadcast transport session for `payload` (9 to 1785 bytes).
+    ///
+    /// * `pgn` – PGN carried by the session (announced in the TP.CM_BAM frame)
+    /// * `source_address` – logical address of the sender
+    /// * `payload` – serialized PGN
+    pub fn new(pgn: u32, source_address: u8, payload: &'a [u8]) -> Self {
+        Self {
+            pgn,
+            source_address,
+            payload,
+            priority: TP_PRIORITY,
+        }
+    }
+
+    /// Override the priority of the TP.CM and TP.DT frames (default 7).
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority & 0x07;
+        self
+    }
+
+    /// Start the iteration; the first item is the TP.CM_BAM announcement.
+    pub fn build(self) -> BamFrameIterator<'a> {
+        BamFrameIterator {
+            builder: self,
+            next_packet: 0,
+            done: false,
+        }
+    }
+}
+
+/// Encode the TP.CM_BAM payload: control byte, size, packet count, reserved, PGN.
+fn encode_bam(payload_len: usize, pgn: u32) -> [u8; 8] {
+    let size = (payload_len as u16).to_le_bytes();
+    let pgn = pgn.to_le_bytes();
+    [
+        TP_CM_BAM,
+        size[0],
+        size[1],
+        packet_count(payload_len) as u8,
+        0xFF,
+        pgn[0],
+        pgn[1],
+        pgn[2],
+    ]
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,90 @@
+//! Tests for the ISO transport BAM frame generator (`BamFrameIterator`).
+// BUILDER
+use super::*;
+
+#[test]
+/// Twenty-byte payload: announcement followed by three data packets.
+fn test_bam_builder_frame_layout() {
+    let payload: [u8; 20] = core::array::from_fn(|i| i as u8 + 1);
+    let mut iter = BamBuilder::new(126464, 35, &payload).build();
+
+    let cm = iter.next().unwrap().unwrap();
+    assert_eq!(cm.id.pgn(), TP_CM_PGN);
+    assert_eq!(cm.id.destination(), Some(TP_GLOBAL_ADDRESS));
+    assert_eq!(cm.id.source_address(), 35);
+    assert_eq!(cm.id.priority(), TP_PRIORITY);
+    // BAM, size = 20, 3 packets, reserved, PGN 126464 (0x01EE00) little-endian
+    assert_eq!(cm.data, [32, 20, 0, 3, 0xFF, 0x00, 0xEE, 0x01]);
+
+    let dt1 = iter.next().unwrap().unwrap();
+    assert_eq!(dt1.id.pgn(), TP_DT_PGN);
+    assert_eq!(dt1.data, [1, 1, 2, 3, 4, 5, 6, 7]);
+
+    let dt2 = iter.next().unwrap().unwrap();
+    assert_eq!(dt2.data, [2, 8, 9, 10, 11, 12, 13, 14]);
+
+    // Last packet carries six bytes and one byte of padding.
+    let dt3 = iter.next().unwrap().unwrap();
+    assert_eq!(dt3.data, [3, 15, 16, 17, 18, 19, 20, 0xFF]);
+    assert_eq!(dt3.len, 8);
+
+    assert!(iter.next().is_none());
+}
+
+#[test]
+/// Largest payload: 255 packets, size field encoded on two bytes.
+fn test_bam_builder_max_payload() {
+    let payload = [0xAB; MAX_ISO_TP_PAYLOAD];
+    let mut iter = BamBuilder::new(126996, 1, &payload).build();
+
+    let cm = iter.next().unwrap().unwrap();
+    assert_eq!(u16::from_le_bytes([cm.data[1], cm.data[2]]), 1785);
+    assert_eq!(cm.data[3], 255);
+
+    let mut count = 0;
+    let mut last_sequence = 0;
+    for frame in iter {
+        count += 1;
+        last_sequence = frame.unwrap().data[0];
+    }
+    assert_eq!(count, 255);
+    assert_eq!(last_sequence, 255);
+}
+
+#[test]
+/// Payloads outside 9..=1785 bytes are rejected once, then the iterator ends.
+fn test_bam_builder_rejects_invalid_sizes() {
+    let short = [0u8; 8];
+    let mut iter = BamBuilder::new(126464, 1, &short).build();
+    assert!(matches!(
+        iter.next(),
+        Some(Err(CanIdBuildError::InvalidData))
+    ));
+    assert!(iter.next().is_none());
+
+    let long = [0u8; MAX_ISO_TP_PAYLOAD + 1];
+    let mut iter = BamBuilder::new(126464, 1, &long).build();
+    assert!(matches!(
+        iter.next(),
+        Some(Err(CanIdBuildError::InvalidData))
+    ));
+    assert!(iter.next().is_none());
+
+    let mut iter = BamBuilder::new(126464, 1, &[]).build();
+    assert!(matches!(
+        iter.next(),
+        Some(Err(CanIdBuildError::EmptyPayload))
+    ));
+}
+
+#[test]
+/// Custom priority propagates to every frame.
+fn test_bam_builder_with_priority() {
+    let payload = [0u8; 10];
+    for frame in BamBuilder::new(126464, 1, &payload)
+        .with_priority(3)
+        .build()
+    {
+        assert_eq!(frame.unwrap().id.priority(), 3);
+    }
+}
@@ -0,0 +1,729 @@
+//! Connection-mode ISO transport sessions (RTS/CTS/EndOfMsgAck/Abort).
+//!
+//! [`SessionTable`] is a bounded, poll-driven state machine: it never touches
+//! the bus. Callers feed it the TP.CM / TP.DT frames addressed to the local
+//! node together with a millisecond timestamp, send back the frames it hands
+//! out, and call [`SessionTable::poll_timeouts`] to enforce T1–T4.
+//!
+//! ```text
+//! originator                         responder
+//!     | --- TP.CM_RTS ------------------> |
+//!     | <-------------------- TP.CM_CTS - |   (window of N packets)
+//!     | --- TP.DT × N ------------------> |
+//!     | <-------------------- TP.CM_CTS - |   (next window…)
+//!     | --- TP.DT × N ------------------> |
+//!     | <------------ TP.CM_EndOfMsgAck - |
+//! ```
+use crate::error::{CanIdBuildError, TpAbortReason, TpSessionError};
+use crate::protocol::transport::can_frame::CanFrame;
+use crate::protocol::transport::can_id::CanId;
+use crate::protocol::transport::iso_tp::{
+    assembler::CompletedMessage, packet_count, MAX_ISO_TP_PAYLOAD, MIN_ISO_TP_PAYLOAD, TP_CM_ABORT,
+    TP_CM_CTS, TP_CM_END_OF_MSG_ACK, TP_CM_PGN, TP_CM_RTS, TP_DT_DATA_BYTES, TP_DT_PGN,
+    TP_GLOBAL_ADDRESS, TP_PRIORITY, TP_T1_TIMEOUT_MS, TP_T2_TIMEOUT_MS, TP_T3_TIMEOUT_MS,
+    TP_T4_TIMEOUT_MS,
+};
+
+//==================================================================================Constants
+
+/// Maximum number of connection-mode sessions handled in parallel (both directions).
+pub const MAX_CONNECTION_SESSIONS: usize = 2;
+
+//==================================================================================Enums and Structs
+// The completed message is handed over by value: the crate does not allocate.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum ProcessResult {
+    /// Frame is not a transport frame addressed to the local node, or does not
+    /// match any session.
+    Ignored,
+    /// Frame consumed; nothing to send back.
+    Consumed,
+    /// Frame consumed; the enclosed TP.CM frame (CTS or Abort) must be sent.
+    Reply(CanFrame),
+    /// Incoming transfer complete; `reply` (EndOfMsgAck) must be sent.
+    MessageComplete {
+        message: CompletedMessage,
+        reply: CanFrame,
+    },
+    /// The peer granted a CTS window: pull packets with [`SessionTable::next_data_frame`].
+    TransmitReady { destination: u8 },
+    /// The peer acknowledged the whole outgoing message.
+    TransferComplete { pgn: u32, destination: u8 },
+    /// A session ended with an abort. When `reply` is set, the local node
+    /// initiated it and the TP.CM_Abort frame must be sent.
+    Aborted {
+        peer: u8,
+        pgn: u32,
+        error: TpSessionError,
+        reply: Option<CanFrame>,
+    },
+}
+
+impl ProcessResult {
+    /// Frame to transmit in response, if any.
+    pub fn reply(&self) -> Option<&CanFrame> {
+        match self {
+            Self::Reply(reply) | Self::MessageComplete { reply, .. } => Some(reply),
+            Self::Aborted { reply, .. } => reply.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Possible states for a connection session.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum SessionState {
+    Inactive,
+    /// Responder: waiting for the packets of the current CTS window (T1/T2).
+    Receiving,
+    /// Originator: RTS or window sent, waiting for a CTS (T3).
+    AwaitingCts,
+    /// Originator: peer answered CTS with zero packets (T4).
+    Holding,
+    /// Originator: packets of a granted window are being pulled.
+    Sending,
+    /// Originator: every packet sent, waiting for EndOfMsgAck (T3).
+    AwaitingAck,
+}
+
+/// Internal structure tracking a connection session.
+#[derive(Debug, Clone, Copy)]
+struct ConnectionSession {
+    state: SessionState,
+    local_address: u8,
+    peer_address: u8,
+    pgn: u32,
+    buffer: [u8; MAX_ISO_TP_PAYLOAD],
+    size: usize,
+    total_packets: u8,
+    /// Window limit requested by the originator in its RTS.
+    max_per_cts: u8,
+    next_sequence: u8,
+    window_end: u8,
+    deadline_ms: u64,
+}
+
+impl ConnectionSession {
+    /// Create a session in the inactive state.
+    const fn new() -> Self {
+        Self {
+            state: SessionState::Inactive,
+            local_address: 0,
+            peer_address: 0,
+            pgn: 0,
+            buffer: [0; MAX_ISO_TP_PAYLOAD],
+            size: 0,
+            total_packets: 0,
+            max_per_cts: 0,
+            next_sequence: 0,
+            window_end: 0,
+            deadline_ms: 0,
+        }
+    }
+
+    /// Reset the session and make it available again.
+    fn reset(&mut self) {
+        self.state = SessionState::Inactive;
+        self.pgn = 0;
+        self.size = 0;
+        self.total_packets = 0;
+        self.next_sequence = 0;
+        self.window_end = 0;
+        // No need to wipe the buffer; upcoming copies will overwrite it.
+    }
+
+    fn is_receiving(&self) -> bool {
+        self.state == SessionState::Receiving
+    }
+
+    fn is_transmitting(&self) -> bool {
+        matches!(
+            self.state,
+            SessionState::AwaitingCts
+                | SessionState::Holding
+                | SessionState::Sending
+                | SessionState::AwaitingAck
+        )
+    }
+
+    /// Open the next CTS window starting at `next_sequence` and build the CTS frame.
+    fn grant_window(&mut self, now_ms: u64) -> Result<CanFrame, CanIdBuildError> {
+        let remaining = self.total_packets - self.next_sequence + 1;
+        let count = remaining.min(self.max_per_cts);
+        self.window_end = (self.next_sequence as u16 + count as u16 - 1) as u8;
+        self.deadline_ms = now_ms + TP_T2_TIMEOUT_MS;
+
+        let pgn = pgn_bytes(self.pgn);
+        cm_frame(
+            self.local_address,
+            self.peer_address,
+            [
+                TP_CM_CTS,
+                count,
+                self.next_sequence,
+                0xFF,
+                0xFF,
+                pgn[0],
+                pgn[1],
+                pgn[2],
+            ],
+        )
+    }
+
+    /// Close the session locally and build the matching TP.CM_Abort.
+    ///
+    /// The session is released even when the frame cannot be built.
+    fn abort(&mut self, reason: TpAbortReason) -> Result<ProcessResult, TpSessionError> {
+        let (local, peer, pgn) = (self.local_address, self.peer_address, self.pgn);
+        self.reset();
+        Ok(ProcessResult::Aborted {
+            peer,
+            pgn,
+            error: TpSessionError::Aborted(reason),
+            reply: Some(abort_frame(local, peer, pgn, reason)?),
+        })
+    }
+}
+
+/// Bounded table of connection-mode sessions, in both directions.
+///
+/// `N` is the number of sessions handled in parallel (default
+/// [`MAX_CONNECTION_SESSIONS`]); each one holds a 1785-byte buffer.
+#[derive(Debug, Clone)]
+pub struct SessionTable<const N: usize = MAX_CONNECTION_SESSIONS> {
+    sessions: [ConnectionSession; N],
+}
+
+impl<const N: usize> Default for SessionTable<N> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl SessionTable {
+    /// Instantiate the table with an inactive session pool.
+    pub const fn new() -> Self {
+        Self::empty()
+    }
+}
+
+impl<const N: usize> SessionTable<N> {
+    /// Instantiate a table of `N` inactive sessions.
+    pub const fn empty() -> Self {
+        Self {
+            sessions: [ConnectionSession::new(); N],
+        }
+    }
+
+    /// Number of incoming transfers in progress.
+    pub fn receiving_sessions(&self) -> usize {
+        self.sessions.iter().filter(|s| s.is_receiving()).count()
+    }
+
+    /// Earliest pending T1–T4 deadline, if any session is active.
+    pub fn next_deadline(&self) -> Option<u64> {
+        self.sessions
+            .iter()
+            .filter(|s| s.state != SessionState::Inactive)
+            .map(|s| s.deadline_ms)
+            .min()
+    }
+
+    /// Abort the first session whose timer elapsed.
+    ///
+    /// Call repeatedly until it returns `Ok(None)`; each result carries the
+    /// TP.CM_Abort (reason [`TpAbortReason::Timeout`]) to send.
+    pub fn poll_timeouts(&mut self, now_ms: u64) -> Result<Option<ProcessResult>, TpSessionError> {
+        self.sessions
+            .iter_mut()
+            .find(|s| s.state != SessionState::Inactive && now_ms > s.deadline_ms)
+            .map(|session| session.abort(TpAbortReason::Timeout))
+            .transpose()
+    }
+
+    /// TP.CM_Abort refusing `frame` when it is an RTS addressed to
+    /// `local_address` (reason [`TpAbortReason::ResourcesUnavailable`]).
+    ///
+    /// Lets the owner of the table turn away transfers it has no room to
+    /// deliver; any other frame yields `Ok(None)`.
+    pub fn refuse_request(
+        frame: &CanFrame,
+        local_address: u8,
+    ) -> Result<Option<CanFrame>, TpSessionError> {
+        if frame.len < 8
+            || frame.id.pgn() != TP_CM_PGN
+            || frame.id.destination() != Some(local_address)
+            || frame.data[0] != TP_CM_RTS
+        {
+            return Ok(None);
+        }
+        let refusal = abort_frame(
+            local_address,
+            frame.id.source_address(),
+            pgn_from_bytes(&frame.data),
+            TpAbortReason::ResourcesUnavailable,
+        )?;
+        Ok(Some(refusal))
+    }
+
+    /// True when `frame` is an RTS restarting a transfer `local_address` is
+    /// already receiving (same peer and PGN): it takes that session over
+    /// instead of needing a free one.
+    pub fn restarts_transfer(&self, frame: &CanFrame, local_address: u8) -> bool {
+        frame.len >= 8
+            && frame.id.pgn() == TP_CM_PGN
+            && frame.id.destination() == Some(local_address)
+            && frame.data[0] == TP_CM_RTS
+            && self.sessions.iter().any(|s| {
+                s.is_receiving()
+                    && s.local_address == local_address
+                    && s.peer_address == frame.id.source_address()
+                    && s.pgn == pgn_from_bytes(&frame.data)
+            })
+    }
+
+    //==================================================================================Originator
+    /// Open an outgoing transfer of `payload` (9 to 1785 bytes) to `destination`.
+    ///
+    /// Returns the TP.CM_RTS frame to send. The session then waits for the
+    /// peer's CTS, reported as [`ProcessResult::TransmitReady`].
+    pub fn open_transfer(
+        &mut self,
+        pgn: u32,
+        source_address: u8,
+        destination: u8,
+        payload: &[u8],
+        now_ms: u64,
+    ) -> Result<CanFrame, TpSessionError> {
+        if !(MIN_ISO_TP_PAYLOAD..=MAX_ISO_TP_PAYLOAD).contains(&payload.len()) {
+            return Err(TpSessionError::InvalidSize { len: payload.len() });
+        }
+        if destination == TP_GLOBAL_ADDRESS {
+            return Err(CanIdBuildError::InvalidDestination.into());
+        }
+        if self.sessions.iter().any(|s| {
+            s.is_transmitting()
+                && s.local_address == source_address
+                && s.peer_address == destination
+        }) {
+            return Err(TpSessionError::SessionInProgress { peer: destination });
+        }
+
+        let size = (payload.len() as u16).to_le_bytes();
+        let pgn_le = pgn_bytes(pgn);
+        let rts = cm_frame(
+            source_address,
+            destination,
+            [
+                TP_CM_RTS,
+                size[0],
+                size[1],
+                packet_count(payload.len()) as u8,
+                0xFF,
+                pgn_le[0],
+                pgn_le[1],
+                pgn_le[2],
+            ],
+        )?;
+
+        let session = self
+            .sessions
+            .iter_mut()
+            .find(|s| s.state == SessionState::Inactive)
+            .ok_or(TpSessionError::NoSessionAvailable)?;
+
+        session.state = SessionState::AwaitingCts;
+        session.local_address = source_address;
+        session.peer_address = destination;
+        session.pgn = pgn;
+        session.buffer[..payload.len()].copy_from_slice(payload);
+        session.size = payload.len();
+        session.total_packets = packet_count(payload.len()) as u8;
+        session.next_sequence = 1;
+        session.window_end = 0;
+        session.deadline_ms = now_ms + TP_T3_TIMEOUT_MS;
+
+        Ok(rts)
+    }
+
+    /// Next TP.DT packet of the window granted by `destination`, if any.
+    ///
+    /// Once the window is exhausted the session waits for the next CTS or for
+    /// the EndOfMsgAck (T3).
+    pub fn next_data_frame(
+        &mut self,
+        source_address: u8,
+        destination: u8,
+        now_ms: u64,
+    ) -> Result<Option<CanFrame>, TpSessionError> {
+        let Some(session) = self.sessions.iter_mut().find(|s| {
+            s.state == SessionState::Sending
+                && s.local_address == source_address
+                && s.peer_address == destination
+        }) else {
+            return Ok(None);
+        };
+        let id = CanId::builder(TP_DT_PGN, source_address)
+            .with_priority(TP_PRIORITY)
+            .to_destination(destination)
+            .build()?;
+
+        let sequence = session.next_sequence;
+        let start = (sequence as usize - 1) * TP_DT_DATA_BYTES;
+        let end = (start + TP_DT_DATA_BYTES).min(session.size);
+
+        // Unused bytes of the last packet are padded with 0xFF.
+        let mut data = [0xFF; 8];
+        data[0] = sequence;
+        data[1..1 + (end - start)].copy_from_slice(&session.buffer[start..end]);
+
+        if sequence >= session.window_end {
+            session.state = if sequence >= session.total_packets {
+                SessionState::AwaitingAck
+            } else {
+                SessionState::AwaitingCts
+            };
+        } else {
+            session.next_sequence += 1;
+        }
+        session.deadline_ms = now_ms + TP_T3_TIMEOUT_MS;
+
+        Ok(Some(CanFrame { id, data, len: 8 }))
+    }
+
+    //==================================================================================Process Functions
+    /// Process a CAN frame that may belong to a connection session of `local_address`.
+    ///
+    /// * `frame` – received CAN frame (any PGN; unrelated frames are ignored)
+    /// * `local_address` – address currently held by the local node
+    /// * `now_ms` – monotonic timestamp of the reception, in milliseconds
+    ///
+    /// Fails with [`TpSessionError::Build`] when the reply cannot be
+    /// addressed; the session concerned is released.
+    pub fn process_frame(
+        &mut self,
+        frame: &CanFrame,
+        local_address: u8,
+        now_ms: u64,
+    ) -> Result<ProcessResult, TpSessionError> {
+        if frame.len < 8
+            || local_address == TP_GLOBAL_ADDRESS
+            || frame.id.destination() != Some(local_address)
+        {
+            return Ok(ProcessResult::Ignored);
+        }
+
+        let peer = frame.id.source_address();
+        let data = &frame.data;
+        match frame.id.pgn() {
+            TP_CM_PGN => match data[0] {
+                TP_CM_RTS => self.on_request_to_send(local_address, peer, data, now_ms),
+                TP_CM_CTS => self.on_clear_to_send(local_address, peer, data, now_ms),
+                TP_CM_END_OF_MSG_ACK => Ok(self.on_end_of_message(local_address, peer, data)),
+                TP_CM_ABORT => Ok(self.on_abort(local_address, peer, data)),
+                _ => Ok(ProcessResult::Ignored),
+            },
+            TP_DT_PGN => self.on_data_transfer(local_address, peer, data, now_ms),
+            _ => Ok(ProcessResult::Ignored),
+        }
+    }
+
+    /// Responder: a peer requests to send us a message.
+    fn on_request_to_send(
+        &mut self,
+        local: u8,
+        peer: u8,
+        data: &[u8; 8],
+        now_ms: u64,
+    ) -> Result<ProcessResult, TpSessionError> {
+        let size = u16::from_le_bytes([data[1], data[2]]) as usize;
+        let total_packets = data[3];
+        let pgn = pgn_from_bytes(data);
+
+        if size > MAX_ISO_TP_PAYLOAD {
+            return Ok(ProcessResult::Reply(abort_frame(
+                local,
+                peer,
+                pgn,
+                TpAbortReason::MessageTooLarge,
+            )?));
+        }
+        if size < MIN_ISO_TP_PAYLOAD || total_packets as usize != packet_count(size) {
+            return Ok(ProcessResult::Ignored);
+        }
+
+        let existing = self
+            .sessions
+            .iter()
+            .position(|s| s.is_receiving() && s.local_address == local && s.peer_address == peer);
+        let index = match existing {
+            // The peer restarted the same transfer: reuse its session.
+            Some(index) if self.sessions[index].pgn == pgn => index,
+            Some(_) => {
+                return Ok(ProcessResult::Reply(abort_frame(
+                    local,
+                    peer,
+                    pgn,
+                    TpAbortReason::AlreadyInSession,
+                )?))
+            }
+            None => match self
+                .sessions
+                .iter()
+                .position(|s| s.state == SessionState::Inactive)
+            {
+                Some(index) => index,
+                None => {
+                    return Ok(ProcessResult::Reply(abort_frame(
+                        local,
+                        peer,
+                        pgn,
+                        TpAbortReason::ResourcesUnavailable,
+                    )?))
+                }
+            },
+        };
+
+        let session = &mut self.sessions[index];
+        session.state = SessionState::Receiving;
+        session.local_address = local;
+        session.peer_address = peer;
+        session.pgn = pgn;
+        session.size = size;
+        session.total_packets = total_packets;
+        // 0xFF means "no limit"; 0 is invalid and treated the same way.
+        session.max_per_cts = if data[4] == 0 { 0xFF } else { data[4] };
+        session.next_sequence = 1;
+
+        match session.grant_window(now_ms) {
+            Ok(cts) => Ok(ProcessResult::Reply(cts)),
+            Err(err) => {
+                session.reset();
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Responder: one data packet of the current window.
+    fn on_data_transfer(
+        &mut self,
+        local: u8,
+        peer: u8,
+        data: &[u8; 8],
+        now_ms: u64,
+    ) -> Result<ProcessResult, TpSessionError> {
+        let Some(session) = self
+            .sessions
+            .iter_mut()
+            .find(|s| s.is_receiving() && s.local_address == local && s.peer_address == peer)
+        else {
+            return Ok(ProcessResult::Ignored);
+        };
+
+        let sequence = data[0];
+        if sequence < session.next_sequence {
+            return session.abort(TpAbortReason::DuplicateSequence);
+        }
+        if sequence != session.next_sequence {
+            return session.abort(TpAbortReason::BadSequence);
+        }
+
+        let offset = (sequence as usize - 1) * TP_DT_DATA_BYTES;
+        let copy_len = (session.size - offset).min(TP_DT_DATA_BYTES);
+        session.buffer[offset..offset + copy_len].copy_from_slice(&data[1..1 + copy_len]);
+
+        if sequence >= session.total_packets {
+            let size = (session.size as u16).to_le_bytes();
+            let pgn = pgn_bytes(session.pgn);
+            let reply = cm_frame(
+                local,
+                peer,
+                [
+                    TP_CM_END_OF_MSG_ACK,
+                    size[0],
+                    size[1],
+                    session.total_packets,
+                    0xFF,
+                    pgn[0],
+                    pgn[1],
+                    pgn[2],
+                ],
+            );
+            let reply = match reply {
+                Ok(reply) => reply,
+                Err(err) => {
+                    session.reset();
+                    return Err(err.into());
+                }
+            };
+
+            // Copy the complete message into a dedicated return structure.
+            let mut payload = [0; MAX_ISO_TP_PAYLOAD];
+            payload[..session.size].copy_from_slice(&session.buffer[..session.size]);
+            let message = CompletedMessage {
+                pgn: session.pgn,
+                source_address: peer,
+                payload,
+                len: session.size,
+            };
+
+            // Release the session for future messages.
+            session.reset();
+
+            return Ok(ProcessResult::MessageComplete { message, reply });
+        }
+
+        session.next_sequence += 1;
+        if sequence >= session.window_end {
+            return match session.grant_window(now_ms) {
+                Ok(cts) => Ok(ProcessResult::Reply(cts)),
+                Err(err) => {
+                    session.reset();
+                    Err(err.into())
+                }
+            };
+        }
+
+        session.deadline_ms = now_ms + TP_T1_TIMEOUT_MS;
+        Ok(ProcessResult::Consumed)
+    }
+
+    /// Originator: the responder grants (or holds) a window.
+    fn on_clear_to_send(
+        &mut self,
+        local: u8,
+        peer: u8,
+        data: &[u8; 8],
+        now_ms: u64,
+    ) -> Result<ProcessResult, TpSessionError> {
+        let pgn = pgn_from_bytes(data);
+        let Some(session) = self
+            .sessions
+            .iter_mut()
+            .find(|s| s.is_transmitting() && s.local_address == local && s.peer_address == peer)
+        else {
+            return Ok(ProcessResult::Ignored);
+        };
+        if session.pgn != pgn {
+            return Ok(ProcessResult::Ignored);
+        }
+        if session.state == SessionState::Sending {
+            return session.abort(TpAbortReason::CtsWhileTransferring);
+        }
+
+        let count = data[1];
+        let next_sequence = data[2];
+        if count == 0 {
+            session.state = SessionState::Holding;
+            session.deadline_ms = now_ms + TP_T4_TIMEOUT_MS;
+            return Ok(ProcessResult::Consumed);
+        }
+        if next_sequence == 0 || next_sequence > session.total_packets {
+            return session.abort(TpAbortReason::BadSequence);
+        }
+
+        let window_end =
+            (next_sequence as u16 + count as u16 - 1).min(session.total_packets as u16);
+        session.state = SessionState::Sending;
+        session.next_sequence = next_sequence;
+        session.window_end = window_end as u8;
+        // The granted window starts now, whatever was left of the T3/T4 wait.
+        session.deadline_ms = now_ms + TP_T3_TIMEOUT_MS;
+
+        Ok(ProcessResult::TransmitReady { destination: peer })
+    }
+
+    /// Originator: the responder acknowledged the whole message.
+    fn on_end_of_message(&mut self, local: u8, peer: u8, data: &[u8; 8]) -> ProcessResult {
+        let pgn = pgn_from_bytes(data);
+        let Some(session) = self.sessions.iter_mut().find(|s| {
+            s.is_transmitting()
+                && s.local_address == local
+                && s.peer_address == peer
+                && s.pgn == pgn
+        }) else {
+            return ProcessResult::Ignored;
+        };
+
+        session.reset();
+        ProcessResult::TransferComplete {
+            pgn,
+            destination: peer,
+        }
+    }
+
+    /// Either side: the peer closed the session.
+    fn on_abort(&mut self, local: u8, peer: u8, data: &[u8; 8]) -> ProcessResult {
+        let pgn = pgn_from_bytes(data);
+        let Some(session) = self.sessions.iter_mut().find(|s| {
+            s.state != SessionState::Inactive
+                && s.local_address == local
+                && s.peer_address == peer
+                && s.pgn == pgn
+        }) else {
+            return ProcessResult::Ignored;
+        };
+
+        session.reset();
+        ProcessResult::Aborted {
+            peer,
+            pgn,
+            error: TpSessionError::AbortedByPeer(TpAbortReason::from(data[1])),
+            reply: None,
+        }
+    }
+}
+
+//==================================================================================Frame helpers
+/// Build a TP.CM frame addressed to `destination`.
+fn cm_frame(
+    source_address: u8,
+    destination: u8,
+    data: [u8; 8],
+) -> Result<CanFrame, CanIdBuildError> {
+    let id = CanId::builder(TP_CM_PGN, source_address)
+        .with_priority(TP_PRIORITY)
+        .to_destination(destination)
+        .build()?;
+    Ok(CanFrame { id, data, len: 8 })
+}
+
+/// Build a TP.CM_Abort frame for `pgn`.
+fn abort_frame(
+    source_address: u8,
+    destination: u8,
+    pgn: u32,
+    reason: TpAbortReason,
+) -> Result<CanFrame, CanIdBuildError> {
+    let pgn = pgn_bytes(pgn);
+    cm_frame(
+        source_address,
+        destination,
+        [
+            TP_CM_ABORT,
+            reason.code(),
+            0xFF,
+            0xFF,
+            0xFF,
+            pgn[0],
+            pgn[1],
+            pgn[2],
+        ],
+    )
+}
+
+/// Little-endian PGN as carried in bytes 5-7 of every TP.CM frame.
+fn pgn_bytes(pgn: u32) -> [u8; 3] {
+    let bytes = pgn.to_le_bytes();
+    [bytes[0], bytes[1], bytes[2]]
+}
+
+/// Extract the PGN from bytes 5-7 of a TP.CM frame.
+fn pgn_from_bytes(data: &[u8; 8]) -> u32 {
+    u32::from_le_bytes([data[5], data[6], data[7], 0])
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
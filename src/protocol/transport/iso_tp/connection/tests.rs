@@ -0,0 +1,378 @@
+//! Connection-mode session tests: handshake, windows, timeouts, and aborts.
+// CONNECTION
+use super::*;
+
+const ORIGINATOR: u8 = 10;
+const RESPONDER: u8 = 20;
+
+/// Feed every frame of a granted window to the responder table.
+fn deliver_window(
+    originator: &mut SessionTable,
+    responder: &mut SessionTable,
+    now_ms: u64,
+) -> ProcessResult {
+    let mut last = ProcessResult::Ignored;
+    while let Some(dt) = originator
+        .next_data_frame(ORIGINATOR, RESPONDER, now_ms)
+        .unwrap()
+    {
+        last = responder.process_frame(&dt, RESPONDER, now_ms).unwrap();
+    }
+    last
+}
+
+#[test]
+/// Full handshake between two tables: RTS → CTS → DT × 3 → EndOfMsgAck.
+fn test_connection_roundtrip() {
+    let mut originator = SessionTable::new();
+    let mut responder = SessionTable::new();
+    let payload: [u8; 20] = core::array::from_fn(|i| i as u8);
+
+    let rts = originator
+        .open_transfer(126208, ORIGINATOR, RESPONDER, &payload, 0)
+        .unwrap();
+    assert_eq!(rts.id.destination(), Some(RESPONDER));
+    assert_eq!(rts.data, [16, 20, 0, 3, 0xFF, 0x00, 0xED, 0x01]);
+
+    let cts = match responder.process_frame(&rts, RESPONDER, 0).unwrap() {
+        ProcessResult::Reply(cts) => cts,
+        other => panic!("expected CTS, got {other:?}"),
+    };
+    // All three packets granted, starting at 1.
+    assert_eq!(&cts.data[..3], &[17, 3, 1]);
+
+    assert!(matches!(
+        originator.process_frame(&cts, ORIGINATOR, 10).unwrap(),
+        ProcessResult::TransmitReady {
+            destination: RESPONDER
+        }
+    ));
+
+    let eoma = match deliver_window(&mut originator, &mut responder, 20) {
+        ProcessResult::MessageComplete { message, reply } => {
+            assert_eq!(message.pgn, 126208);
+            assert_eq!(message.source_address, ORIGINATOR);
+            assert_eq!(&message.payload[..message.len], &payload);
+            reply
+        }
+        other => panic!("expected a complete message, got {other:?}"),
+    };
+    assert_eq!(eoma.data[0], 19);
+
+    assert!(matches!(
+        originator.process_frame(&eoma, ORIGINATOR, 30).unwrap(),
+        ProcessResult::TransferComplete {
+            pgn: 126208,
+            destination: RESPONDER
+        }
+    ));
+    assert_eq!(originator.next_deadline(), None);
+    assert_eq!(responder.next_deadline(), None);
+}
+
+#[test]
+/// The responder honours the RTS window limit and re-opens windows with new CTS frames.
+fn test_connection_respects_max_packets_per_cts() {
+    let mut originator = SessionTable::new();
+    let mut responder = SessionTable::new();
+    let payload = [0x55u8; 30]; // 5 packets
+
+    let mut rts = originator
+        .open_transfer(126208, ORIGINATOR, RESPONDER, &payload, 0)
+        .unwrap();
+    rts.data[4] = 2; // at most two packets per CTS
+
+    let mut reply = responder.process_frame(&rts, RESPONDER, 0).unwrap();
+    let mut windows = 0;
+    loop {
+        match reply {
+            ProcessResult::Reply(cts) => {
+                windows += 1;
+                assert!(cts.data[1] <= 2);
+                originator.process_frame(&cts, ORIGINATOR, 0).unwrap();
+                reply = deliver_window(&mut originator, &mut responder, 0);
+            }
+            ProcessResult::MessageComplete { message, .. } => {
+                assert_eq!(message.len, 30);
+                break;
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+    assert_eq!(windows, 3);
+}
+
+#[test]
+/// A skipped packet aborts the session with reason 7.
+fn test_connection_bad_sequence_aborts() {
+    let mut originator = SessionTable::new();
+    let mut responder = SessionTable::new();
+    let payload = [0u8; 20];
+
+    let rts = originator
+        .open_transfer(126208, ORIGINATOR, RESPONDER, &payload, 0)
+        .unwrap();
+    let ProcessResult::Reply(cts) = responder.process_frame(&rts, RESPONDER, 0).unwrap() else {
+        panic!("expected CTS");
+    };
+    originator.process_frame(&cts, ORIGINATOR, 0).unwrap();
+
+    let _first = originator
+        .next_data_frame(ORIGINATOR, RESPONDER, 0)
+        .unwrap()
+        .unwrap();
+    let second = originator
+        .next_data_frame(ORIGINATOR, RESPONDER, 0)
+        .unwrap()
+        .unwrap();
+
+    let abort = match responder.process_frame(&second, RESPONDER, 0).unwrap() {
+        ProcessResult::Aborted {
+            error: TpSessionError::Aborted(TpAbortReason::BadSequence),
+            reply: Some(abort),
+            ..
+        } => abort,
+        other => panic!("expected a local abort, got {other:?}"),
+    };
+    assert_eq!(&abort.data[..2], &[255, 7]);
+
+    // The originator surfaces the typed reason.
+    assert!(matches!(
+        originator.process_frame(&abort, ORIGINATOR, 0).unwrap(),
+        ProcessResult::Aborted {
+            error: TpSessionError::AbortedByPeer(TpAbortReason::BadSequence),
+            reply: None,
+            ..
+        }
+    ));
+}
+
+#[test]
+/// T2 on the responder and T3 on the originator both produce a timeout abort.
+fn test_connection_timeouts() {
+    let mut originator = SessionTable::new();
+    let mut responder = SessionTable::new();
+    let payload = [0u8; 20];
+
+    let rts = originator
+        .open_transfer(126208, ORIGINATOR, RESPONDER, &payload, 0)
+        .unwrap();
+    responder.process_frame(&rts, RESPONDER, 0).unwrap();
+
+    assert!(responder.poll_timeouts(TP_T2_TIMEOUT_MS).unwrap().is_none());
+    match responder.poll_timeouts(TP_T2_TIMEOUT_MS + 1).unwrap() {
+        Some(ProcessResult::Aborted {
+            peer: ORIGINATOR,
+            error: TpSessionError::Aborted(TpAbortReason::Timeout),
+            reply: Some(abort),
+            ..
+        }) => assert_eq!(&abort.data[..2], &[255, 3]),
+        other => panic!("expected a T2 abort, got {other:?}"),
+    }
+    assert!(responder
+        .poll_timeouts(TP_T2_TIMEOUT_MS + 1)
+        .unwrap()
+        .is_none());
+
+    assert!(matches!(
+        originator.poll_timeouts(TP_T3_TIMEOUT_MS + 1).unwrap(),
+        Some(ProcessResult::Aborted {
+            peer: RESPONDER,
+            error: TpSessionError::Aborted(TpAbortReason::Timeout),
+            ..
+        })
+    ));
+}
+
+#[test]
+/// A hold CTS (zero packets) switches the originator to T4.
+fn test_connection_hold_uses_t4() {
+    let mut originator = SessionTable::new();
+    let payload = [0u8; 20];
+    originator
+        .open_transfer(126208, ORIGINATOR, RESPONDER, &payload, 0)
+        .unwrap();
+
+    let hold = cm_frame(
+        RESPONDER,
+        ORIGINATOR,
+        [17, 0, 0xFF, 0xFF, 0xFF, 0x00, 0xED, 0x01],
+    )
+    .unwrap();
+    assert!(matches!(
+        originator.process_frame(&hold, ORIGINATOR, 100).unwrap(),
+        ProcessResult::Consumed
+    ));
+    assert_eq!(originator.next_deadline(), Some(100 + TP_T4_TIMEOUT_MS));
+    assert!(originator
+        .next_data_frame(ORIGINATOR, RESPONDER, 100)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+/// A CTS granted late in the T3 wait opens the window with a fresh deadline.
+fn test_connection_cts_refreshes_deadline() {
+    let mut originator = SessionTable::new();
+    let payload = [0u8; 20];
+    originator
+        .open_transfer(126208, ORIGINATOR, RESPONDER, &payload, 0)
+        .unwrap();
+
+    let cts = cm_frame(
+        RESPONDER,
+        ORIGINATOR,
+        [17, 3, 1, 0xFF, 0xFF, 0x00, 0xED, 0x01],
+    )
+    .unwrap();
+    let now_ms = TP_T3_TIMEOUT_MS - 10;
+    assert!(matches!(
+        originator.process_frame(&cts, ORIGINATOR, now_ms).unwrap(),
+        ProcessResult::TransmitReady {
+            destination: RESPONDER
+        }
+    ));
+    assert_eq!(originator.next_deadline(), Some(now_ms + TP_T3_TIMEOUT_MS));
+    assert!(originator
+        .poll_timeouts(TP_T3_TIMEOUT_MS + 1)
+        .unwrap()
+        .is_none());
+    assert!(originator
+        .next_data_frame(ORIGINATOR, RESPONDER, TP_T3_TIMEOUT_MS + 1)
+        .unwrap()
+        .is_some());
+}
+
+#[test]
+/// A full table answers new RTS frames with "resources unavailable".
+fn test_connection_table_full() {
+    let mut responder = SessionTable::new();
+    let rts_from =
+        |source: u8| cm_frame(source, RESPONDER, [16, 20, 0, 3, 0xFF, 0x00, 0xED, 0x01]).unwrap();
+
+    for source in 1..=MAX_CONNECTION_SESSIONS as u8 {
+        assert!(matches!(
+            responder
+                .process_frame(&rts_from(source), RESPONDER, 0)
+                .unwrap(),
+            ProcessResult::Reply(_)
+        ));
+    }
+
+    match responder
+        .process_frame(&rts_from(99), RESPONDER, 0)
+        .unwrap()
+    {
+        ProcessResult::Reply(abort) => assert_eq!(&abort.data[..2], &[255, 2]),
+        other => panic!("expected an abort reply, got {other:?}"),
+    }
+}
+
+#[test]
+/// Frames addressed to another node are ignored.
+fn test_connection_ignores_other_destinations() {
+    let mut responder = SessionTable::new();
+    let rts = cm_frame(1, 77, [16, 20, 0, 3, 0xFF, 0x00, 0xED, 0x01]).unwrap();
+    assert!(matches!(
+        responder.process_frame(&rts, RESPONDER, 0).unwrap(),
+        ProcessResult::Ignored
+    ));
+}
+
+#[test]
+/// Invalid sizes and duplicate transfers are refused when opening.
+fn test_open_transfer_validation() {
+    let mut originator = SessionTable::new();
+    assert!(matches!(
+        originator.open_transfer(126208, ORIGINATOR, RESPONDER, &[0; 8], 0),
+        Err(TpSessionError::InvalidSize { len: 8 })
+    ));
+    assert!(matches!(
+        originator.open_transfer(126208, ORIGINATOR, TP_GLOBAL_ADDRESS, &[0; 20], 0),
+        Err(TpSessionError::Build(CanIdBuildError::InvalidDestination))
+    ));
+
+    originator
+        .open_transfer(126208, ORIGINATOR, RESPONDER, &[0; 20], 0)
+        .unwrap();
+    assert!(matches!(
+        originator.open_transfer(126464, ORIGINATOR, RESPONDER, &[0; 20], 0),
+        Err(TpSessionError::SessionInProgress { peer: RESPONDER })
+    ));
+}
+
+#[test]
+/// `refuse_request` only answers RTS frames addressed to the local node.
+fn test_refuse_request() {
+    let rts = cm_frame(
+        ORIGINATOR,
+        RESPONDER,
+        [16, 20, 0, 3, 0xFF, 0x00, 0xED, 0x01],
+    )
+    .unwrap();
+    let refusal = SessionTable::<0>::refuse_request(&rts, RESPONDER)
+        .unwrap()
+        .expect("an RTS is refused");
+    assert_eq!(refusal.id.destination(), Some(ORIGINATOR));
+    assert_eq!(refusal.data, [255, 2, 0xFF, 0xFF, 0xFF, 0x00, 0xED, 0x01]);
+
+    assert!(SessionTable::<0>::refuse_request(&rts, 77)
+        .unwrap()
+        .is_none());
+    let cts = cm_frame(
+        ORIGINATOR,
+        RESPONDER,
+        [17, 1, 1, 0xFF, 0xFF, 0x00, 0xED, 0x01],
+    )
+    .unwrap();
+    assert!(SessionTable::<0>::refuse_request(&cts, RESPONDER)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+/// Only an RTS for the PGN a peer is already sending restarts its transfer.
+fn test_restarts_transfer() {
+    let mut responder = SessionTable::new();
+    let rts = cm_frame(
+        ORIGINATOR,
+        RESPONDER,
+        [16, 20, 0, 3, 0xFF, 0x00, 0xED, 0x01],
+    )
+    .unwrap();
+    assert!(!responder.restarts_transfer(&rts, RESPONDER));
+
+    responder.process_frame(&rts, RESPONDER, 0).unwrap();
+    assert!(responder.restarts_transfer(&rts, RESPONDER));
+    assert!(!responder.restarts_transfer(&rts, 77));
+
+    let other_pgn = cm_frame(
+        ORIGINATOR,
+        RESPONDER,
+        [16, 20, 0, 3, 0xFF, 0x00, 0xEE, 0x01],
+    )
+    .unwrap();
+    assert!(!responder.restarts_transfer(&other_pgn, RESPONDER));
+}
+
+#[test]
+/// A table sized to zero holds nothing and refuses every transfer.
+fn test_empty_table_refuses_transfers() {
+    let mut table: SessionTable<0> = SessionTable::empty();
+    assert!(matches!(
+        table.open_transfer(126208, ORIGINATOR, RESPONDER, &[0; 20], 0),
+        Err(TpSessionError::NoSessionAvailable)
+    ));
+
+    let rts = cm_frame(
+        ORIGINATOR,
+        RESPONDER,
+        [16, 20, 0, 3, 0xFF, 0x00, 0xED, 0x01],
+    )
+    .unwrap();
+    match table.process_frame(&rts, RESPONDER, 0).unwrap() {
+        ProcessResult::Reply(abort) => assert_eq!(&abort.data[..2], &[255, 2]),
+        other => panic!("expected an abort reply, got {other:?}"),
+    }
+    assert_eq!(table.receiving_sessions(), 0);
+}
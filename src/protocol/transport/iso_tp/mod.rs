@@ -0,0 +1,82 @@
+//! ISO 11783-3 / SAE J1939-21 multi-packet transport protocol (TP.CM / TP.DT).
+//!
+//! Carries payloads up to 1785 bytes, well beyond the 223-byte Fast Packet limit.
+//! A message is announced by a connection management frame (PGN 60416) and its
+//! bytes travel in numbered data transfer frames (PGN 60160), seven bytes each.
+//!
+//! Two flavours exist:
+//! - **BAM** (Broadcast Announce Message): no handshake, the receivers simply
+//!   collect the announced packets ([`builder`], [`assembler`], [`sender`]).
+//! - **Connection mode** (RTS/CTS): a destination-specific transfer where the
+//!   receiver paces the sender with Clear To Send windows and acknowledges the
+//!   whole message ([`connection`]).
+//!
+//! ## Timing
+//!
+//! - The BAM sender waits 50 to 200 ms between two packets
+//!   ([`TP_BAM_MIN_PACKET_DELAY_MS`], [`TP_BAM_MAX_PACKET_DELAY_MS`]).
+//! - T1: a receiver drops a session when no packet arrived for [`TP_T1_TIMEOUT_MS`].
+//! - T2: a receiver waits [`TP_T2_TIMEOUT_MS`] for the first packet after a CTS.
+//! - T3: a sender waits [`TP_T3_TIMEOUT_MS`] for a CTS or the acknowledgment.
+//! - T4: a sender put on hold (CTS with zero packets) waits [`TP_T4_TIMEOUT_MS`].
+pub mod assembler;
+pub mod builder;
+pub mod connection;
+pub mod sender;
+
+#[cfg(test)]
+mod tests;
+
+//==================================================================================PGNS
+/// TP.CM – Transport Protocol Connection Management.
+pub const TP_CM_PGN: u32 = 60416;
+/// TP.DT – Transport Protocol Data Transfer.
+pub const TP_DT_PGN: u32 = 60160;
+
+//==================================================================================SIZES
+/// Maximum payload a transport session can carry (255 packets × 7 bytes).
+pub const MAX_ISO_TP_PAYLOAD: usize = 1785;
+/// Smallest payload worth a transport session; shorter ones fit in a single frame.
+pub const MIN_ISO_TP_PAYLOAD: usize = 9;
+/// Useful bytes carried by each TP.DT packet (byte 0 holds the sequence number).
+pub const TP_DT_DATA_BYTES: usize = 7;
+/// Default priority of TP.CM and TP.DT frames.
+pub const TP_PRIORITY: u8 = 7;
+/// Global destination used by broadcast sessions.
+pub const TP_GLOBAL_ADDRESS: u8 = 255;
+
+//==================================================================================CONTROL_BYTES
+/// TP.CM control byte: Request To Send.
+pub const TP_CM_RTS: u8 = 16;
+/// TP.CM control byte: Clear To Send.
+pub const TP_CM_CTS: u8 = 17;
+/// TP.CM control byte: End of Message Acknowledgment.
+pub const TP_CM_END_OF_MSG_ACK: u8 = 19;
+/// TP.CM control byte: Broadcast Announce Message.
+pub const TP_CM_BAM: u8 = 32;
+/// TP.CM control byte: Connection Abort.
+pub const TP_CM_ABORT: u8 = 255;
+
+//==================================================================================TIMING
+/// Minimum delay between two packets of a BAM (ms).
+pub const TP_BAM_MIN_PACKET_DELAY_MS: u32 = 50;
+/// Maximum delay between two packets of a BAM (ms).
+pub const TP_BAM_MAX_PACKET_DELAY_MS: u32 = 200;
+/// T1: maximum silence tolerated by a receiver between two data packets (ms).
+pub const TP_T1_TIMEOUT_MS: u64 = 750;
+/// T2: maximum delay between a CTS and the first data packet it requested (ms).
+pub const TP_T2_TIMEOUT_MS: u64 = 1250;
+/// T3: maximum delay between the last packet sent and the next CTS or acknowledgment (ms).
+pub const TP_T3_TIMEOUT_MS: u64 = 1250;
+/// T4: maximum delay between a hold CTS (zero packets) and the next CTS (ms).
+pub const TP_T4_TIMEOUT_MS: u64 = 1050;
+/// Delay inserted between two data packets of a CTS window (ms).
+///
+/// Connection mode has no mandated spacing; a short pause keeps small embedded
+/// CAN TX buffers from overflowing, as for Fast Packet.
+pub const TP_DT_INTER_PACKET_DELAY_MS: u32 = 2;
+
+/// Number of TP.DT packets required to carry `payload_len` bytes.
+pub const fn packet_count(payload_len: usize) -> usize {
+    payload_len.div_ceil(TP_DT_DATA_BYTES)
+}
@@ -0,0 +1,69 @@
+//! Transmission of ISO transport BAM sessions over a [`CanBus`], pacing the
+//! packets with a [`KorriTimer`] as required by ISO 11783-3.
+use crate::{
+    error::SendPgnError,
+    protocol::transport::iso_tp::{
+        builder::BamBuilder, TP_BAM_MAX_PACKET_DELAY_MS, TP_BAM_MIN_PACKET_DELAY_MS,
+    },
+    protocol::transport::traits::{can_bus::CanBus, korri_timer::KorriTimer},
+};
+
+/// Broadcast `payload` (9 to 1785 bytes) as a BAM session.
+///
+/// Sends the TP.CM_BAM announcement, then each TP.DT packet, waiting
+/// `packet_delay_ms` before every data packet. The delay is clamped to the
+/// 50–200 ms window mandated by the specification.
+///
+/// # Arguments
+///
+/// * `can_bus` – bus used for transmission
+/// * `timer` – timer enforcing the inter-packet delay
+/// * `pgn` – PGN carried by the session
+/// * `source_address` – Source address (0-253)
+/// * `payload` – serialized PGN
+/// * `packet_delay_ms` – requested delay between packets
+///
+/// # Errors
+///
+/// - [`SendPgnError::Build`] when the payload size is out of range
+/// - [`SendPgnError::Send`] when bus transmission fails
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use korri_n2k::protocol::transport::iso_tp::{sender::send_bam, TP_BAM_MIN_PACKET_DELAY_MS};
+///
+/// send_bam(&mut can_bus, &mut timer, 126464, my_address, &pgn_list, TP_BAM_MIN_PACKET_DELAY_MS).await?;
+/// ```
+pub async fn send_bam<C, T>(
+    can_bus: &mut C,
+    timer: &mut T,
+    pgn: u32,
+    source_address: u8,
+    payload: &[u8],
+    packet_delay_ms: u32,
+) -> Result<(), SendPgnError<C::Error>>
+where
+    C: CanBus,
+    C::Error: core::fmt::Debug,
+    T: KorriTimer,
+{
+    let packet_delay_ms =
+        packet_delay_ms.clamp(TP_BAM_MIN_PACKET_DELAY_MS, TP_BAM_MAX_PACKET_DELAY_MS);
+    let mut is_announcement = true;
+
+    for frame_result in BamBuilder::new(pgn, source_address, payload).build() {
+        let frame = frame_result.map_err(SendPgnError::Build)?;
+
+        // Receivers need time to allocate a buffer after the announcement, and
+        // between data packets to keep up with the broadcast.
+        if !is_announcement {
+            timer.delay_ms(packet_delay_ms).await;
+        }
+
+        can_bus.send(&frame).await.map_err(SendPgnError::Send)?;
+        is_announcement = false;
+    }
+
+    Ok(())
+}
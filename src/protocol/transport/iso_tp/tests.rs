@@ -0,0 +1,52 @@
+//! Integration tests combining the BAM builder and assembler.
+use crate::protocol::transport::iso_tp::{
+    assembler::{BamAssembler, ProcessResult},
+    builder::BamBuilder,
+    MAX_ISO_TP_PAYLOAD, TP_BAM_MIN_PACKET_DELAY_MS,
+};
+
+/// Fragment `payload` and feed every frame to a fresh assembler, 50 ms apart.
+fn roundtrip(pgn: u32, source: u8, payload: &[u8]) -> (u32, u8, usize, [u8; MAX_ISO_TP_PAYLOAD]) {
+    let mut assembler = BamAssembler::new();
+    let mut now_ms = 0;
+
+    for frame_result in BamBuilder::new(pgn, source, payload).build() {
+        let frame = frame_result.unwrap();
+        if let ProcessResult::MessageComplete(msg) = assembler.process_frame(&frame, now_ms) {
+            return (msg.pgn, msg.source_address, msg.len, msg.payload);
+        }
+        now_ms += TP_BAM_MIN_PACKET_DELAY_MS as u64;
+    }
+
+    panic!("message never completed");
+}
+
+#[test]
+/// Payload just above a single frame.
+fn test_roundtrip_9_bytes() {
+    let original = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let (pgn, source, len, payload) = roundtrip(126464, 17, &original);
+    assert_eq!(pgn, 126464);
+    assert_eq!(source, 17);
+    assert_eq!(len, 9);
+    assert_eq!(&payload[..9], &original);
+}
+
+#[test]
+/// Payload beyond the Fast Packet limit.
+fn test_roundtrip_300_bytes() {
+    let original: [u8; 300] = core::array::from_fn(|i| (i * 7) as u8);
+    let (pgn, _, len, payload) = roundtrip(126996, 3, &original);
+    assert_eq!(pgn, 126996);
+    assert_eq!(len, 300);
+    assert_eq!(&payload[..300], &original);
+}
+
+#[test]
+/// Largest payload the transport protocol can carry.
+fn test_roundtrip_max_payload() {
+    let original: [u8; MAX_ISO_TP_PAYLOAD] = core::array::from_fn(|i| (i % 251) as u8);
+    let (_, _, len, payload) = roundtrip(130820, 200, &original);
+    assert_eq!(len, MAX_ISO_TP_PAYLOAD);
+    assert_eq!(payload, original);
+}
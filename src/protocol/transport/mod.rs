@@ -1,5 +1,6 @@
 //! NMEA 2000 transport layer: CAN frame representations, 29-bit identifier
-//! management, Fast Packet encoding, and bus abstraction traits.
+//! management, Fast Packet encoding, ISO transport protocol sessions, bus
+//! abstraction traits, and `embedded-can` driver adapters.
 //!
 //! ## NMEA 2000 Timing Constants
 //!
@@ -8,8 +9,17 @@
 
 pub mod can_frame;
 pub mod can_id;
+pub mod embedded_can;
 pub mod fast_packet;
+pub mod iso_tp;
+#[cfg(feature = "socketcan")]
+pub mod socketcan;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod timers;
 pub mod traits;
+#[cfg(feature = "std")]
+pub mod virtual_bus;
 
 /// Recommended minimal delay between two frames of the same Fast Packet message (ms).
 ///
@@ -0,0 +1,202 @@
+//! Linux SocketCAN backend (`socketcan` feature).
+//!
+//! [`SocketCanBus`] opens a raw CAN socket on an interface (`can0`, `vcan0`, …)
+//! and implements [`CanBus`] on top of tokio's reactor. Only extended (29-bit)
+//! data frames are exchanged: standard, remote, and error frames received from
+//! the interface are skipped, as NMEA 2000 never uses them.
+//!
+//! Pair it with [`TokioTimer`](crate::protocol::transport::timers::TokioTimer).
+//!
+//! ```bash
+//! # Virtual interface for local trials
+//! sudo modprobe vcan
+//! sudo ip link add dev vcan0 type vcan
+//! sudo ip link set up vcan0
+//! ```
+//!
+//! ```rust,ignore
+//! let mut bus = SocketCanBus::open("vcan0")?;
+//! let mut timer = TokioTimer::new();
+//! let manager = AddressManager::new(bus, timer, my_name, 42).await?;
+//! ```
+use std::{
+    ffi::CString,
+    io,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+};
+
+use tokio::io::unix::AsyncFd;
+
+use crate::protocol::transport::{can_frame::CanFrame, can_id::CanId, traits::can_bus::CanBus};
+
+/// Size of a classic `struct can_frame` exchanged with the kernel.
+const CAN_FRAME_SIZE: usize = core::mem::size_of::<libc::can_frame>();
+
+/// [`CanBus`] implementation over a Linux raw CAN socket.
+///
+/// Must be opened from within a tokio runtime with IO enabled.
+#[derive(Debug)]
+pub struct SocketCanBus {
+    socket: AsyncFd<OwnedFd>,
+}
+
+impl SocketCanBus {
+    /// Open and bind a non-blocking raw CAN socket on `interface`.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the interface does not exist, the name contains a NUL byte,
+    /// or the socket cannot be created and bound.
+    pub fn open(interface: &str) -> io::Result<Self> {
+        let name = CString::new(interface)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "interface name"))?;
+
+        // SAFETY: `name` is a valid NUL-terminated string.
+        let if_index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        if if_index == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // SAFETY: plain socket creation; the result is checked before use.
+        let fd = unsafe {
+            libc::socket(
+                libc::PF_CAN,
+                libc::SOCK_RAW | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                libc::CAN_RAW,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `fd` is a freshly created descriptor owned by nobody else.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // SAFETY: an all-zero `sockaddr_can` is a valid value.
+        let mut address: libc::sockaddr_can = unsafe { core::mem::zeroed() };
+        address.can_family = libc::AF_CAN as libc::sa_family_t;
+        address.can_ifindex = if_index as libc::c_int;
+
+        // SAFETY: `address` lives for the duration of the call and its size is passed along.
+        let result = unsafe {
+            libc::bind(
+                fd.as_raw_fd(),
+                &address as *const libc::sockaddr_can as *const libc::sockaddr,
+                core::mem::size_of::<libc::sockaddr_can>() as libc::socklen_t,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self {
+            socket: AsyncFd::new(fd)?,
+        })
+    }
+}
+
+impl CanBus for SocketCanBus {
+    type Error = io::Error;
+
+    async fn send<'a>(&'a mut self, frame: &'a CanFrame) -> Result<(), Self::Error> {
+        let raw = to_raw_frame(frame);
+
+        loop {
+            let mut guard = self.socket.writable().await?;
+            // SAFETY: `raw` is a fully initialized `can_frame` of `CAN_FRAME_SIZE` bytes.
+            let result = guard.try_io(|socket| {
+                let written = unsafe {
+                    libc::write(
+                        socket.as_raw_fd(),
+                        &raw as *const libc::can_frame as *const libc::c_void,
+                        CAN_FRAME_SIZE,
+                    )
+                };
+                check_transfer(written)
+            });
+
+            match result {
+                Ok(result) => return result,
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    async fn recv(&mut self) -> Result<CanFrame, Self::Error> {
+        loop {
+            let mut guard = self.socket.readable().await?;
+            // SAFETY: an all-zero `can_frame` is a valid value.
+            let mut raw: libc::can_frame = unsafe { core::mem::zeroed() };
+            // SAFETY: `raw` provides `CAN_FRAME_SIZE` writable bytes.
+            let result = guard.try_io(|socket| {
+                let read = unsafe {
+                    libc::read(
+                        socket.as_raw_fd(),
+                        &mut raw as *mut libc::can_frame as *mut libc::c_void,
+                        CAN_FRAME_SIZE,
+                    )
+                };
+                check_transfer(read)
+            });
+
+            match result {
+                Ok(Ok(())) => {
+                    if let Some(frame) = from_raw_frame(&raw) {
+                        return Ok(frame);
+                    }
+                    // Not an NMEA 2000 frame: wait for the next one.
+                }
+                Ok(Err(e)) => return Err(e),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+
+/// Turn the return value of `read`/`write` into an error unless a whole frame moved.
+fn check_transfer(count: isize) -> io::Result<()> {
+    if count < 0 {
+        Err(io::Error::last_os_error())
+    } else if count as usize != CAN_FRAME_SIZE {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "incomplete CAN frame",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Encode a frame as an extended-identifier SocketCAN frame.
+fn to_raw_frame(frame: &CanFrame) -> libc::can_frame {
+    // SAFETY: an all-zero `can_frame` is a valid value.
+    let mut raw: libc::can_frame = unsafe { core::mem::zeroed() };
+    let len = frame.len.min(frame.data.len());
+
+    raw.can_id = (frame.id.0 & libc::CAN_EFF_MASK) | libc::CAN_EFF_FLAG;
+    raw.can_dlc = len as u8;
+    raw.data[..len].copy_from_slice(&frame.data[..len]);
+    raw
+}
+
+/// Decode a SocketCAN frame; `None` for standard, remote, and error frames.
+fn from_raw_frame(raw: &libc::can_frame) -> Option<CanFrame> {
+    let is_extended = raw.can_id & libc::CAN_EFF_FLAG != 0;
+    let is_remote_or_error = raw.can_id & (libc::CAN_RTR_FLAG | libc::CAN_ERR_FLAG) != 0;
+    if !is_extended || is_remote_or_error {
+        return None;
+    }
+
+    let len = (raw.can_dlc as usize).min(raw.data.len());
+    let mut data = [0; 8];
+    data[..len].copy_from_slice(&raw.data[..len]);
+
+    Some(CanFrame {
+        id: CanId(raw.can_id & libc::CAN_EFF_MASK),
+        data,
+        len,
+    })
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
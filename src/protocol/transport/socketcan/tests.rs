@@ -0,0 +1,49 @@
+//! SocketCAN frame conversion tests (no interface required).
+// SOCKETCAN
+use super::*;
+
+#[test]
+/// Extended identifier, DLC, and payload survive the round trip.
+fn test_raw_frame_roundtrip() {
+    let frame = CanFrame {
+        id: CanId::builder(129025, 42).with_priority(2).build().unwrap(),
+        data: [1, 2, 3, 4, 5, 0, 0, 0],
+        len: 5,
+    };
+
+    let raw = to_raw_frame(&frame);
+    assert_eq!(raw.can_id, frame.id.0 | libc::CAN_EFF_FLAG);
+    assert_eq!(raw.can_dlc, 5);
+
+    let decoded = from_raw_frame(&raw).unwrap();
+    assert_eq!(decoded.id, frame.id);
+    assert_eq!(decoded.len, 5);
+    assert_eq!(&decoded.data[..5], &[1, 2, 3, 4, 5]);
+}
+
+#[test]
+/// Standard, remote, and error frames are not NMEA 2000 traffic.
+fn test_non_extended_frames_are_skipped() {
+    // SAFETY: an all-zero `can_frame` is a valid value.
+    let mut raw: libc::can_frame = unsafe { core::mem::zeroed() };
+
+    raw.can_id = 0x123;
+    assert!(from_raw_frame(&raw).is_none());
+
+    raw.can_id = 0x123 | libc::CAN_EFF_FLAG | libc::CAN_RTR_FLAG;
+    assert!(from_raw_frame(&raw).is_none());
+
+    raw.can_id = libc::CAN_EFF_FLAG | libc::CAN_ERR_FLAG;
+    assert!(from_raw_frame(&raw).is_none());
+}
+
+#[test]
+/// A DLC above eight is clamped to the classic payload size.
+fn test_dlc_is_clamped() {
+    // SAFETY: an all-zero `can_frame` is a valid value.
+    let mut raw: libc::can_frame = unsafe { core::mem::zeroed() };
+    raw.can_id = 0x09F80142 | libc::CAN_EFF_FLAG;
+    raw.can_dlc = 15;
+
+    assert_eq!(from_raw_frame(&raw).unwrap().len, 8);
+}
@@ -0,0 +1,84 @@
+//! Helpers shared by the transport unit tests.
+use core::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll},
+};
+
+use embedded_can::{Frame, Id};
+
+//==================================================================================EXECUTOR
+/// Drive `future` to completion on the current thread, counting the polls it needed.
+pub(crate) fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+    use core::task::{RawWaker, RawWakerVTable, Waker};
+
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    // SAFETY: the vtable functions ignore the data pointer.
+    let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    let mut polls = 0;
+    loop {
+        polls += 1;
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return (output, polls);
+        }
+    }
+}
+
+//==================================================================================DRIVER_FRAME
+/// Minimal driver-side frame accepting every identifier kind and remote frames.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HalFrame {
+    pub(crate) id: Id,
+    pub(crate) data: [u8; 8],
+    pub(crate) dlc: usize,
+    pub(crate) remote: bool,
+}
+
+impl Frame for HalFrame {
+    fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
+        let mut buffer = [0; 8];
+        buffer.get_mut(..data.len())?.copy_from_slice(data);
+        Some(Self {
+            id: id.into(),
+            data: buffer,
+            dlc: data.len(),
+            remote: false,
+        })
+    }
+
+    fn new_remote(id: impl Into<Id>, dlc: usize) -> Option<Self> {
+        Some(Self {
+            id: id.into(),
+            data: [0; 8],
+            dlc,
+            remote: true,
+        })
+    }
+
+    fn is_extended(&self) -> bool {
+        matches!(self.id, Id::Extended(_))
+    }
+
+    fn is_remote_frame(&self) -> bool {
+        self.remote
+    }
+
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn dlc(&self) -> usize {
+        self.dlc
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data[..self.dlc]
+    }
+}
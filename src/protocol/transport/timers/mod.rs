@@ -0,0 +1,165 @@
+//! Ready-made [`KorriTimer`] implementations.
+//!
+//! - [`EmbassyTimer`]: firmware running an `embassy-time` driver.
+//! - [`ManualTimer`]: deterministic virtual clock for tests and simulations.
+//! - [`TokioTimer`]: host tools and tests running on tokio (`std` feature).
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::protocol::transport::traits::korri_timer::KorriTimer;
+
+//==================================================================================EMBASSY
+/// [`KorriTimer`] backed by `embassy-time`.
+///
+/// The firmware must link an `embassy-time` driver (HAL time driver or
+/// `embassy-time/std`), as for any other `embassy_time::Timer`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EmbassyTimer;
+
+impl EmbassyTimer {
+    /// Create the timer; all instances share the embassy clock.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl KorriTimer for EmbassyTimer {
+    async fn delay_ms(&mut self, millis: u32) {
+        embassy_time::Timer::after_millis(millis as u64).await;
+    }
+
+    fn now_ms(&self) -> u64 {
+        embassy_time::Instant::now().as_millis()
+    }
+}
+
+//==================================================================================MANUAL
+/// Deterministic [`KorriTimer`] driven by a virtual clock.
+///
+/// Time only moves when the test says so: [`advance`](Self::advance) and
+/// [`set`](Self::set) move the clock explicitly, and every `delay_ms` jumps it
+/// forward by the requested delay instead of sleeping. A 250 ms claim window
+/// therefore completes at once, while `now_ms` still reports 250 ms later.
+///
+/// Before completing, a delay yields once to the executor, so futures raced
+/// against it (e.g. a pending `recv`) get polled with frames already queued.
+///
+/// ```rust,ignore
+/// let mut timer = ManualTimer::new();
+/// let address = claim_address(&mut bus, &mut timer, my_name, 42).await?;
+/// assert_eq!(timer.now_ms(), 250);
+/// ```
+///
+/// The timer is neither `Clone` nor `Copy`: a copy would carry its own clock and
+/// drift away from the one the code under test advances.
+#[derive(Debug, Default)]
+pub struct ManualTimer {
+    now_ms: u64,
+}
+
+impl ManualTimer {
+    /// Create a timer whose virtual clock starts at zero.
+    pub const fn new() -> Self {
+        Self { now_ms: 0 }
+    }
+
+    /// Create a timer whose virtual clock starts at `now_ms`.
+    pub const fn starting_at(now_ms: u64) -> Self {
+        Self { now_ms }
+    }
+
+    /// Move the virtual clock forward by `millis`.
+    pub fn advance(&mut self, millis: u64) {
+        self.now_ms = self.now_ms.saturating_add(millis);
+    }
+
+    /// Set the virtual clock to `now_ms`.
+    ///
+    /// The clock is monotonic like the real ones: an instant earlier than the
+    /// current time leaves it unchanged.
+    pub fn set(&mut self, now_ms: u64) {
+        self.now_ms = self.now_ms.max(now_ms);
+    }
+}
+
+impl KorriTimer for ManualTimer {
+    async fn delay_ms(&mut self, millis: u32) {
+        YieldOnce::new().await;
+        self.advance(millis as u64);
+    }
+
+    fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+}
+
+/// Future returning `Pending` once, letting the executor poll its siblings.
+pub(crate) struct YieldOnce {
+    yielded: bool,
+}
+
+impl YieldOnce {
+    pub(crate) const fn new() -> Self {
+        Self { yielded: false }
+    }
+}
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+//==================================================================================TOKIO
+/// [`KorriTimer`] backed by `tokio::time` (`std` feature).
+///
+/// `now_ms` counts from the creation of the timer. Delays require a tokio runtime
+/// with the time driver enabled.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct TokioTimer {
+    origin: tokio::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl TokioTimer {
+    /// Create a timer whose clock starts now.
+    pub fn new() -> Self {
+        Self {
+            origin: tokio::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for TokioTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl KorriTimer for TokioTimer {
+    async fn delay_ms(&mut self, millis: u32) {
+        tokio::time::sleep(core::time::Duration::from_millis(millis as u64)).await;
+    }
+
+    fn now_ms(&self) -> u64 {
+        self.origin.elapsed().as_millis() as u64
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
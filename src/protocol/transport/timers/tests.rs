@@ -0,0 +1,36 @@
+//! Virtual clock tests for `ManualTimer`.
+// TIMERS
+use super::*;
+use crate::protocol::transport::test_support::block_on;
+
+#[test]
+/// Delays complete without sleeping and move the clock forward.
+fn test_delay_advances_virtual_clock() {
+    let mut timer = ManualTimer::new();
+
+    let ((), polls) = block_on(timer.delay_ms(250));
+    assert_eq!(polls, 2, "the delay yields exactly once");
+    assert_eq!(timer.now_ms(), 250);
+
+    block_on(timer.delay_ms(50));
+    assert_eq!(timer.now_ms(), 300);
+}
+
+#[test]
+/// The clock moves explicitly with `advance` and `set`.
+fn test_explicit_clock_control() {
+    let mut timer = ManualTimer::starting_at(1_000);
+    assert_eq!(timer.now_ms(), 1_000);
+
+    timer.advance(750);
+    assert_eq!(timer.now_ms(), 1_750);
+
+    timer.set(2_000);
+    assert_eq!(timer.now_ms(), 2_000);
+
+    timer.set(10);
+    assert_eq!(timer.now_ms(), 2_000, "the clock never moves backwards");
+
+    timer.advance(u64::MAX);
+    assert_eq!(timer.now_ms(), u64::MAX);
+}
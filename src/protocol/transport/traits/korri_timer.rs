@@ -4,8 +4,13 @@
 /// Timer trait abstraction; must remain thread-safe when applicable.
 pub trait KorriTimer {
     /// Asynchronously wait for `millis` milliseconds.
-    fn delay_ms<'a>(
-        &'a mut self,
-        millis: u32,
-    ) -> impl core::future::Future<Output = ()> + 'a;
+    fn delay_ms<'a>(&'a mut self, millis: u32) -> impl core::future::Future<Output = ()> + 'a;
+
+    /// Monotonic time in milliseconds, used to enforce protocol timeouts
+    /// (transport sessions T1–T4, Fast Packet reassembly). The origin is
+    /// arbitrary but must not move.
+    ///
+    /// A clock that does not advance disables those timeouts: sessions left
+    /// open by a silent peer are then only released by eviction.
+    fn now_ms(&self) -> u64;
 }
@@ -9,12 +9,16 @@
 //! from saturating their TX buffers.
 //!
 //! The default delay is defined by [`FAST_PACKET_INTER_FRAME_DELAY_MS`].
+//!
+//! Frames and pacing come from [`PgnTransmission`], the poll-driven core
+//! shared with executor-free applications; this trait only awaits them.
+//!
+//! [`FAST_PACKET_INTER_FRAME_DELAY_MS`]: crate::protocol::transport::FAST_PACKET_INTER_FRAME_DELAY_MS
 use crate::{
     error::SendPgnError,
-    infra::codec::traits::PgnData,
-    protocol::transport::fast_packet::{builder::FastPacketBuilder, MAX_FAST_PACKET_PAYLOAD},
+    infra::codec::traits::{PgnData, PgnMetadata},
+    protocol::transport::fast_packet::{sequence::SequenceCounter, transmission::PgnTransmission},
     protocol::transport::traits::{can_bus::CanBus, korri_timer::KorriTimer},
-    protocol::transport::FAST_PACKET_INTER_FRAME_DELAY_MS,
 };
 
 /// Trait extending `CanBus` with ergonomic PGN-sending helpers.
@@ -42,6 +46,7 @@ where
     /// * `pgn` – Parameter Group Number
     /// * `source_address` – Source address (0-253)
     /// * `destination` – Optional destination (None = broadcast)
+    /// * `sequences` – Fast Packet sequence identifiers owned by the sender
     /// * `timer` – Timer to enforce inter-frame delays
     ///
     /// # Errors
@@ -64,7 +69,8 @@ where
     /// pgn.number_of_lines = 1;
     ///
     /// // Send with automatic inter-frame delays
-    /// can_bus.send_pgn(&pgn, 127503, my_address, None, &mut timer).await?;
+    /// let mut sequences = SequenceCounter::new();
+    /// can_bus.send_pgn(&pgn, 127503, my_address, None, &mut sequences, &mut timer).await?;
     /// ```
     fn send_pgn<'a, P: PgnData, T: KorriTimer>(
         &'a mut self,
@@ -72,6 +78,30 @@ where
         pgn: u32,
         source_address: u8,
         destination: Option<u8>,
+        sequences: &'a mut SequenceCounter,
+        timer: &'a mut T,
+    ) -> impl core::future::Future<Output = Result<(), SendPgnError<Self::Error>>> + 'a;
+
+    /// Serialize, segment, and send a generated PGN type.
+    ///
+    /// Same as [`send_pgn`](Self::send_pgn), but the PGN number and the priority
+    /// come from the type's [`PgnMetadata`], so they always match the payload.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut position = Pgn129025::new();
+    /// position.latitude = 47.5;
+    ///
+    /// // Sent as PGN 129025 with priority 2.
+    /// can_bus.send_message(&position, my_address, None, &mut sequences, &mut timer).await?;
+    /// ```
+    fn send_message<'a, P: PgnData + PgnMetadata, T: KorriTimer>(
+        &'a mut self,
+        pgn_data: &'a P,
+        source_address: u8,
+        destination: Option<u8>,
+        sequences: &'a mut SequenceCounter,
         timer: &'a mut T,
     ) -> impl core::future::Future<Output = Result<(), SendPgnError<Self::Error>>> + 'a;
 }
@@ -80,48 +110,106 @@ impl<C: CanBus> PgnSender for C
 where
     C::Error: core::fmt::Debug,
 {
-    fn send_pgn<'a, P: PgnData, T: KorriTimer>(
+    async fn send_pgn<'a, P: PgnData, T: KorriTimer>(
         &'a mut self,
         pgn_data: &'a P,
         pgn: u32,
         source_address: u8,
         destination: Option<u8>,
+        sequences: &'a mut SequenceCounter,
         timer: &'a mut T,
-    ) -> impl core::future::Future<Output = Result<(), SendPgnError<Self::Error>>> + 'a {
-        async move {
-            // Step 1: stack-allocate a buffer to avoid heap usage.
-            let mut payload_buffer = [0u8; MAX_FAST_PACKET_PAYLOAD];
-
-            // Step 2: serialize the PGN into the buffer.
-            let len = pgn_data
-                .to_payload(&mut payload_buffer)
-                .map_err(|_| SendPgnError::Serialization)?;
-            let payload_slice = &payload_buffer[..len];
-
-            // Step 3: prepare the Fast Packet (or single-frame) builder.
-            let builder = FastPacketBuilder::new(pgn, source_address, destination, payload_slice);
-
-            // Step 4: send every frame sequentially with inter-frame delays when required.
-            let frame_iter = builder.build();
-            let mut is_first_frame = true;
-
-            for frame_result in frame_iter {
-                let frame = frame_result.map_err(SendPgnError::Build)?;
+    ) -> Result<(), SendPgnError<Self::Error>> {
+        // Default priority, as for a bare `CanId`.
+        let priority = 6;
+        send_serialized(
+            self,
+            pgn_data,
+            pgn,
+            priority,
+            source_address,
+            destination,
+            sequences,
+            timer,
+        )
+        .await
+    }
 
-                // For multi-frame Fast Packets insert a delay between frames
-                // (skip before the first frame to minimize latency).
-                if !is_first_frame && payload_slice.len() > 8 {
-                    // Recommended inter-frame delay to avoid TX buffer saturation
-                    timer.delay_ms(FAST_PACKET_INTER_FRAME_DELAY_MS).await;
-                }
+    async fn send_message<'a, P: PgnData + PgnMetadata, T: KorriTimer>(
+        &'a mut self,
+        pgn_data: &'a P,
+        source_address: u8,
+        destination: Option<u8>,
+        sequences: &'a mut SequenceCounter,
+        timer: &'a mut T,
+    ) -> Result<(), SendPgnError<Self::Error>> {
+        send_serialized(
+            self,
+            pgn_data,
+            P::PGN,
+            P::DEFAULT_PRIORITY,
+            source_address,
+            destination,
+            sequences,
+            timer,
+        )
+        .await
+    }
+}
 
-                // Send the CAN frame
-                self.send(&frame).await.map_err(SendPgnError::Send)?;
+/// Shared implementation of the `PgnSender` methods.
+#[allow(clippy::too_many_arguments)]
+async fn send_serialized<C, P, T>(
+    can_bus: &mut C,
+    pgn_data: &P,
+    pgn: u32,
+    priority: u8,
+    source_address: u8,
+    destination: Option<u8>,
+    sequences: &mut SequenceCounter,
+    timer: &mut T,
+) -> Result<(), SendPgnError<C::Error>>
+where
+    C: CanBus,
+    C::Error: core::fmt::Debug,
+    P: PgnData,
+    T: KorriTimer,
+{
+    let transmission = PgnTransmission::new(pgn_data, pgn, source_address, destination, sequences)
+        .map_err(|_| SendPgnError::Serialization)?
+        .with_priority(priority);
+    send_transmission(can_bus, timer, transmission).await
+}
 
-                is_first_frame = false;
-            }
+/// Send every frame of `transmission`, waiting on `timer` until each is due.
+///
+/// Async driver of [`PgnTransmission`]; a timer without clock still waits the
+/// full inter-frame delay.
+pub(crate) async fn send_transmission<C, T>(
+    can_bus: &mut C,
+    timer: &mut T,
+    mut transmission: PgnTransmission,
+) -> Result<(), SendPgnError<C::Error>>
+where
+    C: CanBus,
+    C::Error: core::fmt::Debug,
+    T: KorriTimer,
+{
+    let mut now_ms = timer.now_ms();
 
-            Ok(())
+    loop {
+        if let Some(frame) = transmission.on_tick(now_ms) {
+            let frame = frame.map_err(SendPgnError::Build)?;
+            can_bus.send(&frame).await.map_err(SendPgnError::Send)?;
+            continue;
         }
+        let Some(deadline_ms) = transmission.next_deadline() else {
+            return Ok(());
+        };
+        // Recommended inter-frame delay to avoid TX buffer saturation.
+        timer
+            .delay_ms(deadline_ms.saturating_sub(now_ms) as u32)
+            .await;
+        // The delay elapsed: the frame is due even if the clock lags behind.
+        now_ms = timer.now_ms().max(deadline_ms);
     }
 }
@@ -0,0 +1,387 @@
+//! Multi-node virtual CAN bus for host-side simulations (`std` feature).
+//!
+//! A [`VirtualBus`] is a hub to which any number of [`VirtualBusEndpoint`]s
+//! attach. Each endpoint implements [`CanBus`], so address managers, claim
+//! loops, and application tasks run unmodified on top of it.
+//!
+//! ## Behaviour
+//!
+//! - **Broadcast**: a transmitted frame reaches every attached endpoint except
+//!   its sender.
+//! - **Arbitration**: frames submitted during the same scheduling round are
+//!   put on the wire lowest 29-bit identifier first, as CAN bitwise
+//!   arbitration would do. Equal identifiers keep their submission order.
+//!   `send` completes once the frame has been transmitted.
+//! - **Fault injection**: [`inject_errors`](VirtualBus::inject_errors) destroys
+//!   the next transmissions (the sender gets [`VirtualBusError::Transmit`]),
+//!   [`drop_frames`](VirtualBus::drop_frames) silently loses them.
+//! - **Bus-off**: every injected error adds 8 to the sender's transmit error
+//!   counter and every success removes 1; past 255 the endpoint goes bus-off,
+//!   as mandated by ISO 11898-1. [`set_bus_off`](VirtualBus::set_bus_off)
+//!   forces or recovers the state.
+//! - **Trace**: every frame that reached the wire is recorded in order
+//!   ([`trace`](VirtualBus::trace)).
+//!
+//! ```rust,ignore
+//! let bus = VirtualBus::new();
+//! let nodes: Vec<_> = (0..5).map(|_| bus.attach()).collect();
+//! // … run one AddressManager per endpoint …
+//! assert_eq!(bus.trace()[0].frame.id.pgn(), 60928);
+//! ```
+use core::{
+    future::poll_fn,
+    task::{Context, Poll, Waker},
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard},
+    vec::Vec,
+};
+
+use crate::{
+    error::VirtualBusError,
+    protocol::transport::{can_frame::CanFrame, timers::YieldOnce, traits::can_bus::CanBus},
+};
+
+//==================================================================================ERROR_COUNTERS
+/// Transmit error counter increment applied for each destroyed frame.
+const TX_ERROR_INCREMENT: u16 = 8;
+/// Transmit error counter value above which an endpoint goes bus-off.
+const BUS_OFF_THRESHOLD: u16 = 255;
+
+//==================================================================================TRACE
+/// Fate of a frame that reached the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceOutcome {
+    /// Delivered to every other attached endpoint.
+    Delivered,
+    /// Silently lost (see [`VirtualBus::drop_frames`]).
+    Dropped,
+    /// Destroyed by an injected error (see [`VirtualBus::inject_errors`]).
+    Error,
+}
+
+/// One transmission recorded by the hub, in wire order.
+#[derive(Debug, Clone)]
+pub struct TraceEntry {
+    /// Identifier of the transmitting endpoint (see [`VirtualBusEndpoint::id`]).
+    pub sender: usize,
+    /// Transmitted frame.
+    pub frame: CanFrame,
+    /// What happened to the frame.
+    pub outcome: TraceOutcome,
+}
+
+//==================================================================================HUB
+/// Shared state of one endpoint.
+#[derive(Debug, Default)]
+struct Node {
+    attached: bool,
+    rx: VecDeque<CanFrame>,
+    rx_waker: Option<Waker>,
+    tx_error_counter: u16,
+    bus_off: bool,
+}
+
+/// Frame waiting for arbitration.
+#[derive(Debug)]
+struct Pending {
+    ticket: u64,
+    sender: usize,
+    frame: CanFrame,
+}
+
+#[derive(Debug, Default)]
+struct Hub {
+    nodes: Vec<Node>,
+    pending: Vec<Pending>,
+    results: HashMap<u64, Result<(), VirtualBusError>>,
+    next_ticket: u64,
+    errors_to_inject: usize,
+    frames_to_drop: usize,
+    trace: Vec<TraceEntry>,
+}
+
+impl Hub {
+    /// Put every pending frame on the wire, lowest identifier first.
+    fn arbitrate(&mut self) {
+        let mut contenders = core::mem::take(&mut self.pending);
+        // Stable sort: equal identifiers keep their submission order.
+        contenders.sort_by_key(|pending| pending.frame.id.0);
+
+        for Pending {
+            ticket,
+            sender,
+            frame,
+        } in contenders
+        {
+            let (outcome, result) = if self.errors_to_inject > 0 {
+                self.errors_to_inject -= 1;
+                self.record_tx_error(sender);
+                (TraceOutcome::Error, Err(VirtualBusError::Transmit))
+            } else if self.frames_to_drop > 0 {
+                self.frames_to_drop -= 1;
+                self.record_tx_success(sender);
+                (TraceOutcome::Dropped, Ok(()))
+            } else {
+                self.record_tx_success(sender);
+                self.deliver(sender, &frame);
+                (TraceOutcome::Delivered, Ok(()))
+            };
+
+            self.trace.push(TraceEntry {
+                sender,
+                frame,
+                outcome,
+            });
+            self.results.insert(ticket, result);
+        }
+    }
+
+    /// Queue `frame` on every attached, active endpoint except `sender`.
+    fn deliver(&mut self, sender: usize, frame: &CanFrame) {
+        for (id, node) in self.nodes.iter_mut().enumerate() {
+            if id == sender || !node.attached || node.bus_off {
+                continue;
+            }
+            node.rx.push_back(frame.clone());
+            if let Some(waker) = node.rx_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    fn record_tx_error(&mut self, sender: usize) {
+        let node = &mut self.nodes[sender];
+        node.tx_error_counter = node.tx_error_counter.saturating_add(TX_ERROR_INCREMENT);
+        if node.tx_error_counter > BUS_OFF_THRESHOLD {
+            node.enter_bus_off();
+        }
+    }
+
+    fn record_tx_success(&mut self, sender: usize) {
+        let node = &mut self.nodes[sender];
+        node.tx_error_counter = node.tx_error_counter.saturating_sub(1);
+    }
+}
+
+impl Node {
+    fn enter_bus_off(&mut self) {
+        self.bus_off = true;
+        self.rx.clear();
+        if let Some(waker) = self.rx_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+//==================================================================================VIRTUAL_BUS
+/// In-memory CAN segment connecting any number of [`VirtualBusEndpoint`]s.
+///
+/// Cloning the handle shares the same segment.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualBus {
+    hub: Arc<Mutex<Hub>>,
+}
+
+impl VirtualBus {
+    /// Create an empty segment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attach a new endpoint to the segment.
+    ///
+    /// The endpoint only receives frames transmitted after this call.
+    pub fn attach(&self) -> VirtualBusEndpoint {
+        let mut hub = self.lock();
+        hub.nodes.push(Node {
+            attached: true,
+            ..Node::default()
+        });
+        VirtualBusEndpoint {
+            bus: self.clone(),
+            id: hub.nodes.len() - 1,
+        }
+    }
+
+    /// Destroy the next `count` transmissions with a bus error.
+    ///
+    /// The sender receives [`VirtualBusError::Transmit`] and its transmit error
+    /// counter grows; nobody receives the frame.
+    pub fn inject_errors(&self, count: usize) {
+        self.lock().errors_to_inject += count;
+    }
+
+    /// Silently lose the next `count` transmissions.
+    ///
+    /// The sender believes the frame was sent; nobody receives it.
+    pub fn drop_frames(&self, count: usize) {
+        self.lock().frames_to_drop += count;
+    }
+
+    /// Force endpoint `id` into bus-off, or recover it with a cleared error counter.
+    ///
+    /// A bus-off endpoint fails every `send` and `recv` with
+    /// [`VirtualBusError::BusOff`] and misses all traffic.
+    pub fn set_bus_off(&self, id: usize, bus_off: bool) {
+        let mut hub = self.lock();
+        let Some(node) = hub.nodes.get_mut(id) else {
+            return;
+        };
+        if bus_off {
+            node.enter_bus_off();
+        } else {
+            node.bus_off = false;
+            node.tx_error_counter = 0;
+        }
+    }
+
+    /// Whether endpoint `id` is currently bus-off.
+    pub fn is_bus_off(&self, id: usize) -> bool {
+        self.lock().nodes.get(id).is_some_and(|node| node.bus_off)
+    }
+
+    /// Transmit error counter of endpoint `id`.
+    pub fn tx_error_counter(&self, id: usize) -> u16 {
+        self.lock()
+            .nodes
+            .get(id)
+            .map_or(0, |node| node.tx_error_counter)
+    }
+
+    /// Snapshot of every transmission so far, in wire order.
+    pub fn trace(&self) -> Vec<TraceEntry> {
+        self.lock().trace.clone()
+    }
+
+    /// Forget the recorded transmissions.
+    pub fn clear_trace(&self) {
+        self.lock().trace.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Hub> {
+        // A panicking test task must not hide the trace from the others.
+        self.hub
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Queue `frame` for the next arbitration round and return its ticket.
+    fn submit(&self, sender: usize, frame: &CanFrame) -> Result<Ticket<'_>, VirtualBusError> {
+        let mut hub = self.lock();
+        if hub.nodes[sender].bus_off {
+            return Err(VirtualBusError::BusOff);
+        }
+        let ticket = hub.next_ticket;
+        hub.next_ticket += 1;
+        hub.pending.push(Pending {
+            ticket,
+            sender,
+            frame: frame.clone(),
+        });
+        Ok(Ticket { bus: self, ticket })
+    }
+
+    /// Run arbitration if needed and return the outcome of `ticket`.
+    fn complete(&self, ticket: u64) -> Result<(), VirtualBusError> {
+        let mut hub = self.lock();
+        if !hub.results.contains_key(&ticket) {
+            hub.arbitrate();
+        }
+        hub.results.remove(&ticket).unwrap_or(Ok(()))
+    }
+
+    fn poll_recv(
+        &self,
+        id: usize,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<CanFrame, VirtualBusError>> {
+        let mut hub = self.lock();
+        let node = &mut hub.nodes[id];
+        if node.bus_off {
+            return Poll::Ready(Err(VirtualBusError::BusOff));
+        }
+        match node.rx.pop_front() {
+            Some(frame) => Poll::Ready(Ok(frame)),
+            None => {
+                node.rx_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Claim on the outcome of a submitted frame.
+///
+/// Dropping it (e.g. with a cancelled `send` future) withdraws the frame if it
+/// has not been arbitrated yet and discards its outcome otherwise, so the hub
+/// keeps no state for abandoned transmissions.
+struct Ticket<'a> {
+    bus: &'a VirtualBus,
+    ticket: u64,
+}
+
+impl Ticket<'_> {
+    /// Run arbitration if needed and return the outcome of the frame.
+    fn complete(self) -> Result<(), VirtualBusError> {
+        self.bus.complete(self.ticket)
+    }
+}
+
+impl Drop for Ticket<'_> {
+    fn drop(&mut self) {
+        let mut hub = self.bus.lock();
+        hub.pending.retain(|pending| pending.ticket != self.ticket);
+        hub.results.remove(&self.ticket);
+    }
+}
+
+//==================================================================================ENDPOINT
+/// One node attached to a [`VirtualBus`]; detaches when dropped.
+#[derive(Debug)]
+pub struct VirtualBusEndpoint {
+    bus: VirtualBus,
+    id: usize,
+}
+
+impl VirtualBusEndpoint {
+    /// Identifier of the endpoint on its segment (attachment order, from 0).
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Segment the endpoint is attached to.
+    pub fn bus(&self) -> &VirtualBus {
+        &self.bus
+    }
+}
+
+impl CanBus for VirtualBusEndpoint {
+    type Error = VirtualBusError;
+
+    async fn send<'a>(&'a mut self, frame: &'a CanFrame) -> Result<(), Self::Error> {
+        let ticket = self.bus.submit(self.id, frame)?;
+        // Let the other nodes queue their frames for the same arbitration round.
+        YieldOnce::new().await;
+        ticket.complete()
+    }
+
+    async fn recv(&mut self) -> Result<CanFrame, Self::Error> {
+        poll_fn(|cx| self.bus.poll_recv(self.id, cx)).await
+    }
+}
+
+impl Drop for VirtualBusEndpoint {
+    fn drop(&mut self) {
+        let mut hub = self.bus.lock();
+        let node = &mut hub.nodes[self.id];
+        node.attached = false;
+        node.rx.clear();
+        node.rx_waker = None;
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,183 @@
+//! Virtual bus tests: broadcast, arbitration, fault injection, and bus-off.
+// VIRTUAL_BUS
+use super::*;
+use crate::protocol::transport::can_id::CanId;
+
+fn frame(pgn: u32, priority: u8, source: u8) -> CanFrame {
+    CanFrame {
+        id: CanId::builder(pgn, source)
+            .with_priority(priority)
+            .build()
+            .unwrap(),
+        data: [source; 8],
+        len: 8,
+    }
+}
+
+/// Poll `future` a single time.
+async fn poll_once<F: core::future::Future>(future: core::pin::Pin<&mut F>) -> Poll<F::Output> {
+    let mut future = Some(future);
+    poll_fn(|cx| Poll::Ready(future.take().unwrap().poll(cx))).await
+}
+
+/// Returns `true` when `endpoint` has no frame waiting.
+fn is_idle(endpoint: &mut VirtualBusEndpoint) -> bool {
+    futures_util::FutureExt::now_or_never(endpoint.recv()).is_none()
+}
+
+#[tokio::test]
+/// Every endpoint but the sender receives the frame.
+async fn test_broadcast_skips_sender() {
+    let bus = VirtualBus::new();
+    let mut a = bus.attach();
+    let mut b = bus.attach();
+    let mut c = bus.attach();
+
+    a.send(&frame(130306, 2, 10)).await.unwrap();
+
+    assert_eq!(b.recv().await.unwrap().id.source_address(), 10);
+    assert_eq!(c.recv().await.unwrap().id.source_address(), 10);
+    assert!(is_idle(&mut a));
+    assert_eq!(bus.trace().len(), 1);
+    assert_eq!(bus.trace()[0].sender, a.id());
+}
+
+#[tokio::test]
+/// Simultaneous frames go on the wire lowest identifier first.
+async fn test_simultaneous_frames_arbitrated_by_id() {
+    let bus = VirtualBus::new();
+    let mut a = bus.attach();
+    let mut b = bus.attach();
+    let mut c = bus.attach();
+    let mut listener = bus.attach();
+
+    let low_priority = frame(130306, 6, 1);
+    let high_priority = frame(127250, 2, 2);
+    let mid_priority = frame(129025, 3, 3);
+
+    let (ra, rb, rc) = tokio::join!(
+        a.send(&low_priority),
+        b.send(&high_priority),
+        c.send(&mid_priority)
+    );
+    assert!(ra.is_ok() && rb.is_ok() && rc.is_ok());
+
+    let order: Vec<u8> = bus
+        .trace()
+        .iter()
+        .map(|entry| entry.frame.id.source_address())
+        .collect();
+    assert_eq!(order, [2, 3, 1]);
+
+    for expected in [2, 3, 1] {
+        assert_eq!(listener.recv().await.unwrap().id.source_address(), expected);
+    }
+}
+
+#[tokio::test]
+/// Injected errors fail the sender, dropped frames fail silently.
+async fn test_fault_injection() {
+    let bus = VirtualBus::new();
+    let mut a = bus.attach();
+    let mut b = bus.attach();
+
+    bus.inject_errors(1);
+    assert_eq!(
+        a.send(&frame(130306, 2, 1)).await,
+        Err(VirtualBusError::Transmit)
+    );
+    assert_eq!(bus.tx_error_counter(a.id()), 8);
+
+    bus.drop_frames(1);
+    assert_eq!(a.send(&frame(130306, 2, 1)).await, Ok(()));
+    assert!(is_idle(&mut b));
+
+    a.send(&frame(130306, 2, 1)).await.unwrap();
+    assert!(b.recv().await.is_ok());
+
+    let outcomes: Vec<TraceOutcome> = bus.trace().iter().map(|entry| entry.outcome).collect();
+    assert_eq!(
+        outcomes,
+        [
+            TraceOutcome::Error,
+            TraceOutcome::Dropped,
+            TraceOutcome::Delivered
+        ]
+    );
+    assert_eq!(bus.tx_error_counter(a.id()), 6);
+}
+
+#[tokio::test]
+/// Repeated transmit errors drive the sender bus-off until it is recovered.
+async fn test_bus_off_and_recovery() {
+    let bus = VirtualBus::new();
+    let mut a = bus.attach();
+    let mut b = bus.attach();
+
+    bus.inject_errors(32);
+    for _ in 0..32 {
+        assert_eq!(
+            a.send(&frame(130306, 2, 1)).await,
+            Err(VirtualBusError::Transmit)
+        );
+    }
+    assert!(bus.is_bus_off(a.id()));
+    assert_eq!(
+        a.send(&frame(130306, 2, 1)).await,
+        Err(VirtualBusError::BusOff)
+    );
+    assert_eq!(a.recv().await.unwrap_err(), VirtualBusError::BusOff);
+
+    // A bus-off node misses the traffic of the others.
+    b.send(&frame(130306, 2, 2)).await.unwrap();
+
+    bus.set_bus_off(a.id(), false);
+    assert_eq!(bus.tx_error_counter(a.id()), 0);
+    assert!(is_idle(&mut a));
+    a.send(&frame(130306, 2, 1)).await.unwrap();
+    assert!(b.recv().await.is_ok());
+}
+
+#[tokio::test]
+/// Dropped endpoints stop receiving without stalling the others.
+async fn test_detached_endpoint() {
+    let bus = VirtualBus::new();
+    let mut a = bus.attach();
+    let gone = bus.attach();
+    let mut c = bus.attach();
+    drop(gone);
+
+    a.send(&frame(130306, 2, 1)).await.unwrap();
+    assert!(c.recv().await.is_ok());
+}
+
+#[tokio::test]
+/// A cancelled `send` leaves no pending frame nor outcome behind.
+async fn test_cancelled_send_leaves_no_state() {
+    let bus = VirtualBus::new();
+    let mut a = bus.attach();
+    let mut b = bus.attach();
+    let mut listener = bus.attach();
+
+    // Cancelled before arbitration: the frame never reaches the wire.
+    let first = frame(130306, 6, 1);
+    {
+        let mut send = core::pin::pin!(a.send(&first));
+        assert!(poll_once(send.as_mut()).await.is_pending());
+    }
+    assert!(bus.lock().pending.is_empty());
+    b.send(&frame(127250, 2, 2)).await.unwrap();
+    assert_eq!(bus.trace().len(), 1);
+    assert_eq!(listener.recv().await.unwrap().id.source_address(), 2);
+
+    // Arbitrated by another sender, then cancelled: the outcome is discarded.
+    let second = frame(130306, 6, 1);
+    {
+        let mut send = core::pin::pin!(a.send(&second));
+        assert!(poll_once(send.as_mut()).await.is_pending());
+        b.send(&frame(127250, 2, 2)).await.unwrap();
+    }
+    assert_eq!(bus.trace().len(), 3);
+    assert!(bus.lock().pending.is_empty());
+    assert!(bus.lock().results.is_empty());
+}
@@ -0,0 +1,248 @@
+//! Timed replay of captured frames on any [`CanBus`].
+//!
+//! A [`Replayer`] transmits timestamped frames with the spacing they had when
+//! they were captured, measured with a [`KorriTimer`]. Playback can run faster
+//! or slower than real time, repeat the capture, and keep only some PGNs or
+//! source addresses, which lets firmware be bench-tested against a recorded
+//! sea trial.
+//!
+//! Pacing is anchored on the first frame of each pass: every frame is due at
+//! its capture offset divided by the speed factor, so rounding and bus latency
+//! never accumulate. Filtered-out frames keep their time slot, and a timestamp
+//! going backwards (time-of-day logs crossing midnight) re-anchors the pacing
+//! on that frame.
+//!
+//! ```rust,ignore
+//! let file = std::io::BufReader::new(std::fs::File::open("sea_trial.log")?);
+//! let frames: Vec<TimestampedFrame> = CandumpReader::new(file).collect::<Result<_, _>>()?;
+//!
+//! let stats = Replayer::new()
+//!     .with_speed(4.0)
+//!     .with_pgns(&[127250, 129025, 129026])
+//!     .with_repeat(Repeat::Times(3))
+//!     .play(&mut bus, &mut timer, &frames)
+//!     .await?;
+//! ```
+use core::borrow::Borrow;
+
+use crate::{
+    formats::TimestampedFrame,
+    protocol::transport::traits::{can_bus::CanBus, korri_timer::KorriTimer},
+};
+
+//==================================================================================SETTINGS
+/// Number of passes made over the capture by [`Replayer::play`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeat {
+    /// Play the capture the given number of times (zero sends nothing).
+    Times(u32),
+    /// Play the capture until the bus reports an error, or until a pass
+    /// sends nothing (empty capture or no frame passing the filters).
+    Forever,
+}
+
+/// Frames transmitted and skipped by a replay.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayStats {
+    /// Frames sent on the bus.
+    pub sent: usize,
+    /// Frames rejected by the PGN or source filters.
+    pub skipped: usize,
+    /// Completed passes over the capture.
+    pub passes: u32,
+}
+
+//==================================================================================REPLAYER
+/// Replay settings: speed, repetition, and filters.
+///
+/// Filters borrow their lists, so a replayer needs no allocation. Without
+/// filters every frame is sent.
+#[derive(Debug, Clone, Copy)]
+pub struct Replayer<'a> {
+    speed: f64,
+    repeat: Repeat,
+    pgns: Option<&'a [u32]>,
+    sources: Option<&'a [u8]>,
+}
+
+impl Default for Replayer<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Replayer<'a> {
+    /// Replay once, in real time, without filters.
+    pub const fn new() -> Self {
+        Self {
+            speed: 1.0,
+            repeat: Repeat::Times(1),
+            pgns: None,
+            sources: None,
+        }
+    }
+
+    /// Scale the playback speed: `2.0` plays twice as fast, `0.5` at half
+    /// speed, and `f64::INFINITY` sends the frames back to back.
+    ///
+    /// Zero, negative, and NaN factors fall back to real time.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = if speed > 0.0 { speed } else { 1.0 };
+        self
+    }
+
+    /// Set how many times [`play`](Self::play) goes through the capture.
+    pub fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Only send frames carrying one of `pgns`.
+    pub fn with_pgns(mut self, pgns: &'a [u32]) -> Self {
+        self.pgns = Some(pgns);
+        self
+    }
+
+    /// Only send frames transmitted by one of `sources`.
+    pub fn with_sources(mut self, sources: &'a [u8]) -> Self {
+        self.sources = Some(sources);
+        self
+    }
+
+    /// Returns `true` when `frame` passes the PGN and source filters.
+    pub fn accepts(&self, frame: &TimestampedFrame) -> bool {
+        let id = frame.frame.id;
+        self.pgns.is_none_or(|pgns| pgns.contains(&id.pgn()))
+            && self
+                .sources
+                .is_none_or(|sources| sources.contains(&id.source_address()))
+    }
+
+    /// Replay `frames` as many times as the [`Repeat`] setting asks.
+    ///
+    /// Each pass iterates a fresh clone of `frames`; references to slices or
+    /// vectors clone for free. Pacing restarts with every pass, so the next
+    /// pass begins right after the last frame of the previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by `bus.send`.
+    pub async fn play<C, T, I>(
+        &self,
+        bus: &mut C,
+        timer: &mut T,
+        frames: I,
+    ) -> Result<ReplayStats, C::Error>
+    where
+        C: CanBus,
+        T: KorriTimer,
+        I: IntoIterator + Clone,
+        I::Item: Borrow<TimestampedFrame>,
+    {
+        let mut stats = ReplayStats::default();
+        loop {
+            let done = match self.repeat {
+                Repeat::Times(count) => stats.passes >= count,
+                Repeat::Forever => false,
+            };
+            if done {
+                return Ok(stats);
+            }
+            let sent = stats.sent;
+            self.pass(bus, timer, frames.clone(), &mut stats).await?;
+            // Nothing to send: further passes would spin without ever waiting.
+            if stats.sent == sent {
+                return Ok(stats);
+            }
+        }
+    }
+
+    /// Replay `frames` once, whatever the [`Repeat`] setting.
+    ///
+    /// Unlike [`play`](Self::play), `frames` may be a one-shot iterator such
+    /// as a log reader.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error reported by `bus.send`.
+    pub async fn play_once<C, T, I>(
+        &self,
+        bus: &mut C,
+        timer: &mut T,
+        frames: I,
+    ) -> Result<ReplayStats, C::Error>
+    where
+        C: CanBus,
+        T: KorriTimer,
+        I: IntoIterator,
+        I::Item: Borrow<TimestampedFrame>,
+    {
+        let mut stats = ReplayStats::default();
+        self.pass(bus, timer, frames, &mut stats).await?;
+        Ok(stats)
+    }
+
+    /// One pass over `frames`, accumulating into `stats`.
+    async fn pass<C, T, I>(
+        &self,
+        bus: &mut C,
+        timer: &mut T,
+        frames: I,
+        stats: &mut ReplayStats,
+    ) -> Result<(), C::Error>
+    where
+        C: CanBus,
+        T: KorriTimer,
+        I: IntoIterator,
+        I::Item: Borrow<TimestampedFrame>,
+    {
+        // (capture time, playback time) of the frame the pacing is anchored on.
+        let mut anchor: Option<(u64, u64)> = None;
+
+        for item in frames {
+            let record = item.borrow();
+            let (capture_us, playback_ms) = match anchor {
+                Some(current) if record.timestamp_us >= current.0 => current,
+                _ => *anchor.insert((record.timestamp_us, timer.now_ms())),
+            };
+
+            if !self.accepts(record) {
+                stats.skipped += 1;
+                continue;
+            }
+
+            let due_ms = playback_ms.saturating_add(self.scale(record.timestamp_us - capture_us));
+            wait_until(timer, due_ms).await;
+            bus.send(&record.frame).await?;
+            stats.sent += 1;
+        }
+
+        stats.passes += 1;
+        Ok(())
+    }
+
+    /// Playback delay in milliseconds for a capture offset in microseconds.
+    fn scale(&self, offset_us: u64) -> u64 {
+        if self.speed == 1.0 {
+            offset_us / 1000
+        } else {
+            // Saturating cast: infinite speed gives zero, tiny speeds u64::MAX.
+            (offset_us as f64 / self.speed / 1000.0) as u64
+        }
+    }
+}
+
+/// Delay until the timer reaches `due_ms`, in `u32` sized steps.
+async fn wait_until<T: KorriTimer>(timer: &mut T, due_ms: u64) {
+    loop {
+        let remaining = due_ms.saturating_sub(timer.now_ms());
+        if remaining == 0 {
+            return;
+        }
+        timer.delay_ms(remaining.min(u32::MAX as u64) as u32).await;
+    }
+}
+
+#[cfg(test)]
+#[path = "tests.rs"]
+mod tests;
@@ -0,0 +1,192 @@
+//! Replay pacing, repetition, and filter tests on a virtual clock.
+// REPLAY
+use super::*;
+use crate::protocol::transport::{can_frame::CanFrame, can_id::CanId, test_support::block_on};
+use core::cell::Cell;
+
+/// Virtual clock shared by the timer and the bus, so sends can be timestamped.
+struct SharedClock<'c>(&'c Cell<u64>);
+
+impl KorriTimer for SharedClock<'_> {
+    async fn delay_ms(&mut self, millis: u32) {
+        self.0.set(self.0.get() + millis as u64);
+    }
+
+    fn now_ms(&self) -> u64 {
+        self.0.get()
+    }
+}
+
+/// Bus recording the source address and send time of each frame.
+struct RecordingBus<'c> {
+    clock: &'c Cell<u64>,
+    sent: [(u8, u64); 16],
+    len: usize,
+    fail_after: usize,
+}
+
+impl<'c> RecordingBus<'c> {
+    fn new(clock: &'c Cell<u64>) -> Self {
+        Self {
+            clock,
+            sent: [(0, 0); 16],
+            len: 0,
+            fail_after: usize::MAX,
+        }
+    }
+
+    fn sent(&self) -> &[(u8, u64)] {
+        &self.sent[..self.len]
+    }
+}
+
+impl CanBus for RecordingBus<'_> {
+    type Error = ();
+
+    async fn send(&mut self, frame: &CanFrame) -> Result<(), ()> {
+        if self.len == self.fail_after {
+            return Err(());
+        }
+        self.sent[self.len] = (frame.id.source_address(), self.clock.get());
+        self.len += 1;
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<CanFrame, ()> {
+        Err(())
+    }
+}
+
+fn record(timestamp_us: u64, pgn: u32, source: u8) -> TimestampedFrame {
+    TimestampedFrame::new(
+        timestamp_us,
+        CanFrame {
+            id: CanId::builder(pgn, source).build().unwrap(),
+            data: [0; 8],
+            len: 8,
+        },
+    )
+}
+
+/// Capture starting at an arbitrary epoch: frames 0, 100, 350, and 1000 ms in.
+fn capture() -> [TimestampedFrame; 4] {
+    let start = 1_436_509_052_249_713;
+    [
+        record(start, 127250, 1),
+        record(start + 100_000, 129025, 2),
+        record(start + 350_400, 127250, 3),
+        record(start + 1_000_000, 130306, 4),
+    ]
+}
+
+#[test]
+/// Frames keep their captured spacing, relative to the start of the replay.
+fn test_replay_keeps_capture_timing() {
+    let clock = Cell::new(5_000);
+    let mut bus = RecordingBus::new(&clock);
+    let frames = capture();
+
+    let (stats, _) = block_on(Replayer::new().play(&mut bus, &mut SharedClock(&clock), &frames));
+
+    assert_eq!(
+        stats.unwrap(),
+        ReplayStats {
+            sent: 4,
+            skipped: 0,
+            passes: 1
+        }
+    );
+    assert_eq!(bus.sent(), [(1, 5_000), (2, 5_100), (3, 5_350), (4, 6_000)]);
+}
+
+#[test]
+/// The speed factor divides every offset; invalid factors mean real time.
+fn test_speed_scaling() {
+    let frames = capture();
+    for (speed, expected) in [
+        (4.0, [0, 25, 87, 250]),
+        (0.5, [0, 200, 700, 2_000]),
+        (f64::INFINITY, [0, 0, 0, 0]),
+        (f64::NAN, [0, 100, 350, 1_000]),
+    ] {
+        let clock = Cell::new(0);
+        let mut bus = RecordingBus::new(&clock);
+        let replayer = Replayer::new().with_speed(speed);
+        block_on(replayer.play(&mut bus, &mut SharedClock(&clock), &frames))
+            .0
+            .unwrap();
+
+        let times: [u64; 4] = core::array::from_fn(|i| bus.sent()[i].1);
+        assert_eq!(times, expected, "speed {speed}");
+    }
+}
+
+#[test]
+/// Filtered-out frames are skipped but keep their time slot.
+fn test_pgn_and_source_filters() {
+    let clock = Cell::new(0);
+    let mut bus = RecordingBus::new(&clock);
+    let frames = capture();
+    let replayer = Replayer::new()
+        .with_pgns(&[127250, 130306])
+        .with_sources(&[3, 4]);
+
+    let (stats, _) = block_on(replayer.play(&mut bus, &mut SharedClock(&clock), &frames));
+
+    let stats = stats.unwrap();
+    assert_eq!((stats.sent, stats.skipped), (2, 2));
+    assert_eq!(bus.sent(), [(3, 350), (4, 1_000)]);
+}
+
+#[test]
+/// Each pass restarts the pacing where the previous one ended.
+fn test_repeat_times() {
+    let clock = Cell::new(0);
+    let mut bus = RecordingBus::new(&clock);
+    let frames = capture();
+    let replayer = Replayer::new().with_repeat(Repeat::Times(2));
+
+    let (stats, _) = block_on(replayer.play(&mut bus, &mut SharedClock(&clock), &frames));
+
+    assert_eq!(stats.unwrap().passes, 2);
+    let times: [u64; 8] = core::array::from_fn(|i| bus.sent()[i].1);
+    assert_eq!(times, [0, 100, 350, 1_000, 1_000, 1_100, 1_350, 2_000]);
+}
+
+#[test]
+/// Endless replays stop on the first bus error, or when nothing is sent.
+fn test_repeat_forever() {
+    let clock = Cell::new(0);
+    let mut bus = RecordingBus::new(&clock);
+    bus.fail_after = 10;
+    let frames = capture();
+    let replayer = Replayer::new().with_repeat(Repeat::Forever);
+
+    let (result, _) = block_on(replayer.play(&mut bus, &mut SharedClock(&clock), &frames));
+    assert_eq!(result, Err(()));
+    assert_eq!(bus.sent().len(), 10);
+
+    let replayer = replayer.with_sources(&[99]);
+    let (stats, _) = block_on(replayer.play(&mut bus, &mut SharedClock(&clock), &frames));
+    assert_eq!(stats.unwrap().passes, 1);
+}
+
+#[test]
+/// A timestamp going backwards re-anchors the pacing instead of stalling.
+fn test_backward_timestamp_reanchors() {
+    let clock = Cell::new(0);
+    let mut bus = RecordingBus::new(&clock);
+    // Time-of-day log crossing midnight.
+    let frames = [
+        record(86_399_900_000, 127250, 1),
+        record(86_399_950_000, 127250, 2),
+        record(0, 127250, 3),
+        record(20_000, 127250, 4),
+    ];
+
+    let (stats, _) =
+        block_on(Replayer::new().play_once(&mut bus, &mut SharedClock(&clock), frames));
+
+    assert_eq!(stats.unwrap().sent, 4);
+    assert_eq!(bus.sent(), [(1, 0), (2, 50), (3, 50), (4, 70)]);
+}
@@ -4,8 +4,9 @@
 use korri_n2k::{
     infra::codec::traits::PgnData,
     protocol::{
+        decoder::N2kDecoder,
         managment::address_claiming::claim_address,
-        messages::{Pgn129025, Pgn59904},
+        messages::{N2kMessage, Pgn129025, Pgn59904},
         transport::{
             can_frame::CanFrame,
             can_id::CanId,
@@ -65,6 +66,14 @@ impl KorriTimer for MockTimer {
     async fn delay_ms(&mut self, millis: u32) {
         sleep(Duration::from_millis(millis as u64)).await;
     }
+
+    fn now_ms(&self) -> u64 {
+        static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+        START
+            .get_or_init(std::time::Instant::now)
+            .elapsed()
+            .as_millis() as u64
+    }
 }
 
 #[tokio::test]
@@ -206,30 +215,27 @@ async fn test_premiere_conversation() {
         .await
         .expect("Sending the response frame should succeed");
 
-    // 7. Reader processes frames until the response arrives
-    let received_frame = loop {
-        let frame = reader_bus
-            .recv()
+    // 7. Reader decodes messages until the response arrives
+    let mut decoder = N2kDecoder::new();
+    let received_pgn = loop {
+        let message = decoder
+            .recv(&mut reader_bus, &reader_timer)
             .await
             .expect("Reader must receive a frame");
 
-        // Ignore Address Claim frames
-        if frame.id.pgn() == 60928 {
-            println!("Reader ignored an Address Claim frame");
-            continue;
-        }
-
-        // If this is the expected response, process it
-        if frame.id.pgn() == 129025 {
-            assert_eq!(frame.id.source_address(), emitter_claimed_address);
-            break frame;
+        // 8. Decode and keep only the expected PGN 129025
+        match message
+            .decode()
+            .expect("Message deserialization should succeed")
+        {
+            N2kMessage::Pgn129025(position) => {
+                assert_eq!(message.id.source_address(), emitter_claimed_address);
+                break position;
+            }
+            other => println!("Reader ignored PGN {}", other.pgn()),
         }
     };
 
-    // 8. Deserialize PGN 129025
-    let received_pgn = Pgn129025::from_payload(&received_frame.data[..received_frame.len])
-        .expect("Position deserialization should succeed");
-
     // Validate data
     assert!((received_pgn.latitude - position_pgn.latitude).abs() < 1e-6);
     assert!((received_pgn.longitude - position_pgn.longitude).abs() < 1e-6);
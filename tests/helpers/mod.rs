@@ -58,6 +58,14 @@ impl KorriTimer for MockTimer {
     async fn delay_ms(&mut self, millis: u32) {
         sleep(Duration::from_millis(millis as u64)).await;
     }
+
+    fn now_ms(&self) -> u64 {
+        static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+        START
+            .get_or_init(std::time::Instant::now)
+            .elapsed()
+            .as_millis() as u64
+    }
 }
 
 #[allow(dead_code)]
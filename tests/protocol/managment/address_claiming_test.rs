@@ -8,7 +8,12 @@ use korri_n2k::{
     error::ClaimError,
     protocol::{
         managment::address_claiming::claim_address,
-        transport::{can_frame::CanFrame, can_id::CanId, traits::can_bus::CanBus},
+        transport::{
+            can_frame::CanFrame,
+            can_id::CanId,
+            timers::ManualTimer,
+            traits::{can_bus::CanBus, korri_timer::KorriTimer},
+        },
     },
 };
 
@@ -36,7 +41,8 @@ async fn test_claim_address_no_conflict() {
     // Spawn a task that simulates a quiet network (no conflict)
     tokio::spawn(simulate_no_conflict(host_bus));
 
-    let mut timer = MockTimer;
+    // Virtual clock: the 250 ms listening window elapses without sleeping.
+    let mut timer = ManualTimer::new();
     let my_name = 0x1234567890ABCDEF;
     let preferred_address = 42;
 
@@ -46,6 +52,7 @@ async fn test_claim_address_no_conflict() {
     // Assertions
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), preferred_address);
+    assert_eq!(timer.now_ms(), 250);
 }
 
 #[tokio::test]
@@ -286,3 +293,75 @@ async fn test_claim_address_non_arbitrary_conflict_and_win() {
         }
     }
 }
+
+/// Timer whose clock never moves: only its delays elapse.
+struct DelayOnlyTimer;
+
+impl KorriTimer for DelayOnlyTimer {
+    async fn delay_ms(&mut self, millis: u32) {
+        tokio::time::sleep(Duration::from_millis(millis as u64)).await;
+    }
+
+    fn now_ms(&self) -> u64 {
+        0
+    }
+}
+
+#[tokio::test]
+async fn test_claim_address_without_clock() {
+    // A clock that never moves: listening windows still close on their delays.
+    let (mut dut_bus, mut host_bus) = MockCanBus::create_pair();
+
+    let my_name: u64 = 0x9234567890ABCDEF; // Arbitrary capable
+    let their_name: u64 = 0x1234567890ABCDEE; // Lower than my_name → we lose
+    let mut timer = DelayOnlyTimer;
+
+    let (claim_result, _) = tokio::join!(
+        claim_address(&mut dut_bus, &mut timer, my_name, 42),
+        async {
+            let frame1 = host_bus.recv().await.expect("initial claim");
+            assert_eq!(frame1.id.source_address(), 42);
+            host_bus
+                .send(&build_conflict_frame(their_name, 42))
+                .await
+                .expect("Failed to send conflict frame");
+            let frame2 = host_bus.recv().await.expect("claim of the next address");
+            assert_eq!(frame2.id.source_address(), 128);
+        }
+    );
+
+    assert_eq!(claim_result.unwrap(), 128);
+}
+
+#[tokio::test]
+async fn test_claim_address_without_clock_on_busy_bus() {
+    // Traffic received during the window must not restart it when the clock never moves.
+    let (mut dut_bus, mut host_bus) = MockCanBus::create_pair();
+
+    let my_name: u64 = 0x1234567890ABCDEF;
+    let other_name: u64 = 0x1234567890ABCDEE;
+    let mut timer = DelayOnlyTimer;
+
+    let claim = tokio::time::timeout(
+        Duration::from_millis(1_000),
+        claim_address(&mut dut_bus, &mut timer, my_name, 42),
+    );
+    tokio::select! {
+        claim_result = claim => {
+            let claimed_address = claim_result.expect("a busy bus must not hold the claim open");
+            assert_eq!(claimed_address.unwrap(), 42);
+        }
+        _ = async {
+            let frame1 = host_bus.recv().await.expect("initial claim");
+            assert_eq!(frame1.id.source_address(), 42);
+            // Another node claims its own address every 50 ms.
+            loop {
+                host_bus
+                    .send(&build_conflict_frame(other_name, 17))
+                    .await
+                    .expect("Failed to send bus traffic");
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        } => {}
+    }
+}
@@ -1,4 +1,6 @@
 //! Validate address-management and network-discovery logic.
 mod address_claiming_test;
 mod address_manager_test;
+#[cfg(feature = "std")]
+mod multi_node_claim_test;
 mod network_discovering_test;
@@ -0,0 +1,57 @@
+//! Address contention between several nodes sharing a `VirtualBus` (`std` feature).
+use korri_n2k::protocol::{
+    managment::address_claiming::claim_address,
+    transport::{
+        timers::TokioTimer,
+        virtual_bus::{TraceOutcome, VirtualBus},
+    },
+};
+
+/// Arbitrary Address Capable NAMEs, in ascending order (lowest wins).
+const NAMES: [u64; 5] = [
+    0x8000_0000_0000_0001,
+    0x8000_0000_0000_0002,
+    0x8000_0000_0000_0003,
+    0x8000_0000_0000_0004,
+    0x8000_0000_0000_0005,
+];
+
+#[tokio::test]
+async fn test_five_nodes_contend_for_one_address() {
+    let bus = VirtualBus::new();
+
+    // Every node wants address 42 at the same time; declare them in reverse
+    // NAME order so the winner is not simply the first to transmit.
+    let nodes: Vec<_> = NAMES
+        .iter()
+        .rev()
+        .map(|&name| {
+            let mut endpoint = bus.attach();
+            tokio::spawn(async move {
+                let mut timer = TokioTimer::new();
+                let address = claim_address(&mut endpoint, &mut timer, name, 42)
+                    .await
+                    .unwrap();
+                (name, address)
+            })
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for node in nodes {
+        results.push(node.await.unwrap());
+    }
+    results.sort();
+
+    // The lowest NAME keeps 42, the others settle on 128, 129, … in NAME order.
+    let addresses: Vec<u8> = results.iter().map(|&(_, address)| address).collect();
+    assert_eq!(addresses, [42, 128, 129, 130, 131]);
+
+    // The five initial claims for 42 open the trace, all delivered.
+    let trace = bus.trace();
+    assert!(trace[..5].iter().all(|entry| {
+        entry.frame.id.pgn() == 60928
+            && entry.frame.id.source_address() == 42
+            && entry.outcome == TraceOutcome::Delivered
+    }));
+}
@@ -33,7 +33,7 @@ fn generated_artifacts_memory_footprint_is_stable() {
         (
             "PGN 60928 (ISO Address Claim)",
             size_of::<Pgn60928>(),
-            16usize,
+            8usize,
         ),
         (
             "PGN 129025 (Position Rapid Update)",
@@ -130,11 +130,7 @@ fn generated_artifacts_memory_footprint_is_stable() {
         max_allowed
     );
 
-    let overhead = if frame_size > baseline {
-        frame_size - baseline
-    } else {
-        0
-    };
+    let overhead = frame_size.saturating_sub(baseline);
     let overhead_pct = (overhead as f32 / baseline as f32) * 100.0;
 
     println!(
@@ -1,2 +1,4 @@
 //! Validate the integrity of generated artifacts (PGN structs, lookup tables).
 mod generated_sizes_test;
+mod n2k_message_test;
+mod pgn_metadata_test;
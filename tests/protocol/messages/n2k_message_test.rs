@@ -0,0 +1,61 @@
+use korri_n2k::{
+    error::DeserializationError,
+    infra::codec::traits::PgnData,
+    protocol::messages::{N2kMessage, Pgn129025, Pgn60416},
+};
+
+#[test]
+/// A serialized PGN decodes back into the matching variant.
+fn decode_dispatches_to_the_generated_type() {
+    let mut position = Pgn129025::new();
+    position.latitude = 47.5;
+    position.longitude = -3.25;
+
+    let mut buffer = [0u8; 8];
+    let len = position.to_payload(&mut buffer).unwrap();
+
+    let message = N2kMessage::decode(129025, &buffer[..len]).unwrap();
+    assert_eq!(message.pgn(), 129025);
+    match message {
+        N2kMessage::Pgn129025(decoded) => {
+            assert!((decoded.latitude - 47.5).abs() < 1e-6);
+            assert!((decoded.longitude + 3.25).abs() < 1e-6);
+        }
+        other => panic!("expected PGN 129025, got {other:?}"),
+    }
+}
+
+#[test]
+/// Polymorphic PGNs go through their own function-code dispatch.
+fn decode_handles_polymorphic_pgns() {
+    // TP.CM_CTS: 3 packets starting at 1, for PGN 126208.
+    let payload = [17, 3, 1, 0xFF, 0xFF, 0x00, 0xED, 0x01];
+
+    let message = N2kMessage::decode(60416, &payload).unwrap();
+    assert_eq!(message.pgn(), 60416);
+    assert!(matches!(
+        message,
+        N2kMessage::Pgn60416(Pgn60416::IsoTransportProtocolConnectionManagementClearToSend(_))
+    ));
+}
+
+#[test]
+/// PGNs outside the manifest are reported as unsupported.
+fn decode_rejects_unknown_pgns() {
+    assert!(!N2kMessage::SUPPORTED_PGNS.contains(&65280));
+    assert!(matches!(
+        N2kMessage::decode(65280, &[0; 8]),
+        Err(DeserializationError::UnsupportedPgn { pgn: 65280 })
+    ));
+}
+
+#[test]
+/// Every listed PGN has a decoder.
+fn supported_pgns_are_all_dispatched() {
+    for &pgn in N2kMessage::SUPPORTED_PGNS {
+        assert!(!matches!(
+            N2kMessage::decode(pgn, &[0xFF; 8]),
+            Err(DeserializationError::UnsupportedPgn { .. })
+        ));
+    }
+}
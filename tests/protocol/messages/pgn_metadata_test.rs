@@ -0,0 +1,53 @@
+mod helpers {
+    include!("../../helpers/mod.rs");
+}
+
+use helpers::{MockCanBus, MockTimer};
+use korri_n2k::{
+    infra::codec::traits::PgnMetadata,
+    protocol::{
+        messages::{Pgn129025, Pgn129029, Pgn60416},
+        transport::{
+            fast_packet::sequence::SequenceCounter,
+            traits::{can_bus::CanBus, pgn_sender::PgnSender},
+        },
+    },
+};
+
+#[test]
+/// Generated types expose their PGN, default priority, and descriptor.
+fn generated_types_expose_metadata() {
+    assert_eq!(Pgn129025::PGN, 129025);
+    assert_eq!(Pgn129025::DEFAULT_PRIORITY, 2);
+    const { assert!(!Pgn129025::DESCRIPTOR.fastpacket) };
+
+    assert_eq!(Pgn129029::PGN, 129029);
+    assert_eq!(Pgn129029::DEFAULT_PRIORITY, 3);
+    const { assert!(Pgn129029::DESCRIPTOR.fastpacket) };
+
+    // Polymorphic PGN without a priority in its definition.
+    assert_eq!(Pgn60416::PGN, 60416);
+    assert_eq!(Pgn60416::DEFAULT_PRIORITY, 6);
+}
+
+#[tokio::test]
+/// `send_message` derives the identifier from the type.
+async fn send_message_uses_type_metadata() {
+    let (mut device, mut host) = MockCanBus::create_pair();
+    let mut timer = MockTimer;
+    let mut sequences = SequenceCounter::new();
+
+    let mut position = Pgn129025::new();
+    position.latitude = 47.5;
+    position.longitude = -3.25;
+
+    device
+        .send_message(&position, 42, None, &mut sequences, &mut timer)
+        .await
+        .expect("sending must succeed");
+
+    let frame = host.recv().await.expect("host must receive the frame");
+    assert_eq!(frame.id.pgn(), 129025);
+    assert_eq!(frame.id.priority(), 2);
+    assert_eq!(frame.id.source_address(), 42);
+}
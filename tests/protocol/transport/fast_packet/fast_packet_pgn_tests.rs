@@ -4,6 +4,7 @@ use korri_n2k::infra::codec::traits::PgnData;
 use korri_n2k::protocol::transport::fast_packet::{
     assembler::{FastPacketAssembler, ProcessResult},
     builder::FastPacketBuilder,
+    sequence::SequenceCounter,
 };
 use korri_n2k::protocol::{
     lookups::CertificationLevel,
@@ -25,18 +26,26 @@ fn test_pgn_129040_fast_packet_roundtrip() {
         "PGN 129040 should generate a Fast Packet; current length: {len}"
     );
 
-    let builder = FastPacketBuilder::new(129040, 42, None, &buffer[..len]);
-    let mut frames = builder.build();
+    let builder = FastPacketBuilder::new(
+        129040,
+        42,
+        None,
+        &buffer[..len],
+        &mut SequenceCounter::new(),
+    );
+    let frames = builder.build();
 
     let mut assembler = FastPacketAssembler::new();
     let mut complete = None;
     let mut frame_count = 0;
 
-    while let Some(frame_result) = frames.next() {
+    for frame_result in frames {
         let frame = frame_result.expect("frame build");
         frame_count += 1;
 
-        if let ProcessResult::MessageComplete(msg) = assembler.process_frame(42, &frame.data) {
+        if let ProcessResult::MessageComplete(msg) =
+            assembler.process_frame(frame.id, &frame.data, 0)
+        {
             complete = Some(msg);
             break;
         }
@@ -89,17 +98,25 @@ fn test_pgn_126996_fast_packet_roundtrip() {
         "PGN 126996 should produce a Fast Packet; current length: {len}"
     );
 
-    let builder = FastPacketBuilder::new(126996, 35, None, &buffer[..len]);
-    let mut frames = builder.build();
+    let builder = FastPacketBuilder::new(
+        126996,
+        35,
+        None,
+        &buffer[..len],
+        &mut SequenceCounter::new(),
+    );
+    let frames = builder.build();
     let mut assembler = FastPacketAssembler::new();
     let mut complete = None;
     let mut frame_count = 0;
 
-    while let Some(frame_result) = frames.next() {
+    for frame_result in frames {
         let frame = frame_result.expect("frame build");
         frame_count += 1;
 
-        if let ProcessResult::MessageComplete(msg) = assembler.process_frame(35, &frame.data) {
+        if let ProcessResult::MessageComplete(msg) =
+            assembler.process_frame(frame.id, &frame.data, 0)
+        {
             complete = Some(msg);
             break;
         }
@@ -165,14 +182,22 @@ fn test_pgn_126998_fast_packet_roundtrip() {
         .expect("serialize PGN 126998");
     assert!(len > 8, "PGN 126998 must be encoded as a Fast Packet");
 
-    let builder = FastPacketBuilder::new(126998, 77, None, &buffer[..len]);
-    let mut frames = builder.build();
+    let builder = FastPacketBuilder::new(
+        126998,
+        77,
+        None,
+        &buffer[..len],
+        &mut SequenceCounter::new(),
+    );
+    let frames = builder.build();
     let mut assembler = FastPacketAssembler::new();
     let mut complete = None;
 
-    while let Some(frame_result) = frames.next() {
+    for frame_result in frames {
         let frame = frame_result.expect("frame build");
-        if let ProcessResult::MessageComplete(msg) = assembler.process_frame(77, &frame.data) {
+        if let ProcessResult::MessageComplete(msg) =
+            assembler.process_frame(frame.id, &frame.data, 0)
+        {
             complete = Some(msg);
             break;
         }
@@ -9,11 +9,19 @@
 
 use korri_n2k::infra::codec::traits::PgnData;
 use korri_n2k::protocol::messages::{Pgn129029, Pgn129040};
+use korri_n2k::protocol::transport::can_id::CanId;
 use korri_n2k::protocol::transport::fast_packet::{
     assembler::{FastPacketAssembler, ProcessResult},
     builder::FastPacketBuilder,
+    sequence::SequenceCounter,
+    FAST_PACKET_SESSION_TIMEOUT_MS,
 };
 
+/// Identifier of a broadcast Fast Packet PGN (129029) sent by `source`.
+fn fast_packet_id(source: u8) -> CanId {
+    CanId::builder(129029, source).build().unwrap()
+}
+
 //==================================================================================
 // PHASE 2: TESTS WITH REAL PGNS
 //==================================================================================
@@ -44,19 +52,27 @@ fn test_roundtrip_pgn_129029() {
     );
 
     // Build fragmented CAN frames
-    let builder = FastPacketBuilder::new(129029, 42, None, &buffer[..len]);
-    let mut frames = builder.build();
+    let builder = FastPacketBuilder::new(
+        129029,
+        42,
+        None,
+        &buffer[..len],
+        &mut SequenceCounter::new(),
+    );
+    let frames = builder.build();
 
     // Reassemble frames with the assembler
     let mut assembler = FastPacketAssembler::new();
     let mut complete = None;
     let mut frame_count = 0;
 
-    while let Some(frame_result) = frames.next() {
+    for frame_result in frames {
         let frame = frame_result.expect("Frame construction should succeed");
         frame_count += 1;
 
-        if let ProcessResult::MessageComplete(msg) = assembler.process_frame(42, &frame.data) {
+        if let ProcessResult::MessageComplete(msg) =
+            assembler.process_frame(frame.id, &frame.data, 0)
+        {
             complete = Some(msg);
             break;
         }
@@ -64,10 +80,7 @@ fn test_roundtrip_pgn_129029() {
 
     // Ensure the message was reassembled
     let message = complete.expect("Message must be complete after processing");
-    assert_eq!(
-        message.len, len,
-        "Reassembled message length must match"
-    );
+    assert_eq!(message.len, len, "Reassembled message length must match");
     assert_eq!(
         &message.payload[..len],
         &buffer[..len],
@@ -132,8 +145,20 @@ fn test_interleaved_multiple_pgns() {
     assert!(len_gnss > 8, "GNSS must be a Fast Packet");
 
     // Build frame iterators for two different sources
-    let builder_ais = FastPacketBuilder::new(129040, 10, None, &buffer_ais[..len_ais]);
-    let builder_gnss = FastPacketBuilder::new(129029, 20, None, &buffer_gnss[..len_gnss]);
+    let builder_ais = FastPacketBuilder::new(
+        129040,
+        10,
+        None,
+        &buffer_ais[..len_ais],
+        &mut SequenceCounter::new(),
+    );
+    let builder_gnss = FastPacketBuilder::new(
+        129029,
+        20,
+        None,
+        &buffer_gnss[..len_gnss],
+        &mut SequenceCounter::new(),
+    );
 
     let mut frames_ais = builder_ais.build();
     let mut frames_gnss = builder_gnss.build();
@@ -154,7 +179,7 @@ fn test_interleaved_multiple_pgns() {
             if let Some(frame_result) = frames_ais.next() {
                 let frame = frame_result.expect("Valid AIS frame");
                 if let ProcessResult::MessageComplete(msg) =
-                    assembler.process_frame(10, &frame.data)
+                    assembler.process_frame(frame.id, &frame.data, 0)
                 {
                     ais_complete = Some(msg);
                 }
@@ -168,7 +193,7 @@ fn test_interleaved_multiple_pgns() {
             if let Some(frame_result) = frames_gnss.next() {
                 let frame = frame_result.expect("Valid GNSS frame");
                 if let ProcessResult::MessageComplete(msg) =
-                    assembler.process_frame(20, &frame.data)
+                    assembler.process_frame(frame.id, &frame.data, 0)
                 {
                     gnss_complete = Some(msg);
                 }
@@ -216,21 +241,21 @@ fn test_assembler_sequence_wrap() {
 
     // Complete message using sequence identifier 7 (upper bits)
     let frame_seq7: [u8; 8] = [0b111_00000, 15, 1, 2, 3, 4, 5, 6];
-    let result = assembler.process_frame(source, &frame_seq7);
+    let result = assembler.process_frame(fast_packet_id(source), &frame_seq7, 0);
     assert!(
         matches!(result, ProcessResult::FragmentConsumed),
         "Frame with sequence 7 should be consumed"
     );
 
     let frame_seq7_cont: [u8; 8] = [0b111_00001, 7, 8, 9, 10, 11, 12, 13];
-    let result = assembler.process_frame(source, &frame_seq7_cont);
+    let result = assembler.process_frame(fast_packet_id(source), &frame_seq7_cont, 0);
     assert!(
         matches!(result, ProcessResult::FragmentConsumed),
         "Second frame with the same sequence should be accepted"
     );
 
     let frame_seq7_end: [u8; 8] = [0b111_00010, 14, 15, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
-    let result = assembler.process_frame(source, &frame_seq7_end);
+    let result = assembler.process_frame(fast_packet_id(source), &frame_seq7_end, 0);
 
     // Ensure the message is considered complete
     assert!(
@@ -240,7 +265,7 @@ fn test_assembler_sequence_wrap() {
 
     // New message: wrap sequence counter 7 → 0
     let frame_seq0_new: [u8; 8] = [0b000_00000, 9, 42, 43, 44, 45, 46, 47];
-    let result = assembler.process_frame(source, &frame_seq0_new);
+    let result = assembler.process_frame(fast_packet_id(source), &frame_seq0_new, 0);
     assert!(
         matches!(result, ProcessResult::FragmentConsumed),
         "Next message with sequence 0 should be accepted after wrapping"
@@ -258,7 +283,7 @@ fn test_assembler_out_of_order() {
 
     // First frame: start of session (sequence 0)
     let frame0: [u8; 8] = [0b000_00000, 20, 1, 2, 3, 4, 5, 6];
-    let result = assembler.process_frame(source, &frame0);
+    let result = assembler.process_frame(fast_packet_id(source), &frame0, 0);
     assert!(
         matches!(result, ProcessResult::FragmentConsumed),
         "First frame should be consumed"
@@ -266,7 +291,7 @@ fn test_assembler_out_of_order() {
 
     // Send frame 2 before frame 1 (out of order)
     let frame2: [u8; 8] = [0b000_00010, 14, 15, 16, 17, 18, 19, 20];
-    let result = assembler.process_frame(source, &frame2);
+    let result = assembler.process_frame(fast_packet_id(source), &frame2, 0);
     assert!(
         matches!(result, ProcessResult::Ignored),
         "Out-of-sequence frame should be ignored"
@@ -274,7 +299,7 @@ fn test_assembler_out_of_order() {
 
     // Check that the session resets and a new frame 0 starts a new session
     let new_frame0: [u8; 8] = [0b000_00000, 10, 100, 101, 102, 103, 104, 105];
-    let result = assembler.process_frame(source, &new_frame0);
+    let result = assembler.process_frame(fast_packet_id(source), &new_frame0, 0);
     assert!(
         matches!(result, ProcessResult::FragmentConsumed),
         "A new session should start after reset"
@@ -292,13 +317,13 @@ fn test_assembler_partial_message() {
 
     // Start of message: three frames required
     let frame0: [u8; 8] = [0b000_00000, 15, 1, 2, 3, 4, 5, 6];
-    assembler.process_frame(source, &frame0);
+    assembler.process_frame(fast_packet_id(source), &frame0, 0);
 
     // ⚠️ Simulate loss of frame 1
 
     // Receive frame 2 directly (invalid sequence)
     let frame2: [u8; 8] = [0b000_00010, 14, 15, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
-    let result = assembler.process_frame(source, &frame2);
+    let result = assembler.process_frame(fast_packet_id(source), &frame2, 0);
 
     assert!(
         matches!(result, ProcessResult::Ignored),
@@ -317,11 +342,11 @@ fn test_assembler_duplicate_frame() {
 
     // First frame
     let frame0: [u8; 8] = [0b000_00000, 10, 1, 2, 3, 4, 5, 6];
-    let result1 = assembler.process_frame(source, &frame0);
+    let result1 = assembler.process_frame(fast_packet_id(source), &frame0, 0);
     assert!(matches!(result1, ProcessResult::FragmentConsumed));
 
     // ⚠️ Retransmit the same frame (duplicate)
-    let result2 = assembler.process_frame(source, &frame0);
+    let result2 = assembler.process_frame(fast_packet_id(source), &frame0, 0);
 
     // Acceptable behavior: ignore or reset, but never crash or corrupt data
     assert!(
@@ -336,29 +361,63 @@ fn test_assembler_duplicate_frame() {
 #[test]
 /// Exercise the concurrent session limit (pool saturation).
 ///
-/// The assembler supports a limited number of concurrent sessions; additional
-/// sessions must be rejected.
+/// The assembler supports a limited number of concurrent sessions; an additional
+/// session evicts the least recently updated one instead of being dropped.
 fn test_assembler_max_sessions() {
     let mut assembler = FastPacketAssembler::new();
 
-    // Start four concurrent sessions (current limit = 4)
+    // Start four concurrent sessions (current limit = 4), 10 ms apart
     for source_addr in 1..=4 {
         let frame: [u8; 8] = [0b000_00000, 20, source_addr, 0, 0, 0, 0, 0];
-        let result = assembler.process_frame(source_addr, &frame);
+        let result =
+            assembler.process_frame(fast_packet_id(source_addr), &frame, source_addr as u64 * 10);
         assert!(
             matches!(result, ProcessResult::FragmentConsumed),
             "Session {source_addr} should be accepted"
         );
     }
 
-    // Attempt to create a fifth session (must fail)
+    // A fifth session is accepted and evicts the oldest one (source 1)
     let frame5: [u8; 8] = [0b000_00000, 20, 5, 0, 0, 0, 0, 0];
-    let result = assembler.process_frame(5, &frame5);
+    let result = assembler.process_frame(fast_packet_id(5), &frame5, 50);
+    assert!(
+        matches!(result, ProcessResult::FragmentConsumed),
+        "The fifth session must evict the least recently used one"
+    );
 
+    let continuation: [u8; 8] = [0b000_00001, 7, 8, 9, 10, 11, 12, 13];
     assert!(
-        matches!(result, ProcessResult::Ignored),
-        "The fifth session must be rejected (pool saturated)"
+        matches!(
+            assembler.process_frame(fast_packet_id(1), &continuation, 60),
+            ProcessResult::Ignored
+        ),
+        "The evicted session must be gone"
     );
+    assert!(matches!(
+        assembler.process_frame(fast_packet_id(2), &continuation, 60),
+        ProcessResult::FragmentConsumed
+    ));
+}
+
+#[test]
+/// A session whose final frame was lost expires after the session timeout.
+///
+/// Without expiry, four lost final frames would permanently saturate the pool.
+fn test_assembler_session_timeout() {
+    let mut assembler = FastPacketAssembler::new();
+    let source = 80;
+
+    let frame0: [u8; 8] = [0b000_00000, 15, 1, 2, 3, 4, 5, 6];
+    assembler.process_frame(fast_packet_id(source), &frame0, 1_000);
+
+    // Continuation arrives too late: the session is gone.
+    let frame1: [u8; 8] = [0b000_00001, 7, 8, 9, 10, 11, 12, 13];
+    let result = assembler.process_frame(
+        fast_packet_id(source),
+        &frame1,
+        1_000 + FAST_PACKET_SESSION_TIMEOUT_MS + 1,
+    );
+    assert!(matches!(result, ProcessResult::Ignored));
 }
 
 //==================================================================================
@@ -382,15 +441,23 @@ fn test_stress_100_pgns() {
         ais.latitude = 45.0 + (i as f32 * 0.01);
 
         let mut buffer = [0u8; 64];
-        let len = ais.to_payload(&mut buffer).expect("Serialization succeeded");
+        let len = ais
+            .to_payload(&mut buffer)
+            .expect("Serialization succeeded");
 
         // Build and send the frames
-        let builder = FastPacketBuilder::new(129040, source, None, &buffer[..len]);
-        let mut frames = builder.build();
+        let builder = FastPacketBuilder::new(
+            129040,
+            source,
+            None,
+            &buffer[..len],
+            &mut SequenceCounter::new(),
+        );
+        let frames = builder.build();
 
-        while let Some(frame_result) = frames.next() {
+        for frame_result in frames {
             let frame = frame_result.expect("Valid frame");
-            let result = assembler.process_frame(source, &frame.data);
+            let result = assembler.process_frame(frame.id, &frame.data, 0);
 
             if let ProcessResult::MessageComplete(msg) = result {
                 // Quick validation of the message
@@ -424,7 +491,13 @@ fn test_builder_throughput() {
     let mut total_frames = 0;
 
     for _ in 0..iterations {
-        let builder = FastPacketBuilder::new(129040, 42, None, &buffer[..len]);
+        let builder = FastPacketBuilder::new(
+            129040,
+            42,
+            None,
+            &buffer[..len],
+            &mut SequenceCounter::new(),
+        );
         let frames: Vec<_> = builder.build().collect();
         total_frames += frames.len();
     }
@@ -434,9 +507,7 @@ fn test_builder_throughput() {
         total_frames > 0,
         "At least one frame must be generated per iteration"
     );
-    println!(
-        "✅ Throughput test: {total_frames} frames generated over {iterations} iterations"
-    );
+    println!("✅ Throughput test: {total_frames} frames generated over {iterations} iterations");
 }
 
 #[test]
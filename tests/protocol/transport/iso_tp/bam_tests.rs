@@ -0,0 +1,59 @@
+//! BAM transmission tests: `send_bam` on one end, `BamAssembler` on the other.
+mod helpers {
+    include!("../../../helpers/mod.rs");
+}
+
+use helpers::{MockCanBus, MockTimer};
+use korri_n2k::protocol::transport::{
+    iso_tp::{
+        assembler::{BamAssembler, ProcessResult},
+        sender::send_bam,
+        TP_BAM_MIN_PACKET_DELAY_MS, TP_CM_PGN, TP_DT_PGN,
+    },
+    traits::can_bus::CanBus,
+};
+use tokio::time::{Duration, Instant};
+
+#[tokio::test]
+/// A 40-byte broadcast crosses the bus and is reassembled on the host side.
+async fn send_bam_roundtrip_over_bus() {
+    let (mut dut_bus, mut host_bus) = MockCanBus::create_pair();
+    let original: [u8; 40] = core::array::from_fn(|i| i as u8);
+
+    send_bam(&mut dut_bus, &mut MockTimer, 126464, 25, &original, 0)
+        .await
+        .unwrap();
+
+    let mut assembler = BamAssembler::new();
+    let mut frames = 0;
+    loop {
+        let frame = host_bus.recv().await.unwrap();
+        frames += 1;
+        let expected_pgn = if frames == 1 { TP_CM_PGN } else { TP_DT_PGN };
+        assert_eq!(frame.id.pgn(), expected_pgn);
+
+        if let ProcessResult::MessageComplete(msg) = assembler.process_frame(&frame, 0) {
+            assert_eq!(msg.pgn, 126464);
+            assert_eq!(msg.source_address, 25);
+            assert_eq!(&msg.payload[..msg.len], &original);
+            break;
+        }
+    }
+
+    // 1 announcement + 6 data packets.
+    assert_eq!(frames, 7);
+}
+
+#[tokio::test]
+/// The requested delay is clamped to the 50 ms floor between every packet.
+async fn send_bam_enforces_minimum_packet_delay() {
+    let (mut dut_bus, _host_bus) = MockCanBus::create_pair();
+    let payload = [0u8; 20]; // 3 data packets
+
+    let start = Instant::now();
+    send_bam(&mut dut_bus, &mut MockTimer, 126464, 25, &payload, 1)
+        .await
+        .unwrap();
+
+    assert!(start.elapsed() >= Duration::from_millis(3 * TP_BAM_MIN_PACKET_DELAY_MS as u64));
+}
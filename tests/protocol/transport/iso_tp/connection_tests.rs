@@ -0,0 +1,274 @@
+//! Connection-mode transport tests: two `AddressManager` nodes exchanging an
+//! addressed multi-packet message, abort propagation, inbox and timeouts.
+mod helpers {
+    include!("../../../helpers/mod.rs");
+}
+
+use helpers::{MockCanBus, MockTimer};
+use korri_n2k::error::{TpAbortReason, TpSessionError, TransportError};
+use korri_n2k::protocol::{
+    managment::address_manager::AddressManager,
+    transport::{
+        can_frame::CanFrame,
+        can_id::CanId,
+        iso_tp::{TP_CM_PGN, TP_DT_PGN},
+        timers::ManualTimer,
+        traits::can_bus::CanBus,
+    },
+};
+
+/// TP.CM frame from `source` to `destination` for PGN 126208.
+fn cm_frame(source: u8, destination: u8, control: [u8; 5]) -> CanFrame {
+    CanFrame {
+        id: CanId::builder(TP_CM_PGN, source)
+            .to_destination(destination)
+            .with_priority(7)
+            .build()
+            .unwrap(),
+        data: [
+            control[0], control[1], control[2], control[3], control[4], 0x00, 0xED, 0x01,
+        ],
+        len: 8,
+    }
+}
+
+#[tokio::test]
+/// A 100-byte 126208 command travels from node A to node B, which answers
+/// the handshake automatically and hands the message to the application.
+async fn address_managers_exchange_connection_mode_message() {
+    let (bus_a, bus_b) = MockCanBus::create_pair();
+    let (node_a, node_b) = tokio::join!(
+        AddressManager::<_, _, 2>::claim(bus_a, MockTimer, 0x1234567890ABCDEF, 42),
+        AddressManager::<_, _, 2>::claim(bus_b, MockTimer, 0x1234567890ABCDEE, 43),
+    );
+    let (mut node_a, mut node_b) = (node_a.unwrap(), node_b.unwrap());
+    let destination = node_b.current_address();
+
+    let payload: [u8; 100] = core::array::from_fn(|i| i as u8);
+
+    let (sent, received) = tokio::join!(
+        node_a.send_transport(126208, destination, &payload),
+        async {
+            loop {
+                if node_b.recv().await.unwrap().is_none() {
+                    if let Some(message) = node_b.take_transport_message() {
+                        return message;
+                    }
+                }
+            }
+        }
+    );
+
+    sent.expect("transfer must be acknowledged");
+    assert_eq!(received.pgn, 126208);
+    assert_eq!(received.source_address, 42);
+    assert_eq!(&received.payload[..received.len], &payload);
+}
+
+#[tokio::test]
+/// Untaken messages queue up instead of overwriting each other; once the
+/// inbox is full, new transfers are refused.
+async fn transport_inbox_keeps_messages_until_taken() {
+    let (bus_a, bus_b) = MockCanBus::create_pair();
+    let (node_a, node_b) = tokio::join!(
+        AddressManager::<_, _, 2>::claim(bus_a, MockTimer, 0x1234567890ABCDEF, 42),
+        AddressManager::<_, _, 2>::claim(bus_b, MockTimer, 0x1234567890ABCDEE, 43),
+    );
+    let (mut node_a, mut node_b) = (node_a.unwrap(), node_b.unwrap());
+    let destination = node_b.current_address();
+
+    let (first, second, third) = tokio::select! {
+        results = async {
+            let first = node_a.send_transport(126208, destination, &[1; 20]).await;
+            let second = node_a.send_transport(126208, destination, &[2; 20]).await;
+            let third = node_a.send_transport(126208, destination, &[3; 20]).await;
+            (first, second, third)
+        } => results,
+        _ = async {
+            loop {
+                node_b.recv().await.unwrap();
+            }
+        } => unreachable!(),
+    };
+
+    first.expect("first transfer must be acknowledged");
+    second.expect("second transfer must be acknowledged");
+    assert!(matches!(
+        third,
+        Err(TransportError::Session(TpSessionError::AbortedByPeer(
+            TpAbortReason::ResourcesUnavailable
+        )))
+    ));
+
+    let message = node_b.take_transport_message().unwrap();
+    assert_eq!(&message.payload[..message.len], &[1; 20]);
+    let message = node_b.take_transport_message().unwrap();
+    assert_eq!(&message.payload[..message.len], &[2; 20]);
+    assert!(node_b.take_transport_message().is_none());
+}
+
+#[tokio::test]
+/// A peer restarting its transfer keeps its session even when the manager
+/// has no room for another one.
+async fn full_manager_accepts_restarted_transfer() {
+    let (dut_bus, mut host_bus) = MockCanBus::create_pair();
+
+    let (manager, _) = tokio::join!(
+        AddressManager::<_, _, 1>::claim(dut_bus, MockTimer, 0x1234567890ABCDEF, 42),
+        host_bus.recv(),
+    );
+    let mut manager = manager.unwrap();
+
+    let rts = cm_frame(60, 42, [16, 20, 0, 3, 0xFF]);
+    tokio::select! {
+        _ = manager.recv() => panic!("no application frame was sent"),
+        _ = async {
+            host_bus.send(&rts).await.unwrap();
+            let cts = host_bus.recv().await.unwrap();
+            assert_eq!(&cts.data[..3], &[17, 3, 1]);
+
+            host_bus.send(&rts).await.unwrap();
+            let cts = host_bus.recv().await.unwrap();
+            assert_eq!(&cts.data[..3], &[17, 3, 1], "the restart must be granted");
+        } => {}
+    }
+}
+
+#[tokio::test]
+/// Application frames received while a transfer waits for its peer are
+/// handed out by the next `recv`.
+async fn send_transport_keeps_application_frames() {
+    let (dut_bus, mut host_bus) = MockCanBus::create_pair();
+
+    let (manager, _) = tokio::join!(
+        AddressManager::<_, _, 2>::claim(dut_bus, MockTimer, 0x1234567890ABCDEF, 42),
+        host_bus.recv(),
+    );
+    let mut manager = manager.unwrap();
+
+    let wind = CanFrame {
+        id: CanId::builder(130306, 60).build().unwrap(),
+        data: [1, 2, 3, 4, 5, 6, 7, 8],
+        len: 8,
+    };
+
+    let (result, _) = tokio::join!(manager.send_transport(126208, 60, &[0u8; 20]), async {
+        let rts = host_bus.recv().await.unwrap();
+        assert_eq!(rts.data[0], 16);
+
+        host_bus.send(&wind).await.unwrap();
+        host_bus
+            .send(&cm_frame(60, 42, [17, 3, 1, 0xFF, 0xFF]))
+            .await
+            .unwrap();
+        for _ in 0..3 {
+            let packet = host_bus.recv().await.unwrap();
+            assert_eq!(packet.id.pgn(), TP_DT_PGN);
+        }
+        host_bus
+            .send(&cm_frame(60, 42, [19, 20, 0, 3, 0xFF]))
+            .await
+            .unwrap();
+    });
+
+    result.expect("transfer must be acknowledged");
+    let received = manager.recv().await.unwrap().expect("application frame");
+    assert_eq!(received.id.0, wind.id.0);
+    assert_eq!(received.data, wind.data);
+    assert_eq!(manager.dropped_frames(), 0);
+}
+
+#[tokio::test]
+/// An Abort answering our RTS surfaces as a typed error.
+async fn send_transport_reports_peer_abort() {
+    let (dut_bus, mut host_bus) = MockCanBus::create_pair();
+
+    let (manager, _) = tokio::join!(
+        AddressManager::<_, _, 2>::claim(dut_bus, MockTimer, 0x1234567890ABCDEF, 42),
+        host_bus.recv(),
+    );
+    let mut manager = manager.unwrap();
+
+    let (result, _) = tokio::join!(manager.send_transport(126208, 60, &[0u8; 40]), async {
+        let rts = host_bus.recv().await.unwrap();
+        assert_eq!(rts.id.pgn(), TP_CM_PGN);
+        assert_eq!(rts.data[0], 16);
+
+        let abort = CanFrame {
+            id: CanId::builder(TP_CM_PGN, 60)
+                .to_destination(42)
+                .with_priority(7)
+                .build()
+                .unwrap(),
+            data: [
+                255,
+                2,
+                0xFF,
+                0xFF,
+                0xFF,
+                rts.data[5],
+                rts.data[6],
+                rts.data[7],
+            ],
+            len: 8,
+        };
+        host_bus.send(&abort).await.unwrap();
+    });
+
+    assert!(matches!(
+        result,
+        Err(TransportError::Session(TpSessionError::AbortedByPeer(
+            TpAbortReason::ResourcesUnavailable
+        )))
+    ));
+}
+
+#[tokio::test]
+/// `recv` enforces T2 while the bus stays silent: the stalled transfer is
+/// aborted with reason 3 (timeout).
+async fn recv_enforces_transport_timeouts() {
+    let (dut_bus, mut host_bus) = MockCanBus::create_pair();
+    let mut manager =
+        AddressManager::<_, _, 2>::claim(dut_bus, ManualTimer::new(), 0x1234567890ABCDEF, 42)
+            .await
+            .unwrap();
+    let claim = host_bus.recv().await.unwrap();
+    assert_eq!(claim.id.pgn(), 60928);
+
+    host_bus
+        .send(&cm_frame(60, 42, [16, 20, 0, 3, 0xFF]))
+        .await
+        .unwrap();
+
+    tokio::select! {
+        _ = manager.recv() => panic!("no application frame was sent"),
+        _ = async {
+            let cts = host_bus.recv().await.unwrap();
+            assert_eq!(cts.data[0], 17);
+            let abort = host_bus.recv().await.unwrap();
+            assert_eq!(&abort.data[..2], &[255, 3]);
+        } => {}
+    }
+}
+
+#[tokio::test]
+/// Without transport sessions, TP.CM frames reach the application untouched.
+async fn default_manager_forwards_transport_frames() {
+    let (dut_bus, mut host_bus) = MockCanBus::create_pair();
+    let (manager, _) = tokio::join!(
+        AddressManager::new(dut_bus, MockTimer, 0x1234567890ABCDEF, 42),
+        host_bus.recv(),
+    );
+    let mut manager = manager.unwrap();
+
+    let rts = cm_frame(60, 42, [16, 20, 0, 3, 0xFF]);
+    host_bus.send(&rts).await.unwrap();
+
+    let received = manager.recv().await.unwrap().expect("forwarded frame");
+    assert_eq!(received.id.0, rts.id.0);
+    assert_eq!(received.data, rts.data);
+    assert!(matches!(
+        manager.send_transport(126208, 60, &[0u8; 20]).await,
+        Err(TransportError::Session(TpSessionError::NoSessionAvailable))
+    ));
+}
@@ -0,0 +1,3 @@
+//! Verify ISO transport sessions (BAM and RTS/CTS) over the mock CAN bus.
+pub mod bam_tests;
+pub mod connection_tests;
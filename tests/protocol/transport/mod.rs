@@ -1,2 +1,3 @@
 //! Unit tests for the transport layer.
 pub mod fast_packet;
+pub mod iso_tp;